@@ -3,7 +3,7 @@ import { Header } from './header';
 import { CodeEditor } from '../../code-editor/components/code-editor';
 import { VisualProofEditor } from '../../visual-proof-editor/components/visual-proof-editor';
 import { ConfigProvider, message, theme as antdTheme, ThemeConfig } from 'antd';
-import { Prop, VerificationResult, export_as_ocaml, generate_proof_term_from_proof_tree, parse_prop, print_prop_decls, verify } from 'alice';
+import { Logic, Prop, VerificationResult, export_as_ocaml, generate_proof_term_from_proof_tree, parse_proof_term, parse_prop, print_prop_decls, verify } from 'alice';
 import { debounce, isEqual } from 'lodash';
 import { CodeModal } from './code-modal';
 import { aliceProofTreeIntoVisualProofEditorProofTree, VisualProofEditorProofTree, visualProofEditorProofTreeIntoAliceProofTree } from '../../visual-proof-editor/lib/visual-proof-editor-proof-tree';
@@ -33,6 +33,7 @@ const mathjaxConfig: MathJax3Config = {
 export function App() {
     const [proofTerm, setProofTerm] = useState('');
     const [prop, setProp] = useState<Prop | null>(null);
+    const [logic] = useState<Logic>('Intuitionistic');
     const [showCodeExport, setShowCodeExport] = useState(false);
     const [showTutor, setShowTutor] = useState(false);
     const [verificationResult, setVerificationResult] = useState<VerificationResult | null>(null);
@@ -56,7 +57,7 @@ export function App() {
             setProofTerm(newProofTerm);
             setInitialAssumptions([]);
 
-            const verificationResult = verify(newProp, newProofTerm);
+            const verificationResult = verify(newProp, newProofTerm, logic);
             setVerificationResult(verificationResult);
 
             const proofTree: VisualProofEditorProofTree = {
@@ -93,7 +94,7 @@ export function App() {
             return;
         }
 
-        const verificationResult = verify(prop, newProofTerm);
+        const verificationResult = verify(prop, newProofTerm, logic);
         setVerificationResult(verificationResult);
 
         if (verificationResult.kind === 'TypeCheckSucceeded') {
@@ -113,7 +114,7 @@ export function App() {
             setInitialPrimaryContext(primaryCtx);
             setInitialAssumptions(proofTreeResult.assumptions);
         }
-    }, 500), [proofTerm, prop]);
+    }, 500), [proofTerm, prop, logic]);
 
     const handleProofTreeChange = useCallback((proofTree: VisualProofEditorProofTree) => {
         if (!prop) {
@@ -123,15 +124,16 @@ export function App() {
         const code = generate_proof_term_from_proof_tree(
             visualProofEditorProofTreeIntoAliceProofTree(proofTree),
             prop,
+            parse_proof_term(proofTerm, prop),
         );
         setProofTerm(code);
 
-        const result = verify(prop, code);
+        const result = verify(prop, code, logic);
         setVerificationResult(result);
-    }, [prop]);
+    }, [prop, proofTerm, logic]);
 
     const handleVerify = useCallback((prop: string) => {
-        const result = verify(parse_prop(prop), proofTerm);
+        const result = verify(parse_prop(prop), proofTerm, logic);
         setVerificationResult(result);
 
         if (
@@ -157,7 +159,7 @@ export function App() {
         if (!isProof) {
             message.error('Your proof contains errors.');
         }
-    }, [proofTerm, message]);
+    }, [proofTerm, message, logic]);
 
     const handleOcamlExport = useCallback(() => {
         if (!prop) {
@@ -176,8 +178,8 @@ export function App() {
 
         setProofTerm(proofTerm);
 
-        setVerificationResult(verify(prop, proofTerm));
-    }, [prop]);
+        setVerificationResult(verify(prop, proofTerm, logic));
+    }, [prop, logic]);
 
     return (
         <ConfigProvider theme={theme}>
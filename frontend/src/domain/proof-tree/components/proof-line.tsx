@@ -35,7 +35,7 @@ export function ProofLine({ rule, onDeleteClick }: ProofLineProps) {
         <div className={cssLineContainer}>
             <div
                 className={cssLine}
-                style={{ borderStyle: rule.kind === 'AlphaEquivalent' ? 'dashed' : undefined }}
+                style={{ borderStyle: rule.kind === 'AlphaEquivalent' || rule.kind === 'DeltaEquivalent' ? 'dashed' : undefined }}
             />
             <div className={cssLabelContainer}>
                 <div className={cssLabel} style={{ display: 'flex' }}>
@@ -59,6 +59,14 @@ export function TutorProofPipelineErrorDisplay(props: TutorProofPipelineErrorDis
                         </li>
                     )
                 }
+                {
+                    resolveDatatypesError.kind === 'ArgumentDatatypeWrong' && (
+                        <li>
+                            {ei('ArgumentDatatypeWrong')}
+                            Argument `{resolveDatatypesError.value.argument}` of `{resolveDatatypesError.value.ident}` has datatype `{resolveDatatypesError.value.actual}`, but `{resolveDatatypesError.value.expected}` is expected.
+                        </li>
+                    )
+                }
                 {
                     resolveDatatypesError.kind === 'PropContainsDatatypeIdentifier' && (
                         <li>
@@ -2,7 +2,7 @@ import { css } from '@emotion/css';
 import { Progress } from 'antd';
 import React, { ReactNode } from 'react';
 
-export type TutorPropositionSolutionStatusStatus = 'solved' | 'solvable' | 'unsolvable' | 'unknown';
+export type TutorPropositionSolutionStatusStatus = 'solved' | 'solvable' | 'unsolvable' | 'classically-solvable' | 'unknown';
 
 interface TutorPropositionSolutionStatusProps {
     status: TutorPropositionSolutionStatusStatus;
@@ -48,6 +48,11 @@ function getText(status: TutorPropositionSolutionStatusStatus): ReactNode {
                 Alice thinks you <span style={{ color: '#ED2836' }}>can&apos;t prove</span> this proposition.
             </span>
         );
+        case 'classically-solvable': return (
+            <span>
+                Alice thinks you <span style={{ color: '#ED2836' }}>can&apos;t prove</span> this proposition, although it is <span style={{ color: '#FF8343' }}>classically valid</span>.
+            </span>
+        );
         case 'unknown': return (
             <span>
                 Alice <span style={{ color: '#FF8343' }}>doesn&apos;t know</span> whether you can prove this proposition.
@@ -1,6 +1,7 @@
 import { css } from '@emotion/css';
 import { CheckError, PropKind, SynthesizeError, Type } from 'alice';
 import React from 'react';
+import { printPropParameter } from '../../../util/print-prop-parameter';
 import { printType } from '../../../util/print-type';
 
 interface TutorTypeCheckErrorDisplayProps {
@@ -195,6 +196,28 @@ export function TutorTypeCheckErrorDisplay(props: TutorTypeCheckErrorDisplayProp
                         </li>
                     )
                 }
+                {
+                    error.kind === 'ClassicalReasoningNotAllowed' && (
+                        <li>
+                            {ei('ClassicalReasoningNotAllowed')}
+                            Double negation elimination is only allowed in classical logic.
+                            <br />
+                            <br />
+                            {span(error.value)}
+                        </li>
+                    )
+                }
+                {
+                    error.kind === 'ArgumentDatatypeWrong' && (
+                        <li>
+                            {ei('ArgumentDatatypeWrong')}
+                            Argument `{printPropParameter(error.value.argument)}` of `{error.value.ident}` has datatype `{error.value.actual}`, but `{error.value.expected}` is expected.
+                            <br />
+                            <br />
+                            {span(error.value.span)}
+                        </li>
+                    )
+                }
             </ul>
         </div>
     );
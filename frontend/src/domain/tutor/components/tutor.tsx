@@ -67,6 +67,7 @@ function getStatus(verificationResult: VerificationResult): TutorPropositionSolu
     switch (verificationResult.value.solvable) {
         case 'Solvable': return 'solvable';
         case 'Unsolvable': return 'unsolvable';
+        case 'ClassicallySolvable': return 'classically-solvable';
         case 'Unknown': return 'unknown';
     }
 }
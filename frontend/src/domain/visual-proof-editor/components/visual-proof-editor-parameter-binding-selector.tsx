@@ -2,6 +2,7 @@ import { css, cx } from '@emotion/css';
 import { Identifier, Prop, PropParameter } from 'alice';
 import { isEqual } from 'lodash';
 import React, { ReactNode, useCallback, useEffect, useState } from 'react';
+import { printPropParameter } from '../../../util/print-prop-parameter';
 
 interface VisualProofEditorParameterBindingSelectorProps {
     prop: Prop;
@@ -91,7 +92,7 @@ export function VisualProofEditorParameterBindingSelector(props: VisualProofEdit
             const paramNodes = [];
 
             for (const param of params) {
-                const paramName = param.kind === 'Instantiated' ? param.value.name : param.value;
+                const paramName = printPropParameter(param);
 
                 // allow to select any parameter as identifier
                 if (!identifier) {
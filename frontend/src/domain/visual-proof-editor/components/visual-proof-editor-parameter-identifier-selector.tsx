@@ -1,6 +1,7 @@
 import { get_free_parameters, Identifier, instantiate_free_parameter_by_index, Prop } from 'alice';
 import { Select, Space } from 'antd';
 import React, { Fragment, ReactElement, useCallback, useEffect, useRef, useState } from 'react';
+import { printPropParameter } from '../../../util/print-prop-parameter';
 
 interface VisualProofEditorParameterIdentifierSelectorProps {
     prop: Prop;
@@ -45,7 +46,7 @@ export function VisualProofEditorParameterIdentifierSelector(props: VisualProofE
         let instantiatedProp = { ...prop };
         for (const [paramIndex, identifierIndex] of Object.entries(paramMapping)) {
             const param = params[parseInt(paramIndex)];
-            const paramName = param.kind === 'Instantiated' ? param.value.name : param.value;
+            const paramName = printPropParameter(param);
 
             instantiatedProp = instantiate_free_parameter_by_index(
                 instantiatedProp,
@@ -116,7 +117,7 @@ export function VisualProofEditorParameterIdentifierSelector(props: VisualProofE
             const paramNodes = [];
             for (const param of params) {
                 const paramIndex = index;
-                const paramName = param.kind === 'Instantiated' ? param.value.name : param.value;
+                const paramName = printPropParameter(param);
 
                 if (param.kind === 'Instantiated' || boundIdentifiers.includes(paramName)) {
                     paramNodes.push(<span>{paramName}</span>);
@@ -13,11 +13,16 @@ export function printProofRule(proofTreeRule: ProofTreeRule): string {
         case 'OrIntroSnd': return '\\lor I_2';
         case 'OrElim': return `\\lor E^{${proofTreeRule.value[0].name}, ${proofTreeRule.value[1].name}}`;
         case 'FalsumElim': return '\\bot E';
+        case 'DoubleNegElim': return '\\neg\\neg E';
         case 'ForAllIntro': return '\\forall I^' + proofTreeRule.value.name;
         case 'ForAllElim': return '\\forall E';
         case 'ExistsIntro': return '\\exists I';
         case 'ExistsElim': return `\\exists E^{${proofTreeRule.value[0].name}, ${proofTreeRule.value[1].name}}`;
+        case 'EqIntro': return '= I';
+        case 'EqElim': return '= E';
+        case 'FunctionApplication': return proofTreeRule.value;
         case 'Sorry': return 'sorry';
         case 'AlphaEquivalent': return ' \\alpha\\text{-Eq}'
+        case 'DeltaEquivalent': return ' \\delta\\text{-Eq}'
     }
 }
\ No newline at end of file
@@ -1,9 +1,11 @@
 import { print_prop, ProofTreeConclusion } from 'alice';
 import { printTypeJudgment } from './print-type-judgment';
+import { printPropParameter } from './print-prop-parameter';
 
 export function printProofTreeConclusion(conclusion: ProofTreeConclusion): string {
     switch (conclusion.kind) {
         case 'PropIsTrue': return print_prop(conclusion.value);
         case 'TypeJudgement': return printTypeJudgment(conclusion.value);
+        case 'TermJudgement': return `${printPropParameter(conclusion.value[0])} : ${conclusion.value[1]}`;
     }
-}
\ No newline at end of file
+}
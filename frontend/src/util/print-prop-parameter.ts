@@ -0,0 +1,17 @@
+import { PropParameter } from 'alice';
+
+export function printPropParameter(param: PropParameter): string {
+    switch (param.kind) {
+        case 'Uninstantiated': return param.value;
+        case 'Instantiated': return param.value.name;
+        case 'Function': {
+            const [name, args] = param.value;
+
+            if (args.length === 0) {
+                return name;
+            }
+
+            return `${name}(${args.map(printPropParameter).join(', ')})`;
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use std::{io, process::ExitCode};
+
+use alice::lsp::server::run;
+
+// Language server for theorem files, speaking the Language Server Protocol over stdio.
+fn main() -> ExitCode {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+
+    match run(stdin.lock(), stdout.lock()) {
+        Ok(server) if server.shutdown_requested() => ExitCode::SUCCESS,
+        Ok(_) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -7,8 +7,8 @@ use tsify_next::Tsify;
 use crate::{
     kernel::{
         proof_term::{
-            Abort, Application, Case, Function, Ident, LetIn, OrLeft, OrRight, Pair, ProjectFst,
-            ProjectSnd, ProofTerm, ProofTermVisitor, Type, TypeAscription,
+            Abort, Application, Case, DoubleNegElim, Function, Ident, LetIn, OrLeft, OrRight, Pair,
+            ProjectFst, ProjectSnd, ProofTerm, ProofTermVisitor, Subst, Type, TypeAscription,
         },
         proof_tree::{ProofTree, ProofTreeConclusion, ProofTreeRule},
         prop::{InstatiationError, Prop, PropKind, PropParameter},
@@ -20,8 +20,8 @@ use crate::{
 use super::{
     identifier::{Identifier, IdentifierFactory},
     identifier_context::IdentifierContext,
-    synthesize::{synthesize, SynthesizeError},
-    TypeCheckerGoal, TypeCheckerResult,
+    synthesize::{synthesize, unfold_synthesized, SynthesizeError},
+    Logic, TypeCheckerGoal, TypeCheckerResult,
 };
 
 #[derive(Debug, Error, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
@@ -70,6 +70,46 @@ pub enum CheckError {
 
     #[error("Quantified object would escape it's scope")]
     QuantifiedObjectEscapesScope(Option<Range<usize>>),
+
+    #[error("Arity of {ident} wrong: expected {expected}, actual {actual}")]
+    ArityWrong {
+        ident: String,
+        expected: usize,
+        actual: usize,
+        span: Option<Range<usize>>,
+    },
+
+    #[error("Classical reasoning is not allowed in intuitionistic logic")]
+    ClassicalReasoningNotAllowed(Option<Range<usize>>),
+
+    #[error("Argument {argument} of {ident} has datatype {actual}, expected {expected}")]
+    ArgumentDatatypeWrong {
+        ident: String,
+        argument: PropParameter,
+        expected: String,
+        actual: String,
+        span: Option<Range<usize>>,
+    },
+}
+
+impl CheckError {
+    // Location of the offending proof term, if known.
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            CheckError::SynthesizeError(synth_err) => synth_err.span(),
+            CheckError::PropHasFreeParameters(_) => None,
+            CheckError::UnknownIdentifier(_, span)
+            | CheckError::IncompatibleProofTerm { span, .. }
+            | CheckError::UnexpectedPropKind { span, .. }
+            | CheckError::CannotReturnDatatype(span)
+            | CheckError::UnexpectedType { span, .. }
+            | CheckError::UnexpectedTypeAscription { span, .. }
+            | CheckError::QuantifiedObjectEscapesScope(span)
+            | CheckError::ArityWrong { span, .. }
+            | CheckError::ClassicalReasoningNotAllowed(span)
+            | CheckError::ArgumentDatatypeWrong { span, .. } => span.clone(),
+        }
+    }
 }
 
 pub fn check(
@@ -77,26 +117,170 @@ pub fn check(
     expected_prop: &Prop,
     ctx: &IdentifierContext,
 ) -> Result<TypeCheckerResult, CheckError> {
-    if expected_prop.has_free_parameters() {
-        return Err(CheckError::PropHasFreeParameters(expected_prop.clone()));
-    }
+    // resolve constants and identifiers of the context
+    let mut instantiated_prop = expected_prop.clone();
+    instantiated_prop
+        .instantiate_parameters_with_context(ctx)
+        .map_err(|err| match err {
+            InstatiationError::UnknownIdentifier(_) => {
+                CheckError::PropHasFreeParameters(expected_prop.clone())
+            }
+            InstatiationError::ArgumentDatatypeWrong {
+                ident,
+                argument,
+                expected,
+                actual,
+            } => CheckError::ArgumentDatatypeWrong {
+                ident,
+                argument,
+                expected,
+                actual,
+                span: None,
+            },
+            InstatiationError::DefinitionArityWrong {
+                ident,
+                expected,
+                actual,
+            } => CheckError::ArityWrong {
+                ident,
+                expected,
+                actual,
+                span: None,
+            },
+        })?;
 
     let mut identifier_factory = IdentifierFactory::new(Counter::new());
 
     check_allowing_free_params(
         proof_term,
-        &Type::Prop(expected_prop.clone()),
+        &Type::Prop(instantiated_prop),
         ctx,
         &mut identifier_factory,
     )
 }
 
+// Checks that a proof term denotes an object of the given datatype, e.g. "succ (succ zero)".
+pub(crate) fn check_term(
+    proof_term: &ProofTerm,
+    expected_datatype: &String,
+    ctx: &IdentifierContext,
+    identifier_factory: &mut IdentifierFactory,
+) -> Result<(PropParameter, TypeCheckerResult), CheckError> {
+    // collect arguments of the application spine
+    let mut head = proof_term;
+    let mut args = vec![];
+    while let ProofTerm::Application(Application {
+        function,
+        applicant,
+        ..
+    }) = head
+    {
+        args.insert(0, applicant.as_ref());
+        head = function;
+    }
+
+    let ProofTerm::Ident(Ident(name, span)) = head else {
+        return Err(CheckError::CannotReturnDatatype(proof_term.span().clone()));
+    };
+
+    // variables in scope
+    if args.is_empty() {
+        if let Some((identifier, _)) = ctx.get_by_name(name) {
+            let identifier = identifier.clone();
+            let result = check_allowing_free_params(
+                proof_term,
+                &Type::Datatype(expected_datatype.clone()),
+                ctx,
+                identifier_factory,
+            )?;
+
+            return Ok((PropParameter::Instantiated(identifier), result));
+        }
+    }
+
+    let signature = ctx
+        .get_function(name)
+        .ok_or(CheckError::UnknownIdentifier(name.clone(), span.clone()))?
+        .clone();
+
+    if signature.param_types.len() != args.len() {
+        return Err(CheckError::ArityWrong {
+            ident: name.clone(),
+            expected: signature.param_types.len(),
+            actual: args.len(),
+            span: proof_term.span().clone(),
+        });
+    }
+
+    if signature.return_type != *expected_datatype {
+        return Err(CheckError::UnexpectedType {
+            expected: Type::Datatype(expected_datatype.clone()),
+            received: Type::Datatype(signature.return_type),
+            span: proof_term.span().clone(),
+        });
+    }
+
+    let mut params = vec![];
+    let mut premisses = vec![];
+    let mut goals = vec![];
+    for (arg, param_type) in args.into_iter().zip(signature.param_types.iter()) {
+        let (param, arg_result) = check_term(arg, param_type, ctx, identifier_factory)?;
+
+        params.push(param);
+        premisses.push(arg_result.proof_tree);
+        goals.extend(arg_result.goals);
+    }
+
+    let term = PropParameter::Function(name.clone(), params);
+
+    Ok((
+        term.clone(),
+        TypeCheckerResult {
+            goals,
+            proof_tree: ProofTree {
+                premisses,
+                rule: ProofTreeRule::FunctionApplication(name.clone()),
+                conclusion: ProofTreeConclusion::TermJudgement(term, expected_datatype.clone()),
+            },
+        },
+    ))
+}
+
 pub(super) fn check_allowing_free_params(
     proof_term: &ProofTerm,
     expected_type: &Type,
     ctx: &IdentifierContext,
     identifier_factory: &mut IdentifierFactory,
 ) -> Result<TypeCheckerResult, CheckError> {
+    // introduction rules need to see the connective behind a definition
+    let is_introduction = matches!(
+        proof_term,
+        ProofTerm::Pair(_)
+            | ProofTerm::Function(_)
+            | ProofTerm::OrLeft(_)
+            | ProofTerm::OrRight(_)
+            | ProofTerm::Unit(_)
+            | ProofTerm::Refl(_)
+    );
+
+    if let (true, Type::Prop(prop @ Prop::Defined(_, _))) = (is_introduction, expected_type) {
+        let unfolded_prop = prop.unfold_head(ctx.definitions());
+
+        if unfolded_prop != *prop {
+            let result = check_allowing_free_params(
+                proof_term,
+                &Type::Prop(unfolded_prop),
+                ctx,
+                identifier_factory,
+            )?;
+
+            return Ok(result.create_with_equivalence_tree(
+                ProofTreeConclusion::PropIsTrue(prop.clone()),
+                ctx.definitions(),
+            ));
+        }
+    }
+
     let mut visitor = CheckVisitor::new(expected_type.clone(), ctx, identifier_factory);
     proof_term.visit(&mut visitor)
 }
@@ -140,7 +324,7 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
             return Ok(type_checker_result);
         }
 
-        if Type::alpha_eq(&_type, &self.expected_type) {
+        if Type::alpha_eq_modulo_definitions(&_type, &self.expected_type, self.ctx.definitions()) {
             let conclusion = match &self.expected_type {
                 Type::Prop(prop) => ProofTreeConclusion::PropIsTrue(prop.clone()),
                 Type::Datatype(_) => type_checker_result.proof_tree.conclusion.clone(),
@@ -148,7 +332,7 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
 
             type_checker_result.proof_tree = type_checker_result
                 .proof_tree
-                .create_alphq_eq_tree(conclusion);
+                .create_equivalence_tree(conclusion, self.ctx.definitions());
 
             return Ok(type_checker_result);
         }
@@ -163,62 +347,57 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
     fn visit_pair(&mut self, pair: &Pair) -> Result<TypeCheckerResult, CheckError> {
         let Pair(fst_term, snd_term, span) = pair;
 
-        let (expected_fst_type, expected_snd_type, rule, conclusion) =
-            match self.expected_type {
-                // And
-                Type::Prop(ref prop @ Prop::And(ref fst, ref snd)) => (
-                    Type::Prop(*fst.clone()),
-                    Type::Prop(*snd.clone()),
-                    ProofTreeRule::AndIntro,
+        let (fst_result, expected_snd_type, rule, conclusion) = match self.expected_type {
+            // And
+            Type::Prop(ref prop @ Prop::And(ref fst, ref snd)) => (
+                check_allowing_free_params(
+                    fst_term,
+                    &Type::Prop(*fst.clone()),
+                    self.ctx,
+                    self.identifier_factory,
+                )?,
+                Type::Prop(*snd.clone()),
+                ProofTreeRule::AndIntro,
+                ProofTreeConclusion::PropIsTrue(prop.clone()),
+            ),
+
+            // Exists
+            Type::Prop(
+                ref prop @ Prop::Exists {
+                    ref object_ident,
+                    ref object_type_ident,
+                    ref body,
+                },
+            ) => {
+                // the witness can be any term of the datatype, e.g. "succ x"
+                let (term, fst_result) = check_term(
+                    fst_term,
+                    object_type_ident,
+                    self.ctx,
+                    self.identifier_factory,
+                )?;
+
+                // instantiate body
+                let mut substitued_body = *body.clone();
+                substitued_body.substitute_free_parameter(object_ident, &term);
+
+                (
+                    fst_result,
+                    Type::Prop(substitued_body),
+                    ProofTreeRule::ExistsIntro,
                     ProofTreeConclusion::PropIsTrue(prop.clone()),
-                ),
-
-                // Exists
-                Type::Prop(
-                    ref prop @ Prop::Exists {
-                        ref object_ident,
-                        ref object_type_ident,
-                        ref body,
-                    },
-                ) => {
-                    // instantiate body with parameter name of function to account for \alpha-Equivalence
-                    let (substitution, ident_span) = match **fst_term {
-                        ProofTerm::Ident(Ident(ref ident, ref ident_span)) => {
-                            (ident.clone(), ident_span.clone())
-                        }
-                        _ => return Err(CheckError::CannotReturnDatatype(fst_term.span().clone())),
-                    };
-
-                    // instantiate body
-                    let (identifier, _) = self.ctx.get_by_name(&substitution).ok_or(
-                        CheckError::UnknownIdentifier(substitution.clone(), ident_span),
-                    )?;
-                    let mut substitued_body = *body.clone();
-                    substitued_body.instantiate_free_parameter(object_ident, identifier);
-
-                    (
-                        Type::Datatype(object_type_ident.clone()),
-                        Type::Prop(substitued_body),
-                        ProofTreeRule::ExistsIntro,
-                        ProofTreeConclusion::PropIsTrue(prop.clone()),
-                    )
-                }
-                _ => {
-                    return Err(CheckError::IncompatibleProofTerm {
-                        expected_type: self.expected_type.clone(),
-                        proof_term: ProofTerm::Pair(pair.clone()),
-                        span: span.clone(),
-                    })
-                }
-            };
+                )
+            }
+            _ => {
+                return Err(CheckError::IncompatibleProofTerm {
+                    expected_type: self.expected_type.clone(),
+                    proof_term: ProofTerm::Pair(pair.clone()),
+                    span: span.clone(),
+                })
+            }
+        };
 
-        // check pair components
-        let fst_result = check_allowing_free_params(
-            fst_term,
-            &expected_fst_type,
-            self.ctx,
-            self.identifier_factory,
-        )?;
+        // check second pair component
         let snd_result = check_allowing_free_params(
             snd_term,
             &expected_snd_type,
@@ -257,13 +436,19 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
             return Ok(projection_result);
         }
 
-        if Type::alpha_eq(&self.expected_type, &projection_type) {
+        if Type::alpha_eq_modulo_definitions(
+            &self.expected_type,
+            &projection_type,
+            self.ctx.definitions(),
+        ) {
             let conclusion = match self.expected_type {
                 Type::Prop(ref prop) => ProofTreeConclusion::PropIsTrue(prop.clone()),
                 Type::Datatype(_) => projection_result.proof_tree.conclusion.clone(),
             };
 
-            return Ok(projection_result.create_with_alphq_eq_tree(conclusion));
+            return Ok(
+                projection_result.create_with_equivalence_tree(conclusion, self.ctx.definitions())
+            );
         }
 
         Err(CheckError::UnexpectedType {
@@ -294,13 +479,19 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
             return Ok(projection_result);
         }
 
-        if Type::alpha_eq(&self.expected_type, &projection_type) {
+        if Type::alpha_eq_modulo_definitions(
+            &self.expected_type,
+            &projection_type,
+            self.ctx.definitions(),
+        ) {
             let conclusion = match &self.expected_type {
                 Type::Prop(prop) => ProofTreeConclusion::PropIsTrue(prop.clone()),
                 Type::Datatype(_) => projection_result.proof_tree.conclusion.clone(),
             };
 
-            return Ok(projection_result.create_with_alphq_eq_tree(conclusion));
+            return Ok(
+                projection_result.create_with_equivalence_tree(conclusion, self.ctx.definitions())
+            );
         }
 
         Err(CheckError::UnexpectedType {
@@ -367,10 +558,36 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
                     InstatiationError::UnknownIdentifier(ident) => {
                         CheckError::UnknownIdentifier(ident, span.clone())
                     }
+                    InstatiationError::ArgumentDatatypeWrong {
+                        ident,
+                        argument,
+                        expected,
+                        actual,
+                    } => CheckError::ArgumentDatatypeWrong {
+                        ident,
+                        argument,
+                        expected,
+                        actual,
+                        span: span.clone(),
+                    },
+                    InstatiationError::DefinitionArityWrong {
+                        ident,
+                        expected,
+                        actual,
+                    } => CheckError::ArityWrong {
+                        ident,
+                        expected,
+                        actual,
+                        span: span.clone(),
+                    },
                 })?;
 
             // fail if type annotation is not expected type
-            if !Type::alpha_eq(&instantiated_param_type, &expected_param_type) {
+            if !Type::alpha_eq_modulo_definitions(
+                &instantiated_param_type,
+                &expected_param_type,
+                self.ctx.definitions(),
+            ) {
                 return Err(CheckError::UnexpectedType {
                     expected: expected_param_type.clone(),
                     received: instantiated_param_type,
@@ -460,13 +677,19 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
             return Ok(application_result);
         }
 
-        if Type::alpha_eq(&application_type, &self.expected_type) {
+        if Type::alpha_eq_modulo_definitions(
+            &application_type,
+            &self.expected_type,
+            self.ctx.definitions(),
+        ) {
             let conclusion = match &self.expected_type {
                 Type::Prop(prop) => ProofTreeConclusion::PropIsTrue(prop.clone()),
                 Type::Datatype(_) => application_result.proof_tree.conclusion.clone(),
             };
 
-            return Ok(application_result.create_with_alphq_eq_tree(conclusion));
+            return Ok(
+                application_result.create_with_equivalence_tree(conclusion, self.ctx.definitions())
+            );
         }
 
         Err(CheckError::UnexpectedType {
@@ -490,6 +713,7 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
                 SynthesizeError::CheckError(check_err) => *check_err,
                 _ => CheckError::SynthesizeError(synth_err),
             })?;
+        let (head_type, head_result) = unfold_synthesized((head_type, head_result), self.ctx);
 
         if let Type::Prop(Prop::Exists {
             object_ident,
@@ -628,6 +852,7 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
                 SynthesizeError::CheckError(check_err) => *check_err,
                 _ => CheckError::SynthesizeError(synth_err),
             })?;
+        let (head_type, head_result) = unfold_synthesized((head_type, head_result), self.ctx);
 
         let (fst, snd) = match head_type {
             Type::Prop(Prop::Or(fst, snd)) => (fst, snd),
@@ -707,6 +932,42 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
         })
     }
 
+    fn visit_double_neg_elim(
+        &mut self,
+        double_neg_elim: &DoubleNegElim,
+    ) -> Result<TypeCheckerResult, CheckError> {
+        let DoubleNegElim(body, span) = double_neg_elim;
+
+        if *self.ctx.logic() != Logic::Classical {
+            return Err(CheckError::ClassicalReasoningNotAllowed(span.clone()));
+        }
+
+        let Type::Prop(ref prop) = self.expected_type else {
+            return Err(CheckError::CannotReturnDatatype(span.clone()));
+        };
+
+        let double_negation = Prop::Impl(
+            Prop::Impl(prop.boxed(), Prop::False.boxed()).boxed(),
+            Prop::False.boxed(),
+        );
+
+        let body_result = check_allowing_free_params(
+            body,
+            &Type::Prop(double_negation),
+            self.ctx,
+            self.identifier_factory,
+        )?;
+
+        Ok(TypeCheckerResult {
+            goals: body_result.goals,
+            proof_tree: ProofTree {
+                premisses: vec![body_result.proof_tree],
+                rule: ProofTreeRule::DoubleNegElim,
+                conclusion: ProofTreeConclusion::PropIsTrue(prop.clone()),
+            },
+        })
+    }
+
     fn visit_unit(&mut self, span: Option<Range<usize>>) -> Result<TypeCheckerResult, CheckError> {
         if self.expected_type == Type::Prop(Prop::True) {
             Ok(TypeCheckerResult {
@@ -743,9 +1004,35 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
                 InstatiationError::UnknownIdentifier(ident) => {
                     CheckError::UnknownIdentifier(ident, span.clone())
                 }
+                InstatiationError::ArgumentDatatypeWrong {
+                    ident,
+                    argument,
+                    expected,
+                    actual,
+                } => CheckError::ArgumentDatatypeWrong {
+                    ident,
+                    argument,
+                    expected,
+                    actual,
+                    span: span.clone(),
+                },
+                InstatiationError::DefinitionArityWrong {
+                    ident,
+                    expected,
+                    actual,
+                } => CheckError::ArityWrong {
+                    ident,
+                    expected,
+                    actual,
+                    span: span.clone(),
+                },
             })?;
 
-        if !Type::alpha_eq(&self.expected_type, &instantiated_ascription) {
+        if !Type::alpha_eq_modulo_definitions(
+            &self.expected_type,
+            &instantiated_ascription,
+            self.ctx.definitions(),
+        ) {
             return Err(CheckError::UnexpectedTypeAscription {
                 expected: self.expected_type.clone(),
                 ascription: instantiated_ascription.clone(),
@@ -763,7 +1050,7 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
 
     fn visit_sorry(
         &mut self,
-        _span: Option<Range<usize>>,
+        span: Option<Range<usize>>,
     ) -> Result<TypeCheckerResult, CheckError> {
         let conclusion = match self.expected_type {
             Type::Prop(ref prop) => ProofTreeConclusion::PropIsTrue(prop.clone()),
@@ -775,6 +1062,7 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
         let mut goal = TypeCheckerGoal {
             solution: None,
             conclusion: conclusion.clone(),
+            span,
         };
 
         let ProofTreeConclusion::PropIsTrue(ref prop) = conclusion else {
@@ -801,4 +1089,75 @@ impl<'a> ProofTermVisitor<Result<TypeCheckerResult, CheckError>> for CheckVisito
             },
         })
     }
+
+    fn visit_refl(&mut self, span: Option<Range<usize>>) -> Result<TypeCheckerResult, CheckError> {
+        match self.expected_type {
+            Type::Prop(ref prop @ Prop::Eq(ref lhs, ref rhs)) if lhs == rhs => {
+                Ok(TypeCheckerResult {
+                    goals: vec![],
+                    proof_tree: ProofTree {
+                        premisses: vec![],
+                        rule: ProofTreeRule::EqIntro,
+                        conclusion: ProofTreeConclusion::PropIsTrue(prop.clone()),
+                    },
+                })
+            }
+            _ => Err(CheckError::IncompatibleProofTerm {
+                expected_type: self.expected_type.clone(),
+                proof_term: ProofTerm::Refl(span.clone()),
+                span,
+            }),
+        }
+    }
+
+    fn visit_subst(&mut self, subst: &Subst) -> Result<TypeCheckerResult, CheckError> {
+        let Subst {
+            equality,
+            body,
+            span,
+        } = subst;
+
+        let (equality_type, equality_result) =
+            synthesize(equality, self.ctx, self.identifier_factory).map_err(|synth_err| {
+                match synth_err {
+                    SynthesizeError::CheckError(check_err) => *check_err,
+                    _ => CheckError::SynthesizeError(synth_err),
+                }
+            })?;
+        let (equality_type, equality_result) =
+            unfold_synthesized((equality_type, equality_result), self.ctx);
+
+        let Type::Prop(Prop::Eq(lhs, rhs)) = equality_type else {
+            return Err(CheckError::UnexpectedPropKind {
+                expected: vec![PropKind::Eq],
+                received: equality_type,
+                span: equality.span().clone(),
+            });
+        };
+
+        let prop = match self.expected_type {
+            Type::Prop(ref prop) => prop,
+            _ => return Err(CheckError::CannotReturnDatatype(span.clone())),
+        };
+
+        // body has to prove the expected proposition with rhs replaced by lhs
+        let mut expected_body_prop = prop.clone();
+        expected_body_prop.replace_free_parameter(&rhs, &lhs);
+
+        let body_result = check_allowing_free_params(
+            body,
+            &Type::Prop(expected_body_prop),
+            self.ctx,
+            self.identifier_factory,
+        )?;
+
+        Ok(TypeCheckerResult {
+            goals: [equality_result.goals, body_result.goals].concat(),
+            proof_tree: ProofTree {
+                premisses: vec![equality_result.proof_tree, body_result.proof_tree],
+                rule: ProofTreeRule::EqElim,
+                conclusion: ProofTreeConclusion::PropIsTrue(prop.clone()),
+            },
+        })
+    }
 }
@@ -1,12 +1,21 @@
 use itertools::Itertools;
 
-use crate::kernel::proof_term::Type;
+use crate::kernel::{
+    proof_term::Type,
+    prop::{AtomSignature, FunctionSignature, PropDefinition},
+    prove::ProverConfig,
+};
 
-use super::identifier::Identifier;
+use super::{identifier::Identifier, Logic};
 
 #[derive(Clone, Debug)]
 pub struct IdentifierContext {
     ctx: Vec<(Identifier, Type)>,
+    functions: Vec<FunctionSignature>,
+    atoms: Vec<AtomSignature>,
+    definitions: Vec<PropDefinition>,
+    logic: Logic,
+    prover_config: ProverConfig,
 }
 
 impl Default for IdentifierContext {
@@ -17,7 +26,45 @@ impl Default for IdentifierContext {
 
 impl IdentifierContext {
     pub fn new() -> Self {
-        Self { ctx: Vec::new() }
+        Self {
+            ctx: Vec::new(),
+            functions: Vec::new(),
+            atoms: Vec::new(),
+            definitions: PropDefinition::builtins(),
+            logic: Logic::Intuitionistic,
+            prover_config: ProverConfig::default(),
+        }
+    }
+
+    pub fn with_functions(functions: Vec<FunctionSignature>) -> Self {
+        Self::with_signatures(functions, vec![])
+    }
+
+    pub fn with_signatures(functions: Vec<FunctionSignature>, atoms: Vec<AtomSignature>) -> Self {
+        Self {
+            ctx: Vec::new(),
+            functions,
+            atoms,
+            definitions: PropDefinition::builtins(),
+            logic: Logic::Intuitionistic,
+            prover_config: ProverConfig::default(),
+        }
+    }
+
+    pub fn logic(&self) -> &Logic {
+        &self.logic
+    }
+
+    pub fn set_logic(&mut self, logic: Logic) {
+        self.logic = logic;
+    }
+
+    pub fn prover_config(&self) -> &ProverConfig {
+        &self.prover_config
+    }
+
+    pub fn set_prover_config(&mut self, prover_config: ProverConfig) {
+        self.prover_config = prover_config;
     }
 
     pub fn len(&self) -> usize {
@@ -41,6 +88,29 @@ impl IdentifierContext {
         self.ctx.push((ident, identifer_type));
     }
 
+    pub fn functions(&self) -> &[FunctionSignature] {
+        &self.functions
+    }
+
+    pub fn get_function(&self, name: &String) -> Option<&FunctionSignature> {
+        self.functions
+            .iter()
+            .find(|signature| signature.name == *name)
+    }
+
+    pub fn definitions(&self) -> &[PropDefinition] {
+        &self.definitions
+    }
+
+    // Declared definitions shadow the built-in ones.
+    pub fn add_definitions(&mut self, definitions: Vec<PropDefinition>) {
+        self.definitions.extend(definitions);
+    }
+
+    pub fn get_atom(&self, name: &String) -> Option<&AtomSignature> {
+        self.atoms.iter().find(|signature| signature.name == *name)
+    }
+
     pub fn get_by_name(&self, ident: &String) -> Option<(&Identifier, &Type)> {
         self.ctx
             .iter()
@@ -92,7 +162,9 @@ impl IdentifierContext {
 
 #[cfg(test)]
 mod tests {
-    use crate::kernel::{checker::identifier::Identifier, proof_term::Type};
+    use crate::kernel::{
+        checker::identifier::Identifier, proof_term::Type, prop::FunctionSignature,
+    };
 
     use super::IdentifierContext;
 
@@ -279,6 +351,26 @@ mod tests {
         assert_eq!(2, ctx.len())
     }
 
+    #[test]
+    fn test_get_function() {
+        let signature = FunctionSignature {
+            name: "succ".to_string(),
+            param_types: vec!["nat".to_string()],
+            return_type: "nat".to_string(),
+        };
+
+        let mut ctx = IdentifierContext::with_functions(vec![signature.clone()]);
+        ctx.insert(
+            Identifier::new("succ".to_string(), 1),
+            Type::Datatype("nat".to_string()),
+        );
+
+        assert_eq!(ctx.get_function(&"succ".to_string()), Some(&signature));
+        assert_eq!(ctx.get_function(&"zero".to_string()), None);
+
+        assert_eq!(1, ctx.len())
+    }
+
     #[test]
     fn test_shadowing_1() {
         let mut ctx = IdentifierContext::new();
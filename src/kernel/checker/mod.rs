@@ -1,9 +1,12 @@
+use std::ops::Range;
+
 use serde::{Deserialize, Serialize};
 use tsify_next::Tsify;
 
 use super::{
     proof_term::ProofTerm,
     proof_tree::{ProofTree, ProofTreeConclusion},
+    prop::PropDefinition,
 };
 
 pub mod check;
@@ -11,11 +14,21 @@ pub mod identifier;
 pub mod identifier_context;
 pub mod synthesize;
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum Logic {
+    Intuitionistic,
+    Classical,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
 pub struct TypeCheckerGoal {
     pub conclusion: ProofTreeConclusion,
     pub solution: Option<ProofTerm>,
+
+    // span of the sorry that left this goal open
+    pub span: Option<Range<usize>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
@@ -36,6 +49,19 @@ impl TypeCheckerResult {
             proof_tree: self.proof_tree.create_alphq_eq_tree(conclusion),
         }
     }
+
+    pub fn create_with_equivalence_tree(
+        &self,
+        conclusion: ProofTreeConclusion,
+        definitions: &[PropDefinition],
+    ) -> Self {
+        Self {
+            goals: self.goals.clone(),
+            proof_tree: self
+                .proof_tree
+                .create_equivalence_tree(conclusion, definitions),
+        }
+    }
 }
 
 #[cfg(test)]
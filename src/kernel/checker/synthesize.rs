@@ -6,18 +6,19 @@ use tsify_next::Tsify;
 
 use crate::kernel::{
     proof_term::{
-        Abort, Application, Case, Function, Ident, LetIn, OrLeft, OrRight, Pair, ProjectFst,
-        ProjectSnd, ProofTerm, ProofTermKind, ProofTermVisitor, Type, TypeAscription,
+        Abort, Application, Case, DoubleNegElim, Function, Ident, LetIn, OrLeft, OrRight, Pair,
+        ProjectFst, ProjectSnd, ProofTerm, ProofTermKind, ProofTermVisitor, Subst, Type,
+        TypeAscription,
     },
     proof_tree::{ProofTree, ProofTreeConclusion, ProofTreeRule},
     prop::{InstatiationError, Prop, PropKind, PropParameter, QuantifierKind},
 };
 
 use super::{
-    check::{check_allowing_free_params, CheckError},
+    check::{check_allowing_free_params, check_term, CheckError},
     identifier::IdentifierFactory,
     identifier_context::IdentifierContext,
-    TypeCheckerResult,
+    Logic, TypeCheckerResult,
 };
 
 #[derive(Debug, Error, PartialEq, Eq, Serialize, Deserialize, Clone, Tsify)]
@@ -61,6 +62,37 @@ pub enum SynthesizeError {
 
     #[error("Quantified object would escape it's scope")]
     QuantifiedObjectEscapesScope(Option<Range<usize>>),
+
+    #[error("Classical reasoning is not allowed in intuitionistic logic")]
+    ClassicalReasoningNotAllowed(Option<Range<usize>>),
+
+    #[error("Argument {argument} of {ident} has datatype {actual}, expected {expected}")]
+    ArgumentDatatypeWrong {
+        ident: String,
+        argument: PropParameter,
+        expected: String,
+        actual: String,
+        span: Option<Range<usize>>,
+    },
+}
+
+impl SynthesizeError {
+    // Location of the offending proof term, if known.
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            SynthesizeError::CheckError(check_err) => check_err.span(),
+            SynthesizeError::UnknownIdentifier(_, span)
+            | SynthesizeError::TypeAnnotationsNeeded(span)
+            | SynthesizeError::UnexpectedPropKind { span, .. }
+            | SynthesizeError::ExpectedPropAsSecondPairComponent { span, .. }
+            | SynthesizeError::CannotReturnDatatype(span)
+            | SynthesizeError::NotSynthesizing(_, span)
+            | SynthesizeError::CaseArmsDifferent { span, .. }
+            | SynthesizeError::QuantifiedObjectEscapesScope(span)
+            | SynthesizeError::ClassicalReasoningNotAllowed(span)
+            | SynthesizeError::ArgumentDatatypeWrong { span, .. } => span.clone(),
+        }
+    }
 }
 
 pub fn synthesize(
@@ -73,6 +105,24 @@ pub fn synthesize(
     proof_term.visit(&mut visitor)
 }
 
+// Unfolds definitions at the head of a synthesized type whose connective gets eliminated.
+pub(super) fn unfold_synthesized(
+    (_type, result): (Type, TypeCheckerResult),
+    ctx: &IdentifierContext,
+) -> (Type, TypeCheckerResult) {
+    let unfolded_type = _type.unfold_head(ctx.definitions());
+    if unfolded_type == _type {
+        return (_type, result);
+    }
+
+    let conclusion = ProofTreeConclusion::PropIsTrue(unfolded_type.clone().into());
+
+    (
+        unfolded_type,
+        result.create_with_equivalence_tree(conclusion, ctx.definitions()),
+    )
+}
+
 struct SynthesizeVisitor<'a> {
     ctx: &'a IdentifierContext,
     identifier_factory: &'a mut IdentifierFactory,
@@ -170,7 +220,10 @@ impl<'a> ProofTermVisitor<Result<(Type, TypeCheckerResult), SynthesizeError>>
     ) -> Result<(Type, TypeCheckerResult), SynthesizeError> {
         let ProjectFst(body, span) = projection;
 
-        let (body_type, body_result) = synthesize(body, self.ctx, self.identifier_factory)?;
+        let (body_type, body_result) = unfold_synthesized(
+            synthesize(body, self.ctx, self.identifier_factory)?,
+            self.ctx,
+        );
 
         let fst = match body_type {
             Type::Prop(Prop::And(fst, _)) => fst,
@@ -202,7 +255,10 @@ impl<'a> ProofTermVisitor<Result<(Type, TypeCheckerResult), SynthesizeError>>
     ) -> Result<(Type, TypeCheckerResult), SynthesizeError> {
         let ProjectSnd(body, span) = projection;
 
-        let (body_type, body_result) = synthesize(body, self.ctx, self.identifier_factory)?;
+        let (body_type, body_result) = unfold_synthesized(
+            synthesize(body, self.ctx, self.identifier_factory)?,
+            self.ctx,
+        );
 
         let snd = match body_type {
             Type::Prop(Prop::And(_, snd)) => snd,
@@ -253,6 +309,28 @@ impl<'a> ProofTermVisitor<Result<(Type, TypeCheckerResult), SynthesizeError>>
                 InstatiationError::UnknownIdentifier(ident) => {
                     SynthesizeError::UnknownIdentifier(ident, span.clone())
                 }
+                InstatiationError::ArgumentDatatypeWrong {
+                    ident,
+                    argument,
+                    expected,
+                    actual,
+                } => SynthesizeError::ArgumentDatatypeWrong {
+                    ident,
+                    argument,
+                    expected,
+                    actual,
+                    span: span.clone(),
+                },
+                InstatiationError::DefinitionArityWrong {
+                    ident,
+                    expected,
+                    actual,
+                } => SynthesizeError::CheckError(Box::new(CheckError::ArityWrong {
+                    ident,
+                    expected,
+                    actual,
+                    span: span.clone(),
+                })),
             })?;
 
         // add param to context
@@ -320,65 +398,58 @@ impl<'a> ProofTermVisitor<Result<(Type, TypeCheckerResult), SynthesizeError>>
         } = application;
 
         // synthesize function
-        let (function_type, function_result) =
-            synthesize(function, self.ctx, self.identifier_factory)?;
-
-        let (requested_applicant_type, return_type, rule) =
-            match function_type {
-                // Implication
-                Type::Prop(Prop::Impl(fst, snd)) => (
-                    Type::Prop(*fst.clone()),
-                    *snd.clone(),
-                    ProofTreeRule::ImplElim,
-                ),
-
-                // Universal quantification
-                Type::Prop(Prop::ForAll {
-                    object_ident,
-                    object_type_ident,
-                    mut body,
-                }) => {
-                    let (param_ident, param_span) = match **applicant {
-                        ProofTerm::Ident(Ident(ref ident, ref span)) => (ident, span),
-                        _ => {
-                            return Err(SynthesizeError::CannotReturnDatatype(
-                                applicant.span().clone(),
-                            ))
-                        }
-                    };
-
-                    let (identifier, _) = self.ctx.get_by_name(param_ident).ok_or(
-                        SynthesizeError::UnknownIdentifier(param_ident.clone(), param_span.clone()),
-                    )?;
-                    body.instantiate_free_parameter(&object_ident, identifier);
-
-                    (
-                        Type::Datatype(object_type_ident.clone()),
-                        *body.clone(),
-                        ProofTreeRule::ForAllElim,
-                    )
-                }
-
-                // other
-                _ => {
-                    return Err(SynthesizeError::UnexpectedPropKind {
-                        expected: vec![PropKind::Impl, PropKind::ForAll],
-                        received: function_type,
-                        span: function.span().clone(),
-                    })
-                }
-            };
-
-        let applicant_result = check_allowing_free_params(
-            applicant,
-            &requested_applicant_type,
+        let (function_type, function_result) = unfold_synthesized(
+            synthesize(function, self.ctx, self.identifier_factory)?,
             self.ctx,
-            self.identifier_factory,
-        )
-        .map_err(|check_err| match check_err {
+        );
+
+        let map_check_err = |check_err| match check_err {
             CheckError::SynthesizeError(synth_err) => synth_err,
             _ => SynthesizeError::CheckError(Box::new(check_err)),
-        })?;
+        };
+
+        let (applicant_result, return_type, rule) = match function_type {
+            // Implication
+            Type::Prop(Prop::Impl(fst, snd)) => {
+                let applicant_result = check_allowing_free_params(
+                    applicant,
+                    &Type::Prop(*fst),
+                    self.ctx,
+                    self.identifier_factory,
+                )
+                .map_err(map_check_err)?;
+
+                (applicant_result, *snd, ProofTreeRule::ImplElim)
+            }
+
+            // Universal quantification
+            Type::Prop(Prop::ForAll {
+                object_ident,
+                object_type_ident,
+                mut body,
+            }) => {
+                let (term, applicant_result) = check_term(
+                    applicant,
+                    &object_type_ident,
+                    self.ctx,
+                    self.identifier_factory,
+                )
+                .map_err(map_check_err)?;
+
+                body.substitute_free_parameter(&object_ident, &term);
+
+                (applicant_result, *body, ProofTreeRule::ForAllElim)
+            }
+
+            // other
+            _ => {
+                return Err(SynthesizeError::UnexpectedPropKind {
+                    expected: vec![PropKind::Impl, PropKind::ForAll],
+                    received: function_type,
+                    span: function.span().clone(),
+                })
+            }
+        };
 
         Ok((
             Type::Prop(return_type.clone()),
@@ -405,7 +476,10 @@ impl<'a> ProofTermVisitor<Result<(Type, TypeCheckerResult), SynthesizeError>>
             ..
         } = let_in;
 
-        let (head_type, pair_result) = synthesize(head, self.ctx, self.identifier_factory)?;
+        let (head_type, pair_result) = unfold_synthesized(
+            synthesize(head, self.ctx, self.identifier_factory)?,
+            self.ctx,
+        );
 
         if let Type::Prop(Prop::Exists {
             object_ident,
@@ -491,7 +565,10 @@ impl<'a> ProofTermVisitor<Result<(Type, TypeCheckerResult), SynthesizeError>>
             span,
         } = case;
 
-        let (head_type, head_result) = synthesize(head, self.ctx, self.identifier_factory)?;
+        let (head_type, head_result) = unfold_synthesized(
+            synthesize(head, self.ctx, self.identifier_factory)?,
+            self.ctx,
+        );
 
         let (fst, snd) = match head_type {
             Type::Prop(Prop::Or(fst, snd)) => (fst, snd),
@@ -517,7 +594,7 @@ impl<'a> ProofTermVisitor<Result<(Type, TypeCheckerResult), SynthesizeError>>
         let (snd_type, snd_result) = synthesize(snd_term, &snd_ctx, self.identifier_factory)?;
 
         // check for alpha-equivalence
-        if !Type::alpha_eq(&fst_type, &snd_type) {
+        if !Type::alpha_eq_modulo_definitions(&fst_type, &snd_type, self.ctx.definitions()) {
             return Err(SynthesizeError::CaseArmsDifferent {
                 fst_type,
                 snd_type,
@@ -557,6 +634,57 @@ impl<'a> ProofTermVisitor<Result<(Type, TypeCheckerResult), SynthesizeError>>
         ))
     }
 
+    fn visit_double_neg_elim(
+        &mut self,
+        double_neg_elim: &DoubleNegElim,
+    ) -> Result<(Type, TypeCheckerResult), SynthesizeError> {
+        let DoubleNegElim(body, span) = double_neg_elim;
+
+        if *self.ctx.logic() != Logic::Classical {
+            return Err(SynthesizeError::ClassicalReasoningNotAllowed(span.clone()));
+        }
+
+        let (body_type, body_result) = unfold_synthesized(
+            synthesize(body, self.ctx, self.identifier_factory)?,
+            self.ctx,
+        );
+
+        // body has to be of the form (A -> False) -> False
+        let prop = match body_type {
+            Type::Prop(Prop::Impl(ref negation, ref falsum)) if **falsum == Prop::False => {
+                match **negation {
+                    Prop::Impl(ref prop, ref falsum) if **falsum == Prop::False => *prop.clone(),
+                    _ => {
+                        return Err(SynthesizeError::UnexpectedPropKind {
+                            expected: vec![PropKind::Impl],
+                            received: Type::Prop(*negation.clone()),
+                            span: span.clone(),
+                        })
+                    }
+                }
+            }
+            _ => {
+                return Err(SynthesizeError::UnexpectedPropKind {
+                    expected: vec![PropKind::Impl],
+                    received: body_type,
+                    span: span.clone(),
+                })
+            }
+        };
+
+        Ok((
+            Type::Prop(prop.clone()),
+            TypeCheckerResult {
+                goals: body_result.goals,
+                proof_tree: ProofTree {
+                    premisses: vec![body_result.proof_tree],
+                    rule: ProofTreeRule::DoubleNegElim,
+                    conclusion: ProofTreeConclusion::PropIsTrue(prop),
+                },
+            },
+        ))
+    }
+
     fn visit_unit(
         &mut self,
         _span: Option<Range<usize>>,
@@ -592,6 +720,28 @@ impl<'a> ProofTermVisitor<Result<(Type, TypeCheckerResult), SynthesizeError>>
                 InstatiationError::UnknownIdentifier(ident) => {
                     SynthesizeError::UnknownIdentifier(ident, span.clone())
                 }
+                InstatiationError::ArgumentDatatypeWrong {
+                    ident,
+                    argument,
+                    expected,
+                    actual,
+                } => SynthesizeError::ArgumentDatatypeWrong {
+                    ident,
+                    argument,
+                    expected,
+                    actual,
+                    span: span.clone(),
+                },
+                InstatiationError::DefinitionArityWrong {
+                    ident,
+                    expected,
+                    actual,
+                } => SynthesizeError::CheckError(Box::new(CheckError::ArityWrong {
+                    ident,
+                    expected,
+                    actual,
+                    span: span.clone(),
+                })),
             })?;
 
         check_allowing_free_params(
@@ -613,4 +763,53 @@ impl<'a> ProofTermVisitor<Result<(Type, TypeCheckerResult), SynthesizeError>>
     ) -> Result<(Type, TypeCheckerResult), SynthesizeError> {
         Err(SynthesizeError::NotSynthesizing(ProofTermKind::Sorry, span))
     }
+
+    fn visit_refl(
+        &mut self,
+        span: Option<Range<usize>>,
+    ) -> Result<(Type, TypeCheckerResult), SynthesizeError> {
+        Err(SynthesizeError::NotSynthesizing(ProofTermKind::Refl, span))
+    }
+
+    fn visit_subst(&mut self, subst: &Subst) -> Result<(Type, TypeCheckerResult), SynthesizeError> {
+        let Subst {
+            equality,
+            body,
+            span,
+        } = subst;
+
+        let (equality_type, equality_result) = unfold_synthesized(
+            synthesize(equality, self.ctx, self.identifier_factory)?,
+            self.ctx,
+        );
+
+        let Type::Prop(Prop::Eq(lhs, rhs)) = equality_type else {
+            return Err(SynthesizeError::UnexpectedPropKind {
+                expected: vec![PropKind::Eq],
+                received: equality_type,
+                span: equality.span().clone(),
+            });
+        };
+
+        let (body_type, body_result) = synthesize(body, self.ctx, self.identifier_factory)?;
+
+        let Type::Prop(mut prop) = body_type else {
+            return Err(SynthesizeError::CannotReturnDatatype(span.clone()));
+        };
+
+        // rewrite lhs into rhs
+        prop.replace_free_parameter(&lhs, &rhs);
+
+        Ok((
+            Type::Prop(prop.clone()),
+            TypeCheckerResult {
+                goals: [equality_result.goals, body_result.goals].concat(),
+                proof_tree: ProofTree {
+                    premisses: vec![equality_result.proof_tree, body_result.proof_tree],
+                    rule: ProofTreeRule::EqElim,
+                    conclusion: ProofTreeConclusion::PropIsTrue(prop),
+                },
+            },
+        ))
+    }
 }
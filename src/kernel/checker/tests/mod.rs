@@ -7,23 +7,26 @@ mod tests {
     use crate::{
         kernel::{
             checker::{
-                check::check,
+                check::{check, CheckError},
                 identifier::{Identifier, IdentifierFactory},
                 identifier_context::IdentifierContext,
                 synthesize::{synthesize, SynthesizeError},
+                Logic,
             },
             parse::{fol::fol_parser, lexer::lexer, proof::proof_parser},
             process::{stages::resolve_datatypes::ResolveDatatypes, ProofPipeline},
+            proof::Proof,
             proof_term::ProofTerm,
             proof_tree::{ProofTree, ProofTreeConclusion, ProofTreeRule},
-            prop::Prop,
+            prop::{Prop, PropParameter},
+            prove::ProverConfig,
         },
         util::counter::Counter,
     };
 
     // HELPER
 
-    fn parse_proof(proof: &str, prop: &Prop) -> ProofTerm {
+    fn parse_processed_proof(proof: &str, prop: &Prop) -> Proof {
         let len = proof.chars().count();
         let tokens = lexer().parse(proof).unwrap();
 
@@ -32,12 +35,14 @@ mod tests {
             .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
             .unwrap();
 
-        let processed_proof = ProofPipeline::new()
+        ProofPipeline::new()
             .pipe(ResolveDatatypes::boxed())
             .apply(proof_ast, &prop)
-            .unwrap();
+            .unwrap()
+    }
 
-        processed_proof.proof_term
+    fn parse_proof(proof: &str, prop: &Prop) -> ProofTerm {
+        parse_processed_proof(proof, prop).proof_term
     }
 
     fn parse_prop(prop: &str) -> Prop {
@@ -54,13 +59,17 @@ mod tests {
 
     fn check_proof_term(proof: &str, prop: &str) -> (Prop, ProofTree) {
         let prop_ast = parse_prop(prop);
-        let proof_term_ast = parse_proof(proof, &prop_ast);
+        let proof_ast = parse_processed_proof(proof, &prop_ast);
 
         (
             prop_ast.clone(),
-            check(&proof_term_ast, &prop_ast, &IdentifierContext::new())
-                .unwrap()
-                .proof_tree,
+            check(
+                &proof_ast.proof_term,
+                &prop_ast,
+                &IdentifierContext::with_signatures(proof_ast.functions, proof_ast.atom_signatures),
+            )
+            .unwrap()
+            .proof_tree,
         )
     }
 
@@ -752,6 +761,391 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sorry_solution_with_quantifiers() {
+        let prop = parse_prop("(\\forall x:t. A(x)) -> \\forall y:t. A(y) && \\exists z:t. A(z)");
+        let proof = parse_processed_proof(
+            "
+            datatype t;
+            atom A(1);
+
+            fn u => fn y => sorry
+        ",
+            &prop,
+        );
+
+        let result = check(
+            &proof.proof_term,
+            &prop,
+            &IdentifierContext::with_signatures(proof.functions, proof.atom_signatures),
+        )
+        .unwrap();
+
+        assert_eq!(result.goals.len(), 1);
+        assert!(result.goals[0].solution.is_some());
+    }
+
+    #[test]
+    fn test_sorry_solution_with_prover_limits() {
+        let prop = parse_prop("(A -> B) -> (B -> C) -> A -> C");
+        let proof = parse_processed_proof(
+            "
+            atom A;
+            atom B;
+            atom C;
+
+            fn u => sorry
+        ",
+            &prop,
+        );
+
+        let mut ctx = IdentifierContext::new();
+        ctx.set_prover_config(ProverConfig {
+            max_steps: 2,
+            ..ProverConfig::default()
+        });
+
+        let result = check(&proof.proof_term, &prop, &ctx).unwrap();
+
+        assert_eq!(result.goals.len(), 1);
+        assert_eq!(result.goals[0].solution, None);
+    }
+
+    #[test]
+    fn test_eq_symmetry() {
+        check_proof_term(
+            "
+                datatype t;
+
+                fn x => fn y => fn u => subst u refl
+            ",
+            "\\forall x:t. \\forall y:t. x = y -> y = x",
+        );
+    }
+
+    #[test]
+    fn test_eq_transitivity() {
+        check_proof_term(
+            "
+                datatype t;
+
+                fn x => fn y => fn z => fn u => fn v => subst v u
+            ",
+            "\\forall x:t. \\forall y:t. \\forall z:t. x = y -> y = z -> x = z",
+        );
+    }
+
+    #[test]
+    fn test_eq_substitution_in_atom() {
+        check_proof_term(
+            "
+                datatype t;
+                atom A(1);
+
+                fn x => fn y => fn u => fn v => subst u v
+            ",
+            "\\forall x:t. \\forall y:t. x = y -> A(x) -> A(y)",
+        );
+    }
+
+    #[test]
+    fn test_eq_synthesize_subst() {
+        check_proof_term(
+            "
+                datatype t;
+                atom A(1);
+
+                fn x => fn y => fn u: x = y => fn v: A(x) => subst u v : A(y)
+            ",
+            "\\forall x:t. \\forall y:t. x = y -> A(x) -> A(y)",
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_eq_refl_different_objects() {
+        check_proof_term(
+            "
+                datatype t;
+
+                fn x => fn y => refl
+            ",
+            "\\forall x:t. \\forall y:t. x = y",
+        );
+    }
+
+    #[test]
+    fn test_forall_elim_with_function_term() {
+        check_proof_term(
+            "
+                datatype nat;
+                atom A(1);
+                function zero: nat;
+                function succ: nat -> nat;
+
+                fn u => u (succ zero)
+            ",
+            "(\\forall x:nat. A(x)) -> A(succ(zero))",
+        );
+    }
+
+    #[test]
+    fn test_forall_elim_with_nested_function_term() {
+        check_proof_term(
+            "
+                datatype nat;
+                atom A(1);
+                function succ: nat -> nat;
+                function add: nat -> nat -> nat;
+
+                fn u => fn y => u (add y (succ y))
+            ",
+            "(\\forall x:nat. A(x)) -> \\forall y:nat. A(add(y, succ(y)))",
+        );
+    }
+
+    #[test]
+    fn test_forall_elim_function_term_proof_tree() {
+        let (_, proof_tree) = check_proof_term(
+            "
+                datatype nat;
+                atom A(1);
+                function zero: nat;
+
+                fn u => u zero
+            ",
+            "(\\forall x:nat. A(x)) -> A(zero)",
+        );
+
+        let zero = PropParameter::Function("zero".to_string(), vec![]);
+
+        assert_eq!(
+            proof_tree.premisses[0].premisses[1],
+            ProofTree {
+                premisses: vec![],
+                rule: ProofTreeRule::FunctionApplication("zero".to_string()),
+                conclusion: ProofTreeConclusion::TermJudgement(zero, "nat".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_exists_intro_with_function_term() {
+        check_proof_term(
+            "
+                datatype t;
+                atom P(1);
+                function succ: t -> t;
+
+                fn x => fn h => (succ x, h)
+            ",
+            "\\forall x:t. P(succ(x)) -> \\exists y:t. P(y)",
+        );
+    }
+
+    #[test]
+    fn test_eq_with_function_terms() {
+        check_proof_term(
+            "
+                datatype nat;
+                function succ: nat -> nat;
+
+                fn x => fn y => fn u => subst u refl
+            ",
+            "\\forall x:nat. \\forall y:nat. succ(x) = y -> y = succ(x)",
+        );
+    }
+
+    #[test]
+    fn test_forall_elim_ill_sorted_term() {
+        let prop = parse_prop("(\\forall x:nat. A(x)) -> \\forall y:t. \\top");
+        let proof = parse_processed_proof(
+            "
+                datatype nat;
+                datatype t;
+                atom A(1);
+                function f: t -> t;
+
+                fn u => fn y => snd (u (f y), ())
+            ",
+            &prop,
+        );
+
+        let result = check(
+            &proof.proof_term,
+            &prop,
+            &IdentifierContext::with_functions(proof.functions),
+        );
+
+        assert!(matches!(result, Err(CheckError::UnexpectedType { .. })));
+    }
+
+    #[test]
+    fn test_forall_elim_function_arity_wrong() {
+        let prop = parse_prop("(\\forall x:nat. A(x)) -> \\forall y:nat. \\top");
+        let proof = parse_processed_proof(
+            "
+                datatype nat;
+                atom A(1);
+                function add: nat -> nat -> nat;
+
+                fn u => fn y => snd (u (add y), ())
+            ",
+            &prop,
+        );
+
+        let result = check(
+            &proof.proof_term,
+            &prop,
+            &IdentifierContext::with_functions(proof.functions),
+        );
+
+        assert!(matches!(result, Err(CheckError::ArityWrong { .. })));
+    }
+
+    #[test]
+    fn test_sorry_with_constant_in_goal() {
+        check_proof_term(
+            "
+                datatype nat;
+                atom A(1);
+                function zero: nat;
+
+                fn x => sorry
+            ",
+            "\\forall x:nat. A(x) -> A(zero)",
+        );
+    }
+
+    #[test]
+    fn test_sorted_atoms() {
+        check_proof_term(
+            "
+                datatype nat;
+                datatype person;
+                atom Less(nat, nat);
+                atom Older(person, person);
+
+                fn x: nat => fn p: person => fn u: Less(x, x) && Older(p, p) => u
+            ",
+            "\\forall x:nat. \\forall p:person. Less(x, x) && Older(p, p) -> Less(x, x) && Older(p, p)",
+        );
+    }
+
+    #[test]
+    fn test_sorted_atom_annotation_datatype_wrong() {
+        let prop = parse_prop("\\forall p:person. \\top -> \\top");
+        let proof = parse_processed_proof(
+            "
+                datatype nat;
+                datatype person;
+                atom Less(nat, nat);
+
+                fn p: person => fn u: Less(p, p) => ()
+            ",
+            &prop,
+        );
+
+        let result = check(
+            &proof.proof_term,
+            &prop,
+            &IdentifierContext::with_signatures(proof.functions, proof.atom_signatures),
+        );
+
+        assert!(matches!(result, Err(CheckError::ArgumentDatatypeWrong { .. })));
+    }
+
+    fn check_classical_proof_term(proof: &str, prop: &str) {
+        let prop = parse_prop(prop);
+        let proof = parse_processed_proof(proof, &prop);
+
+        let mut ctx = IdentifierContext::new();
+        ctx.set_logic(Logic::Classical);
+
+        check(&proof.proof_term, &prop, &ctx).unwrap();
+    }
+
+    #[test]
+    fn test_double_negation_elimination() {
+        check_classical_proof_term("atom A; fn u => dne u", "~~A -> A");
+    }
+
+    #[test]
+    fn test_excluded_middle() {
+        check_classical_proof_term(
+            "atom A; dne (fn u => u (inr (fn a => u (inl a))))",
+            "A || ~A",
+        );
+    }
+
+    #[test]
+    fn test_peirce() {
+        check_classical_proof_term(
+            "atom A; atom B; fn f => dne (fn u => u (f (fn a => abort (u a))))",
+            "((A -> B) -> A) -> A",
+        );
+    }
+
+    #[test]
+    fn test_double_negation_elimination_synthesizing() {
+        check_classical_proof_term("atom A; fn u => (dne u, ())", "~~A -> A && \\top");
+    }
+
+    #[test]
+    fn test_double_negation_elimination_intuitionistic() {
+        let prop = parse_prop("~~A -> A");
+        let proof = parse_proof("atom A; fn u => dne u", &prop);
+
+        assert!(matches!(
+            check(&proof, &prop, &IdentifierContext::new()),
+            Err(CheckError::ClassicalReasoningNotAllowed(_))
+        ));
+    }
+
+    #[test]
+    fn test_iff_intro() {
+        check_proof_term("atom A; (fn u => u, fn u => u)", "A <-> A");
+    }
+
+    #[test]
+    fn test_iff_elim() {
+        check_proof_term(
+            "atom A; atom B; fn h => (snd h, fst h)",
+            "(A <-> B) -> (B <-> A)",
+        );
+    }
+
+    #[test]
+    fn test_iff_as_assumption() {
+        check_proof_term(
+            "atom A; atom B; fn h => fn a => fst h a",
+            "(A <-> B) -> A -> B",
+        );
+    }
+
+    #[test]
+    fn test_iff_unfolded_conclusion() {
+        let (_, proof_tree) =
+            check_proof_term("atom A; fn u => u", "(A <-> A) -> (A -> A) && (A -> A)");
+
+        assert!(matches!(proof_tree.rule, ProofTreeRule::ImplIntro(_)));
+        assert_eq!(proof_tree.premisses[0].rule, ProofTreeRule::DeltaEquivalent);
+    }
+
+    #[test]
+    fn test_declared_definition() {
+        let prop = parse_prop("Not(A) -> A -> B");
+        let proof = parse_processed_proof(
+            "atom A; atom B; def Not(X) := X -> \\bot; fn u => fn a => abort (u a)",
+            &prop,
+        );
+
+        let mut ctx = IdentifierContext::new();
+        ctx.add_definitions(proof.definitions);
+
+        assert!(check(&proof.proof_term, &prop, &ctx).is_ok());
+    }
+
     // #[test]
     // fn test_sorry_in_application_as_function() {
     //     check_proof_term(
@@ -0,0 +1,619 @@
+use itertools::Itertools;
+
+use crate::kernel::{
+    proof::Proof,
+    proof_term::{ProofTerm, Type},
+    proof_tree::{ProofTree, ProofTreeConclusion, ProofTreeRule},
+    prop::{AtomSignature, FunctionSignature, Prop, PropDefinition, PropParameter},
+};
+
+use super::{checked_proof_tree, ProofExporter};
+
+const PREAMBLE: &str = "data ⊥ : Set where
+
+⊥-elim : {A : Set} → ⊥ → A
+⊥-elim ()
+
+record ⊤ : Set where
+  constructor tt
+
+infix 3 ¬_
+¬_ : Set → Set
+¬ A = A → ⊥
+
+record Σ (A : Set) (B : A → Set) : Set where
+  constructor _,_
+  field
+    proj₁ : A
+    proj₂ : B proj₁
+open Σ public
+infixr 4 _,_
+
+infixr 2 _×_
+_×_ : Set → Set → Set
+A × B = Σ A (λ _ → B)
+
+infix 0 _↔_
+_↔_ : Set → Set → Set
+A ↔ B = (A → B) × (B → A)
+
+infixr 1 _⊎_
+data _⊎_ (A B : Set) : Set where
+  inj₁ : A → A ⊎ B
+  inj₂ : B → A ⊎ B
+
+infix 4 _≡_
+data _≡_ {A : Set} (x : A) : A → Set where
+  refl : x ≡ x
+
+subst : {A : Set} (P : A → Set) {x y : A} → x ≡ y → P x → P y
+subst P refl p = p
+
+case_of_ : {A B : Set} → A → (A → B) → B
+case x of f = f x";
+
+// Keywords and names of the preamble that declarations must not shadow.
+const AGDA_RESERVED_NAMES: [&str; 22] = [
+    "case",
+    "constructor",
+    "data",
+    "dne",
+    "field",
+    "forall",
+    "import",
+    "in",
+    "let",
+    "module",
+    "of",
+    "open",
+    "postulate",
+    "proof",
+    "record",
+    "refl",
+    "Set",
+    "subst",
+    "tt",
+    "where",
+    "with",
+    "Σ",
+];
+
+// Like the Coq exporter, the proof is translated from its checked proof tree,
+// which knows the motive of every substitution.
+pub struct AgdaExporter {
+    prop: Prop,
+    datatypes: Vec<String>,
+    atoms: Vec<(String, usize)>,
+    atom_signatures: Vec<AtomSignature>,
+    functions: Vec<FunctionSignature>,
+    definitions: Vec<PropDefinition>,
+}
+
+impl AgdaExporter {
+    pub fn new(prop: &Prop, proof: &Proof) -> Self {
+        // definitions used like atoms, e.g. "Iff(A, B)", are exported with their notation
+        let definitions = [PropDefinition::builtins(), proof.definitions.clone()].concat();
+        let mut prop = prop.clone();
+        let _ = prop.resolve_definitions(&definitions);
+
+        Self {
+            prop,
+            datatypes: proof.datatypes.clone(),
+            atoms: proof.atoms.clone(),
+            atom_signatures: proof.atom_signatures.clone(),
+            functions: proof.functions.clone(),
+            definitions: proof.definitions.clone(),
+        }
+    }
+
+    fn proof_tree(&self, proof_term: &ProofTerm) -> Option<ProofTree> {
+        checked_proof_tree(
+            proof_term,
+            &self.prop,
+            &self.functions,
+            &self.atom_signatures,
+            &self.definitions,
+        )
+    }
+
+    fn generate_postulates(&self, classical: bool) -> Vec<String> {
+        let mut postulates = vec![];
+
+        for datatype in self.datatypes.iter() {
+            postulates.push(format!("{} : Set", agda_ident(datatype)));
+        }
+
+        for FunctionSignature {
+            name,
+            param_types,
+            return_type,
+        } in self.functions.iter()
+        {
+            let signature = param_types
+                .iter()
+                .chain([return_type])
+                .map(|datatype| agda_ident(datatype))
+                .join(" → ");
+
+            postulates.push(format!("{} : {}", agda_ident(name), signature));
+        }
+
+        for (name, arity) in self.atoms.iter() {
+            let signature = self
+                .atom_signatures
+                .iter()
+                .find(|signature| signature.name == *name);
+
+            let postulate = match (signature, arity) {
+                (_, 0) => format!("{} : Set", agda_ident(name)),
+                (Some(AtomSignature { param_types, .. }), _) => format!(
+                    "{} : {} → Set",
+                    agda_ident(name),
+                    param_types
+                        .iter()
+                        .map(|datatype| agda_ident(datatype))
+                        .join(" → ")
+                ),
+                // unsorted parameters may be of any datatype
+                (None, _) => {
+                    let type_variables = (1..=*arity).map(|i| format!("T{}", i)).collect_vec();
+
+                    format!(
+                        "{} : {{{} : Set}} → {} → Set",
+                        agda_ident(name),
+                        type_variables.join(" "),
+                        type_variables.join(" → ")
+                    )
+                }
+            };
+
+            postulates.push(postulate);
+        }
+
+        if classical {
+            postulates.push("dne : {A : Set} → ¬ ¬ A → A".to_string());
+        }
+
+        postulates
+    }
+
+    fn generate_definitions(&self) -> Vec<String> {
+        // Iff is part of the preamble
+        self.definitions
+            .iter()
+            .filter(|definition| definition.name != "Iff")
+            .map(|PropDefinition { name, params, body }| {
+                let signature = params.iter().map(|_| "Set → ").join("");
+                let lhs = [name]
+                    .into_iter()
+                    .chain(params.iter())
+                    .map(|ident| agda_ident(ident))
+                    .join(" ");
+
+                format!(
+                    "{} : {}Set\n{} = {}",
+                    agda_ident(name),
+                    signature,
+                    lhs,
+                    agda_prop(body)
+                )
+            })
+            .collect()
+    }
+
+    fn is_atomic(proof_tree: &ProofTree) -> bool {
+        if !matches!(proof_tree.conclusion, ProofTreeConclusion::PropIsTrue(_)) {
+            return !matches!(
+                proof_tree.conclusion_term(),
+                PropParameter::Function(_, params) if !params.is_empty()
+            );
+        }
+
+        match proof_tree.rule {
+            ProofTreeRule::AlphaEquivalent | ProofTreeRule::DeltaEquivalent => {
+                Self::is_atomic(&proof_tree.premisses[0])
+            }
+            ProofTreeRule::Ident(_)
+            | ProofTreeRule::TrueIntro
+            | ProofTreeRule::EqIntro
+            | ProofTreeRule::Sorry => true,
+            _ => false,
+        }
+    }
+
+    fn generate_agda_argument(proof_tree: &ProofTree) -> String {
+        if Self::is_atomic(proof_tree) {
+            Self::generate_agda_term(proof_tree)
+        } else {
+            format!("({})", Self::generate_agda_term(proof_tree))
+        }
+    }
+
+    fn generate_agda_term(proof_tree: &ProofTree) -> String {
+        let ProofTree {
+            premisses,
+            rule,
+            conclusion,
+        } = proof_tree;
+
+        // terms of ∀E and ∃I
+        if !matches!(conclusion, ProofTreeConclusion::PropIsTrue(_)) {
+            return agda_term(&proof_tree.conclusion_term());
+        }
+
+        match rule {
+            ProofTreeRule::AlphaEquivalent | ProofTreeRule::DeltaEquivalent => {
+                Self::generate_agda_term(&premisses[0])
+            }
+            ProofTreeRule::Ident(ident) => agda_ident(ident.name()),
+            ProofTreeRule::TrueIntro => "tt".to_string(),
+            ProofTreeRule::EqIntro => "refl".to_string(),
+            ProofTreeRule::Sorry => "{! !}".to_string(),
+            ProofTreeRule::AndIntro | ProofTreeRule::ExistsIntro => format!(
+                "{} , {}",
+                Self::generate_agda_argument(&premisses[0]),
+                Self::generate_agda_term(&premisses[1])
+            ),
+            ProofTreeRule::AndElimFst => {
+                format!("proj₁ {}", Self::generate_agda_argument(&premisses[0]))
+            }
+            ProofTreeRule::AndElimSnd => {
+                format!("proj₂ {}", Self::generate_agda_argument(&premisses[0]))
+            }
+            ProofTreeRule::ImplIntro(ident) | ProofTreeRule::ForAllIntro(ident) => format!(
+                "λ {} → {}",
+                agda_ident(ident.name()),
+                Self::generate_agda_term(&premisses[0])
+            ),
+            ProofTreeRule::ImplElim | ProofTreeRule::ForAllElim => {
+                // application is left associative
+                let function = match premisses[0].rule {
+                    ProofTreeRule::ImplElim | ProofTreeRule::ForAllElim => {
+                        Self::generate_agda_term(&premisses[0])
+                    }
+                    _ => Self::generate_agda_argument(&premisses[0]),
+                };
+
+                format!(
+                    "{} {}",
+                    function,
+                    Self::generate_agda_argument(&premisses[1])
+                )
+            }
+            ProofTreeRule::OrIntroFst => {
+                format!("inj₁ {}", Self::generate_agda_argument(&premisses[0]))
+            }
+            ProofTreeRule::OrIntroSnd => {
+                format!("inj₂ {}", Self::generate_agda_argument(&premisses[0]))
+            }
+            ProofTreeRule::OrElim(fst_ident, snd_ident) => format!(
+                "case {} of λ {{ (inj₁ {}) → {} ; (inj₂ {}) → {} }}",
+                Self::generate_agda_argument(&premisses[0]),
+                agda_ident(fst_ident.name()),
+                Self::generate_agda_term(&premisses[1]),
+                agda_ident(snd_ident.name()),
+                Self::generate_agda_term(&premisses[2]),
+            ),
+            ProofTreeRule::ExistsElim(fst_ident, snd_ident) => format!(
+                "case {} of λ {{ ({} , {}) → {} }}",
+                Self::generate_agda_argument(&premisses[0]),
+                agda_ident(fst_ident.name()),
+                agda_ident(snd_ident.name()),
+                Self::generate_agda_term(&premisses[1]),
+            ),
+            ProofTreeRule::FalsumElim => {
+                format!("⊥-elim {}", Self::generate_agda_argument(&premisses[0]))
+            }
+            ProofTreeRule::DoubleNegElim => {
+                format!("dne {}", Self::generate_agda_argument(&premisses[0]))
+            }
+            ProofTreeRule::EqElim => {
+                let Prop::Eq(_, rhs) = premisses[0].conclusion_prop() else {
+                    panic!("Expected equality.");
+                };
+
+                // the motive abstracts over the occurrences of the right hand side
+                let mut motive = proof_tree.conclusion_prop().clone();
+                let mut variable = "z".to_string();
+                while motive.contains_name(&variable) {
+                    variable.push('\'');
+                }
+                motive
+                    .replace_free_parameter(rhs, &PropParameter::Uninstantiated(variable.clone()));
+
+                format!(
+                    "subst (λ {} → {}) {} {}",
+                    variable,
+                    agda_prop(&motive),
+                    Self::generate_agda_argument(&premisses[0]),
+                    Self::generate_agda_argument(&premisses[1])
+                )
+            }
+            ProofTreeRule::FunctionApplication(_) => agda_term(&proof_tree.conclusion_term()),
+        }
+    }
+}
+
+impl ProofExporter for AgdaExporter {
+    fn export(&self, proof_term: &ProofTerm) -> String {
+        let Some(proof_tree) = self.proof_tree(proof_term) else {
+            return "-- The proof term does not prove the proposition.".to_string();
+        };
+
+        let mut sections = vec!["module Proof where".to_string(), PREAMBLE.to_string()];
+
+        let postulates =
+            self.generate_postulates(proof_tree.contains_rule(&ProofTreeRule::DoubleNegElim));
+        if !postulates.is_empty() {
+            sections.push(format!("postulate\n  {}", postulates.join("\n  ")));
+        }
+
+        sections.extend(self.generate_definitions());
+
+        sections.push(format!(
+            "proof : {}\nproof = {}",
+            agda_prop(&self.prop),
+            Self::generate_agda_term(&proof_tree)
+        ));
+
+        sections.join("\n\n")
+    }
+
+    fn can_export(&self, proof_term: &ProofTerm) -> bool {
+        self.proof_tree(proof_term).is_some()
+    }
+
+    fn can_export_for_type(&self, _type: Type) -> bool {
+        _type.is_prop()
+    }
+}
+
+fn agda_ident(ident: &str) -> String {
+    if AGDA_RESERVED_NAMES.contains(&ident) {
+        format!("{}'", ident)
+    } else {
+        ident.to_string()
+    }
+}
+
+fn agda_term(term: &PropParameter) -> String {
+    match term {
+        PropParameter::Uninstantiated(_) | PropParameter::Instantiated(_) => {
+            agda_ident(term.name())
+        }
+        PropParameter::Function(name, params) if params.is_empty() => agda_ident(name),
+        PropParameter::Function(name, params) => format!(
+            "{} {}",
+            agda_ident(name),
+            params.iter().map(agda_term_argument).join(" ")
+        ),
+    }
+}
+
+fn agda_term_argument(term: &PropParameter) -> String {
+    match term {
+        PropParameter::Function(_, params) if !params.is_empty() => {
+            format!("({})", agda_term(term))
+        }
+        _ => agda_term(term),
+    }
+}
+
+// Precedences of the operators of the preamble, function types bind weakest.
+fn agda_precedence(prop: &Prop) -> usize {
+    match prop {
+        Prop::ForAll { .. } => 0,
+        Prop::Impl(_, snd) if **snd == Prop::False => 40,
+        Prop::Impl(_, _) => 0,
+        Prop::Defined(_, _) if prop.is_iff() => 10,
+        Prop::Or(_, _) => 20,
+        Prop::And(_, _) => 30,
+        Prop::Eq(_, _) => 50,
+        Prop::Atom(_, params) if !params.is_empty() => 100,
+        Prop::Defined(_, args) if !args.is_empty() => 100,
+        Prop::Exists { .. } => 100,
+        Prop::Atom(_, _) | Prop::Defined(_, _) | Prop::True | Prop::False => 1000,
+    }
+}
+
+pub fn agda_prop(prop: &Prop) -> String {
+    agda_prop_at(prop, 0)
+}
+
+// Renders the proposition as operand that needs at least the given precedence.
+fn agda_prop_at(prop: &Prop, precedence: usize) -> String {
+    let code = match prop {
+        Prop::Atom(name, params) if params.is_empty() => agda_ident(name),
+        Prop::Atom(name, params) => format!(
+            "{} {}",
+            agda_ident(name),
+            params.iter().map(agda_term_argument).join(" ")
+        ),
+        Prop::Eq(lhs, rhs) => format!("{} ≡ {}", agda_term(lhs), agda_term(rhs)),
+        Prop::True => "⊤".to_string(),
+        Prop::False => "⊥".to_string(),
+        Prop::ForAll {
+            object_ident,
+            object_type_ident,
+            body,
+        } => format!(
+            "({} : {}) → {}",
+            agda_ident(object_ident),
+            agda_ident(object_type_ident),
+            agda_prop_at(body, 0)
+        ),
+        Prop::Exists {
+            object_ident,
+            object_type_ident,
+            body,
+        } => format!(
+            "Σ {} (λ {} → {})",
+            agda_ident(object_type_ident),
+            agda_ident(object_ident),
+            agda_prop_at(body, 0)
+        ),
+        Prop::Defined(_, args) if prop.is_iff() => format!(
+            "{} ↔ {}",
+            agda_prop_at(&args[0], 11),
+            agda_prop_at(&args[1], 11)
+        ),
+        Prop::Defined(name, args) if args.is_empty() => agda_ident(name),
+        Prop::Defined(name, args) => format!(
+            "{} {}",
+            agda_ident(name),
+            args.iter().map(|arg| agda_prop_at(arg, 1000)).join(" ")
+        ),
+        Prop::Impl(fst, snd) if **snd == Prop::False => format!("¬ {}", agda_prop_at(fst, 40)),
+        Prop::Impl(fst, snd) => format!("{} → {}", agda_prop_at(fst, 1), agda_prop_at(snd, 0)),
+        Prop::And(fst, snd) => format!("{} × {}", agda_prop_at(fst, 31), agda_prop_at(snd, 30)),
+        Prop::Or(fst, snd) => format!("{} ⊎ {}", agda_prop_at(fst, 21), agda_prop_at(snd, 20)),
+    };
+
+    match agda_precedence(prop) < precedence {
+        true => format!("({})", code),
+        false => code,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        export::ProofExporter,
+        parse::{fol::fol_parser, lexer::lexer, proof::proof_parser},
+        process::{stages::resolve_datatypes::ResolveDatatypes, ProofPipeline},
+        prop::Prop,
+    };
+
+    use super::{agda_prop, AgdaExporter, PREAMBLE};
+
+    fn parse_prop(prop: &str) -> Prop {
+        let len = prop.chars().count();
+
+        let tokens = lexer().parse(prop).unwrap();
+        fol_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    // Returns the module without its preamble.
+    fn export(proof: &str, prop: &str) -> String {
+        let prop = parse_prop(prop);
+
+        let len = proof.chars().count();
+        let tokens = lexer().parse(proof).unwrap();
+        let proof_ast = proof_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+        let proof = ProofPipeline::new()
+            .pipe(ResolveDatatypes::boxed())
+            .apply(proof_ast, &prop)
+            .unwrap();
+
+        let module = AgdaExporter::new(&prop, &proof).export(&proof.proof_term);
+        let (header, body) = module.split_once(PREAMBLE).unwrap();
+        assert_eq!(header, "module Proof where\n\n");
+
+        body.trim_start().to_string()
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_agda_prop() {
+        assert_eq!(
+            agda_prop(&parse_prop("(A -> B) -> ~C && (A || B)")),
+            "(A → B) → ¬ C × (A ⊎ B)"
+        );
+        assert_eq!(
+            agda_prop(&parse_prop(
+                "(\\forall x:t. P(x)) && ~(\\exists y:t. x = f(y)) <-> A -> \\forall z:t. P(z)"
+            )),
+            "((x : t) → P x) × ¬ Σ t (λ y → x ≡ f y) ↔ (A → (z : t) → P z)"
+        );
+    }
+
+    #[test]
+    fn test_export_propositional() {
+        assert_eq!(
+            export(
+                "atom A; atom B; fn u => fn v => case u of inl a => abort (v a), inr b => fst (b, v)",
+                "A || B -> ~A -> B"
+            ),
+            "postulate
+  A : Set
+  B : Set
+
+proof : A ⊎ B → ¬ A → B
+proof = λ u → λ v → case u of λ { (inj₁ a) → ⊥-elim (v a) ; (inj₂ b) → proj₁ (b , v) }"
+        );
+    }
+
+    #[test]
+    fn test_export_iff_atom() {
+        assert_eq!(
+            export(
+                "atom A; atom B; fn u => (snd u, fst u)",
+                "Iff(A, B) -> Iff(B, A)"
+            ),
+            "postulate
+  A : Set
+  B : Set
+
+proof : A ↔ B → B ↔ A
+proof = λ u → (proj₂ u) , proj₁ u"
+        );
+    }
+
+    #[test]
+    fn test_export_quantifiers() {
+        assert_eq!(
+            export(
+                "datatype t; function f: t -> t; atom P(t); atom Q(1);
+                fn u => fn v => let (x, w) = u in (x, v x w)",
+                "(\\exists x:t. P(x)) -> (\\forall x:t. P(x) -> Q(f(x))) -> \\exists y:t. Q(f(y))"
+            ),
+            "postulate
+  t : Set
+  f : t → t
+  P : t → Set
+  Q : {T1 : Set} → T1 → Set
+
+proof : Σ t (λ x → P x) → ((x : t) → P x → Q (f x)) → Σ t (λ y → Q (f y))
+proof = λ u → λ v → case u of λ { (x , w) → x , v x w }"
+        );
+    }
+
+    #[test]
+    fn test_export_equality() {
+        assert_eq!(
+            export(
+                "datatype t; atom P(t); fn x => fn y => fn u => fn v => subst u v",
+                "\\forall x:t. \\forall y:t. x = y -> P(x) -> P(y)"
+            ),
+            "postulate
+  t : Set
+  P : t → Set
+
+proof : (x : t) → (y : t) → x ≡ y → P x → P y
+proof = λ x → λ y → λ u → λ v → subst (λ z → P z) u v"
+        );
+    }
+
+    #[test]
+    fn test_export_classical_with_sorry() {
+        assert_eq!(
+            export("atom A; atom B; fn u => (dne u, sorry)", "~~A -> A && B"),
+            "postulate
+  A : Set
+  B : Set
+  dne : {A : Set} → ¬ ¬ A → A
+
+proof : ¬ ¬ A → A × B
+proof = λ u → (dne u) , {! !}"
+        );
+    }
+}
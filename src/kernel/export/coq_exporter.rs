@@ -0,0 +1,580 @@
+use itertools::Itertools;
+
+use crate::kernel::{
+    proof::Proof,
+    proof_term::{ProofTerm, Type},
+    proof_tree::{ProofTree, ProofTreeConclusion, ProofTreeRule},
+    prop::{AtomSignature, FunctionSignature, Prop, PropDefinition, PropParameter},
+};
+
+use super::{checked_proof_tree, ProofExporter};
+
+// Keywords and constants of the standard library that declarations must not shadow.
+const COQ_RESERVED_NAMES: [&str; 22] = [
+    "as", "at", "cofix", "else", "end", "exists", "False", "fix", "for", "forall", "fun", "I",
+    "if", "in", "let", "match", "Prop", "return", "Set", "then", "True", "Type",
+];
+
+// Pairs are either conjunctions or existential witnesses, so the proof is translated
+// from its checked proof tree instead of the bare proof term.
+pub struct CoqExporter {
+    prop: Prop,
+    datatypes: Vec<String>,
+    atoms: Vec<(String, usize)>,
+    atom_signatures: Vec<AtomSignature>,
+    functions: Vec<FunctionSignature>,
+    definitions: Vec<PropDefinition>,
+}
+
+impl CoqExporter {
+    pub fn new(prop: &Prop, proof: &Proof) -> Self {
+        // definitions used like atoms, e.g. "Iff(A, B)", are exported with their notation
+        let definitions = [PropDefinition::builtins(), proof.definitions.clone()].concat();
+        let mut prop = prop.clone();
+        let _ = prop.resolve_definitions(&definitions);
+
+        Self {
+            prop,
+            datatypes: proof.datatypes.clone(),
+            atoms: proof.atoms.clone(),
+            atom_signatures: proof.atom_signatures.clone(),
+            functions: proof.functions.clone(),
+            definitions: proof.definitions.clone(),
+        }
+    }
+
+    fn proof_tree(&self, proof_term: &ProofTerm) -> Option<ProofTree> {
+        checked_proof_tree(
+            proof_term,
+            &self.prop,
+            &self.functions,
+            &self.atom_signatures,
+            &self.definitions,
+        )
+    }
+
+    fn generate_declarations(&self) -> Vec<String> {
+        let mut declarations = vec![];
+
+        for datatype in self.datatypes.iter() {
+            declarations.push(format!("Parameter {} : Type.", coq_ident(datatype)));
+        }
+
+        for FunctionSignature {
+            name,
+            param_types,
+            return_type,
+        } in self.functions.iter()
+        {
+            let signature = param_types
+                .iter()
+                .chain([return_type])
+                .map(|datatype| coq_ident(datatype))
+                .join(" -> ");
+
+            declarations.push(format!("Parameter {} : {}.", coq_ident(name), signature));
+        }
+
+        for (name, arity) in self.atoms.iter() {
+            let signature = self
+                .atom_signatures
+                .iter()
+                .find(|signature| signature.name == *name);
+
+            let declaration = match (signature, arity) {
+                (_, 0) => format!("Parameter {} : Prop.", coq_ident(name)),
+                (Some(AtomSignature { param_types, .. }), _) => format!(
+                    "Parameter {} : {} -> Prop.",
+                    coq_ident(name),
+                    param_types
+                        .iter()
+                        .map(|datatype| coq_ident(datatype))
+                        .join(" -> ")
+                ),
+                // unsorted parameters may be of any datatype
+                (None, _) => {
+                    let type_variables = (1..=*arity).map(|i| format!("T{}", i)).collect_vec();
+
+                    format!(
+                        "Parameter {} : forall {{{} : Type}}, {} -> Prop.",
+                        coq_ident(name),
+                        type_variables.join(" "),
+                        type_variables.join(" -> ")
+                    )
+                }
+            };
+
+            declarations.push(declaration);
+        }
+
+        // Iff is translated to the biconditional of Coq
+        for PropDefinition { name, params, body } in self.definitions.iter() {
+            if name == "Iff" {
+                continue;
+            }
+
+            let params = match params.is_empty() {
+                true => "".to_string(),
+                false => format!(
+                    " ({} : Prop)",
+                    params.iter().map(|param| coq_ident(param)).join(" ")
+                ),
+            };
+
+            declarations.push(format!(
+                "Definition {}{} : Prop := {}.",
+                coq_ident(name),
+                params,
+                coq_prop(body)
+            ));
+        }
+
+        declarations
+    }
+
+    fn is_atomic(proof_tree: &ProofTree) -> bool {
+        if !matches!(proof_tree.conclusion, ProofTreeConclusion::PropIsTrue(_)) {
+            return !matches!(
+                proof_tree.conclusion_term(),
+                PropParameter::Function(_, params) if !params.is_empty()
+            );
+        }
+
+        match proof_tree.rule {
+            ProofTreeRule::AlphaEquivalent | ProofTreeRule::DeltaEquivalent => {
+                Self::is_atomic(&proof_tree.premisses[0])
+            }
+            ProofTreeRule::Ident(_)
+            | ProofTreeRule::TrueIntro
+            | ProofTreeRule::EqIntro
+            | ProofTreeRule::Sorry => true,
+            _ => false,
+        }
+    }
+
+    fn generate_coq_argument(proof_tree: &ProofTree) -> String {
+        if Self::is_atomic(proof_tree) {
+            Self::generate_coq_term(proof_tree)
+        } else {
+            format!("({})", Self::generate_coq_term(proof_tree))
+        }
+    }
+
+    fn generate_coq_term(proof_tree: &ProofTree) -> String {
+        let ProofTree {
+            premisses,
+            rule,
+            conclusion,
+        } = proof_tree;
+
+        // terms of ∀E and ∃I
+        if !matches!(conclusion, ProofTreeConclusion::PropIsTrue(_)) {
+            return coq_term(&proof_tree.conclusion_term());
+        }
+
+        match rule {
+            ProofTreeRule::AlphaEquivalent | ProofTreeRule::DeltaEquivalent => {
+                Self::generate_coq_term(&premisses[0])
+            }
+            ProofTreeRule::Ident(ident) => coq_ident(ident.name()),
+            ProofTreeRule::TrueIntro => "I".to_string(),
+            ProofTreeRule::EqIntro => "eq_refl".to_string(),
+            ProofTreeRule::Sorry => "_".to_string(),
+            ProofTreeRule::AndIntro => format!(
+                "conj {} {}",
+                Self::generate_coq_argument(&premisses[0]),
+                Self::generate_coq_argument(&premisses[1])
+            ),
+            ProofTreeRule::AndElimFst => {
+                format!("proj1 {}", Self::generate_coq_argument(&premisses[0]))
+            }
+            ProofTreeRule::AndElimSnd => {
+                format!("proj2 {}", Self::generate_coq_argument(&premisses[0]))
+            }
+            ProofTreeRule::ImplIntro(ident) => format!(
+                "fun {} => {}",
+                coq_ident(ident.name()),
+                Self::generate_coq_term(&premisses[0])
+            ),
+            ProofTreeRule::ImplElim | ProofTreeRule::ForAllElim => {
+                // application is left associative
+                let function = match premisses[0].rule {
+                    ProofTreeRule::ImplElim | ProofTreeRule::ForAllElim => {
+                        Self::generate_coq_term(&premisses[0])
+                    }
+                    _ => Self::generate_coq_argument(&premisses[0]),
+                };
+
+                format!(
+                    "{} {}",
+                    function,
+                    Self::generate_coq_argument(&premisses[1])
+                )
+            }
+            ProofTreeRule::OrIntroFst => {
+                format!("or_introl {}", Self::generate_coq_argument(&premisses[0]))
+            }
+            ProofTreeRule::OrIntroSnd => {
+                format!("or_intror {}", Self::generate_coq_argument(&premisses[0]))
+            }
+            ProofTreeRule::OrElim(fst_ident, snd_ident) => format!(
+                "match {} with or_introl {} => {} | or_intror {} => {} end",
+                Self::generate_coq_term(&premisses[0]),
+                coq_ident(fst_ident.name()),
+                Self::generate_coq_term(&premisses[1]),
+                coq_ident(snd_ident.name()),
+                Self::generate_coq_term(&premisses[2]),
+            ),
+            ProofTreeRule::FalsumElim => {
+                format!(
+                    "False_rect _ {}",
+                    Self::generate_coq_argument(&premisses[0])
+                )
+            }
+            ProofTreeRule::DoubleNegElim => {
+                format!("NNPP _ {}", Self::generate_coq_argument(&premisses[0]))
+            }
+            ProofTreeRule::ForAllIntro(ident) => {
+                let Prop::ForAll {
+                    object_type_ident, ..
+                } = proof_tree.conclusion_prop()
+                else {
+                    panic!("Expected universal quantification.");
+                };
+
+                format!(
+                    "fun ({} : {}) => {}",
+                    coq_ident(ident.name()),
+                    coq_ident(object_type_ident),
+                    Self::generate_coq_term(&premisses[0])
+                )
+            }
+            ProofTreeRule::ExistsIntro => format!(
+                "ex_intro _ {} {}",
+                Self::generate_coq_argument(&premisses[0]),
+                Self::generate_coq_argument(&premisses[1])
+            ),
+            ProofTreeRule::ExistsElim(fst_ident, snd_ident) => format!(
+                "match {} with ex_intro _ {} {} => {} end",
+                Self::generate_coq_term(&premisses[0]),
+                coq_ident(fst_ident.name()),
+                coq_ident(snd_ident.name()),
+                Self::generate_coq_term(&premisses[1]),
+            ),
+            ProofTreeRule::EqElim => {
+                let Prop::Eq(lhs, rhs) = premisses[0].conclusion_prop() else {
+                    panic!("Expected equality.");
+                };
+
+                // the motive abstracts over the occurrences of the right hand side
+                let mut motive = proof_tree.conclusion_prop().clone();
+                let mut variable = "z".to_string();
+                while motive.contains_name(&variable) {
+                    variable.push('\'');
+                }
+                motive
+                    .replace_free_parameter(rhs, &PropParameter::Uninstantiated(variable.clone()));
+
+                format!(
+                    "eq_ind {} (fun {} => {}) {} {} {}",
+                    coq_term_argument(lhs),
+                    variable,
+                    coq_prop(&motive),
+                    Self::generate_coq_argument(&premisses[1]),
+                    coq_term_argument(rhs),
+                    Self::generate_coq_argument(&premisses[0])
+                )
+            }
+            ProofTreeRule::FunctionApplication(_) => coq_term(&proof_tree.conclusion_term()),
+        }
+    }
+}
+
+impl ProofExporter for CoqExporter {
+    fn export(&self, proof_term: &ProofTerm) -> String {
+        let Some(proof_tree) = self.proof_tree(proof_term) else {
+            return "(* The proof term does not prove the proposition. *)".to_string();
+        };
+
+        let mut sections = vec![];
+
+        if proof_tree.contains_rule(&ProofTreeRule::DoubleNegElim) {
+            sections.push("Require Import Classical.".to_string());
+        }
+
+        let declarations = self.generate_declarations();
+        if !declarations.is_empty() {
+            sections.push(declarations.join("\n"));
+        }
+
+        let code = Self::generate_coq_term(&proof_tree);
+        let statement = format!("Theorem proof : {}.", coq_prop(&self.prop));
+
+        // open goals are left to the user
+        let body = match proof_tree.contains_rule(&ProofTreeRule::Sorry) {
+            true => format!("Proof.\n  refine ({}).\n  all: admit.\nAdmitted.", code),
+            false => format!("Proof.\n  exact ({}).\nQed.", code),
+        };
+
+        sections.push(format!("{}\n{}", statement, body));
+
+        sections.join("\n\n")
+    }
+
+    fn can_export(&self, proof_term: &ProofTerm) -> bool {
+        self.proof_tree(proof_term).is_some()
+    }
+
+    fn can_export_for_type(&self, _type: Type) -> bool {
+        _type.is_prop()
+    }
+}
+
+fn coq_ident(ident: &str) -> String {
+    if COQ_RESERVED_NAMES.contains(&ident) {
+        format!("{}'", ident)
+    } else {
+        ident.to_string()
+    }
+}
+
+fn coq_term(term: &PropParameter) -> String {
+    match term {
+        PropParameter::Uninstantiated(_) | PropParameter::Instantiated(_) => coq_ident(term.name()),
+        PropParameter::Function(name, params) if params.is_empty() => coq_ident(name),
+        PropParameter::Function(name, params) => format!(
+            "{} {}",
+            coq_ident(name),
+            params.iter().map(coq_term_argument).join(" ")
+        ),
+    }
+}
+
+fn coq_term_argument(term: &PropParameter) -> String {
+    match term {
+        PropParameter::Function(_, params) if !params.is_empty() => {
+            format!("({})", coq_term(term))
+        }
+        _ => coq_term(term),
+    }
+}
+
+// Levels of the Coq notations, lower levels bind stronger.
+fn coq_level(prop: &Prop) -> usize {
+    match prop {
+        Prop::ForAll { .. } | Prop::Exists { .. } => 200,
+        Prop::Impl(_, snd) if **snd == Prop::False => 75,
+        Prop::Impl(_, _) => 99,
+        Prop::Defined(_, _) if prop.is_iff() => 95,
+        Prop::Or(_, _) => 85,
+        Prop::And(_, _) => 80,
+        Prop::Eq(_, _) => 70,
+        Prop::Atom(_, params) if !params.is_empty() => 10,
+        Prop::Defined(_, args) if !args.is_empty() => 10,
+        Prop::Atom(_, _) | Prop::Defined(_, _) | Prop::True | Prop::False => 0,
+    }
+}
+
+pub fn coq_prop(prop: &Prop) -> String {
+    coq_prop_at(prop, 200)
+}
+
+// Renders the proposition as operand of a notation that accepts the given level.
+fn coq_prop_at(prop: &Prop, level: usize) -> String {
+    let code = match prop {
+        Prop::Atom(name, params) if params.is_empty() => coq_ident(name),
+        Prop::Atom(name, params) => format!(
+            "{} {}",
+            coq_ident(name),
+            params.iter().map(coq_term_argument).join(" ")
+        ),
+        Prop::Eq(lhs, rhs) => format!("{} = {}", coq_term(lhs), coq_term(rhs)),
+        Prop::True => "True".to_string(),
+        Prop::False => "False".to_string(),
+        Prop::ForAll {
+            object_ident,
+            object_type_ident,
+            body,
+        } => format!(
+            "forall {} : {}, {}",
+            coq_ident(object_ident),
+            coq_ident(object_type_ident),
+            coq_prop_at(body, 200)
+        ),
+        Prop::Exists {
+            object_ident,
+            object_type_ident,
+            body,
+        } => format!(
+            "exists {} : {}, {}",
+            coq_ident(object_ident),
+            coq_ident(object_type_ident),
+            coq_prop_at(body, 200)
+        ),
+        Prop::Defined(_, args) if prop.is_iff() => format!(
+            "{} <-> {}",
+            coq_prop_at(&args[0], 94),
+            coq_prop_at(&args[1], 94)
+        ),
+        Prop::Defined(name, args) if args.is_empty() => coq_ident(name),
+        Prop::Defined(name, args) => format!(
+            "{} {}",
+            coq_ident(name),
+            args.iter().map(|arg| coq_prop_at(arg, 9)).join(" ")
+        ),
+        Prop::Impl(fst, snd) if **snd == Prop::False => format!("~ {}", coq_prop_at(fst, 75)),
+        Prop::Impl(fst, snd) => format!("{} -> {}", coq_prop_at(fst, 98), coq_prop_at(snd, 200)),
+        Prop::And(fst, snd) => format!("{} /\\ {}", coq_prop_at(fst, 79), coq_prop_at(snd, 80)),
+        Prop::Or(fst, snd) => format!("{} \\/ {}", coq_prop_at(fst, 84), coq_prop_at(snd, 85)),
+    };
+
+    match coq_level(prop) > level {
+        true => format!("({})", code),
+        false => code,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        export::ProofExporter,
+        parse::{fol::fol_parser, lexer::lexer, proof::proof_parser},
+        process::{stages::resolve_datatypes::ResolveDatatypes, ProofPipeline},
+        prop::Prop,
+    };
+
+    use super::{coq_prop, CoqExporter};
+
+    fn parse_prop(prop: &str) -> Prop {
+        let len = prop.chars().count();
+
+        let tokens = lexer().parse(prop).unwrap();
+        fol_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    fn export(proof: &str, prop: &str) -> String {
+        let prop = parse_prop(prop);
+
+        let len = proof.chars().count();
+        let tokens = lexer().parse(proof).unwrap();
+        let proof_ast = proof_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+        let proof = ProofPipeline::new()
+            .pipe(ResolveDatatypes::boxed())
+            .apply(proof_ast, &prop)
+            .unwrap();
+
+        CoqExporter::new(&prop, &proof).export(&proof.proof_term)
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_coq_prop() {
+        assert_eq!(
+            coq_prop(&parse_prop("(A -> B) -> ~C && (A || B)")),
+            "(A -> B) -> ~ C /\\ (A \\/ B)"
+        );
+        assert_eq!(
+            coq_prop(&parse_prop(
+                "(\\forall x:t. P(x)) && ~(\\exists y:t. x = f(y)) <-> A -> \\forall z:t. P(z)"
+            )),
+            "(forall x : t, P x) /\\ ~ (exists y : t, x = f y) <-> (A -> forall z : t, P z)"
+        );
+    }
+
+    #[test]
+    fn test_export_propositional() {
+        assert_eq!(
+            export(
+                "atom A; atom B; fn u => fn v => case u of inl a => abort (v a), inr b => fst (b, v)",
+                "A || B -> ~A -> B"
+            ),
+            "Parameter A : Prop.
+Parameter B : Prop.
+
+Theorem proof : A \\/ B -> ~ A -> B.
+Proof.
+  exact (fun u => fun v => match u with or_introl a => False_rect _ (v a) | or_intror b => proj1 (conj b v) end).
+Qed."
+        );
+    }
+
+    #[test]
+    fn test_export_iff_atom() {
+        assert_eq!(
+            export(
+                "atom A; atom B; fn u => (snd u, fst u)",
+                "Iff(A, B) -> Iff(B, A)"
+            ),
+            "Parameter A : Prop.
+Parameter B : Prop.
+
+Theorem proof : A <-> B -> B <-> A.
+Proof.
+  exact (fun u => conj (proj2 u) (proj1 u)).
+Qed."
+        );
+    }
+
+    #[test]
+    fn test_export_quantifiers() {
+        assert_eq!(
+            export(
+                "datatype t; function f: t -> t; atom P(t); atom Q(1);
+                fn u => fn v => let (x, w) = u in (x, v x w)",
+                "(\\exists x:t. P(x)) -> (\\forall x:t. P(x) -> Q(f(x))) -> \\exists y:t. Q(f(y))"
+            ),
+            "Parameter t : Type.
+Parameter f : t -> t.
+Parameter P : t -> Prop.
+Parameter Q : forall {T1 : Type}, T1 -> Prop.
+
+Theorem proof : (exists x : t, P x) -> (forall x : t, P x -> Q (f x)) -> exists y : t, Q (f y).
+Proof.
+  exact (fun u => fun v => match u with ex_intro _ x w => ex_intro _ x (v x w) end).
+Qed."
+        );
+    }
+
+    #[test]
+    fn test_export_equality() {
+        assert_eq!(
+            export(
+                "datatype t; atom P(t); fn x => fn y => fn u => fn v => subst u v",
+                "\\forall x:t. \\forall y:t. x = y -> P(x) -> P(y)"
+            ),
+            "Parameter t : Type.
+Parameter P : t -> Prop.
+
+Theorem proof : forall x : t, forall y : t, x = y -> P x -> P y.
+Proof.
+  exact (fun (x : t) => fun (y : t) => fun u => fun v => eq_ind x (fun z => P z) v y u).
+Qed."
+        );
+    }
+
+    #[test]
+    fn test_export_classical_with_sorry() {
+        assert_eq!(
+            export("atom A; atom B; fn u => (dne u, sorry)", "~~A -> A && B"),
+            "Require Import Classical.
+
+Parameter A : Prop.
+Parameter B : Prop.
+
+Theorem proof : ~ ~ A -> A /\\ B.
+Proof.
+  refine (fun u => conj (NNPP _ u) _).
+  all: admit.
+Admitted."
+        );
+    }
+}
@@ -0,0 +1,640 @@
+use std::fmt::{self, Display};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+
+use crate::kernel::{
+    checker::identifier::Identifier,
+    proof_tree::{ProofTree, ProofTreeConclusion, ProofTreeRule},
+    prop::{Prop, PropParameter},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum FitchReference {
+    Line(usize),
+    Subproof(usize, usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum FitchJustification {
+    // hypothesis of the context that is not discharged in the proof, e.g. a lemma
+    Premise(Identifier),
+    Assumption(Identifier),
+    Reiteration(usize),
+    Rule {
+        rule: ProofTreeRule,
+        references: Vec<FitchReference>,
+
+        // term of ∀E and ∃I
+        witness: Option<PropParameter>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct FitchLine {
+    pub number: usize,
+    pub prop: Prop,
+    pub justification: FitchJustification,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct FitchSubproof {
+    // eigenvariable of ∀I and ∃E
+    pub variable: Option<Identifier>,
+    pub assumption: Option<FitchLine>,
+    pub items: Vec<FitchItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum FitchItem {
+    Line(FitchLine),
+    Subproof(FitchSubproof),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct FitchProof {
+    pub items: Vec<FitchItem>,
+}
+
+impl FitchProof {
+    pub fn from_proof_tree(proof_tree: &ProofTree) -> Self {
+        FitchProofBuilder::new().build(proof_tree)
+    }
+}
+
+struct FitchProofBuilder<'a> {
+    next_number: usize,
+
+    // line numbers of the hypotheses in scope
+    hypotheses: Vec<(&'a Identifier, usize)>,
+}
+
+impl<'a> FitchProofBuilder<'a> {
+    pub fn new() -> Self {
+        Self {
+            next_number: 1,
+            hypotheses: vec![],
+        }
+    }
+
+    pub fn build(mut self, proof_tree: &'a ProofTree) -> FitchProof {
+        let mut items = vec![];
+
+        // hypotheses that are not discharged in the proof are stated upfront
+        let mut premisses = vec![];
+        Self::collect_premisses(proof_tree, &mut vec![], &mut premisses);
+
+        for (ident, prop) in premisses {
+            let line = self.line(prop.clone(), FitchJustification::Premise(ident.clone()));
+
+            self.hypotheses.push((ident, line.number));
+            items.push(FitchItem::Line(line));
+        }
+
+        self.emit(proof_tree, &mut items);
+
+        FitchProof { items }
+    }
+
+    fn collect_premisses(
+        proof_tree: &'a ProofTree,
+        discharged: &mut Vec<&'a Identifier>,
+        premisses: &mut Vec<(&'a Identifier, &'a Prop)>,
+    ) {
+        if let (ProofTreeRule::Ident(ident), ProofTreeConclusion::PropIsTrue(prop)) =
+            (&proof_tree.rule, &proof_tree.conclusion)
+        {
+            if !discharged.contains(&ident) && premisses.iter().all(|(other, _)| *other != ident) {
+                premisses.push((ident, prop));
+            }
+        }
+
+        for (i, premisse) in proof_tree.premisses.iter().enumerate() {
+            let introduced = proof_tree.rule.discharged_identifiers(i);
+
+            discharged.extend(introduced.iter());
+            Self::collect_premisses(premisse, discharged, premisses);
+            discharged.truncate(discharged.len() - introduced.len());
+        }
+    }
+
+    fn line(&mut self, prop: Prop, justification: FitchJustification) -> FitchLine {
+        let number = self.next_number;
+        self.next_number += 1;
+
+        FitchLine {
+            number,
+            prop,
+            justification,
+        }
+    }
+
+    fn push_rule(
+        &mut self,
+        proof_tree: &ProofTree,
+        references: Vec<FitchReference>,
+        witness: Option<PropParameter>,
+        items: &mut Vec<FitchItem>,
+    ) -> usize {
+        let line = self.line(
+            proof_tree.conclusion_prop().clone(),
+            FitchJustification::Rule {
+                rule: proof_tree.rule.clone(),
+                references,
+                witness,
+            },
+        );
+        let number = line.number;
+
+        items.push(FitchItem::Line(line));
+
+        number
+    }
+
+    // Emits the lines that derive the conclusion of the tree and returns the line of the conclusion.
+    fn emit(&mut self, proof_tree: &'a ProofTree, items: &mut Vec<FitchItem>) -> usize {
+        let ProofTree {
+            premisses, rule, ..
+        } = proof_tree;
+
+        match rule {
+            ProofTreeRule::Ident(ident) => match self.line_of(ident) {
+                Some(number) => number,
+                None => panic!("Hypothesis not in scope: {:#?}", ident),
+            },
+            ProofTreeRule::ImplIntro(ident) => {
+                let Prop::Impl(fst, _) = proof_tree.conclusion_prop() else {
+                    panic!("Expected conclusion to be an implication.");
+                };
+
+                let reference =
+                    self.subproof(None, Some((ident, *fst.clone())), &premisses[0], items);
+
+                self.push_rule(proof_tree, vec![reference], None, items)
+            }
+            ProofTreeRule::OrElim(fst_ident, snd_ident) => {
+                let head = self.emit(&premisses[0], items);
+
+                let Prop::Or(fst, snd) = premisses[0].conclusion_prop() else {
+                    panic!("Expected disjunction.");
+                };
+
+                let fst_reference =
+                    self.subproof(None, Some((fst_ident, *fst.clone())), &premisses[1], items);
+                let snd_reference =
+                    self.subproof(None, Some((snd_ident, *snd.clone())), &premisses[2], items);
+
+                self.push_rule(
+                    proof_tree,
+                    vec![FitchReference::Line(head), fst_reference, snd_reference],
+                    None,
+                    items,
+                )
+            }
+            ProofTreeRule::ForAllIntro(ident) => {
+                let reference = self.subproof(Some(ident), None, &premisses[0], items);
+
+                self.push_rule(proof_tree, vec![reference], None, items)
+            }
+            ProofTreeRule::ForAllElim => {
+                let head = self.emit(&premisses[0], items);
+                let witness = premisses[1].conclusion_term();
+
+                self.push_rule(
+                    proof_tree,
+                    vec![FitchReference::Line(head)],
+                    Some(witness),
+                    items,
+                )
+            }
+            ProofTreeRule::ExistsIntro => {
+                let witness = premisses[0].conclusion_term();
+                let body = self.emit(&premisses[1], items);
+
+                self.push_rule(
+                    proof_tree,
+                    vec![FitchReference::Line(body)],
+                    Some(witness),
+                    items,
+                )
+            }
+            ProofTreeRule::ExistsElim(fst_ident, snd_ident) => {
+                let head = self.emit(&premisses[0], items);
+
+                let Prop::Exists {
+                    object_ident, body, ..
+                } = premisses[0].conclusion_prop()
+                else {
+                    panic!("Expected existential quantification.");
+                };
+
+                let mut body = body.clone();
+                body.instantiate_free_parameter(object_ident, fst_ident);
+
+                let reference = self.subproof(
+                    Some(fst_ident),
+                    Some((snd_ident, *body)),
+                    &premisses[1],
+                    items,
+                );
+
+                self.push_rule(
+                    proof_tree,
+                    vec![FitchReference::Line(head), reference],
+                    None,
+                    items,
+                )
+            }
+            _ => {
+                let references = premisses
+                    .iter()
+                    .map(|premisse| FitchReference::Line(self.emit(premisse, items)))
+                    .collect();
+
+                self.push_rule(proof_tree, references, None, items)
+            }
+        }
+    }
+
+    fn subproof(
+        &mut self,
+        variable: Option<&Identifier>,
+        assumption: Option<(&'a Identifier, Prop)>,
+        body: &'a ProofTree,
+        items: &mut Vec<FitchItem>,
+    ) -> FitchReference {
+        let start = self.next_number;
+        let scope = self.hypotheses.len();
+
+        let assumption = assumption.map(|(ident, prop)| {
+            let line = self.line(prop, FitchJustification::Assumption(ident.clone()));
+            self.hypotheses.push((ident, line.number));
+
+            line
+        });
+
+        let mut subproof_items = vec![];
+        let mut end = self.emit(body, &mut subproof_items);
+
+        // a subproof has to end with its conclusion
+        if end < start {
+            let line = self.line(
+                body.conclusion_prop().clone(),
+                FitchJustification::Reiteration(end),
+            );
+            end = line.number;
+
+            subproof_items.push(FitchItem::Line(line));
+        }
+
+        self.hypotheses.truncate(scope);
+
+        items.push(FitchItem::Subproof(FitchSubproof {
+            variable: variable.cloned(),
+            assumption,
+            items: subproof_items,
+        }));
+
+        FitchReference::Subproof(start, end)
+    }
+
+    fn line_of(&self, ident: &Identifier) -> Option<usize> {
+        self.hypotheses
+            .iter()
+            .rev()
+            .find(|(hypothesis, _)| *hypothesis == ident)
+            .map(|(_, number)| *number)
+    }
+}
+
+fn rule_label(rule: &ProofTreeRule) -> String {
+    match rule {
+        ProofTreeRule::AndIntro => "∧I".to_string(),
+        ProofTreeRule::AndElimFst | ProofTreeRule::AndElimSnd => "∧E".to_string(),
+        ProofTreeRule::TrueIntro => "⊤I".to_string(),
+        ProofTreeRule::ImplIntro(_) => "⊃I".to_string(),
+        ProofTreeRule::ImplElim => "⊃E".to_string(),
+        ProofTreeRule::Ident(ident) => ident.name().clone(),
+        ProofTreeRule::OrIntroFst | ProofTreeRule::OrIntroSnd => "∨I".to_string(),
+        ProofTreeRule::OrElim(_, _) => "∨E".to_string(),
+        ProofTreeRule::FalsumElim => "⊥E".to_string(),
+        ProofTreeRule::DoubleNegElim => "¬¬E".to_string(),
+        ProofTreeRule::ForAllIntro(_) => "∀I".to_string(),
+        ProofTreeRule::ForAllElim => "∀E".to_string(),
+        ProofTreeRule::ExistsIntro => "∃I".to_string(),
+        ProofTreeRule::ExistsElim(_, _) => "∃E".to_string(),
+        ProofTreeRule::EqIntro => "=I".to_string(),
+        ProofTreeRule::EqElim => "=E".to_string(),
+        ProofTreeRule::FunctionApplication(name) => name.clone(),
+        ProofTreeRule::Sorry => "sorry".to_string(),
+        ProofTreeRule::AlphaEquivalent => "≡α".to_string(),
+        ProofTreeRule::DeltaEquivalent => "≡δ".to_string(),
+    }
+}
+
+impl Display for FitchReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitchReference::Line(number) => write!(f, "{}", number),
+            FitchReference::Subproof(start, end) => write!(f, "{}-{}", start, end),
+        }
+    }
+}
+
+impl Display for FitchJustification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitchJustification::Premise(_) => write!(f, "premise"),
+            FitchJustification::Assumption(_) => write!(f, "assumption"),
+            FitchJustification::Reiteration(number) => write!(f, "R {}", number),
+            FitchJustification::Rule {
+                rule,
+                references,
+                witness,
+            } => {
+                write!(f, "{}", rule_label(rule))?;
+
+                if !references.is_empty() {
+                    write!(f, " {}", references.iter().join(", "))?;
+                }
+
+                if let Some(witness) = witness {
+                    write!(f, " [{}]", witness)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+enum FitchRow {
+    Line {
+        number: usize,
+        depth: usize,
+        prop: String,
+        justification: String,
+    },
+    Variable {
+        depth: usize,
+        ident: String,
+    },
+    Separator {
+        depth: usize,
+    },
+    End {
+        depth: usize,
+    },
+}
+
+fn collect_rows(items: &[FitchItem], depth: usize, rows: &mut Vec<FitchRow>) {
+    let line_row = |line: &FitchLine, depth: usize| FitchRow::Line {
+        number: line.number,
+        depth,
+        prop: line.prop.to_string(),
+        justification: line.justification.to_string(),
+    };
+
+    for (idx, item) in items.iter().enumerate() {
+        let follows_subproof = idx > 0 && matches!(items[idx - 1], FitchItem::Subproof(_));
+
+        match item {
+            FitchItem::Line(line) => rows.push(line_row(line, depth)),
+            FitchItem::Subproof(FitchSubproof {
+                variable,
+                assumption,
+                items,
+            }) => {
+                // the bars of adjacent subproofs would merge otherwise
+                if follows_subproof {
+                    rows.push(FitchRow::End { depth: depth + 1 });
+                }
+
+                if let Some(variable) = variable {
+                    rows.push(FitchRow::Variable {
+                        depth: depth + 1,
+                        ident: variable.name().clone(),
+                    });
+                }
+
+                if let Some(assumption) = assumption {
+                    rows.push(line_row(assumption, depth + 1));
+
+                    rows.push(FitchRow::Separator { depth: depth + 1 });
+                }
+
+                collect_rows(items, depth + 1, rows);
+            }
+        }
+    }
+}
+
+// Renders the proof as plain text, subproofs are indented by vertical bars.
+impl Display for FitchProof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rows = vec![];
+        collect_rows(&self.items, 0, &mut rows);
+
+        let bars = |depth: usize| "│ ".repeat(depth);
+
+        let number_width = rows
+            .iter()
+            .filter_map(|row| match row {
+                FitchRow::Line { number, .. } => Some(number.to_string().len()),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(1);
+
+        let prop_width = rows
+            .iter()
+            .filter_map(|row| match row {
+                FitchRow::Line { depth, prop, .. } => Some(2 * depth + prop.chars().count()),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+
+        let lines = rows.iter().map(|row| match row {
+            FitchRow::Line {
+                number,
+                depth,
+                prop,
+                justification,
+            } => {
+                let padding = prop_width - 2 * depth - prop.chars().count();
+
+                format!(
+                    "{:>number_width$} │ {}{}{}  {}",
+                    number,
+                    bars(*depth),
+                    prop,
+                    " ".repeat(padding),
+                    justification,
+                )
+            }
+            FitchRow::Variable { depth, ident } => format!(
+                "{} │ {}[{}]",
+                " ".repeat(number_width),
+                bars(depth - 1),
+                ident
+            ),
+            FitchRow::Separator { depth } => {
+                format!("{} │ {}├──", " ".repeat(number_width), bars(depth - 1))
+            }
+            FitchRow::End { depth } => {
+                format!("{} │ {}└──", " ".repeat(number_width), bars(depth - 1))
+            }
+        });
+
+        write!(
+            f,
+            "{}",
+            lines.map(|line| line.trim_end().to_string()).join("\n")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        checker::{check::check, identifier_context::IdentifierContext},
+        parse::{fol::fol_parser, lexer::lexer, proof::proof_parser},
+        process::{stages::resolve_datatypes::ResolveDatatypes, ProofPipeline},
+        proof_tree::ProofTree,
+        prop::Prop,
+    };
+
+    use super::{FitchItem, FitchJustification, FitchProof, FitchReference};
+
+    fn parse_prop(prop: &str) -> Prop {
+        let len = prop.chars().count();
+
+        let tokens = lexer().parse(prop).unwrap();
+        fol_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    fn check_proof(proof: &str, prop: &str) -> ProofTree {
+        let prop = parse_prop(prop);
+
+        let len = proof.chars().count();
+        let tokens = lexer().parse(proof).unwrap();
+        let proof_ast = proof_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+        let proof = ProofPipeline::new()
+            .pipe(ResolveDatatypes::boxed())
+            .apply(proof_ast, &prop)
+            .unwrap();
+
+        let ctx = IdentifierContext::with_signatures(proof.functions, proof.atom_signatures);
+
+        check(&proof.proof_term, &prop, &ctx).unwrap().proof_tree
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_or_swap() {
+        let proof_tree = check_proof(
+            "atom A; atom B; fn u => case u of inl a => inr a, inr b => inl b",
+            "A || B -> B || A",
+        );
+
+        assert_eq!(
+            FitchProof::from_proof_tree(&proof_tree).to_string(),
+            "1 │ │ A ∨ B        assumption
+  │ ├──
+2 │ │ │ A          assumption
+  │ │ ├──
+3 │ │ │ B ∨ A      ∨I 2
+  │ │ └──
+4 │ │ │ B          assumption
+  │ │ ├──
+5 │ │ │ B ∨ A      ∨I 4
+6 │ │ B ∨ A        ∨E 1, 2-3, 4-5
+7 │ A ∨ B ⊃ B ∨ A  ⊃I 1-6"
+        );
+    }
+
+    #[test]
+    fn test_reiteration() {
+        let proof_tree = check_proof("atom A; atom B; fn u => fn v => u", "A -> B -> A");
+        let proof = FitchProof::from_proof_tree(&proof_tree);
+
+        assert_eq!(
+            proof.to_string(),
+            "1 │ │ A        assumption
+  │ ├──
+2 │ │ │ B      assumption
+  │ │ ├──
+3 │ │ │ A      R 1
+4 │ │ B ⊃ A    ⊃I 2-3
+5 │ A ⊃ B ⊃ A  ⊃I 1-4"
+        );
+
+        let FitchItem::Subproof(ref outer) = proof.items[0] else {
+            panic!("Expected a subproof.");
+        };
+        let FitchItem::Subproof(ref inner) = outer.items[0] else {
+            panic!("Expected a nested subproof.");
+        };
+        let FitchItem::Line(ref line) = inner.items[0] else {
+            panic!("Expected a line.");
+        };
+
+        assert_eq!(line.justification, FitchJustification::Reiteration(1));
+    }
+
+    #[test]
+    fn test_quantifiers() {
+        let proof_tree = check_proof(
+            "datatype t; atom A(1); atom B(1); fn u => fn x: t => let (y, v) = u in (y, v x)",
+            "(\\exists y:t. \\forall x:t. A(x) -> B(y)) -> \\forall x:t. \\exists y:t. A(x) -> B(y)",
+        );
+        let proof = FitchProof::from_proof_tree(&proof_tree);
+
+        assert_eq!(
+            proof.to_string(),
+            "1 │ │ ∃y:t. ∀x:t. A(x) ⊃ B(y)                              assumption
+  │ ├──
+  │ │ [x]
+  │ │ │ [y]
+2 │ │ │ │ ∀x:t. A(x) ⊃ B(y)                                assumption
+  │ │ │ ├──
+3 │ │ │ │ A(x) ⊃ B(y)                                      ∀E 2 [x]
+4 │ │ │ │ ∃y:t. A(x) ⊃ B(y)                                ∃I 3 [y]
+5 │ │ │ ∃y:t. A(x) ⊃ B(y)                                  ∃E 1, 2-4
+6 │ │ ∀x:t. ∃y:t. A(x) ⊃ B(y)                              ∀I 2-5
+7 │ (∃y:t. ∀x:t. A(x) ⊃ B(y)) ⊃ (∀x:t. ∃y:t. A(x) ⊃ B(y))  ⊃I 1-6"
+        );
+
+        let FitchItem::Subproof(ref outer) = proof.items[0] else {
+            panic!("Expected a subproof.");
+        };
+        let FitchItem::Line(ref last) = outer.items.last().unwrap() else {
+            panic!("Expected a line.");
+        };
+
+        assert!(matches!(
+            last.justification,
+            FitchJustification::Rule { ref references, .. } if references == &vec![FitchReference::Subproof(2, 5)]
+        ));
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+
+use crate::kernel::{
+    proof_term::{
+        Abort, Application, Case, Function, Ident, OrLeft, OrRight, Pair, ProjectFst, ProjectSnd,
+        ProofTerm, Subst, Type, TypeAscription,
+    },
+    prop::{Prop, PropDefinition},
+};
+
+use super::ProofExporter;
+
+const HASKELL_KEYWORDS: [&str; 21] = [
+    "case", "class", "data", "default", "deriving", "do", "else", "forall", "foreign", "if",
+    "import", "in", "infix", "instance", "let", "module", "newtype", "of", "then", "type", "where",
+];
+
+const PREAMBLE: &str = "import Data.Void";
+
+pub struct HaskellExporter {
+    prop: Prop,
+}
+
+impl HaskellExporter {
+    pub fn new(prop: &Prop, definitions: &[PropDefinition]) -> Self {
+        let definitions = [PropDefinition::builtins(), definitions.to_vec()].concat();
+
+        // applications of definitions have already been validated by the proof pipeline
+        let mut prop = prop.clone();
+        let _ = prop.resolve_definitions(&definitions);
+
+        Self {
+            prop: prop.unfold_definitions(&definitions),
+        }
+    }
+
+    fn is_atomic(proof_term: &ProofTerm) -> bool {
+        match proof_term {
+            ProofTerm::Unit(_)
+            | ProofTerm::Refl(_)
+            | ProofTerm::Sorry(_)
+            | ProofTerm::Ident(_)
+            | ProofTerm::Pair(_) => true,
+            ProofTerm::TypeAscription(TypeAscription { proof_term, .. }) => {
+                Self::is_atomic(proof_term)
+            }
+            ProofTerm::Subst(Subst { body, .. }) => Self::is_atomic(body),
+            _ => false,
+        }
+    }
+
+    fn generate_haskell_argument(proof_term: &ProofTerm) -> String {
+        if Self::is_atomic(proof_term) {
+            Self::generate_haskell_term(proof_term)
+        } else {
+            format!("({})", Self::generate_haskell_term(proof_term))
+        }
+    }
+
+    fn generate_haskell_term(proof_term: &ProofTerm) -> String {
+        match proof_term {
+            ProofTerm::Unit(_) => "()".to_string(),
+            ProofTerm::Refl(_) => "()".to_string(),
+            ProofTerm::Sorry(_) => "undefined".to_string(),
+            ProofTerm::Ident(Ident(ident, _)) => haskell_variable(ident),
+            ProofTerm::Abort(Abort(body, _)) => {
+                format!("absurd {}", Self::generate_haskell_argument(body))
+            }
+            ProofTerm::Pair(Pair(fst, snd, _)) => format!(
+                "({}, {})",
+                Self::generate_haskell_term(fst),
+                Self::generate_haskell_term(snd)
+            ),
+            ProofTerm::ProjectFst(ProjectFst(body, _)) => {
+                format!("fst {}", Self::generate_haskell_argument(body))
+            }
+            ProofTerm::ProjectSnd(ProjectSnd(body, _)) => {
+                format!("snd {}", Self::generate_haskell_argument(body))
+            }
+            ProofTerm::OrLeft(OrLeft(body, _)) => {
+                format!("Left {}", Self::generate_haskell_argument(body))
+            }
+            ProofTerm::OrRight(OrRight(body, _)) => {
+                format!("Right {}", Self::generate_haskell_argument(body))
+            }
+            ProofTerm::Case(Case {
+                head,
+                fst_ident,
+                fst_term,
+                snd_ident,
+                snd_term,
+                ..
+            }) => format!(
+                "case {} of {{ Left {} -> {}; Right {} -> {} }}",
+                Self::generate_haskell_term(head),
+                haskell_variable(fst_ident),
+                Self::generate_haskell_term(fst_term),
+                haskell_variable(snd_ident),
+                Self::generate_haskell_term(snd_term),
+            ),
+            ProofTerm::Function(Function {
+                param_ident, body, ..
+            }) => format!(
+                "\\{} -> {}",
+                haskell_variable(param_ident),
+                Self::generate_haskell_term(body)
+            ),
+            ProofTerm::Application(Application {
+                function,
+                applicant,
+                ..
+            }) => {
+                // application is left associative
+                let function = match function.as_ref() {
+                    ProofTerm::Application(_) => Self::generate_haskell_term(function),
+                    _ => Self::generate_haskell_argument(function),
+                };
+
+                format!(
+                    "{} {}",
+                    function,
+                    Self::generate_haskell_argument(applicant)
+                )
+            }
+            // type variables of annotations would not refer to the ones of the signature
+            ProofTerm::TypeAscription(TypeAscription { proof_term, .. }) => {
+                Self::generate_haskell_term(proof_term)
+            }
+            ProofTerm::Subst(Subst { body, .. }) => Self::generate_haskell_term(body),
+            ProofTerm::LetIn(_) => panic!("Existential quantification cannot be exported."),
+            ProofTerm::DoubleNegElim(_) => panic!("Classical proofs cannot be exported."),
+        }
+    }
+}
+
+impl ProofExporter for HaskellExporter {
+    fn export(&self, proof_term: &ProofTerm) -> String {
+        let type_variables = haskell_type_variables(&self.prop);
+
+        format!(
+            "{}\n\nproof :: {}\nproof = {}",
+            PREAMBLE,
+            haskell_type(&self.prop, &type_variables),
+            Self::generate_haskell_term(proof_term)
+        )
+    }
+
+    fn can_export(&self, proof_term: &ProofTerm) -> bool {
+        match proof_term {
+            ProofTerm::Unit(_) => true,
+            ProofTerm::Refl(_) => true,
+            ProofTerm::Ident(_) => true,
+            ProofTerm::Sorry(_) => true,
+            ProofTerm::TypeAscription(TypeAscription { proof_term, .. }) => {
+                self.can_export(proof_term)
+            }
+            ProofTerm::Abort(Abort(body, _)) => self.can_export(body),
+            // programs only have a computational meaning for constructive proofs
+            ProofTerm::DoubleNegElim(_) => false,
+            ProofTerm::OrLeft(OrLeft(body, _)) => self.can_export(body),
+            ProofTerm::OrRight(OrRight(body, _)) => self.can_export(body),
+            ProofTerm::Case(Case {
+                head,
+                fst_term,
+                snd_term,
+                ..
+            }) => self.can_export(head) && self.can_export(fst_term) && self.can_export(snd_term),
+            ProofTerm::Pair(Pair(fst, snd, _)) => self.can_export(fst) && self.can_export(snd),
+            ProofTerm::ProjectFst(ProjectFst(body, _)) => self.can_export(body),
+            ProofTerm::ProjectSnd(ProjectSnd(body, _)) => self.can_export(body),
+            ProofTerm::Application(Application {
+                function,
+                applicant,
+                ..
+            }) => self.can_export(function) && self.can_export(applicant),
+            ProofTerm::Function(Function {
+                param_type, body, ..
+            }) => {
+                if let Some(param_type) = param_type {
+                    if param_type.has_quantifiers() || param_type.is_datatype() {
+                        return false;
+                    }
+                }
+
+                self.can_export(body)
+            }
+            ProofTerm::Subst(Subst { equality, body, .. }) => {
+                self.can_export(equality) && self.can_export(body)
+            }
+            ProofTerm::LetIn(_) => false,
+        }
+    }
+
+    fn can_export_for_type(&self, _type: Type) -> bool {
+        let Type::Prop(prop) = _type else {
+            return false;
+        };
+
+        !prop.has_quantifiers()
+    }
+}
+
+// Variables have to start with a lower case letter.
+fn haskell_variable(ident: &str) -> String {
+    match ident.chars().next() {
+        Some(first) if first.is_uppercase() => format!("_{}", ident),
+        _ if HASKELL_KEYWORDS.contains(&ident) => format!("{}'", ident),
+        _ => ident.to_string(),
+    }
+}
+
+// Atoms become type variables, which have to be distinct and start with a lower case letter.
+fn haskell_type_variables(prop: &Prop) -> HashMap<String, String> {
+    let mut type_variables: HashMap<String, String> = HashMap::new();
+
+    for (atom, _) in prop.get_atoms() {
+        if type_variables.contains_key(&atom) {
+            continue;
+        }
+
+        let mut chars = atom.chars();
+        let mut type_variable = match chars.next() {
+            Some(first) => first.to_lowercase().chain(chars).collect(),
+            None => atom.clone(),
+        };
+        while HASKELL_KEYWORDS.contains(&type_variable.as_str())
+            || type_variables.values().any(|other| *other == type_variable)
+        {
+            type_variable.push('\'');
+        }
+
+        type_variables.insert(atom, type_variable);
+    }
+
+    type_variables
+}
+
+fn haskell_type(prop: &Prop, type_variables: &HashMap<String, String>) -> String {
+    let argument = |prop: &Prop| match prop {
+        Prop::Or(_, _) | Prop::Impl(_, _) => format!("({})", haskell_type(prop, type_variables)),
+        _ => haskell_type(prop, type_variables),
+    };
+
+    match prop {
+        Prop::Atom(name, _) => type_variables[name].clone(),
+        Prop::True | Prop::Eq(_, _) => "()".to_string(),
+        Prop::False => "Void".to_string(),
+        Prop::And(fst, snd) => format!(
+            "({}, {})",
+            haskell_type(fst, type_variables),
+            haskell_type(snd, type_variables)
+        ),
+        Prop::Or(fst, snd) => format!("Either {} {}", argument(fst), argument(snd)),
+        Prop::Impl(fst, snd) => {
+            let fst = match **fst {
+                Prop::Impl(_, _) => format!("({})", haskell_type(fst, type_variables)),
+                _ => haskell_type(fst, type_variables),
+            };
+
+            format!("{} -> {}", fst, haskell_type(snd, type_variables))
+        }
+        Prop::Defined(_, _) | Prop::ForAll { .. } | Prop::Exists { .. } => {
+            panic!("Only unfolded propositions without quantifiers can be exported.")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        export::ProofExporter,
+        parse::{fol::fol_parser, lexer::lexer, proof::proof_parser},
+        process::{stages::resolve_datatypes::ResolveDatatypes, ProofPipeline},
+        proof::Proof,
+        prop::Prop,
+    };
+
+    use super::{HaskellExporter, PREAMBLE};
+
+    fn parse_prop(prop: &str) -> Prop {
+        let len = prop.chars().count();
+
+        let tokens = lexer().parse(prop).unwrap();
+        fol_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    fn parse_proof(proof: &str, prop: &Prop) -> Proof {
+        let len = proof.chars().count();
+        let tokens = lexer().parse(proof).unwrap();
+        let proof_ast = proof_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+        ProofPipeline::new()
+            .pipe(ResolveDatatypes::boxed())
+            .apply(proof_ast, prop)
+            .unwrap()
+    }
+
+    fn export(proof: &str, prop: &str) -> String {
+        let prop = parse_prop(prop);
+        let proof = parse_proof(proof, &prop);
+
+        let exporter = HaskellExporter::new(&prop, &proof.definitions);
+        assert!(exporter.can_export(&proof.proof_term));
+
+        exporter
+            .export(&proof.proof_term)
+            .strip_prefix(PREAMBLE)
+            .unwrap()
+            .trim()
+            .to_string()
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_export_propositional() {
+        assert_eq!(
+            export(
+                "atom A; atom B; fn u => fn v => case u of inl a => abort (v a), inr b => fst (b, v)",
+                "A || B -> ~A -> B"
+            ),
+            "proof :: Either a b -> (a -> Void) -> b\nproof = \\u -> \\v -> case u of { Left a -> absurd (v a); Right b -> fst (b, v) }"
+        );
+    }
+
+    #[test]
+    fn test_export_names() {
+        assert_eq!(
+            export(
+                "atom Class; atom data; fn X => fn data => (data, X)",
+                "Class -> data -> data && Class"
+            ),
+            "proof :: class' -> data' -> (data', class')\nproof = \\_X -> \\data' -> (data', _X)"
+        );
+    }
+
+    #[test]
+    fn test_classical_proofs_cannot_be_exported() {
+        let prop = parse_prop("A || ~A");
+        let proof = parse_proof("atom A; dne (fn v => v (inr (fn a => v (inl a))))", &prop);
+
+        let exporter = HaskellExporter::new(&prop, &proof.definitions);
+        assert!(!exporter.can_export(&proof.proof_term));
+    }
+
+    #[test]
+    fn test_export_definitions() {
+        assert_eq!(
+            export(
+                "atom A; def Dup(X) := X && X; fn u => ((fn a => a, fn a => a), fst u)",
+                "Dup(A) -> (A <-> A) && A"
+            ),
+            "proof :: (a, a) -> ((a -> a, a -> a), a)\nproof = \\u -> ((\\a -> a, \\a -> a), fst u)"
+        );
+    }
+}
@@ -0,0 +1,413 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+
+use crate::kernel::{
+    checker::identifier::Identifier,
+    proof_tree::{ProofTree, ProofTreeConclusion, ProofTreeRule},
+    prop::{Prop, PropParameter},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum LatexProofTreeStyle {
+    Bussproofs,
+    Ebproof,
+}
+
+pub struct LatexExporter {
+    style: LatexProofTreeStyle,
+}
+
+impl LatexExporter {
+    pub fn new(style: LatexProofTreeStyle) -> Self {
+        Self { style }
+    }
+
+    pub fn export(&self, proof_tree: &ProofTree) -> String {
+        let mut lines = vec![];
+        self.export_tree(proof_tree, &mut vec![], &mut lines);
+
+        let package = match self.style {
+            LatexProofTreeStyle::Bussproofs => "bussproofs",
+            LatexProofTreeStyle::Ebproof => "ebproof",
+        };
+
+        format!(
+            "% requires \\usepackage{{{}}}\n\\begin{{prooftree}}\n{}\n\\end{{prooftree}}",
+            package,
+            lines.join("\n")
+        )
+    }
+
+    // Both packages expect the premisses before the rule that combines them (postfix order).
+    fn export_tree<'a>(
+        &self,
+        proof_tree: &'a ProofTree,
+        discharged: &mut Vec<&'a Identifier>,
+        lines: &mut Vec<String>,
+    ) {
+        let ProofTree {
+            premisses,
+            rule,
+            conclusion,
+        } = proof_tree;
+
+        let conclusion = latex_conclusion(conclusion);
+
+        if let ProofTreeRule::Ident(ident) = rule {
+            let hypothesis = match discharged.contains(&ident) {
+                true => format!("[{}]^{{{}}}", conclusion, latex_ident(ident.name())),
+                false => conclusion,
+            };
+
+            lines.push(match self.style {
+                LatexProofTreeStyle::Bussproofs => format!("\\AxiomC{{${}$}}", hypothesis),
+                LatexProofTreeStyle::Ebproof => format!("\\hypo{{{}}}", hypothesis),
+            });
+
+            return;
+        }
+
+        for (i, premisse) in premisses.iter().enumerate() {
+            let introduced = rule.discharged_identifiers(i);
+
+            discharged.extend(introduced.iter());
+            self.export_tree(premisse, discharged, lines);
+            discharged.truncate(discharged.len() - introduced.len());
+        }
+
+        let label = latex_rule_label(rule);
+
+        match self.style {
+            LatexProofTreeStyle::Bussproofs => {
+                if premisses.is_empty() {
+                    lines.push("\\AxiomC{}".to_string());
+                }
+
+                // bussproofs supports at most five premisses, the last ones are grouped into
+                // inferences without line and conclusion until five remain
+                let mut remaining = premisses.len().max(1);
+                while remaining > 5 {
+                    let group = usize::min(5, remaining - 4);
+
+                    lines.push("\\noLine".to_string());
+                    lines.push(format!("\\{}{{}}", bussproofs_inference(group)));
+                    remaining -= group - 1;
+                }
+
+                lines.push(format!("\\RightLabel{{${}$}}", label));
+                lines.push(format!(
+                    "\\{}{{${}$}}",
+                    bussproofs_inference(remaining),
+                    conclusion
+                ));
+            }
+            LatexProofTreeStyle::Ebproof => {
+                lines.push(format!(
+                    "\\infer{}[${}$]{{{}}}",
+                    premisses.len(),
+                    label,
+                    conclusion
+                ));
+            }
+        }
+    }
+}
+
+fn bussproofs_inference(premisses: usize) -> &'static str {
+    match premisses {
+        1 => "UnaryInfC",
+        2 => "BinaryInfC",
+        3 => "TrinaryInfC",
+        4 => "QuaternaryInfC",
+        5 => "QuinaryInfC",
+        _ => panic!("Expected one to five premisses."),
+    }
+}
+
+pub fn latex_rule_label(rule: &ProofTreeRule) -> String {
+    match rule {
+        ProofTreeRule::AndIntro => "\\land I".to_string(),
+        ProofTreeRule::AndElimFst => "\\land E_1".to_string(),
+        ProofTreeRule::AndElimSnd => "\\land E_2".to_string(),
+        ProofTreeRule::TrueIntro => "\\top I".to_string(),
+        ProofTreeRule::ImplIntro(ident) => format!("\\to I^{{{}}}", latex_ident(ident.name())),
+        ProofTreeRule::ImplElim => "\\to E".to_string(),
+        ProofTreeRule::Ident(ident) => latex_ident(ident.name()),
+        ProofTreeRule::OrIntroFst => "\\lor I_1".to_string(),
+        ProofTreeRule::OrIntroSnd => "\\lor I_2".to_string(),
+        ProofTreeRule::OrElim(fst_ident, snd_ident) => format!(
+            "\\lor E^{{{},{}}}",
+            latex_ident(fst_ident.name()),
+            latex_ident(snd_ident.name())
+        ),
+        ProofTreeRule::FalsumElim => "\\bot E".to_string(),
+        ProofTreeRule::DoubleNegElim => "\\neg\\neg E".to_string(),
+        ProofTreeRule::ForAllIntro(ident) => {
+            format!("\\forall I^{{{}}}", latex_ident(ident.name()))
+        }
+        ProofTreeRule::ForAllElim => "\\forall E".to_string(),
+        ProofTreeRule::ExistsIntro => "\\exists I".to_string(),
+        // the discharged hypothesis comes first, followed by the eigenvariable
+        ProofTreeRule::ExistsElim(fst_ident, snd_ident) => format!(
+            "\\exists E^{{{},{}}}",
+            latex_ident(snd_ident.name()),
+            latex_ident(fst_ident.name())
+        ),
+        ProofTreeRule::EqIntro => "= I".to_string(),
+        ProofTreeRule::EqElim => "= E".to_string(),
+        ProofTreeRule::FunctionApplication(name) => latex_ident(name),
+        ProofTreeRule::Sorry => "\\mathit{sorry}".to_string(),
+        ProofTreeRule::AlphaEquivalent => "\\equiv_\\alpha".to_string(),
+        ProofTreeRule::DeltaEquivalent => "\\equiv_\\delta".to_string(),
+    }
+}
+
+pub fn latex_conclusion(conclusion: &ProofTreeConclusion) -> String {
+    match conclusion {
+        ProofTreeConclusion::PropIsTrue(prop) => latex_prop(prop),
+        ProofTreeConclusion::TypeJudgement(ident, datatype) => {
+            format!("{} : {}", latex_ident(ident.name()), latex_ident(datatype))
+        }
+        ProofTreeConclusion::TermJudgement(term, datatype) => {
+            format!("{} : {}", latex_term(term), latex_ident(datatype))
+        }
+    }
+}
+
+// Same parenthesization as the Display implementation of Prop.
+pub fn latex_prop(prop: &Prop) -> String {
+    let wrap = |prop: &Prop, should_wrap: bool| match should_wrap {
+        true => format!("({})", latex_prop(prop)),
+        false => latex_prop(prop),
+    };
+
+    match prop {
+        Prop::Atom(name, params) if params.is_empty() => latex_ident(name),
+        Prop::Atom(name, params) => format!(
+            "{}({})",
+            latex_ident(name),
+            params.iter().map(latex_term).join(", ")
+        ),
+        Prop::Eq(lhs, rhs) => format!("{} = {}", latex_term(lhs), latex_term(rhs)),
+        Prop::True => "\\top".to_string(),
+        Prop::False => "\\bot".to_string(),
+        Prop::ForAll {
+            object_ident,
+            object_type_ident,
+            body,
+        } => format!(
+            "\\forall {}{{:}}{}.\\, {}",
+            latex_ident(object_ident),
+            latex_ident(object_type_ident),
+            latex_prop(body)
+        ),
+        Prop::Exists {
+            object_ident,
+            object_type_ident,
+            body,
+        } => format!(
+            "\\exists {}{{:}}{}.\\, {}",
+            latex_ident(object_ident),
+            latex_ident(object_type_ident),
+            latex_prop(body)
+        ),
+        Prop::Defined(_, args) if prop.is_iff() => {
+            // quantifiers extend as far right as possible
+            let should_wrap_fst = args[0].precedence() <= 1;
+            let should_wrap_snd = args[1].precedence() == 0;
+
+            format!(
+                "{} \\leftrightarrow {}",
+                wrap(&args[0], should_wrap_fst),
+                wrap(&args[1], should_wrap_snd)
+            )
+        }
+        Prop::Defined(name, args) if args.is_empty() => latex_ident(name),
+        Prop::Defined(name, args) => format!(
+            "{}({})",
+            latex_ident(name),
+            args.iter().map(latex_prop).join(", ")
+        ),
+        Prop::Impl(fst, snd) if **snd == Prop::False => {
+            format!("\\neg {}", wrap(fst, prop.precedence() > fst.precedence()))
+        }
+        Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+            let connective_symbol = match prop {
+                Prop::And(_, _) => "\\land",
+                Prop::Or(_, _) => "\\lor",
+                _ => "\\to",
+            };
+
+            let should_wrap_fst = (prop.precedence() > fst.precedence())
+                || (prop.precedence() == fst.precedence() && prop.right_associative());
+            let should_wrap_snd = (prop.precedence() > snd.precedence())
+                || (prop.precedence() == snd.precedence() && prop.left_associative());
+
+            format!(
+                "{} {} {}",
+                wrap(fst, should_wrap_fst),
+                connective_symbol,
+                wrap(snd, should_wrap_snd)
+            )
+        }
+    }
+}
+
+fn latex_term(term: &PropParameter) -> String {
+    match term {
+        PropParameter::Uninstantiated(_) | PropParameter::Instantiated(_) => {
+            latex_ident(term.name())
+        }
+        PropParameter::Function(name, params) if params.is_empty() => latex_ident(name),
+        PropParameter::Function(name, params) => format!(
+            "{}({})",
+            latex_ident(name),
+            params.iter().map(latex_term).join(", ")
+        ),
+    }
+}
+
+// Single letters are set as math variables, longer names as italic words.
+fn latex_ident(name: &str) -> String {
+    let escaped = name.replace('_', "\\_");
+
+    match name.chars().count() {
+        1 => escaped,
+        _ => format!("\\mathit{{{}}}", escaped),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        checker::{check::check, identifier_context::IdentifierContext},
+        parse::{fol::fol_parser, lexer::lexer, proof::proof_parser},
+        process::{stages::resolve_datatypes::ResolveDatatypes, ProofPipeline},
+        proof_tree::ProofTree,
+        prop::Prop,
+    };
+
+    use super::{latex_prop, LatexExporter, LatexProofTreeStyle};
+
+    fn parse_prop(prop: &str) -> Prop {
+        let len = prop.chars().count();
+
+        let tokens = lexer().parse(prop).unwrap();
+        fol_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    fn check_proof(proof: &str, prop: &str) -> ProofTree {
+        let prop = parse_prop(prop);
+
+        let len = proof.chars().count();
+        let tokens = lexer().parse(proof).unwrap();
+        let proof_ast = proof_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+        let proof = ProofPipeline::new()
+            .pipe(ResolveDatatypes::boxed())
+            .apply(proof_ast, &prop)
+            .unwrap();
+
+        let ctx = IdentifierContext::with_signatures(proof.functions, proof.atom_signatures);
+
+        check(&proof.proof_term, &prop, &ctx).unwrap().proof_tree
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_latex_prop() {
+        assert_eq!(
+            latex_prop(&parse_prop("(A -> B) -> ~C && (A || B)")),
+            "(A \\to B) \\to \\neg C \\land (A \\lor B)"
+        );
+        assert_eq!(
+            latex_prop(&parse_prop("\\forall x:t. Even(x) <-> \\exists y:t. x = y")),
+            "\\forall x{:}t.\\, \\mathit{Even}(x) \\leftrightarrow \\exists y{:}t.\\, x = y"
+        );
+    }
+
+    #[test]
+    fn test_bussproofs() {
+        let proof_tree = check_proof("atom A; atom B; fn u => (u, ())", "A -> A && \\top");
+
+        assert_eq!(
+            LatexExporter::new(LatexProofTreeStyle::Bussproofs).export(&proof_tree),
+            "% requires \\usepackage{bussproofs}
+\\begin{prooftree}
+\\AxiomC{$[A]^{u}$}
+\\AxiomC{}
+\\RightLabel{$\\top I$}
+\\UnaryInfC{$\\top$}
+\\RightLabel{$\\land I$}
+\\BinaryInfC{$A \\land \\top$}
+\\RightLabel{$\\to I^{u}$}
+\\UnaryInfC{$A \\to A \\land \\top$}
+\\end{prooftree}"
+        );
+    }
+
+    #[test]
+    fn test_bussproofs_many_premisses() {
+        let proof_tree = check_proof(
+            "datatype t; atom A(1); function f: t -> t -> t -> t -> t -> t -> t;
+            fn u => fn x => u (f x x x x x x)",
+            "(\\forall y:t. A(y)) -> \\forall x:t. A(f(x, x, x, x, x, x))",
+        );
+
+        assert_eq!(
+            LatexExporter::new(LatexProofTreeStyle::Bussproofs).export(&proof_tree),
+            "% requires \\usepackage{bussproofs}
+\\begin{prooftree}
+\\AxiomC{$[\\forall y{:}t.\\, A(y)]^{u}$}
+\\AxiomC{$x : t$}
+\\AxiomC{$x : t$}
+\\AxiomC{$x : t$}
+\\AxiomC{$x : t$}
+\\AxiomC{$x : t$}
+\\AxiomC{$x : t$}
+\\noLine
+\\BinaryInfC{}
+\\RightLabel{$f$}
+\\QuinaryInfC{$f(x, x, x, x, x, x) : t$}
+\\RightLabel{$\\forall E$}
+\\BinaryInfC{$A(f(x, x, x, x, x, x))$}
+\\RightLabel{$\\forall I^{x}$}
+\\UnaryInfC{$\\forall x{:}t.\\, A(f(x, x, x, x, x, x))$}
+\\RightLabel{$\\to I^{u}$}
+\\UnaryInfC{$(\\forall y{:}t.\\, A(y)) \\to (\\forall x{:}t.\\, A(f(x, x, x, x, x, x)))$}
+\\end{prooftree}"
+        );
+    }
+
+    #[test]
+    fn test_ebproof() {
+        let proof_tree = check_proof(
+            "atom A; atom B; fn u => case u of inl a => inr a, inr b => inl b",
+            "A || B -> B || A",
+        );
+
+        assert_eq!(
+            LatexExporter::new(LatexProofTreeStyle::Ebproof).export(&proof_tree),
+            "% requires \\usepackage{ebproof}
+\\begin{prooftree}
+\\hypo{[A \\lor B]^{u}}
+\\hypo{[A]^{a}}
+\\infer1[$\\lor I_2$]{B \\lor A}
+\\hypo{[B]^{b}}
+\\infer1[$\\lor I_1$]{B \\lor A}
+\\infer3[$\\lor E^{a,b}$]{B \\lor A}
+\\infer1[$\\to I^{u}$]{A \\lor B \\to B \\lor A}
+\\end{prooftree}"
+        );
+    }
+}
@@ -0,0 +1,495 @@
+use itertools::Itertools;
+
+use crate::kernel::{
+    proof::Proof,
+    proof_term::{
+        Abort, Application, Case, DoubleNegElim, Function, Ident, LetIn, OrLeft, OrRight, Pair,
+        ProjectFst, ProjectSnd, ProofTerm, Subst, Type, TypeAscription,
+    },
+    prop::{AtomSignature, FunctionSignature, Prop, PropDefinition, PropParameter},
+};
+
+use super::ProofExporter;
+
+const LEAN_KEYWORDS: [&str; 24] = [
+    "abbrev", "at", "axiom", "by", "def", "do", "else", "end", "example", "from", "fun", "have",
+    "if", "in", "let", "match", "Prop", "show", "sorry", "then", "theorem", "Type", "variable",
+    "with",
+];
+
+pub struct LeanExporter {
+    prop: Prop,
+    datatypes: Vec<String>,
+    atoms: Vec<(String, usize)>,
+    atom_signatures: Vec<AtomSignature>,
+    functions: Vec<FunctionSignature>,
+    definitions: Vec<PropDefinition>,
+}
+
+impl LeanExporter {
+    pub fn new(prop: &Prop, proof: &Proof) -> Self {
+        Self {
+            prop: prop.clone(),
+            datatypes: proof.datatypes.clone(),
+            atoms: proof.atoms.clone(),
+            atom_signatures: proof.atom_signatures.clone(),
+            functions: proof.functions.clone(),
+            definitions: proof.definitions.clone(),
+        }
+    }
+
+    fn generate_declarations(&self) -> Vec<String> {
+        let mut declarations = vec![];
+
+        for datatype in self.datatypes.iter() {
+            declarations.push(format!("axiom {} : Type", lean_ident(datatype)));
+        }
+
+        for FunctionSignature {
+            name,
+            param_types,
+            return_type,
+        } in self.functions.iter()
+        {
+            let signature = param_types
+                .iter()
+                .chain([return_type])
+                .map(|datatype| lean_ident(datatype))
+                .join(" → ");
+
+            declarations.push(format!("axiom {} : {}", lean_ident(name), signature));
+        }
+
+        for (name, arity) in self.atoms.iter() {
+            let signature = self
+                .atom_signatures
+                .iter()
+                .find(|signature| signature.name == *name);
+
+            let declaration = match (signature, arity) {
+                (_, 0) => format!("axiom {} : Prop", lean_ident(name)),
+                (Some(AtomSignature { param_types, .. }), _) => format!(
+                    "axiom {} : {} → Prop",
+                    lean_ident(name),
+                    param_types
+                        .iter()
+                        .map(|datatype| lean_ident(datatype))
+                        .join(" → ")
+                ),
+                // unsorted parameters may be of any datatype
+                (None, _) => {
+                    let type_variables = (1..=*arity).map(|i| format!("α{}", i)).collect_vec();
+
+                    format!(
+                        "axiom {} {{{} : Type}} : {} → Prop",
+                        lean_ident(name),
+                        type_variables.join(" "),
+                        type_variables.join(" → ")
+                    )
+                }
+            };
+
+            declarations.push(declaration);
+        }
+
+        // Iff is translated to the biconditional of Lean
+        for PropDefinition { name, params, body } in self.definitions.iter() {
+            if name == "Iff" {
+                continue;
+            }
+
+            let params = match params.is_empty() {
+                true => "".to_string(),
+                false => format!(
+                    " ({} : Prop)",
+                    params.iter().map(|param| lean_ident(param)).join(" ")
+                ),
+            };
+
+            declarations.push(format!(
+                "abbrev {}{} : Prop := {}",
+                lean_ident(name),
+                params,
+                lean_prop(body)
+            ));
+        }
+
+        declarations
+    }
+
+    fn is_atomic(proof_term: &ProofTerm) -> bool {
+        matches!(
+            proof_term,
+            ProofTerm::Ident(_)
+                | ProofTerm::Unit(_)
+                | ProofTerm::Refl(_)
+                | ProofTerm::Sorry(_)
+                | ProofTerm::Pair(_)
+                | ProofTerm::ProjectFst(_)
+                | ProofTerm::ProjectSnd(_)
+                | ProofTerm::TypeAscription(_)
+        )
+    }
+
+    fn generate_lean_argument(proof_term: &ProofTerm) -> String {
+        if Self::is_atomic(proof_term) {
+            Self::generate_lean_term(proof_term)
+        } else {
+            format!("({})", Self::generate_lean_term(proof_term))
+        }
+    }
+
+    fn generate_lean_term(proof_term: &ProofTerm) -> String {
+        match proof_term {
+            ProofTerm::Unit(_) => "trivial".to_string(),
+            ProofTerm::Refl(_) => "rfl".to_string(),
+            ProofTerm::Sorry(_) => "sorry".to_string(),
+            ProofTerm::Ident(Ident(ident, _)) => lean_ident(ident),
+            ProofTerm::Abort(Abort(body, _)) => {
+                format!("False.elim {}", Self::generate_lean_argument(body))
+            }
+            ProofTerm::DoubleNegElim(DoubleNegElim(body, _)) => {
+                format!(
+                    "Classical.byContradiction {}",
+                    Self::generate_lean_argument(body)
+                )
+            }
+            ProofTerm::Pair(Pair(fst, snd, _)) => format!(
+                "⟨{}, {}⟩",
+                Self::generate_lean_term(fst),
+                Self::generate_lean_term(snd)
+            ),
+            ProofTerm::ProjectFst(ProjectFst(body, _)) => {
+                format!("{}.1", Self::generate_lean_argument(body))
+            }
+            ProofTerm::ProjectSnd(ProjectSnd(body, _)) => {
+                format!("{}.2", Self::generate_lean_argument(body))
+            }
+            ProofTerm::OrLeft(OrLeft(body, _)) => {
+                format!("Or.inl {}", Self::generate_lean_argument(body))
+            }
+            ProofTerm::OrRight(OrRight(body, _)) => {
+                format!("Or.inr {}", Self::generate_lean_argument(body))
+            }
+            ProofTerm::Case(Case {
+                head,
+                fst_ident,
+                fst_term,
+                snd_ident,
+                snd_term,
+                ..
+            }) => format!(
+                "Or.elim {} (fun {} => {}) (fun {} => {})",
+                Self::generate_lean_argument(head),
+                lean_ident(fst_ident),
+                Self::generate_lean_term(fst_term),
+                lean_ident(snd_ident),
+                Self::generate_lean_term(snd_term),
+            ),
+            ProofTerm::LetIn(LetIn {
+                fst_ident,
+                snd_ident,
+                head,
+                body,
+                ..
+            }) => format!(
+                "Exists.elim {} (fun {} {} => {})",
+                Self::generate_lean_argument(head),
+                lean_ident(fst_ident),
+                lean_ident(snd_ident),
+                Self::generate_lean_term(body),
+            ),
+            ProofTerm::Function(Function {
+                param_ident,
+                param_type: Some(param_type),
+                body,
+                ..
+            }) => format!(
+                "fun ({} : {}) => {}",
+                lean_ident(param_ident),
+                lean_type(param_type),
+                Self::generate_lean_term(body)
+            ),
+            ProofTerm::Function(Function {
+                param_ident, body, ..
+            }) => format!(
+                "fun {} => {}",
+                lean_ident(param_ident),
+                Self::generate_lean_term(body)
+            ),
+            ProofTerm::Application(Application {
+                function,
+                applicant,
+                ..
+            }) => {
+                // application is left associative
+                let function = match function.as_ref() {
+                    ProofTerm::Application(_) => Self::generate_lean_term(function),
+                    _ => Self::generate_lean_argument(function),
+                };
+
+                format!("{} {}", function, Self::generate_lean_argument(applicant))
+            }
+            ProofTerm::TypeAscription(TypeAscription {
+                proof_term,
+                ascription,
+                ..
+            }) => format!(
+                "({} : {})",
+                Self::generate_lean_term(proof_term),
+                lean_type(ascription)
+            ),
+            ProofTerm::Subst(Subst { equality, body, .. }) => format!(
+                "{} ▸ {}",
+                Self::generate_lean_argument(equality),
+                Self::generate_lean_argument(body)
+            ),
+        }
+    }
+}
+
+impl ProofExporter for LeanExporter {
+    fn export(&self, proof_term: &ProofTerm) -> String {
+        let declarations = self.generate_declarations();
+        let code = Self::generate_lean_term(proof_term);
+
+        let theorem = format!("theorem proof : {} :=\n  {}", lean_prop(&self.prop), code);
+
+        match declarations.is_empty() {
+            true => theorem,
+            false => format!("{}\n\n{}", declarations.join("\n"), theorem),
+        }
+    }
+
+    fn can_export(&self, _proof_term: &ProofTerm) -> bool {
+        true
+    }
+
+    fn can_export_for_type(&self, _type: Type) -> bool {
+        _type.is_prop()
+    }
+}
+
+fn lean_ident(ident: &str) -> String {
+    if LEAN_KEYWORDS.contains(&ident) {
+        format!("«{}»", ident)
+    } else {
+        ident.to_string()
+    }
+}
+
+fn lean_type(_type: &Type) -> String {
+    match _type {
+        Type::Prop(prop) => lean_prop(prop),
+        Type::Datatype(datatype) => lean_ident(datatype),
+    }
+}
+
+fn lean_term(term: &PropParameter) -> String {
+    match term {
+        PropParameter::Uninstantiated(_) | PropParameter::Instantiated(_) => {
+            lean_ident(term.name())
+        }
+        PropParameter::Function(name, params) if params.is_empty() => lean_ident(name),
+        PropParameter::Function(name, params) => format!(
+            "{} {}",
+            lean_ident(name),
+            params.iter().map(lean_term_argument).join(" ")
+        ),
+    }
+}
+
+fn lean_term_argument(term: &PropParameter) -> String {
+    match term {
+        PropParameter::Function(_, params) if !params.is_empty() => {
+            format!("({})", lean_term(term))
+        }
+        _ => lean_term(term),
+    }
+}
+
+// Precedences of the Lean notations.
+fn lean_precedence(prop: &Prop) -> usize {
+    match prop {
+        Prop::ForAll { .. } | Prop::Exists { .. } => 0,
+        Prop::Defined(_, _) if prop.is_iff() => 20,
+        Prop::Impl(_, snd) if **snd == Prop::False => 1024,
+        Prop::Impl(_, _) => 25,
+        Prop::Or(_, _) => 30,
+        Prop::And(_, _) => 35,
+        Prop::Eq(_, _) => 50,
+        Prop::Atom(_, _) | Prop::Defined(_, _) | Prop::True | Prop::False => 1024,
+    }
+}
+
+pub fn lean_prop(prop: &Prop) -> String {
+    lean_prop_at(prop, 0, true)
+}
+
+// Renders the proposition as operand of a notation that expects the given precedence.
+// Quantifiers only go without parentheses if nothing follows them.
+fn lean_prop_at(prop: &Prop, precedence: usize, trailing: bool) -> String {
+    let should_wrap = match prop {
+        Prop::ForAll { .. } | Prop::Exists { .. } => !trailing,
+        _ => lean_precedence(prop) < precedence,
+    };
+    let trailing = trailing || should_wrap;
+
+    let code = match prop {
+        Prop::Atom(name, params) if params.is_empty() => lean_ident(name),
+        Prop::Atom(name, params) => format!(
+            "{} {}",
+            lean_ident(name),
+            params.iter().map(lean_term_argument).join(" ")
+        ),
+        Prop::Eq(lhs, rhs) => format!("{} = {}", lean_term(lhs), lean_term(rhs)),
+        Prop::True => "True".to_string(),
+        Prop::False => "False".to_string(),
+        Prop::ForAll {
+            object_ident,
+            object_type_ident,
+            body,
+        } => format!(
+            "∀ {} : {}, {}",
+            lean_ident(object_ident),
+            lean_ident(object_type_ident),
+            lean_prop_at(body, 0, trailing)
+        ),
+        Prop::Exists {
+            object_ident,
+            object_type_ident,
+            body,
+        } => format!(
+            "∃ {} : {}, {}",
+            lean_ident(object_ident),
+            lean_ident(object_type_ident),
+            lean_prop_at(body, 0, trailing)
+        ),
+        Prop::Defined(_, args) if prop.is_iff() => format!(
+            "{} ↔ {}",
+            lean_prop_at(&args[0], 21, false),
+            lean_prop_at(&args[1], 21, trailing)
+        ),
+        Prop::Defined(name, args) if args.is_empty() => lean_ident(name),
+        Prop::Defined(name, args) => format!(
+            "{} {}",
+            lean_ident(name),
+            args.iter()
+                .map(|arg| lean_prop_at(arg, 1024, false))
+                .join(" ")
+        ),
+        Prop::Impl(fst, snd) if **snd == Prop::False => {
+            format!("¬{}", lean_prop_at(fst, 40, trailing))
+        }
+        Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+            let connective_symbol = match prop {
+                Prop::And(_, _) => "∧",
+                Prop::Or(_, _) => "∨",
+                _ => "→",
+            };
+
+            // all connectives are right associative
+            format!(
+                "{} {} {}",
+                lean_prop_at(fst, lean_precedence(prop) + 1, false),
+                connective_symbol,
+                lean_prop_at(snd, lean_precedence(prop), trailing)
+            )
+        }
+    };
+
+    match should_wrap {
+        true => format!("({})", code),
+        false => code,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        export::ProofExporter,
+        parse::{fol::fol_parser, lexer::lexer, proof::proof_parser},
+        process::{stages::resolve_datatypes::ResolveDatatypes, ProofPipeline},
+        prop::Prop,
+    };
+
+    use super::{lean_prop, LeanExporter};
+
+    fn parse_prop(prop: &str) -> Prop {
+        let len = prop.chars().count();
+
+        let tokens = lexer().parse(prop).unwrap();
+        fol_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    fn export(proof: &str, prop: &str) -> String {
+        let prop = parse_prop(prop);
+
+        let len = proof.chars().count();
+        let tokens = lexer().parse(proof).unwrap();
+        let proof_ast = proof_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+        let proof = ProofPipeline::new()
+            .pipe(ResolveDatatypes::boxed())
+            .apply(proof_ast, &prop)
+            .unwrap();
+
+        LeanExporter::new(&prop, &proof).export(&proof.proof_term)
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_lean_prop() {
+        assert_eq!(
+            lean_prop(&parse_prop("(A -> B) -> ~C && (A || B)")),
+            "(A → B) → ¬C ∧ (A ∨ B)"
+        );
+        assert_eq!(
+            lean_prop(&parse_prop(
+                "(\\forall x:t. P(x)) && ~(\\exists y:t. x = f(y)) <-> A"
+            )),
+            "(∀ x : t, P x) ∧ ¬(∃ y : t, x = f y) ↔ A"
+        );
+    }
+
+    #[test]
+    fn test_export_propositional() {
+        assert_eq!(
+            export(
+                "atom A; atom B; fn u => fn v => case u of inl a => abort (v a), inr b => fst (b, v)",
+                "A || B -> ~A -> B"
+            ),
+            "axiom A : Prop
+axiom B : Prop
+
+theorem proof : A ∨ B → ¬A → B :=
+  fun u => fun v => Or.elim u (fun a => False.elim (v a)) (fun b => ⟨b, v⟩.1)"
+        );
+    }
+
+    #[test]
+    fn test_export_quantifiers() {
+        assert_eq!(
+            export(
+                "datatype t; function f: t -> t; atom P(t); atom Q(1);
+                fn u: (\\exists x:t. P(x)) => fn v => let (x, w) = u in (x, v x w)",
+                "(\\exists x:t. P(x)) -> (\\forall x:t. P(x) -> Q(f(x))) -> \\exists y:t. Q(f(y))"
+            ),
+            "axiom t : Type
+axiom f : t → t
+axiom P : t → Prop
+axiom Q {α1 : Type} : α1 → Prop
+
+theorem proof : (∃ x : t, P x) → (∀ x : t, P x → Q (f x)) → ∃ y : t, Q (f y) :=
+  fun (u : ∃ x : t, P x) => fun v => Exists.elim u (fun x w => ⟨x, v x w⟩)"
+        );
+    }
+}
@@ -1,9 +1,38 @@
-use super::proof_term::{ProofTerm, Type};
+use super::{
+    checker::{check::check, identifier_context::IdentifierContext, Logic},
+    proof_term::{ProofTerm, Type},
+    proof_tree::ProofTree,
+    prop::{AtomSignature, FunctionSignature, Prop, PropDefinition},
+};
 
+pub mod agda_exporter;
+pub mod coq_exporter;
+pub mod fitch;
+pub mod haskell_exporter;
+pub mod latex_exporter;
+pub mod lean_exporter;
 pub mod ocaml_exporter;
+pub mod typescript_exporter;
 
 pub trait ProofExporter {
     fn can_export(&self, proof_term: &ProofTerm) -> bool;
     fn can_export_for_type(&self, _type: Type) -> bool;
     fn export(&self, proof_term: &ProofTerm) -> String;
-}
\ No newline at end of file
+}
+
+// Checks the proof term in classical logic, so exporters can detect classical reasoning in the proof tree.
+pub fn checked_proof_tree(
+    proof_term: &ProofTerm,
+    prop: &Prop,
+    functions: &[FunctionSignature],
+    atom_signatures: &[AtomSignature],
+    definitions: &[PropDefinition],
+) -> Option<ProofTree> {
+    let mut ctx = IdentifierContext::with_signatures(functions.to_vec(), atom_signatures.to_vec());
+    ctx.add_definitions(definitions.to_vec());
+    ctx.set_logic(Logic::Classical);
+
+    check(proof_term, prop, &ctx)
+        .ok()
+        .map(|result| result.proof_tree)
+}
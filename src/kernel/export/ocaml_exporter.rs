@@ -1,6 +1,6 @@
 use crate::kernel::proof_term::{
-    Abort, Application, Case, Function, Ident, OrLeft, OrRight, Pair, ProjectFst, ProjectSnd,
-    ProofTerm, Type, TypeAscription,
+    Abort, Application, Case, DoubleNegElim, Function, Ident, OrLeft, OrRight, Pair, ProjectFst,
+    ProjectSnd, ProofTerm, Subst, Type, TypeAscription,
 };
 
 use super::ProofExporter;
@@ -10,6 +10,7 @@ type ('a, 'b) disjunction = Inl of 'a | Inr of 'b
 let fst (x, _) = x
 let snd (_, y) = y
 let abort m : empty -> 'a = failwith \"abort\"
+let dne m : 'a = failwith \"dne\"
 let rec sorry () = sorry ()
 ";
 
@@ -27,6 +28,7 @@ impl OcamlExporter {
     fn generate_ocaml_term(proof_term: &ProofTerm) -> String {
         match proof_term {
             ProofTerm::Unit(_) => "()".to_string(),
+            ProofTerm::Refl(_) => "()".to_string(),
             ProofTerm::Sorry(_) => "sorry ()".to_string(),
             ProofTerm::Ident(Ident(ident, _)) => ident.clone(),
             ProofTerm::Abort(Abort(body, _)) => {
@@ -36,6 +38,13 @@ impl OcamlExporter {
                     format!("abort {}", Self::generate_ocaml_term(body.as_ref()))
                 }
             }
+            ProofTerm::DoubleNegElim(DoubleNegElim(body, _)) => {
+                if Self::should_wrap_unary(proof_term.precedence(), body.precedence()) {
+                    format!("dne ({})", Self::generate_ocaml_term(body))
+                } else {
+                    format!("dne {}", Self::generate_ocaml_term(body))
+                }
+            }
             ProofTerm::Pair(Pair(fst, snd, _)) => format!(
                 "({}, {})",
                 Self::generate_ocaml_term(fst),
@@ -122,6 +131,7 @@ impl OcamlExporter {
             ProofTerm::TypeAscription(TypeAscription { proof_term, .. }) => {
                 Self::generate_ocaml_term(proof_term)
             }
+            ProofTerm::Subst(Subst { body, .. }) => Self::generate_ocaml_term(body),
             ProofTerm::LetIn(_) => panic!(""),
         }
     }
@@ -137,10 +147,12 @@ impl ProofExporter for OcamlExporter {
     fn can_export(&self, proof_term: &ProofTerm) -> bool {
         match proof_term {
             ProofTerm::Unit(_) => true,
+            ProofTerm::Refl(_) => true,
             ProofTerm::Ident(_) => true,
             ProofTerm::Sorry(_) => true,
             ProofTerm::TypeAscription(_) => true,
             ProofTerm::Abort(Abort(body, _)) => self.can_export(body),
+            ProofTerm::DoubleNegElim(DoubleNegElim(body, _)) => self.can_export(body),
             ProofTerm::OrLeft(OrLeft(body, _)) => self.can_export(body),
             ProofTerm::OrRight(OrRight(body, _)) => self.can_export(body),
             ProofTerm::Case(Case {
@@ -168,6 +180,9 @@ impl ProofExporter for OcamlExporter {
 
                 self.can_export(body)
             }
+            ProofTerm::Subst(Subst { equality, body, .. }) => {
+                self.can_export(equality) && self.can_export(body)
+            }
             ProofTerm::LetIn(_) => false,
         }
     }
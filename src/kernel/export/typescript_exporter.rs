@@ -0,0 +1,391 @@
+use itertools::Itertools;
+
+use crate::kernel::{
+    proof_term::{
+        Abort, Application, Case, Function, Ident, OrLeft, OrRight, Pair, ProjectFst, ProjectSnd,
+        ProofTerm, Subst, Type, TypeAscription,
+    },
+    prop::{Prop, PropDefinition},
+};
+
+use super::ProofExporter;
+
+const TYPESCRIPT_KEYWORDS: [&str; 46] = [
+    "any",
+    "as",
+    "boolean",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "number",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "string",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+];
+
+// names which are already taken by the preamble
+const PREAMBLE_NAMES: [&str; 6] = ["Either", "inl", "inr", "caseOf", "absurd", "sorry"];
+
+const PREAMBLE: &str =
+    "type Either<A, B> = { tag: \"inl\"; value: A } | { tag: \"inr\"; value: B };
+
+function inl<A, B>(value: A): Either<A, B> {
+  return { tag: \"inl\", value };
+}
+
+function inr<A, B>(value: B): Either<A, B> {
+  return { tag: \"inr\", value };
+}
+
+function caseOf<A, B, C>(x: Either<A, B>, left: (a: A) => C, right: (b: B) => C): C {
+  return x.tag === \"inl\" ? left(x.value) : right(x.value);
+}
+
+function absurd<A>(_: never): A {
+  throw new Error(\"absurd\");
+}
+
+function sorry<A>(): A {
+  throw new Error(\"sorry\");
+}";
+
+pub struct TypescriptExporter {
+    prop: Prop,
+}
+
+impl TypescriptExporter {
+    pub fn new(prop: &Prop, definitions: &[PropDefinition]) -> Self {
+        let definitions = [PropDefinition::builtins(), definitions.to_vec()].concat();
+
+        // applications of definitions have already been validated by the proof pipeline
+        let mut prop = prop.clone();
+        let _ = prop.resolve_definitions(&definitions);
+
+        Self {
+            prop: prop.unfold_definitions(&definitions),
+        }
+    }
+
+    // Arrow functions are the only terms which have to be wrapped when being called or indexed.
+    fn generate_typescript_callee(proof_term: &ProofTerm) -> String {
+        match proof_term {
+            ProofTerm::Function(_) => format!("({})", Self::generate_typescript_term(proof_term)),
+            ProofTerm::TypeAscription(TypeAscription { proof_term, .. }) => {
+                Self::generate_typescript_callee(proof_term)
+            }
+            ProofTerm::Subst(Subst { body, .. }) => Self::generate_typescript_callee(body),
+            _ => Self::generate_typescript_term(proof_term),
+        }
+    }
+
+    fn generate_typescript_term(proof_term: &ProofTerm) -> String {
+        match proof_term {
+            ProofTerm::Unit(_) => "null".to_string(),
+            ProofTerm::Refl(_) => "null".to_string(),
+            ProofTerm::Sorry(_) => "sorry()".to_string(),
+            ProofTerm::Ident(Ident(ident, _)) => typescript_identifier(ident),
+            ProofTerm::Abort(Abort(body, _)) => {
+                format!("absurd({})", Self::generate_typescript_term(body))
+            }
+            ProofTerm::Pair(Pair(fst, snd, _)) => format!(
+                "[{}, {}]",
+                Self::generate_typescript_term(fst),
+                Self::generate_typescript_term(snd)
+            ),
+            ProofTerm::ProjectFst(ProjectFst(body, _)) => {
+                format!("{}[0]", Self::generate_typescript_callee(body))
+            }
+            ProofTerm::ProjectSnd(ProjectSnd(body, _)) => {
+                format!("{}[1]", Self::generate_typescript_callee(body))
+            }
+            ProofTerm::OrLeft(OrLeft(body, _)) => {
+                format!("inl({})", Self::generate_typescript_term(body))
+            }
+            ProofTerm::OrRight(OrRight(body, _)) => {
+                format!("inr({})", Self::generate_typescript_term(body))
+            }
+            ProofTerm::Case(Case {
+                head,
+                fst_ident,
+                fst_term,
+                snd_ident,
+                snd_term,
+                ..
+            }) => format!(
+                "caseOf({}, ({}) => {}, ({}) => {})",
+                Self::generate_typescript_term(head),
+                typescript_identifier(fst_ident),
+                Self::generate_typescript_term(fst_term),
+                typescript_identifier(snd_ident),
+                Self::generate_typescript_term(snd_term),
+            ),
+            ProofTerm::Function(Function {
+                param_ident, body, ..
+            }) => format!(
+                "({}) => {}",
+                typescript_identifier(param_ident),
+                Self::generate_typescript_term(body)
+            ),
+            ProofTerm::Application(Application {
+                function,
+                applicant,
+                ..
+            }) => format!(
+                "{}({})",
+                Self::generate_typescript_callee(function),
+                Self::generate_typescript_term(applicant)
+            ),
+            // parameter types are inferred from the signature of the proof
+            ProofTerm::TypeAscription(TypeAscription { proof_term, .. }) => {
+                Self::generate_typescript_term(proof_term)
+            }
+            ProofTerm::Subst(Subst { body, .. }) => Self::generate_typescript_term(body),
+            ProofTerm::LetIn(_) => panic!("Existential quantification cannot be exported."),
+            ProofTerm::DoubleNegElim(_) => panic!("Classical proofs cannot be exported."),
+        }
+    }
+}
+
+impl ProofExporter for TypescriptExporter {
+    fn export(&self, proof_term: &ProofTerm) -> String {
+        let type_parameters = self
+            .prop
+            .get_atoms()
+            .into_iter()
+            .map(|(atom, _)| typescript_identifier(&atom))
+            .unique()
+            .collect::<Vec<_>>();
+        let type_parameters = if type_parameters.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", type_parameters.join(", "))
+        };
+
+        format!(
+            "{}\n\nexport function proof{}(): {} {{\n  return {};\n}}",
+            PREAMBLE,
+            type_parameters,
+            typescript_type(&self.prop),
+            Self::generate_typescript_term(proof_term)
+        )
+    }
+
+    fn can_export(&self, proof_term: &ProofTerm) -> bool {
+        match proof_term {
+            ProofTerm::Unit(_) => true,
+            ProofTerm::Refl(_) => true,
+            ProofTerm::Ident(_) => true,
+            ProofTerm::Sorry(_) => true,
+            ProofTerm::TypeAscription(TypeAscription { proof_term, .. }) => {
+                self.can_export(proof_term)
+            }
+            ProofTerm::Abort(Abort(body, _)) => self.can_export(body),
+            // programs only have a computational meaning for constructive proofs
+            ProofTerm::DoubleNegElim(_) => false,
+            ProofTerm::OrLeft(OrLeft(body, _)) => self.can_export(body),
+            ProofTerm::OrRight(OrRight(body, _)) => self.can_export(body),
+            ProofTerm::Case(Case {
+                head,
+                fst_term,
+                snd_term,
+                ..
+            }) => self.can_export(head) && self.can_export(fst_term) && self.can_export(snd_term),
+            ProofTerm::Pair(Pair(fst, snd, _)) => self.can_export(fst) && self.can_export(snd),
+            ProofTerm::ProjectFst(ProjectFst(body, _)) => self.can_export(body),
+            ProofTerm::ProjectSnd(ProjectSnd(body, _)) => self.can_export(body),
+            ProofTerm::Application(Application {
+                function,
+                applicant,
+                ..
+            }) => self.can_export(function) && self.can_export(applicant),
+            ProofTerm::Function(Function {
+                param_type, body, ..
+            }) => {
+                if let Some(param_type) = param_type {
+                    if param_type.has_quantifiers() || param_type.is_datatype() {
+                        return false;
+                    }
+                }
+
+                self.can_export(body)
+            }
+            ProofTerm::Subst(Subst { equality, body, .. }) => {
+                self.can_export(equality) && self.can_export(body)
+            }
+            ProofTerm::LetIn(_) => false,
+        }
+    }
+
+    fn can_export_for_type(&self, _type: Type) -> bool {
+        let Type::Prop(prop) = _type else {
+            return false;
+        };
+
+        !prop.has_quantifiers()
+    }
+}
+
+fn typescript_identifier(ident: &str) -> String {
+    if TYPESCRIPT_KEYWORDS.contains(&ident) || PREAMBLE_NAMES.contains(&ident) {
+        format!("{}_", ident)
+    } else {
+        ident.to_string()
+    }
+}
+
+fn typescript_type(prop: &Prop) -> String {
+    match prop {
+        Prop::Atom(name, _) => typescript_identifier(name),
+        Prop::True | Prop::Eq(_, _) => "null".to_string(),
+        Prop::False => "never".to_string(),
+        Prop::And(fst, snd) => format!("[{}, {}]", typescript_type(fst), typescript_type(snd)),
+        Prop::Or(fst, snd) => format!("Either<{}, {}>", typescript_type(fst), typescript_type(snd)),
+        Prop::Impl(fst, snd) => {
+            format!("(_: {}) => {}", typescript_type(fst), typescript_type(snd))
+        }
+        Prop::Defined(_, _) | Prop::ForAll { .. } | Prop::Exists { .. } => {
+            panic!("Only unfolded propositions without quantifiers can be exported.")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        export::ProofExporter,
+        parse::{fol::fol_parser, lexer::lexer, proof::proof_parser},
+        process::{stages::resolve_datatypes::ResolveDatatypes, ProofPipeline},
+        proof::Proof,
+        prop::Prop,
+    };
+
+    use super::{TypescriptExporter, PREAMBLE};
+
+    fn parse_prop(prop: &str) -> Prop {
+        let len = prop.chars().count();
+
+        let tokens = lexer().parse(prop).unwrap();
+        fol_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    fn parse_proof(proof: &str, prop: &Prop) -> Proof {
+        let len = proof.chars().count();
+        let tokens = lexer().parse(proof).unwrap();
+        let proof_ast = proof_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+        ProofPipeline::new()
+            .pipe(ResolveDatatypes::boxed())
+            .apply(proof_ast, prop)
+            .unwrap()
+    }
+
+    fn export(proof: &str, prop: &str) -> String {
+        let prop = parse_prop(prop);
+        let proof = parse_proof(proof, &prop);
+
+        let exporter = TypescriptExporter::new(&prop, &proof.definitions);
+        assert!(exporter.can_export(&proof.proof_term));
+
+        exporter
+            .export(&proof.proof_term)
+            .strip_prefix(PREAMBLE)
+            .unwrap()
+            .trim()
+            .to_string()
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_export_propositional() {
+        assert_eq!(
+            export(
+                "atom A; atom B; fn u => fn v => case u of inl a => abort (v a), inr b => fst (b, v)",
+                "A || B -> ~A -> B"
+            ),
+            "export function proof<A, B>(): (_: Either<A, B>) => (_: (_: A) => never) => B {\n  return (u) => (v) => caseOf(u, (a) => absurd(v(a)), (b) => [b, v][0]);\n}"
+        );
+    }
+
+    #[test]
+    fn test_export_application() {
+        assert_eq!(
+            export(
+                "atom A; fn u => (fn p => snd p) (u, ())",
+                "A -> \\top"
+            ),
+            "export function proof<A>(): (_: A) => null {\n  return (u) => ((p) => p[1])([u, null]);\n}"
+        );
+    }
+
+    #[test]
+    fn test_export_names() {
+        assert_eq!(
+            export("atom Either; atom this; fn this => fn Either => (Either, this)", "Either -> this -> this && Either"),
+            "export function proof<Either_, this_>(): (_: Either_) => (_: this_) => [this_, Either_] {\n  return (this_) => (Either_) => [Either_, this_];\n}"
+        );
+    }
+
+    #[test]
+    fn test_classical_proofs_cannot_be_exported() {
+        let prop = parse_prop("A || ~A");
+        let proof = parse_proof("atom A; dne (fn v => v (inr (fn a => v (inl a))))", &prop);
+
+        let exporter = TypescriptExporter::new(&prop, &proof.definitions);
+        assert!(!exporter.can_export(&proof.proof_term));
+    }
+
+    #[test]
+    fn test_export_definitions() {
+        assert_eq!(
+            export(
+                "atom A; def Dup(X) := X && X; fn u => ((fn a => a, fn a => a), fst u)",
+                "Dup(A) -> (A <-> A) && A"
+            ),
+            "export function proof<A>(): (_: [A, A]) => [[(_: A) => A, (_: A) => A], A] {\n  return (u) => [[(a) => a, (a) => a], u[0]];\n}"
+        );
+    }
+}
@@ -3,7 +3,12 @@ pub mod export;
 pub mod parse;
 pub mod process;
 pub mod proof;
+pub mod proof_state;
 pub mod proof_term;
 pub mod proof_tree;
 pub mod prop;
-pub mod prove;
\ No newline at end of file
+pub mod prove;
+pub mod reduction;
+pub mod semantics;
+pub mod theorem_file;
+pub mod tptp;
\ No newline at end of file
@@ -1,7 +1,6 @@
 use std::vec;
 
 use chumsky::prelude::*;
-use itertools::Itertools;
 
 use crate::kernel::prop::{Prop, PropParameter};
 
@@ -42,30 +41,63 @@ pub fn fol_parser() -> impl Parser<Token, Prop, Error = Simple<Token>> {
 
         let quantor = choice((allquant, existsquant)).boxed();
 
-        let ident_list = ident
-            .then(just(Token::COMMA).ignore_then(ident).repeated())
-            .boxed();
-
-        let atom_params = ident_list
+        let term = recursive(|term| {
+            let term_list = term
+                .clone()
+                .then(just(Token::COMMA).ignore_then(term).repeated())
+                .then_ignore(just(Token::COMMA).or_not())
+                .delimited_by(just(Token::LROUND), just(Token::RROUND))
+                .map(|(head, mut tail): (PropParameter, Vec<PropParameter>)| {
+                    tail.insert(0, head);
+                    tail
+                });
+
+            ident
+                .then(term_list.or_not())
+                .map(|(ident, params)| match params {
+                    Some(params) => PropParameter::Function(ident, params),
+                    None => PropParameter::Uninstantiated(ident),
+                })
+        })
+        .boxed();
+
+        let atom_params = term
+            .clone()
+            .then(just(Token::COMMA).ignore_then(term.clone()).repeated())
             .then_ignore(just(Token::COMMA).or_not())
             .delimited_by(just(Token::LROUND), just(Token::RROUND))
             .boxed();
 
-        let atom = ident
-            .then(atom_params.or_not())
-            .map(|(ident, params)| {
-                if let Some((head, mut tail)) = params {
-                    tail.insert(0, head);
-                    Prop::Atom(
-                        ident,
-                        tail.into_iter()
-                            .map(PropParameter::Uninstantiated)
-                            .collect_vec(),
-                    )
-                } else {
-                    Prop::Atom(ident, vec![])
-                }
+        let equality = term
+            .clone()
+            .then_ignore(just(Token::EQUAL))
+            .then(term.clone())
+            .map(|(lhs, rhs)| Prop::Eq(lhs, rhs))
+            .boxed();
+
+        let predicate = ident
+            .then(atom_params)
+            .map(|(ident, (head, mut tail))| {
+                tail.insert(0, head);
+                Prop::Atom(ident, tail)
             })
+            .boxed();
+
+        // arguments of definitions can be propositions, e.g. "Iff(A & B, C)"
+        let definition_application = ident
+            .then(
+                prop.clone()
+                    .separated_by(just(Token::COMMA))
+                    .allow_trailing()
+                    .delimited_by(just(Token::LROUND), just(Token::RROUND)),
+            )
+            .map(|(ident, args)| Prop::Defined(ident, args))
+            .boxed();
+
+        let atom = equality
+            .or(predicate)
+            .or(definition_application)
+            .or(ident.map(|ident| Prop::Atom(ident, vec![])))
             .or(prop
                 .clone()
                 .delimited_by(just(Token::LROUND), just(Token::RROUND)))
@@ -137,7 +169,16 @@ pub fn fol_parser() -> impl Parser<Token, Prop, Error = Simple<Token>> {
             .foldr(|lhs, rhs| Prop::Impl(lhs.boxed(), rhs.boxed()))
             .boxed();
 
-        implication
+        let iff = implication
+            .clone()
+            .then(just(Token::IFF).ignore_then(implication).or_not())
+            .map(|(lhs, rhs)| match rhs {
+                Some(rhs) => Prop::Defined("Iff".to_string(), vec![lhs, rhs]),
+                None => lhs,
+            })
+            .boxed();
+
+        iff
     });
 
     prop
@@ -589,6 +630,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_equality() {
+        let fol = "\\forall x:t. x = y -> A(x)";
+        let len = fol.chars().count();
+
+        let tokens = lexer().parse(fol).unwrap();
+        let ast = fol_parser()
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+
+        assert_eq!(
+            ast,
+            Prop::ForAll {
+                object_ident: s!("x"),
+                object_type_ident: s!("t"),
+                body: Prop::Impl(
+                    Prop::Eq(
+                        PropParameter::Uninstantiated(s!("x")),
+                        PropParameter::Uninstantiated(s!("y"))
+                    )
+                    .boxed(),
+                    Prop::Atom(s!("A"), vec![PropParameter::Uninstantiated(s!("x"))]).boxed()
+                )
+                .boxed()
+            }
+        );
+    }
+
+    #[test]
+    fn test_function_terms() {
+        let fol = "A(f(x), succ(zero))";
+        let len = fol.chars().count();
+
+        let tokens = lexer().parse(fol).unwrap();
+        let ast = fol_parser()
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+
+        assert_eq!(
+            ast,
+            Prop::Atom(
+                s!("A"),
+                vec![
+                    PropParameter::Function(s!("f"), vec![PropParameter::Uninstantiated(s!("x"))]),
+                    PropParameter::Function(
+                        s!("succ"),
+                        vec![PropParameter::Uninstantiated(s!("zero"))]
+                    ),
+                ]
+            )
+        );
+    }
+
     #[test]
     fn test_nested_forall() {
         let fol = "A && (\\forall x:t. x) && C";
@@ -644,4 +738,57 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_iff() {
+        let fol = "A && B <-> C -> A";
+        let len = fol.chars().count();
+
+        let tokens = lexer().parse(fol).unwrap();
+        let ast = fol_parser()
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+
+        assert_eq!(
+            ast,
+            Prop::Defined(
+                s!("Iff"),
+                vec![
+                    Prop::And(
+                        Prop::Atom(s!("A"), vec![]).boxed(),
+                        Prop::Atom(s!("B"), vec![]).boxed()
+                    ),
+                    Prop::Impl(
+                        Prop::Atom(s!("C"), vec![]).boxed(),
+                        Prop::Atom(s!("A"), vec![]).boxed()
+                    ),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_definition_application() {
+        let fol = "Xor(A && B, C(x),)";
+        let len = fol.chars().count();
+
+        let tokens = lexer().parse(fol).unwrap();
+        let ast = fol_parser()
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+
+        assert_eq!(
+            ast,
+            Prop::Defined(
+                s!("Xor"),
+                vec![
+                    Prop::And(
+                        Prop::Atom(s!("A"), vec![]).boxed(),
+                        Prop::Atom(s!("B"), vec![]).boxed()
+                    ),
+                    Prop::Atom(s!("C"), vec![PropParameter::Uninstantiated(s!("x"))]),
+                ]
+            )
+        );
+    }
 }
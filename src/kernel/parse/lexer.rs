@@ -37,7 +37,13 @@ pub fn lexer() -> impl Parser<char, Vec<(Token, Range<usize>)>, Error = Simple<c
         "in" => Token::IN,
         "atom" => Token::ATOM,
         "datatype" => Token::DATATYPE,
+        "function" => Token::FUNCTION,
         "sorry" => Token::SORRY,
+        "refl" => Token::REFL,
+        "subst" => Token::SUBST,
+        "theorem" => Token::THEOREM,
+        "lemma" => Token::LEMMA,
+        "def" => Token::DEF,
         _ => Token::IDENT(s),
     });
 
@@ -55,6 +61,10 @@ pub fn lexer() -> impl Parser<char, Vec<(Token, Range<usize>)>, Error = Simple<c
         .map(|_| Token::ARROW)
         .boxed();
 
+    let iff = choice((just("<->"), just("↔"), just("\\iff")))
+        .map(|_| Token::IFF)
+        .boxed();
+
     let implication = choice((just("->"), just("→"), just("⊃")))
         .map(|_| Token::IMPLICATION)
         .boxed();
@@ -71,6 +81,8 @@ pub fn lexer() -> impl Parser<char, Vec<(Token, Range<usize>)>, Error = Simple<c
 
     let comma = just(",").map(|_| Token::COMMA).boxed();
 
+    let assign = just(":=").map(|_| Token::ASSIGN).boxed();
+
     let colon = just(":").map(|_| Token::COLON).boxed();
 
     let semicolon = just(";").map(|_| Token::SEMICOLON).boxed();
@@ -112,12 +124,14 @@ pub fn lexer() -> impl Parser<char, Vec<(Token, Range<usize>)>, Error = Simple<c
         and,
         or,
         arrow,
+        iff,
         implication,
         not,
         lround,
         rround,
         dot,
         comma,
+        assign,
         colon,
         semicolon,
         forall,
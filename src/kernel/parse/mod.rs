@@ -13,6 +13,7 @@ pub enum Token {
     OR,
     ARROW,
     IMPLICATION,
+    IFF,
     NOT,
     LROUND,
     RROUND,
@@ -33,7 +34,14 @@ pub enum Token {
     EQUAL,
     ATOM,
     DATATYPE,
+    FUNCTION,
     SORRY,
+    REFL,
+    SUBST,
+    THEOREM,
+    LEMMA,
+    ASSIGN,
+    DEF,
 }
 
 impl fmt::Display for Token {
@@ -45,6 +53,7 @@ impl fmt::Display for Token {
             Token::OR => write!(f, "∨"),
             Token::ARROW => write!(f, "=>"),
             Token::IMPLICATION => write!(f, "→"),
+            Token::IFF => write!(f, "↔"),
             Token::NOT => write!(f, "¬"),
             Token::LROUND => write!(f, "("),
             Token::RROUND => write!(f, ")"),
@@ -65,8 +74,15 @@ impl fmt::Display for Token {
             Token::EQUAL => write!(f, "="),
             Token::ATOM => write!(f, "atom"),
             Token::DATATYPE => write!(f, "datatype"),
+            Token::FUNCTION => write!(f, "function"),
 
             Token::SORRY => write!(f, "sorry"),
+            Token::REFL => write!(f, "refl"),
+            Token::SUBST => write!(f, "subst"),
+            Token::THEOREM => write!(f, "theorem"),
+            Token::LEMMA => write!(f, "lemma"),
+            Token::ASSIGN => write!(f, ":="),
+            Token::DEF => write!(f, "def"),
         }
     }
 }
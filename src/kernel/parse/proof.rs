@@ -1,21 +1,57 @@
 use chumsky::prelude::*;
-use itertools::{Either, Itertools};
 
-use crate::kernel::proof::{Proof, ProofProcessingState};
-
-use super::{proof_term::proof_term_parser, Token};
+use crate::kernel::{
+    proof::{Proof, ProofProcessingState},
+    prop::{AtomSignature, FunctionSignature, PropDefinition},
+    theorem_file::{Theorem, TheoremFile},
+};
+
+use super::{
+    fol::fol_parser,
+    proof_term::{inner_proof_term_parser, proof_term_parser},
+    Token,
+};
+
+enum DeclarationType {
+    Atom(String, usize),
+    SortedAtom(AtomSignature),
+    Datatype(String),
+    Function(FunctionSignature),
+    Definition(PropDefinition),
+}
 
-/**
- *     == Proof Parser ==
- */
-pub fn proof_parser() -> impl Parser<Token, Proof, Error = Simple<Token>> {
-    let ident = select! { Token::IDENT(ident) => ident };
-    let num = select! { Token::NUM(num) => num };
+#[derive(Default)]
+struct Declarations {
+    atoms: Vec<(String, usize)>,
+    atom_signatures: Vec<AtomSignature>,
+    datatypes: Vec<String>,
+    functions: Vec<FunctionSignature>,
+    definitions: Vec<PropDefinition>,
+}
 
-    enum DeclarationType {
-        Atom(String, usize),
-        Datatype(String),
+impl Declarations {
+    fn push(&mut self, decl: DeclarationType) {
+        match decl {
+            DeclarationType::Atom(atom, arity) => self.atoms.push((atom, arity)),
+            DeclarationType::SortedAtom(signature) => {
+                self.atoms
+                    .push((signature.name.clone(), signature.param_types.len()));
+                self.atom_signatures.push(signature);
+            }
+            DeclarationType::Datatype(datatype) => self.datatypes.push(datatype),
+            DeclarationType::Function(signature) => self.functions.push(signature),
+            DeclarationType::Definition(definition) => self.definitions.push(definition),
+        }
     }
+}
+
+fn ident_parser() -> impl Parser<Token, String, Error = Simple<Token>> + Copy {
+    select! { Token::IDENT(ident) => ident }
+}
+
+fn declaration_parser() -> impl Parser<Token, DeclarationType, Error = Simple<Token>> {
+    let ident = ident_parser();
+    let num = select! { Token::NUM(num) => num };
 
     let atom_decl = just(Token::ATOM)
         .ignore_then(ident)
@@ -29,33 +65,156 @@ pub fn proof_parser() -> impl Parser<Token, Proof, Error = Simple<Token>> {
         .map(|(atom, arity)| DeclarationType::Atom(atom, arity.unwrap_or(0)))
         .boxed();
 
+    // atom Less(nat, nat);
+    let sorted_atom_decl = just(Token::ATOM)
+        .ignore_then(ident)
+        .then(
+            ident
+                .separated_by(just(Token::COMMA))
+                .at_least(1)
+                .delimited_by(just(Token::LROUND), just(Token::RROUND)),
+        )
+        .then_ignore(just(Token::SEMICOLON))
+        .map(|(name, param_types)| DeclarationType::SortedAtom(AtomSignature { name, param_types }))
+        .boxed();
+
     let datatype_decl = just(Token::DATATYPE)
         .ignore_then(ident)
         .then_ignore(just(Token::SEMICOLON))
         .map(DeclarationType::Datatype)
         .boxed();
 
-    let decls = choice((datatype_decl, atom_decl)).repeated().boxed();
+    // function succ: nat -> nat;
+    let function_decl = just(Token::FUNCTION)
+        .ignore_then(ident)
+        .then_ignore(just(Token::COLON))
+        .then(ident.then_ignore(just(Token::IMPLICATION)).repeated())
+        .then(ident)
+        .then_ignore(just(Token::SEMICOLON))
+        .map(|((name, param_types), return_type)| {
+            DeclarationType::Function(FunctionSignature {
+                name,
+                param_types,
+                return_type,
+            })
+        })
+        .boxed();
 
-    decls
+    // def Iff(X, Y) := (X -> Y) & (Y -> X);
+    let definition_decl = just(Token::DEF)
+        .ignore_then(ident)
+        .then(
+            ident
+                .separated_by(just(Token::COMMA))
+                .delimited_by(just(Token::LROUND), just(Token::RROUND))
+                .or_not(),
+        )
+        .then_ignore(just(Token::ASSIGN))
+        .then(fol_parser())
+        .then_ignore(just(Token::SEMICOLON))
+        .map(|((name, params), body)| {
+            DeclarationType::Definition(PropDefinition {
+                name,
+                params: params.unwrap_or_default(),
+                body,
+            })
+        })
+        .boxed();
+
+    choice((
+        datatype_decl,
+        atom_decl,
+        sorted_atom_decl,
+        function_decl,
+        definition_decl,
+    ))
+    .boxed()
+}
+
+/**
+ *     == Proof Parser ==
+ */
+pub fn proof_parser() -> impl Parser<Token, Proof, Error = Simple<Token>> {
+    declaration_parser()
+        .repeated()
         .then(proof_term_parser().then_ignore(end()))
         .map(|(declarations, proof_term)| {
-            let (atoms, datatypes): (Vec<(String, usize)>, Vec<String>) =
-                declarations.into_iter().partition_map(|decl| match decl {
-                    DeclarationType::Atom(atom, arity) => Either::Left((atom, arity)),
-                    DeclarationType::Datatype(datatype) => Either::Right(datatype),
-                });
+            let mut decls = Declarations::default();
+            for decl in declarations {
+                decls.push(decl);
+            }
 
             Proof {
                 processing_state: ProofProcessingState::Parsed,
-                datatypes,
-                atoms,
+                datatypes: decls.datatypes,
+                atoms: decls.atoms,
+                atom_signatures: decls.atom_signatures,
+                functions: decls.functions,
+                definitions: decls.definitions,
                 proof_term,
             }
         })
         .boxed()
 }
 
+/**
+ *     == Theorem File Parser ==
+ *
+ *     atom A; atom B;
+ *     theorem swap : A & B -> B & A := fn p => (snd p, fst p);
+ */
+pub fn theorem_file_parser() -> impl Parser<Token, TheoremFile, Error = Simple<Token>> {
+    enum Item {
+        Declaration(DeclarationType),
+        Theorem(Box<Theorem>),
+    }
+
+    let theorem = choice((just(Token::THEOREM), just(Token::LEMMA)))
+        .ignore_then(ident_parser())
+        .then_ignore(just(Token::COLON))
+        .then(fol_parser())
+        .then_ignore(just(Token::ASSIGN))
+        .then(inner_proof_term_parser())
+        .then_ignore(just(Token::SEMICOLON))
+        .map_with_span(|((name, prop), proof_term), span| {
+            Box::new(Theorem {
+                name,
+                prop,
+                proof_term,
+                span: Some(span),
+            })
+        })
+        .boxed();
+
+    choice((
+        declaration_parser().map(Item::Declaration),
+        theorem.map(Item::Theorem),
+    ))
+    .repeated()
+    .then_ignore(end())
+    .map(|items| {
+        let mut decls = Declarations::default();
+        let mut theorems = vec![];
+
+        for item in items {
+            match item {
+                Item::Declaration(decl) => decls.push(decl),
+                Item::Theorem(theorem) => theorems.push(*theorem),
+            }
+        }
+
+        TheoremFile {
+            datatypes: decls.datatypes,
+            atoms: decls.atoms,
+            atom_signatures: decls.atom_signatures,
+            functions: decls.functions,
+            definitions: decls.definitions,
+            theorems,
+        }
+    })
+    .boxed()
+}
+
 // ==== TESTS ====
 
 #[cfg(test)]
@@ -66,9 +225,10 @@ mod tests {
         parse::lexer::lexer,
         proof::{Proof, ProofProcessingState},
         proof_term::{Function, Ident, ProofTerm},
+        prop::{AtomSignature, FunctionSignature, Prop, PropDefinition},
     };
 
-    use super::proof_parser;
+    use super::{proof_parser, theorem_file_parser};
 
     #[test]
     fn test_no_datatypes_no_atoms_function() {
@@ -86,6 +246,9 @@ mod tests {
                 processing_state: ProofProcessingState::Parsed,
                 datatypes: vec![],
                 atoms: vec![],
+                atom_signatures: vec![],
+                functions: vec![],
+                definitions: vec![],
                 proof_term: ProofTerm::Function(Function {
                     param_type: None,
                     param_ident: "u".to_string(),
@@ -111,7 +274,10 @@ mod tests {
             Proof {
                 processing_state: ProofProcessingState::Parsed,
                 atoms: vec![],
+                atom_signatures: vec![],
+                functions: vec![],
                 datatypes: vec![],
+                definitions: vec![],
                 proof_term: ProofTerm::Unit(Some(0..2)),
             }
         )
@@ -132,7 +298,10 @@ mod tests {
             Proof {
                 processing_state: ProofProcessingState::Parsed,
                 atoms: vec![],
+                atom_signatures: vec![],
+                functions: vec![],
                 datatypes: vec!["nat".to_string()],
+                definitions: vec![],
                 proof_term: ProofTerm::Function(Function {
                     param_ident: "u".to_string(),
                     param_type: None,
@@ -158,7 +327,10 @@ mod tests {
             Proof {
                 processing_state: ProofProcessingState::Parsed,
                 atoms: vec![("A".to_string(), 0)],
+                atom_signatures: vec![],
+                functions: vec![],
                 datatypes: vec![],
+                definitions: vec![],
                 proof_term: ProofTerm::Function(Function {
                     param_ident: "u".to_string(),
                     param_type: None,
@@ -184,7 +356,10 @@ mod tests {
             Proof {
                 processing_state: ProofProcessingState::Parsed,
                 atoms: vec![],
+                atom_signatures: vec![],
+                functions: vec![],
                 datatypes: vec!["nat".to_string()],
+                definitions: vec![],
                 proof_term: ProofTerm::Unit(Some(14..16)),
             }
         )
@@ -210,7 +385,10 @@ mod tests {
                     ("B".to_string(), 1),
                     ("C".to_string(), 2)
                 ],
+                atom_signatures: vec![],
+                functions: vec![],
                 datatypes: vec!["nat".to_string(), "t".to_string(), "list".to_string()],
+                definitions: vec![],
                 proof_term: ProofTerm::Function(Function {
                     param_ident: "u".to_string(),
                     param_type: None,
@@ -236,12 +414,84 @@ mod tests {
             Proof {
                 processing_state: ProofProcessingState::Parsed,
                 atoms: vec![("A".to_string(), 0), ("B".to_string(), 42)],
+                atom_signatures: vec![],
+                functions: vec![],
                 datatypes: vec!["nat".to_string(), "t".to_string(), "list".to_string()],
+                definitions: vec![],
                 proof_term: ProofTerm::Unit(Some(61..63)),
             }
         )
     }
 
+    #[test]
+    fn test_function_declarations() {
+        let proof_term = "datatype nat; function zero: nat; function add: nat -> nat -> nat; ()";
+        let len = proof_term.chars().count();
+
+        let tokens = lexer().parse(proof_term).unwrap();
+        let ast = proof_parser()
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+
+        assert_eq!(
+            ast,
+            Proof {
+                processing_state: ProofProcessingState::Parsed,
+                atoms: vec![],
+                atom_signatures: vec![],
+                functions: vec![
+                    FunctionSignature {
+                        name: "zero".to_string(),
+                        param_types: vec![],
+                        return_type: "nat".to_string(),
+                    },
+                    FunctionSignature {
+                        name: "add".to_string(),
+                        param_types: vec!["nat".to_string(), "nat".to_string()],
+                        return_type: "nat".to_string(),
+                    },
+                ],
+                datatypes: vec!["nat".to_string()],
+                definitions: vec![],
+                proof_term: ProofTerm::Unit(Some(67..69)),
+            }
+        )
+    }
+
+    #[test]
+    fn test_sorted_atom_declarations() {
+        let proof_term =
+            "datatype nat; datatype person; atom Less(nat, nat); atom Likes(person, nat); ()";
+        let len = proof_term.chars().count();
+
+        let tokens = lexer().parse(proof_term).unwrap();
+        let ast = proof_parser()
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+
+        assert_eq!(
+            ast,
+            Proof {
+                processing_state: ProofProcessingState::Parsed,
+                atoms: vec![("Less".to_string(), 2), ("Likes".to_string(), 2)],
+                atom_signatures: vec![
+                    AtomSignature {
+                        name: "Less".to_string(),
+                        param_types: vec!["nat".to_string(), "nat".to_string()],
+                    },
+                    AtomSignature {
+                        name: "Likes".to_string(),
+                        param_types: vec!["person".to_string(), "nat".to_string()],
+                    },
+                ],
+                functions: vec![],
+                datatypes: vec!["nat".to_string(), "person".to_string()],
+                definitions: vec![],
+                proof_term: ProofTerm::Unit(Some(77..79)),
+            }
+        )
+    }
+
     #[test]
     fn test_datatypes_after_proof_term() {
         let proof_term = "datatype nat; (fn u => u) datatype uff;";
@@ -269,4 +519,90 @@ mod tests {
 
         assert!(ast.is_err())
     }
+
+    #[test]
+    fn test_definition_declarations() {
+        let proof_term = "def Xor(X, Y) := (X || Y) && ~(X && Y); def Unit := \\top; sorry";
+        let len = proof_term.chars().count();
+
+        let tokens = lexer().parse(proof_term).unwrap();
+        let ast = proof_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+
+        let var = |name: &str| Prop::Atom(name.to_string(), vec![]);
+
+        assert_eq!(
+            ast.definitions,
+            vec![
+                PropDefinition {
+                    name: "Xor".to_string(),
+                    params: vec!["X".to_string(), "Y".to_string()],
+                    body: Prop::And(
+                        Prop::Or(var("X").boxed(), var("Y").boxed()).boxed(),
+                        Prop::Impl(
+                            Prop::And(var("X").boxed(), var("Y").boxed()).boxed(),
+                            Prop::False.boxed()
+                        )
+                        .boxed()
+                    ),
+                },
+                PropDefinition {
+                    name: "Unit".to_string(),
+                    params: vec![],
+                    body: Prop::True,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_theorem_file() {
+        let src = "atom A; theorem id : A -> A := fn u => u; datatype t; lemma top : T := ();";
+        let len = src.chars().count();
+
+        let tokens = lexer().parse(src).unwrap();
+        let ast = theorem_file_parser()
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+
+        assert_eq!(ast.atoms, vec![("A".to_string(), 0)]);
+        assert_eq!(ast.datatypes, vec!["t".to_string()]);
+        assert_eq!(ast.theorems.len(), 2);
+
+        assert_eq!(ast.theorems[0].name, "id");
+        assert_eq!(
+            ast.theorems[0].prop,
+            Prop::Impl(
+                Prop::Atom("A".to_string(), vec![]).boxed(),
+                Prop::Atom("A".to_string(), vec![]).boxed()
+            )
+        );
+        assert_eq!(
+            ast.theorems[0].proof_term,
+            ProofTerm::Function(Function {
+                param_ident: "u".to_string(),
+                param_type: None,
+                body: ProofTerm::Ident(Ident("u".to_string(), Some(39..40))).boxed(),
+                span: Some(31..40),
+            })
+        );
+        assert_eq!(ast.theorems[0].span, Some(8..41));
+
+        assert_eq!(ast.theorems[1].name, "top");
+        assert_eq!(ast.theorems[1].prop, Prop::True);
+        assert_eq!(ast.theorems[1].proof_term, ProofTerm::Unit(Some(71..73)));
+    }
+
+    #[test]
+    fn test_theorem_file_missing_semicolon() {
+        let src = "atom A; theorem id : A -> A := fn u => u";
+        let len = src.chars().count();
+
+        let tokens = lexer().parse(src).unwrap();
+        let ast = theorem_file_parser().parse(Stream::from_iter(len..len + 1, tokens.into_iter()));
+
+        assert!(ast.is_err())
+    }
 }
@@ -1,8 +1,8 @@
 use chumsky::prelude::*;
 
 use crate::kernel::proof_term::{
-    Abort, Application, Case, Function, Ident, LetIn, OrLeft, OrRight, Pair, ProjectFst,
-    ProjectSnd, ProofTerm, Type, TypeAscription,
+    Abort, Application, Case, DoubleNegElim, Function, Ident, LetIn, OrLeft, OrRight, Pair,
+    ProjectFst, ProjectSnd, ProofTerm, Subst, Type, TypeAscription,
 };
 
 use super::{fol::fol_parser, Token};
@@ -11,6 +11,11 @@ use super::{fol::fol_parser, Token};
     == Proof Term Parser ==
 */
 pub fn proof_term_parser() -> impl Parser<Token, ProofTerm, Error = Simple<Token>> {
+    inner_proof_term_parser().then_ignore(end())
+}
+
+// Parses a proof term that may be followed by other tokens, e.g. inside a theorem file.
+pub(super) fn inner_proof_term_parser() -> impl Parser<Token, ProofTerm, Error = Simple<Token>> {
     let ident_token = select! { Token::IDENT(ident) => ident }.labelled("identifier");
 
     let type_ascription = just(Token::COLON).ignore_then(fol_parser()).boxed();
@@ -38,6 +43,8 @@ pub fn proof_term_parser() -> impl Parser<Token, ProofTerm, Error = Simple<Token
 
         let sorry = just(Token::SORRY).map_with_span(|_, span| ProofTerm::Sorry(Some(span)));
 
+        let refl = just(Token::REFL).map_with_span(|_, span| ProofTerm::Refl(Some(span)));
+
         let atom = choice((
             proof_term
                 .clone()
@@ -46,9 +53,18 @@ pub fn proof_term_parser() -> impl Parser<Token, ProofTerm, Error = Simple<Token
             pair,
             unit,
             sorry,
+            refl,
         ))
         .boxed();
 
+        let subst = just(Token::SUBST)
+            .ignore_then(atom.clone())
+            .then(atom.clone())
+            .map_with_span(|(equality, body), span| {
+                Subst::create(equality.boxed(), body.boxed(), Some(span))
+            })
+            .boxed();
+
         let function = just(Token::FN)
             .ignore_then(ident_token)
             .then(just(Token::COLON).ignore_then(fol_parser()).or_not())
@@ -138,7 +154,7 @@ pub fn proof_term_parser() -> impl Parser<Token, ProofTerm, Error = Simple<Token
             .then(atom.clone().repeated())
             .try_map(|(lhs, rhs), span| {
                 //  check that if lhs is constructor/destructor, we got a rhs
-                let identifiers = ["inl", "inr", "abort", "fst", "snd"];
+                let identifiers = ["inl", "inr", "abort", "dne", "fst", "snd"];
                 if let ProofTerm::Ident(Ident(ref ident, _)) = lhs {
                     if identifiers.contains(&ident.as_str()) && rhs.is_empty() {
                         return Err(Simple::custom(span, "Missing applicant"));
@@ -173,6 +189,7 @@ pub fn proof_term_parser() -> impl Parser<Token, ProofTerm, Error = Simple<Token
                         "inl" => ProofTerm::OrLeft(OrLeft(Box::new(rhs), span)),
                         "inr" => ProofTerm::OrRight(OrRight(Box::new(rhs), span)),
                         "abort" => ProofTerm::Abort(Abort(Box::new(rhs), span)),
+                        "dne" => ProofTerm::DoubleNegElim(DoubleNegElim(Box::new(rhs), span)),
                         "fst" => ProofTerm::ProjectFst(ProjectFst(Box::new(rhs), span)),
                         "snd" => ProofTerm::ProjectSnd(ProjectSnd(Box::new(rhs), span)),
                         _ => ProofTerm::Application(Application {
@@ -191,7 +208,13 @@ pub fn proof_term_parser() -> impl Parser<Token, ProofTerm, Error = Simple<Token
             })
             .boxed();
 
-        choice((function, case(application.clone()), application, let_in))
+        choice((
+            function,
+            case(application.clone()),
+            subst,
+            application,
+            let_in,
+        ))
             .then(type_ascription.or_not())
             .map_with_span(|(proof_term, ascription), span| {
                 if let Some(ascription) = ascription {
@@ -207,7 +230,7 @@ pub fn proof_term_parser() -> impl Parser<Token, ProofTerm, Error = Simple<Token
             .boxed()
     });
 
-    proof_term.then_ignore(end())
+    proof_term
 }
 
 // === TESTS ===
@@ -219,8 +242,8 @@ mod tests {
     use crate::kernel::{
         parse::lexer::lexer,
         proof_term::{
-            Abort, Application, Case, Function, Ident, LetIn, OrLeft, OrRight, Pair, ProjectFst,
-            ProjectSnd, ProofTerm, Type,
+            Abort, Application, Case, DoubleNegElim, Function, Ident, LetIn, OrLeft, OrRight,
+            Pair, ProjectFst, ProjectSnd, ProofTerm, Type,
         },
         prop::Prop,
     };
@@ -856,6 +879,28 @@ mod tests {
         )
     }
 
+    #[test]
+    pub fn test_double_neg_elim() {
+        assert_eq!(
+            parse("dne (fn u => u a)"),
+            ProofTerm::DoubleNegElim(DoubleNegElim(
+                ProofTerm::Function(Function {
+                    param_ident: "u".to_string(),
+                    param_type: None,
+                    body: ProofTerm::Application(Application {
+                        function: ProofTerm::Ident(Ident("u".to_string(), Some(13..14))).boxed(),
+                        applicant: ProofTerm::Ident(Ident("a".to_string(), Some(15..16))).boxed(),
+                        span: Some(13..16),
+                    })
+                    .boxed(),
+                    span: Some(5..16),
+                })
+                .boxed(),
+                Some(0..16),
+            ))
+        )
+    }
+
     #[test]
     pub fn test_simple_case() {
         let proof_term = "case (a,b) of inl u => u, inr u => u,";
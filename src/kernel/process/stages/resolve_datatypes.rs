@@ -5,13 +5,14 @@ use thiserror::Error;
 use tsify_next::Tsify;
 
 use crate::kernel::{
+    checker::identifier_context::IdentifierContext,
     process::{ProofPipelineStage, StageError},
     proof::{Proof, ProofProcessingState},
     proof_term::{
-        Abort, Application, Case, Function, LetIn, OrLeft, OrRight, Pair, ProjectFst, ProjectSnd,
-        ProofTerm, Type, TypeAscription,
+        Abort, Application, Case, DoubleNegElim, Function, LetIn, OrLeft, OrRight, Pair,
+        ProjectFst, ProjectSnd, ProofTerm, Subst, Type, TypeAscription,
     },
-    prop::Prop,
+    prop::{find_definition, FunctionSignature, InstatiationError, Prop, PropDefinition},
 };
 
 #[derive(Debug, Error, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
@@ -27,15 +28,38 @@ pub enum ResolveDatatypesStageError {
     #[error("Datatype \"{0}\" is unknown")]
     DatatypeUnknown(String),
 
+    #[error("Function \"{0}\" is unknown")]
+    FunctionUnknown(String),
+
+    #[error("Definition \"{0}\" is unknown")]
+    DefinitionUnknown(String),
+
     #[error("Identifier \"{0}\" is defined multiple times")]
     DuplicateIdentifier(String),
 
+    #[error("Identifier \"{0}\" is reserved for a builtin definition")]
+    ReservedIdentifier(String),
+
+    #[error("Definition \"{0}\" refers to itself")]
+    RecursiveDefinition(String),
+
+    #[error("Definition \"{ident}\" refers to the later definition \"{reference}\"")]
+    DefinitionForwardReference { ident: String, reference: String },
+
     #[error("Arity of {ident} wrong: expected {expected}, actual {actual}")]
     ArityWrong {
         ident: String,
         expected: usize,
         actual: usize,
     },
+
+    #[error("Argument {argument} of {ident} has datatype {actual}, expected {expected}")]
+    ArgumentDatatypeWrong {
+        ident: String,
+        argument: String,
+        expected: String,
+        actual: String,
+    },
 }
 
 pub struct ResolveDatatypes {}
@@ -65,28 +89,29 @@ impl ProofPipelineStage for ResolveDatatypes {
         let Proof {
             proof_term,
             atoms,
+            atom_signatures,
             datatypes,
+            functions,
+            definitions,
             ..
         } = proof;
 
-        // check for duplicates
+        let definitions = resolve_declarations(&atoms, &datatypes, &functions, &definitions)?;
         let atom_names: Vec<&String> = atoms.iter().map(|(name, _)| name).collect(); // collect as ref
-        let datatype_names: Vec<&String> = datatypes.iter().map(|datatype| datatype).collect(); // collect as ref
+        let all_definitions = [PropDefinition::builtins(), definitions.clone()].concat();
 
-        let mut seen_names = vec![];
-        for name in [&atom_names[..], &datatype_names[..]].concat() {
-            if seen_names.contains(&name) {
-                return Err(StageError::ResolveDatatypesStageError(
-                    ResolveDatatypesStageError::DuplicateIdentifier(name.clone()),
-                ));
-            }
+        let mut resolved_prop = prop.clone();
+        resolve_definitions(&mut resolved_prop, &all_definitions)?;
 
-            seen_names.push(name);
-        }
+        // bodies of definitions are checked like the prop, with their parameters left out
+        let definition_bodies = definitions
+            .iter()
+            .map(|definition| definition.instantiate(&vec![Prop::True; definition.params.len()]));
+        let props = [vec![resolved_prop], definition_bodies.collect()].concat();
 
         // check if Atom/Datatype decl from Prop is missing
-        let prop_atoms = prop.get_atoms();
-        let prop_datatypes = prop.get_datatypes();
+        let prop_atoms = props.iter().flat_map(Prop::get_atoms);
+        let prop_datatypes = props.iter().flat_map(Prop::get_datatypes);
 
         for (prop_atom_name, prop_atom_arity) in prop_atoms {
             if !atom_names.contains(&&prop_atom_name) {
@@ -119,7 +144,83 @@ impl ProofPipelineStage for ResolveDatatypes {
             }
         }
 
-        let atom_map = HashMap::from_iter(atoms.clone());
+        // check if function signatures only use declared datatypes
+        for function in functions.iter() {
+            for datatype in function
+                .param_types
+                .iter()
+                .chain(std::iter::once(&function.return_type))
+            {
+                if !datatypes.contains(datatype) {
+                    return Err(StageError::ResolveDatatypesStageError(
+                        ResolveDatatypesStageError::DatatypeUnknown(datatype.clone()),
+                    ));
+                }
+            }
+        }
+
+        // check if atom signatures only use declared datatypes
+        for signature in atom_signatures.iter() {
+            for datatype in signature.param_types.iter() {
+                if !datatypes.contains(datatype) {
+                    return Err(StageError::ResolveDatatypesStageError(
+                        ResolveDatatypesStageError::DatatypeUnknown(datatype.clone()),
+                    ));
+                }
+            }
+        }
+
+        // check if functions from Prop are declared
+        let prop_functions = props.iter().flat_map(Prop::get_functions);
+        for (prop_function_name, prop_function_arity) in prop_functions {
+            let Some(function) = functions
+                .iter()
+                .find(|function| function.name == prop_function_name)
+            else {
+                return Err(StageError::ResolveDatatypesStageError(
+                    ResolveDatatypesStageError::FunctionUnknown(prop_function_name),
+                ));
+            };
+
+            if function.param_types.len() != prop_function_arity {
+                return Err(StageError::ResolveDatatypesStageError(
+                    ResolveDatatypesStageError::ArityWrong {
+                        ident: prop_function_name,
+                        expected: function.param_types.len(),
+                        actual: prop_function_arity,
+                    },
+                ));
+            }
+        }
+
+        // check if arguments in Prop match the declared datatypes
+        let mut ctx =
+            IdentifierContext::with_signatures(functions.clone(), atom_signatures.clone());
+        ctx.add_definitions(definitions.clone());
+        for prop in props.iter() {
+            if let Err(InstatiationError::ArgumentDatatypeWrong {
+                ident,
+                argument,
+                expected,
+                actual,
+            }) = prop.clone().instantiate_parameters_with_context(&ctx)
+            {
+                return Err(StageError::ResolveDatatypesStageError(
+                    ResolveDatatypesStageError::ArgumentDatatypeWrong {
+                        ident,
+                        argument: argument.to_string(),
+                        expected,
+                        actual,
+                    },
+                ));
+            }
+        }
+
+        // definitions in type annotations are resolved by the checker, here they pass like atoms
+        let mut atom_map = HashMap::from_iter(atoms.clone());
+        for definition in all_definitions.iter() {
+            atom_map.insert(definition.name.clone(), definition.params.len());
+        }
 
         let new_proof_term = resolve_datatypes(proof_term, &atom_map, &datatypes)
             .map_err(StageError::ResolveDatatypesStageError)?;
@@ -128,11 +229,137 @@ impl ProofPipelineStage for ResolveDatatypes {
             processing_state: ProofProcessingState::TypesResolved,
             proof_term: new_proof_term,
             atoms,
+            atom_signatures,
             datatypes,
+            functions,
+            definitions,
         })
     }
 }
 
+// Checks the declarations on their own and resolves definitions in the bodies of definitions.
+pub fn resolve_declarations(
+    atoms: &[(String, usize)],
+    datatypes: &[String],
+    functions: &[FunctionSignature],
+    definitions: &[PropDefinition],
+) -> Result<Vec<PropDefinition>, StageError> {
+    // check for duplicates
+    let atom_names: Vec<&String> = atoms.iter().map(|(name, _)| name).collect(); // collect as ref
+    let datatype_names: Vec<&String> = datatypes.iter().map(|datatype| datatype).collect(); // collect as ref
+    let function_names: Vec<&String> = functions.iter().map(|function| &function.name).collect(); // collect as ref
+    let definition_names: Vec<&String> = definitions
+        .iter()
+        .map(|definition| &definition.name)
+        .collect(); // collect as ref
+
+    let mut seen_names = vec![];
+    for name in [
+        &atom_names[..],
+        &datatype_names[..],
+        &function_names[..],
+        &definition_names[..],
+    ]
+    .concat()
+    {
+        if seen_names.contains(&name) {
+            return Err(StageError::ResolveDatatypesStageError(
+                ResolveDatatypesStageError::DuplicateIdentifier(name.clone()),
+            ));
+        }
+
+        seen_names.push(name);
+    }
+
+    // only definitions may shadow builtin definitions, as atoms would be unfolded like them
+    let builtin_names = PropDefinition::builtins()
+        .into_iter()
+        .map(|definition| definition.name)
+        .collect::<Vec<_>>();
+    for name in [&atom_names[..], &datatype_names[..], &function_names[..]].concat() {
+        if builtin_names.contains(name) {
+            return Err(StageError::ResolveDatatypesStageError(
+                ResolveDatatypesStageError::ReservedIdentifier(name.clone()),
+            ));
+        }
+    }
+
+    // definitions may only refer to earlier ones, which rules out recursion
+    let mut resolved_definitions: Vec<PropDefinition> = vec![];
+    for (idx, definition) in definitions.iter().cloned().enumerate() {
+        let earlier_definitions =
+            [PropDefinition::builtins(), resolved_definitions.clone()].concat();
+
+        for reference in get_referenced_names(&definition.body) {
+            if definition.params.contains(&reference)
+                || find_definition(&earlier_definitions, &reference).is_some()
+            {
+                continue;
+            }
+
+            if reference == definition.name {
+                return Err(StageError::ResolveDatatypesStageError(
+                    ResolveDatatypesStageError::RecursiveDefinition(reference),
+                ));
+            }
+
+            if definitions[idx + 1..]
+                .iter()
+                .any(|later| later.name == reference)
+            {
+                return Err(StageError::ResolveDatatypesStageError(
+                    ResolveDatatypesStageError::DefinitionForwardReference {
+                        ident: definition.name.clone(),
+                        reference,
+                    },
+                ));
+            }
+        }
+
+        let mut body = definition.body.clone();
+        resolve_definitions(&mut body, &earlier_definitions)?;
+
+        resolved_definitions.push(PropDefinition { body, ..definition });
+    }
+
+    Ok(resolved_definitions)
+}
+
+fn resolve_definitions(prop: &mut Prop, definitions: &[PropDefinition]) -> Result<(), StageError> {
+    prop.resolve_definitions(definitions).map_err(|err| {
+        StageError::ResolveDatatypesStageError(match err {
+            InstatiationError::DefinitionArityWrong {
+                ident,
+                expected,
+                actual,
+            } => ResolveDatatypesStageError::ArityWrong {
+                ident,
+                expected,
+                actual,
+            },
+            InstatiationError::UnknownIdentifier(ident)
+            | InstatiationError::ArgumentDatatypeWrong { ident, .. } => {
+                ResolveDatatypesStageError::DefinitionUnknown(ident)
+            }
+        })
+    })
+}
+
+// names of atoms and definitions the prop refers to
+fn get_referenced_names(prop: &Prop) -> Vec<String> {
+    match prop {
+        Prop::True | Prop::False | Prop::Eq(_, _) => vec![],
+        Prop::Atom(name, _) => vec![name.clone()],
+        Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+            [get_referenced_names(fst), get_referenced_names(snd)].concat()
+        }
+        Prop::ForAll { body, .. } | Prop::Exists { body, .. } => get_referenced_names(body),
+        Prop::Defined(name, args) => std::iter::once(name.clone())
+            .chain(args.iter().flat_map(get_referenced_names))
+            .collect(),
+    }
+}
+
 fn get_datatype_identifier(prop: &Prop, datatypes: &Vec<String>) -> Option<String> {
     match prop {
         Prop::Atom(ident, _) => {
@@ -152,6 +379,10 @@ fn get_datatype_identifier(prop: &Prop, datatypes: &Vec<String>) -> Option<Strin
         Prop::Exists { body, .. } | Prop::ForAll { body, .. } => {
             get_datatype_identifier(body, datatypes)
         }
+        Prop::Defined(_, args) => args
+            .iter()
+            .find_map(|arg| get_datatype_identifier(arg, datatypes)),
+        Prop::Eq(_, _) => None,
         Prop::False => None,
         Prop::True => None,
     }
@@ -209,6 +440,9 @@ fn resolve_datatypes(
             resolve_datatypes(*body, atoms, datatypes)?.boxed(),
             span,
         )),
+        ProofTerm::DoubleNegElim(DoubleNegElim(body, span)) => ProofTerm::DoubleNegElim(
+            DoubleNegElim(resolve_datatypes(*body, atoms, datatypes)?.boxed(), span),
+        ),
         ProofTerm::Application(Application {
             function,
             applicant,
@@ -295,6 +529,16 @@ fn resolve_datatypes(
             proof_term: resolve_datatypes(*proof_term, atoms, datatypes)?.boxed(),
             span,
         }),
+        ProofTerm::Refl(span) => ProofTerm::Refl(span),
+        ProofTerm::Subst(Subst {
+            equality,
+            body,
+            span,
+        }) => ProofTerm::Subst(Subst {
+            equality: resolve_datatypes(*equality, atoms, datatypes)?.boxed(),
+            body: resolve_datatypes(*body, atoms, datatypes)?.boxed(),
+            span,
+        }),
         ProofTerm::Sorry(span) => ProofTerm::Sorry(span),
     };
 
@@ -306,9 +550,15 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::kernel::{
-        process::stages::resolve_datatypes::resolve_datatypes,
+        process::{
+            stages::resolve_datatypes::{
+                resolve_datatypes, ResolveDatatypes, ResolveDatatypesStageError,
+            },
+            ProofPipelineStage, StageError,
+        },
+        proof::{Proof, ProofProcessingState},
         proof_term::{Function, Pair, ProofTerm, Type},
-        prop::{Prop, PropParameter},
+        prop::{AtomSignature, Prop, PropDefinition, PropParameter},
     };
 
     #[test]
@@ -465,4 +715,186 @@ mod tests {
 
         resolve_datatypes(proof_term, &HashMap::from([("A".to_string(), 3)]), &vec![]).unwrap();
     }
+
+    #[test]
+    fn test_argument_datatype_wrong() {
+        let proof = Proof {
+            processing_state: ProofProcessingState::Parsed,
+            datatypes: vec!["nat".to_string(), "person".to_string()],
+            atoms: vec![("Less".to_string(), 2)],
+            atom_signatures: vec![AtomSignature {
+                name: "Less".to_string(),
+                param_types: vec!["nat".to_string(), "nat".to_string()],
+            }],
+            functions: vec![],
+            definitions: vec![],
+            proof_term: ProofTerm::Sorry(None),
+        };
+
+        let prop = Prop::ForAll {
+            object_ident: "x".to_string(),
+            object_type_ident: "nat".to_string(),
+            body: Prop::ForAll {
+                object_ident: "p".to_string(),
+                object_type_ident: "person".to_string(),
+                body: Prop::Atom(
+                    "Less".to_string(),
+                    vec![
+                        PropParameter::Uninstantiated("x".to_string()),
+                        PropParameter::Uninstantiated("p".to_string()),
+                    ],
+                )
+                .boxed(),
+            }
+            .boxed(),
+        };
+
+        assert_eq!(
+            ResolveDatatypes::new().process(proof, &prop),
+            Err(StageError::ResolveDatatypesStageError(
+                ResolveDatatypesStageError::ArgumentDatatypeWrong {
+                    ident: "Less".to_string(),
+                    argument: "p".to_string(),
+                    expected: "nat".to_string(),
+                    actual: "person".to_string(),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_definition_arity_wrong() {
+        let proof = Proof {
+            processing_state: ProofProcessingState::Parsed,
+            datatypes: vec![],
+            atoms: vec![("A".to_string(), 0)],
+            atom_signatures: vec![],
+            functions: vec![],
+            definitions: vec![],
+            proof_term: ProofTerm::Sorry(None),
+        };
+
+        let prop = Prop::Defined("Iff".to_string(), vec![Prop::Atom("A".to_string(), vec![])]);
+
+        assert_eq!(
+            ResolveDatatypes::new().process(proof, &prop),
+            Err(StageError::ResolveDatatypesStageError(
+                ResolveDatatypesStageError::ArityWrong {
+                    ident: "Iff".to_string(),
+                    expected: 2,
+                    actual: 1,
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_definition_unknown() {
+        let proof = Proof {
+            processing_state: ProofProcessingState::Parsed,
+            datatypes: vec![],
+            atoms: vec![("A".to_string(), 0)],
+            atom_signatures: vec![],
+            functions: vec![],
+            definitions: vec![],
+            proof_term: ProofTerm::Sorry(None),
+        };
+
+        let prop = Prop::Defined("Xor".to_string(), vec![Prop::Atom("A".to_string(), vec![])]);
+
+        assert_eq!(
+            ResolveDatatypes::new().process(proof, &prop),
+            Err(StageError::ResolveDatatypesStageError(
+                ResolveDatatypesStageError::DefinitionUnknown("Xor".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_builtin_name_reserved() {
+        let proof = Proof {
+            processing_state: ProofProcessingState::Parsed,
+            datatypes: vec![],
+            atoms: vec![("Iff".to_string(), 2), ("A".to_string(), 0)],
+            atom_signatures: vec![],
+            functions: vec![],
+            definitions: vec![],
+            proof_term: ProofTerm::Sorry(None),
+        };
+
+        let prop = Prop::Atom("A".to_string(), vec![]);
+
+        assert_eq!(
+            ResolveDatatypes::new().process(proof, &prop),
+            Err(StageError::ResolveDatatypesStageError(
+                ResolveDatatypesStageError::ReservedIdentifier("Iff".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_recursive_definition() {
+        let var = |name: &str| Prop::Atom(name.to_string(), vec![]);
+        let proof = Proof {
+            processing_state: ProofProcessingState::Parsed,
+            datatypes: vec![],
+            atoms: vec![("A".to_string(), 0)],
+            atom_signatures: vec![],
+            functions: vec![],
+            definitions: vec![PropDefinition {
+                name: "Loop".to_string(),
+                params: vec!["X".to_string()],
+                body: Prop::And(
+                    Prop::Defined("Loop".to_string(), vec![var("X")]).boxed(),
+                    var("X").boxed(),
+                ),
+            }],
+            proof_term: ProofTerm::Sorry(None),
+        };
+
+        assert_eq!(
+            ResolveDatatypes::new().process(proof, &var("A")),
+            Err(StageError::ResolveDatatypesStageError(
+                ResolveDatatypesStageError::RecursiveDefinition("Loop".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_definition_forward_reference() {
+        let var = |name: &str| Prop::Atom(name.to_string(), vec![]);
+        let proof = Proof {
+            processing_state: ProofProcessingState::Parsed,
+            datatypes: vec![],
+            atoms: vec![("A".to_string(), 0)],
+            atom_signatures: vec![],
+            functions: vec![],
+            definitions: vec![
+                PropDefinition {
+                    name: "First".to_string(),
+                    params: vec!["X".to_string()],
+                    body: Prop::Atom(
+                        "Second".to_string(),
+                        vec![PropParameter::Uninstantiated("X".to_string())],
+                    ),
+                },
+                PropDefinition {
+                    name: "Second".to_string(),
+                    params: vec!["X".to_string()],
+                    body: var("X"),
+                },
+            ],
+            proof_term: ProofTerm::Sorry(None),
+        };
+
+        assert_eq!(
+            ResolveDatatypes::new().process(proof, &var("A")),
+            Err(StageError::ResolveDatatypesStageError(
+                ResolveDatatypesStageError::DefinitionForwardReference {
+                    ident: "First".to_string(),
+                    reference: "Second".to_string(),
+                }
+            ))
+        );
+    }
 }
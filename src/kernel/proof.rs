@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 use tsify_next::Tsify;
 
-use super::proof_term::ProofTerm;
+use super::{
+    proof_term::ProofTerm,
+    prop::{AtomSignature, FunctionSignature, PropDefinition},
+};
 
 #[derive(Clone, PartialEq, Eq, Tsify, Serialize, Deserialize, Debug)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
@@ -16,5 +19,8 @@ pub struct Proof {
     pub processing_state: ProofProcessingState,
     pub datatypes: Vec<String>,
     pub atoms: Vec<(String, usize)>,
+    pub atom_signatures: Vec<AtomSignature>,
+    pub functions: Vec<FunctionSignature>,
+    pub definitions: Vec<PropDefinition>,
     pub proof_term: ProofTerm,
-}
\ No newline at end of file
+}
@@ -0,0 +1,1014 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tsify_next::Tsify;
+
+use crate::util::counter::Counter;
+
+use super::{
+    checker::{
+        check::check_term,
+        identifier::{Identifier, IdentifierFactory},
+        identifier_context::IdentifierContext,
+        Logic,
+    },
+    proof::Proof,
+    proof_term::{Application, Ident, ProofTerm, Type},
+    proof_tree::{ProofTree, ProofTreeConclusion, ProofTreeRule},
+    prop::{InstatiationError, Prop, PropParameter},
+};
+
+#[derive(Debug, Error, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum ProofStateError {
+    #[error("Goal {0} does not exist")]
+    UnknownGoal(usize),
+
+    #[error("Rule cannot be applied to the goal")]
+    RuleNotApplicable(ProofTreeRule),
+
+    #[error("Rule needs the proposition of its major premisse")]
+    PropMissing,
+
+    #[error("Rule needs a witness")]
+    WitnessMissing,
+
+    #[error("Proposition does not fit the goal")]
+    PropMismatch(Prop),
+
+    #[error("Identifier {0} unknown")]
+    UnknownIdentifier(String),
+
+    #[error("Proposition cannot be used in the context of the goal")]
+    PropInvalid(Prop),
+
+    #[error("Hypothesis is not visible in the context of the goal")]
+    UnknownHypothesis(Identifier),
+
+    #[error("Witness does not have the expected datatype")]
+    WitnessInvalid(PropParameter),
+
+    #[error("Classical reasoning is not allowed in intuitionistic logic")]
+    ClassicalReasoningNotAllowed,
+
+    #[error("Nothing to undo")]
+    NothingToUndo,
+}
+
+// Everything a rule needs besides the goal it is applied to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct RuleArguments {
+    // proposition of the major premisse of an elimination rule,
+    // or the new goal for AlphaEquivalent and DeltaEquivalent
+    pub prop: Option<Prop>,
+
+    // term that a quantifier is instantiated with
+    pub witness: Option<PropParameter>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct ProofStateGoal {
+    pub hypotheses: Vec<(Identifier, Type)>,
+    pub conclusion: Prop,
+}
+
+#[derive(Debug, Clone)]
+enum ProofStateNode {
+    Goal {
+        prop: Prop,
+        // hypotheses introduced on the way from the root to the goal
+        hypotheses: Vec<(Identifier, Type)>,
+    },
+    Applied {
+        rule: ProofTreeRule,
+        conclusion: Prop,
+        premisses: Vec<ProofStateNode>,
+    },
+    // judgements about terms are decided as soon as the term is known
+    Finished(ProofTree),
+}
+
+impl ProofStateNode {
+    fn goal(prop: Prop, hypotheses: &[(Identifier, Type)]) -> Self {
+        ProofStateNode::Goal {
+            prop,
+            hypotheses: hypotheses.to_vec(),
+        }
+    }
+
+    fn leaf(rule: ProofTreeRule, conclusion: Prop) -> Self {
+        ProofStateNode::Applied {
+            rule,
+            conclusion,
+            premisses: vec![],
+        }
+    }
+
+    fn collect_goals<'a>(&'a self, goals: &mut Vec<&'a ProofStateNode>) {
+        match self {
+            ProofStateNode::Goal { .. } => goals.push(self),
+            ProofStateNode::Applied { premisses, .. } => premisses
+                .iter()
+                .for_each(|premisse| premisse.collect_goals(goals)),
+            ProofStateNode::Finished(_) => {}
+        }
+    }
+
+    // Returns the n-th open goal from the left, counting down the goals that come before it.
+    fn find_goal_mut(&mut self, n: &mut usize) -> Option<&mut ProofStateNode> {
+        match self {
+            ProofStateNode::Goal { .. } => {
+                if *n == 0 {
+                    return Some(self);
+                }
+
+                *n -= 1;
+                None
+            }
+            ProofStateNode::Applied { premisses, .. } => premisses
+                .iter_mut()
+                .find_map(|premisse| premisse.find_goal_mut(n)),
+            ProofStateNode::Finished(_) => None,
+        }
+    }
+
+    fn as_proof_tree(&self) -> ProofTree {
+        match self {
+            ProofStateNode::Goal { prop, .. } => ProofTree {
+                premisses: vec![],
+                rule: ProofTreeRule::Sorry,
+                conclusion: ProofTreeConclusion::PropIsTrue(prop.clone()),
+            },
+            ProofStateNode::Applied {
+                rule,
+                conclusion,
+                premisses,
+            } => ProofTree {
+                premisses: premisses.iter().map(Self::as_proof_tree).collect(),
+                rule: rule.clone(),
+                conclusion: ProofTreeConclusion::PropIsTrue(conclusion.clone()),
+            },
+            ProofStateNode::Finished(proof_tree) => proof_tree.clone(),
+        }
+    }
+}
+
+// Goal-directed construction of a proof tree: rules are applied bottom-up to open goals,
+// which are replaced by the premisses of the rule.
+pub struct ProofState {
+    ctx: IdentifierContext,
+    root: ProofStateNode,
+    history: Vec<ProofStateNode>,
+    identifier_factory: IdentifierFactory,
+}
+
+impl ProofState {
+    pub fn new(prop: &Prop) -> Result<Self, ProofStateError> {
+        Self::with_context(prop, IdentifierContext::new())
+    }
+
+    // The context provides the signatures, definitions, logic and lemmas of the proof.
+    pub fn with_context(prop: &Prop, ctx: IdentifierContext) -> Result<Self, ProofStateError> {
+        let prop = instantiate(prop, &ctx)?;
+
+        // identifiers of the context must not clash with the ones created for binders
+        let next_unique_id = ctx
+            .get_all_visible()
+            .iter()
+            .map(|(identifier, _)| identifier.unique_id() + 1)
+            .max()
+            .unwrap_or(0);
+
+        Ok(Self {
+            ctx,
+            root: ProofStateNode::goal(prop, &[]),
+            history: vec![],
+            identifier_factory: IdentifierFactory::new(Counter::starting_at(next_unique_id)),
+        })
+    }
+
+    pub fn goals(&self) -> Vec<ProofStateGoal> {
+        let mut goals = vec![];
+        self.root.collect_goals(&mut goals);
+
+        goals
+            .into_iter()
+            .map(|goal| {
+                let ProofStateNode::Goal { prop, hypotheses } = goal else {
+                    unreachable!()
+                };
+
+                ProofStateGoal {
+                    hypotheses: self.goal_ctx(hypotheses).get_all_visible(),
+                    conclusion: prop.clone(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.goals().is_empty()
+    }
+
+    // Applies the rule to the goal with the given index, as listed by goals().
+    // Binder rules only use the names of their identifiers, the unique ids are assigned by the state.
+    pub fn apply(
+        &mut self,
+        goal: usize,
+        rule: &ProofTreeRule,
+        args: &RuleArguments,
+    ) -> Result<(), ProofStateError> {
+        let mut n = goal;
+        let Some(ProofStateNode::Goal { prop, hypotheses }) = self.root.find_goal_mut(&mut n)
+        else {
+            return Err(ProofStateError::UnknownGoal(goal));
+        };
+
+        let (prop, hypotheses) = (prop.clone(), hypotheses.clone());
+        let node = self.expand(prop, hypotheses, rule, args)?;
+
+        self.history.push(self.root.clone());
+
+        let mut n = goal;
+        *self.root.find_goal_mut(&mut n).unwrap() = node;
+
+        Ok(())
+    }
+
+    pub fn undo(&mut self) -> Result<(), ProofStateError> {
+        self.root = self.history.pop().ok_or(ProofStateError::NothingToUndo)?;
+
+        Ok(())
+    }
+
+    // Open goals are left as sorry.
+    pub fn proof_tree(&self) -> ProofTree {
+        self.root.as_proof_tree()
+    }
+
+    pub fn proof(&self) -> Proof {
+        self.proof_tree().as_proof()
+    }
+
+    pub fn proof_term(&self) -> ProofTerm {
+        self.proof().proof_term
+    }
+
+    fn goal_ctx(&self, hypotheses: &[(Identifier, Type)]) -> IdentifierContext {
+        let mut ctx = self.ctx.clone();
+        for (identifier, _type) in hypotheses.iter() {
+            ctx.insert(identifier.clone(), _type.clone());
+        }
+
+        ctx
+    }
+
+    fn expand(
+        &mut self,
+        prop: Prop,
+        hypotheses: Vec<(Identifier, Type)>,
+        rule: &ProofTreeRule,
+        args: &RuleArguments,
+    ) -> Result<ProofStateNode, ProofStateError> {
+        let ctx = self.goal_ctx(&hypotheses);
+        let not_applicable = || ProofStateError::RuleNotApplicable(rule.clone());
+
+        // introduction rules see the connective behind a definition, like the checker
+        let unfolded = prop.unfold_head(ctx.definitions());
+        let introduce = |node: ProofStateNode| {
+            if unfolded == prop {
+                return node;
+            }
+
+            ProofStateNode::Applied {
+                rule: ProofTreeRule::DeltaEquivalent,
+                conclusion: prop.clone(),
+                premisses: vec![node],
+            }
+        };
+
+        let node = match rule {
+            ProofTreeRule::AndIntro => {
+                let Prop::And(ref fst, ref snd) = unfolded else {
+                    return Err(not_applicable());
+                };
+
+                introduce(ProofStateNode::Applied {
+                    rule: rule.clone(),
+                    conclusion: unfolded.clone(),
+                    premisses: vec![
+                        ProofStateNode::goal(*fst.clone(), &hypotheses),
+                        ProofStateNode::goal(*snd.clone(), &hypotheses),
+                    ],
+                })
+            }
+            ProofTreeRule::AndElimFst | ProofTreeRule::AndElimSnd => {
+                let (major_prop, major_node) = self.major_premisse(args, &hypotheses, &ctx)?;
+                let Prop::And(fst, snd) = major_prop else {
+                    return Err(not_applicable());
+                };
+
+                let component = match rule {
+                    ProofTreeRule::AndElimFst => fst,
+                    _ => snd,
+                };
+                expect_alpha_eq(&component, &prop)?;
+
+                ProofStateNode::Applied {
+                    rule: rule.clone(),
+                    conclusion: prop,
+                    premisses: vec![major_node],
+                }
+            }
+            ProofTreeRule::TrueIntro => {
+                if unfolded != Prop::True {
+                    return Err(not_applicable());
+                }
+
+                introduce(ProofStateNode::leaf(rule.clone(), unfolded.clone()))
+            }
+            ProofTreeRule::ImplIntro(param_ident) => {
+                let Prop::Impl(ref fst, ref snd) = unfolded else {
+                    return Err(not_applicable());
+                };
+
+                let param_identifier = self.identifier_factory.create(param_ident.name().clone());
+                let body_hypotheses = extend(
+                    &hypotheses,
+                    vec![(param_identifier.clone(), Type::Prop(*fst.clone()))],
+                );
+
+                introduce(ProofStateNode::Applied {
+                    rule: ProofTreeRule::ImplIntro(param_identifier),
+                    conclusion: unfolded.clone(),
+                    premisses: vec![ProofStateNode::goal(*snd.clone(), &body_hypotheses)],
+                })
+            }
+            ProofTreeRule::ImplElim => {
+                let (major_prop, major_node) = self.major_premisse(args, &hypotheses, &ctx)?;
+                let Prop::Impl(fst, snd) = major_prop else {
+                    return Err(not_applicable());
+                };
+                expect_alpha_eq(&snd, &prop)?;
+
+                ProofStateNode::Applied {
+                    rule: rule.clone(),
+                    conclusion: prop,
+                    premisses: vec![major_node, ProofStateNode::goal(*fst, &hypotheses)],
+                }
+            }
+            ProofTreeRule::Ident(identifier) => {
+                let Some((visible_identifier, _type)) = ctx.get_by_name(identifier.name()) else {
+                    return Err(ProofStateError::UnknownHypothesis(identifier.clone()));
+                };
+
+                if visible_identifier != identifier {
+                    return Err(ProofStateError::UnknownHypothesis(identifier.clone()));
+                }
+
+                let Type::Prop(hypothesis) = _type else {
+                    return Err(not_applicable());
+                };
+
+                // hypotheses are used as they are, a differing goal needs an equivalence rule first
+                if !Prop::alpha_eq(hypothesis, &prop) {
+                    return Err(not_applicable());
+                }
+
+                ProofStateNode::leaf(rule.clone(), prop)
+            }
+            ProofTreeRule::OrIntroFst | ProofTreeRule::OrIntroSnd => {
+                let Prop::Or(ref fst, ref snd) = unfolded else {
+                    return Err(not_applicable());
+                };
+
+                let component = match rule {
+                    ProofTreeRule::OrIntroFst => fst,
+                    _ => snd,
+                };
+
+                introduce(ProofStateNode::Applied {
+                    rule: rule.clone(),
+                    conclusion: unfolded.clone(),
+                    premisses: vec![ProofStateNode::goal(*component.clone(), &hypotheses)],
+                })
+            }
+            ProofTreeRule::OrElim(fst_ident, snd_ident) => {
+                let (major_prop, major_node) = self.major_premisse(args, &hypotheses, &ctx)?;
+                let Prop::Or(fst, snd) = major_prop else {
+                    return Err(not_applicable());
+                };
+
+                let fst_identifier = self.identifier_factory.create(fst_ident.name().clone());
+                let snd_identifier = self.identifier_factory.create(snd_ident.name().clone());
+
+                let fst_hypotheses = extend(
+                    &hypotheses,
+                    vec![(fst_identifier.clone(), Type::Prop(*fst))],
+                );
+                let snd_hypotheses = extend(
+                    &hypotheses,
+                    vec![(snd_identifier.clone(), Type::Prop(*snd))],
+                );
+
+                ProofStateNode::Applied {
+                    rule: ProofTreeRule::OrElim(fst_identifier, snd_identifier),
+                    conclusion: prop.clone(),
+                    premisses: vec![
+                        major_node,
+                        ProofStateNode::goal(prop.clone(), &fst_hypotheses),
+                        ProofStateNode::goal(prop, &snd_hypotheses),
+                    ],
+                }
+            }
+            ProofTreeRule::FalsumElim => ProofStateNode::Applied {
+                rule: rule.clone(),
+                conclusion: prop,
+                premisses: vec![ProofStateNode::goal(Prop::False, &hypotheses)],
+            },
+            ProofTreeRule::DoubleNegElim => {
+                if *ctx.logic() != Logic::Classical {
+                    return Err(ProofStateError::ClassicalReasoningNotAllowed);
+                }
+
+                let double_negation = Prop::Impl(
+                    Prop::Impl(prop.boxed(), Prop::False.boxed()).boxed(),
+                    Prop::False.boxed(),
+                );
+
+                ProofStateNode::Applied {
+                    rule: rule.clone(),
+                    conclusion: prop,
+                    premisses: vec![ProofStateNode::goal(double_negation, &hypotheses)],
+                }
+            }
+            ProofTreeRule::ForAllIntro(param_ident) => {
+                let Prop::ForAll {
+                    ref object_ident,
+                    ref object_type_ident,
+                    ref body,
+                } = unfolded
+                else {
+                    return Err(not_applicable());
+                };
+
+                let param_identifier = self.identifier_factory.create(param_ident.name().clone());
+
+                let mut body = body.clone();
+                body.instantiate_free_parameter(object_ident, &param_identifier);
+
+                let body_hypotheses = extend(
+                    &hypotheses,
+                    vec![(
+                        param_identifier.clone(),
+                        Type::Datatype(object_type_ident.clone()),
+                    )],
+                );
+
+                introduce(ProofStateNode::Applied {
+                    rule: ProofTreeRule::ForAllIntro(param_identifier),
+                    conclusion: unfolded.clone(),
+                    premisses: vec![ProofStateNode::goal(*body, &body_hypotheses)],
+                })
+            }
+            ProofTreeRule::ForAllElim => {
+                let (major_prop, major_node) = self.major_premisse(args, &hypotheses, &ctx)?;
+                let Prop::ForAll {
+                    object_ident,
+                    object_type_ident,
+                    mut body,
+                } = major_prop
+                else {
+                    return Err(not_applicable());
+                };
+
+                let (term, term_tree) = self.witness(args, &object_type_ident, &ctx)?;
+                body.substitute_free_parameter(&object_ident, &term);
+                expect_alpha_eq(&body, &prop)?;
+
+                ProofStateNode::Applied {
+                    rule: rule.clone(),
+                    conclusion: prop,
+                    premisses: vec![major_node, ProofStateNode::Finished(term_tree)],
+                }
+            }
+            ProofTreeRule::ExistsIntro => {
+                let Prop::Exists {
+                    ref object_ident,
+                    ref object_type_ident,
+                    ref body,
+                } = unfolded
+                else {
+                    return Err(not_applicable());
+                };
+
+                let (term, term_tree) = self.witness(args, object_type_ident, &ctx)?;
+
+                let mut body = body.clone();
+                body.substitute_free_parameter(object_ident, &term);
+
+                introduce(ProofStateNode::Applied {
+                    rule: rule.clone(),
+                    conclusion: unfolded.clone(),
+                    premisses: vec![
+                        ProofStateNode::Finished(term_tree),
+                        ProofStateNode::goal(*body, &hypotheses),
+                    ],
+                })
+            }
+            ProofTreeRule::ExistsElim(fst_ident, snd_ident) => {
+                let (major_prop, major_node) = self.major_premisse(args, &hypotheses, &ctx)?;
+                let Prop::Exists {
+                    object_ident,
+                    object_type_ident,
+                    mut body,
+                } = major_prop
+                else {
+                    return Err(not_applicable());
+                };
+
+                let fst_identifier = self.identifier_factory.create(fst_ident.name().clone());
+                let snd_identifier = self.identifier_factory.create(snd_ident.name().clone());
+
+                body.instantiate_free_parameter(&object_ident, &fst_identifier);
+
+                let body_hypotheses = extend(
+                    &hypotheses,
+                    vec![
+                        (fst_identifier.clone(), Type::Datatype(object_type_ident)),
+                        (snd_identifier.clone(), Type::Prop(*body)),
+                    ],
+                );
+
+                ProofStateNode::Applied {
+                    rule: ProofTreeRule::ExistsElim(fst_identifier, snd_identifier),
+                    conclusion: prop.clone(),
+                    premisses: vec![major_node, ProofStateNode::goal(prop, &body_hypotheses)],
+                }
+            }
+            ProofTreeRule::EqIntro => {
+                let Prop::Eq(ref lhs, ref rhs) = unfolded else {
+                    return Err(not_applicable());
+                };
+
+                if lhs != rhs {
+                    return Err(not_applicable());
+                }
+
+                introduce(ProofStateNode::leaf(rule.clone(), unfolded.clone()))
+            }
+            ProofTreeRule::EqElim => {
+                let (major_prop, major_node) = self.major_premisse(args, &hypotheses, &ctx)?;
+                let Prop::Eq(lhs, rhs) = major_prop else {
+                    return Err(not_applicable());
+                };
+
+                // the body has to prove the goal with rhs replaced by lhs
+                let mut body = prop.clone();
+                body.replace_free_parameter(&rhs, &lhs);
+
+                ProofStateNode::Applied {
+                    rule: rule.clone(),
+                    conclusion: prop,
+                    premisses: vec![major_node, ProofStateNode::goal(body, &hypotheses)],
+                }
+            }
+            ProofTreeRule::AlphaEquivalent | ProofTreeRule::DeltaEquivalent => {
+                let new_prop = match args.prop {
+                    Some(ref new_prop) => instantiate(new_prop, &ctx)?,
+                    None if *rule == ProofTreeRule::DeltaEquivalent => unfolded.clone(),
+                    None => return Err(ProofStateError::PropMissing),
+                };
+
+                let equivalent = match rule {
+                    ProofTreeRule::AlphaEquivalent => Prop::alpha_eq(&new_prop, &prop),
+                    _ => new_prop.alpha_eq_modulo_definitions(&prop, ctx.definitions()),
+                };
+
+                if !equivalent {
+                    return Err(ProofStateError::PropMismatch(new_prop));
+                }
+
+                ProofStateNode::Applied {
+                    rule: rule.clone(),
+                    conclusion: prop,
+                    premisses: vec![ProofStateNode::goal(new_prop, &hypotheses)],
+                }
+            }
+            ProofTreeRule::FunctionApplication(_) | ProofTreeRule::Sorry => {
+                return Err(not_applicable())
+            }
+        };
+
+        Ok(node)
+    }
+
+    // Returns the unfolded major premisse together with the goal that has to prove it.
+    fn major_premisse(
+        &self,
+        args: &RuleArguments,
+        hypotheses: &[(Identifier, Type)],
+        ctx: &IdentifierContext,
+    ) -> Result<(Prop, ProofStateNode), ProofStateError> {
+        let prop = instantiate(args.prop.as_ref().ok_or(ProofStateError::PropMissing)?, ctx)?;
+        let unfolded = prop.unfold_head(ctx.definitions());
+
+        let goal = ProofStateNode::goal(prop.clone(), hypotheses);
+        if unfolded == prop {
+            return Ok((unfolded, goal));
+        }
+
+        let node = ProofStateNode::Applied {
+            rule: ProofTreeRule::DeltaEquivalent,
+            conclusion: unfolded.clone(),
+            premisses: vec![goal],
+        };
+
+        Ok((unfolded, node))
+    }
+
+    fn witness(
+        &mut self,
+        args: &RuleArguments,
+        datatype: &String,
+        ctx: &IdentifierContext,
+    ) -> Result<(PropParameter, ProofTree), ProofStateError> {
+        let witness = args
+            .witness
+            .as_ref()
+            .ok_or(ProofStateError::WitnessMissing)?;
+
+        match check_term(
+            &term_as_proof_term(witness),
+            datatype,
+            ctx,
+            &mut self.identifier_factory,
+        ) {
+            Ok((term, result)) => Ok((term, result.proof_tree)),
+            Err(_) => Err(ProofStateError::WitnessInvalid(witness.clone())),
+        }
+    }
+}
+
+fn extend(
+    hypotheses: &[(Identifier, Type)],
+    new_hypotheses: Vec<(Identifier, Type)>,
+) -> Vec<(Identifier, Type)> {
+    [hypotheses.to_vec(), new_hypotheses].concat()
+}
+
+fn expect_alpha_eq(prop: &Prop, goal: &Prop) -> Result<(), ProofStateError> {
+    if !Prop::alpha_eq(prop, goal) {
+        return Err(ProofStateError::PropMismatch(prop.clone()));
+    }
+
+    Ok(())
+}
+
+// Resolves the names of a proposition given by the user in the context of a goal.
+fn instantiate(prop: &Prop, ctx: &IdentifierContext) -> Result<Prop, ProofStateError> {
+    // the instantiation expects instantiated parameters to be in the context
+    for param in prop.get_free_parameters() {
+        if let PropParameter::Instantiated(identifier) = param {
+            if !matches!(ctx.get(&identifier), Some(Type::Datatype(_))) {
+                return Err(ProofStateError::UnknownHypothesis(identifier));
+            }
+        }
+    }
+
+    let mut prop = prop.clone();
+    prop.instantiate_parameters_with_context(ctx)
+        .map_err(|err| match err {
+            InstatiationError::UnknownIdentifier(name) => ProofStateError::UnknownIdentifier(name),
+            _ => ProofStateError::PropInvalid(prop.clone()),
+        })?;
+
+    Ok(prop)
+}
+
+fn term_as_proof_term(term: &PropParameter) -> ProofTerm {
+    match term {
+        PropParameter::Uninstantiated(name) => Ident::create(name.clone()),
+        PropParameter::Instantiated(identifier) => Ident::create(identifier.name().clone()),
+        PropParameter::Function(name, args) => {
+            args.iter()
+                .fold(Ident::create(name.clone()), |function, arg| {
+                    Application::create(function.boxed(), term_as_proof_term(arg).boxed(), None)
+                })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        checker::{
+            check::check, identifier::Identifier, identifier_context::IdentifierContext, Logic,
+        },
+        parse::{fol::fol_parser, lexer::lexer},
+        proof_term::Type,
+        proof_tree::ProofTreeRule,
+        prop::{FunctionSignature, Prop, PropParameter},
+    };
+
+    use super::{ProofState, ProofStateError, RuleArguments};
+
+    fn parse_prop(prop: &str) -> Prop {
+        let prop_tokens = lexer().then_ignore(end()).parse(prop).unwrap();
+
+        let prop_len = prop.chars().count();
+
+        fol_parser()
+            .parse(Stream::from_iter(
+                prop_len..prop_len + 1,
+                prop_tokens.into_iter(),
+            ))
+            .unwrap()
+    }
+
+    fn ident(name: &str) -> Identifier {
+        Identifier::new(name.to_string(), 0)
+    }
+
+    fn with_prop(prop: &str) -> RuleArguments {
+        RuleArguments {
+            prop: Some(parse_prop(prop)),
+            witness: None,
+        }
+    }
+
+    fn assert_proves(state: &ProofState, prop: &str, ctx: &IdentifierContext) {
+        assert!(state.is_closed());
+        assert_eq!(state.proof_tree().validate(ctx), Ok(()));
+
+        let result = check(&state.proof_term(), &parse_prop(prop), ctx).unwrap();
+        assert!(result.is_closed());
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_impl_intro() {
+        let mut state = ProofState::new(&parse_prop("A -> A")).unwrap();
+        let none = RuleArguments::default();
+
+        state
+            .apply(0, &ProofTreeRule::ImplIntro(ident("u")), &none)
+            .unwrap();
+
+        let goals = state.goals();
+        assert_eq!(goals.len(), 1);
+        assert_eq!(goals[0].conclusion, parse_prop("A"));
+        assert_eq!(goals[0].hypotheses.len(), 1);
+
+        let (hypothesis, _type) = goals[0].hypotheses[0].clone();
+        assert_eq!(hypothesis.name(), "u");
+        assert_eq!(_type, Type::Prop(parse_prop("A")));
+
+        state
+            .apply(0, &ProofTreeRule::Ident(hypothesis), &none)
+            .unwrap();
+
+        assert_proves(&state, "A -> A", &IdentifierContext::new());
+    }
+
+    #[test]
+    fn test_goal_order() {
+        let mut state = ProofState::new(&parse_prop("A && B -> B && A")).unwrap();
+        let none = RuleArguments::default();
+
+        state
+            .apply(0, &ProofTreeRule::ImplIntro(ident("p")), &none)
+            .unwrap();
+        state.apply(0, &ProofTreeRule::AndIntro, &none).unwrap();
+
+        let conclusions: Vec<Prop> = state
+            .goals()
+            .into_iter()
+            .map(|goal| goal.conclusion)
+            .collect();
+        assert_eq!(conclusions, vec![parse_prop("B"), parse_prop("A")]);
+
+        state
+            .apply(1, &ProofTreeRule::AndElimFst, &with_prop("A && B"))
+            .unwrap();
+        state
+            .apply(0, &ProofTreeRule::AndElimSnd, &with_prop("A && B"))
+            .unwrap();
+
+        let p = state.goals()[0].hypotheses[0].0.clone();
+        state
+            .apply(0, &ProofTreeRule::Ident(p.clone()), &none)
+            .unwrap();
+        state.apply(0, &ProofTreeRule::Ident(p), &none).unwrap();
+
+        assert_proves(&state, "A && B -> B && A", &IdentifierContext::new());
+    }
+
+    #[test]
+    fn test_open_goals_as_sorry() {
+        let mut state = ProofState::new(&parse_prop("A -> B || A")).unwrap();
+        let none = RuleArguments::default();
+
+        state
+            .apply(0, &ProofTreeRule::ImplIntro(ident("u")), &none)
+            .unwrap();
+        state.apply(0, &ProofTreeRule::OrIntroSnd, &none).unwrap();
+
+        let result = check(
+            &state.proof_term(),
+            &parse_prop("A -> B || A"),
+            &IdentifierContext::new(),
+        )
+        .unwrap();
+
+        assert_eq!(result.goals.len(), 1);
+        assert_eq!(state.proof_tree().premisses.len(), 1);
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut state = ProofState::new(&parse_prop("A && B")).unwrap();
+
+        assert_eq!(state.undo(), Err(ProofStateError::NothingToUndo));
+
+        state
+            .apply(0, &ProofTreeRule::AndIntro, &RuleArguments::default())
+            .unwrap();
+        assert_eq!(state.goals().len(), 2);
+
+        state.undo().unwrap();
+        assert_eq!(state.goals().len(), 1);
+        assert_eq!(state.goals()[0].conclusion, parse_prop("A && B"));
+    }
+
+    #[test]
+    fn test_rule_not_applicable() {
+        let mut state = ProofState::new(&parse_prop("A || B")).unwrap();
+        let none = RuleArguments::default();
+
+        assert_eq!(
+            state.apply(0, &ProofTreeRule::AndIntro, &none),
+            Err(ProofStateError::RuleNotApplicable(ProofTreeRule::AndIntro))
+        );
+        assert_eq!(
+            state.apply(1, &ProofTreeRule::OrIntroFst, &none),
+            Err(ProofStateError::UnknownGoal(1))
+        );
+        assert_eq!(
+            state.apply(0, &ProofTreeRule::ImplElim, &none),
+            Err(ProofStateError::PropMissing)
+        );
+        assert_eq!(
+            state.apply(0, &ProofTreeRule::ImplElim, &with_prop("C -> B")),
+            Err(ProofStateError::PropMismatch(parse_prop("B")))
+        );
+        assert_eq!(
+            state.apply(0, &ProofTreeRule::Ident(ident("u")), &none),
+            Err(ProofStateError::UnknownHypothesis(ident("u")))
+        );
+
+        // failed applications leave nothing to undo
+        assert_eq!(state.undo(), Err(ProofStateError::NothingToUndo));
+    }
+
+    #[test]
+    fn test_impl_elim() {
+        let mut state = ProofState::new(&parse_prop("(A -> B) -> A -> B")).unwrap();
+        let none = RuleArguments::default();
+
+        state
+            .apply(0, &ProofTreeRule::ImplIntro(ident("f")), &none)
+            .unwrap();
+        state
+            .apply(0, &ProofTreeRule::ImplIntro(ident("a")), &none)
+            .unwrap();
+        state
+            .apply(0, &ProofTreeRule::ImplElim, &with_prop("A -> B"))
+            .unwrap();
+
+        let hypotheses = state.goals()[0].hypotheses.clone();
+        let [(a, _), (f, _)] = &hypotheses[..] else {
+            panic!("Expected two hypotheses.");
+        };
+
+        state
+            .apply(0, &ProofTreeRule::Ident(f.clone()), &none)
+            .unwrap();
+        state
+            .apply(0, &ProofTreeRule::Ident(a.clone()), &none)
+            .unwrap();
+
+        assert_proves(&state, "(A -> B) -> A -> B", &IdentifierContext::new());
+    }
+
+    #[test]
+    fn test_quantifiers() {
+        let ctx = IdentifierContext::with_functions(vec![FunctionSignature {
+            name: "zero".to_string(),
+            param_types: vec![],
+            return_type: "t".to_string(),
+        }]);
+        let prop = "(\\forall x:t. P(x)) -> \\forall z:t. \\exists y:t. P(y)";
+
+        let mut state = ProofState::with_context(&parse_prop(prop), ctx.clone()).unwrap();
+        let none = RuleArguments::default();
+
+        state
+            .apply(0, &ProofTreeRule::ImplIntro(ident("h")), &none)
+            .unwrap();
+        state
+            .apply(0, &ProofTreeRule::ForAllIntro(ident("z")), &none)
+            .unwrap();
+
+        let witness = |name: &str| RuleArguments {
+            prop: Some(parse_prop("\\forall x:t. P(x)")),
+            witness: Some(PropParameter::Uninstantiated(name.to_string())),
+        };
+        assert_eq!(
+            state.apply(0, &ProofTreeRule::ExistsIntro, &none),
+            Err(ProofStateError::WitnessMissing)
+        );
+        assert_eq!(
+            state.apply(0, &ProofTreeRule::ExistsIntro, &witness("w")),
+            Err(ProofStateError::WitnessInvalid(PropParameter::Uninstantiated(
+                "w".to_string()
+            )))
+        );
+        state
+            .apply(0, &ProofTreeRule::ExistsIntro, &witness("zero"))
+            .unwrap();
+
+        assert_eq!(state.goals()[0].conclusion.to_string(), "P(zero)");
+
+        let Err(ProofStateError::PropMismatch(instance)) =
+            state.apply(0, &ProofTreeRule::ForAllElim, &witness("z"))
+        else {
+            panic!("Expected the instance not to match the goal.");
+        };
+        assert_eq!(instance.to_string(), "P(z)");
+        state
+            .apply(0, &ProofTreeRule::ForAllElim, &witness("zero"))
+            .unwrap();
+
+        let h = state.goals()[0].hypotheses[1].0.clone();
+        state.apply(0, &ProofTreeRule::Ident(h), &none).unwrap();
+
+        assert_proves(&state, prop, &ctx);
+    }
+
+    #[test]
+    fn test_definitions_unfold() {
+        let mut state = ProofState::new(&parse_prop("A <-> A")).unwrap();
+        let none = RuleArguments::default();
+
+        state.apply(0, &ProofTreeRule::AndIntro, &none).unwrap();
+        assert_eq!(state.proof_tree().rule, ProofTreeRule::DeltaEquivalent);
+
+        for _ in 0..2 {
+            state
+                .apply(0, &ProofTreeRule::ImplIntro(ident("u")), &none)
+                .unwrap();
+
+            let u = state.goals()[0].hypotheses[0].0.clone();
+            state.apply(0, &ProofTreeRule::Ident(u), &none).unwrap();
+        }
+
+        assert_proves(&state, "A <-> A", &IdentifierContext::new());
+    }
+
+    #[test]
+    fn test_double_neg_elim() {
+        let prop = parse_prop("~~A -> A");
+        let none = RuleArguments::default();
+
+        let mut state = ProofState::new(&prop).unwrap();
+        state
+            .apply(0, &ProofTreeRule::ImplIntro(ident("u")), &none)
+            .unwrap();
+        assert_eq!(
+            state.apply(0, &ProofTreeRule::DoubleNegElim, &none),
+            Err(ProofStateError::ClassicalReasoningNotAllowed)
+        );
+
+        let mut ctx = IdentifierContext::new();
+        ctx.set_logic(Logic::Classical);
+
+        let mut state = ProofState::with_context(&prop, ctx.clone()).unwrap();
+        state
+            .apply(0, &ProofTreeRule::ImplIntro(ident("u")), &none)
+            .unwrap();
+        state
+            .apply(0, &ProofTreeRule::DoubleNegElim, &none)
+            .unwrap();
+
+        let u = state.goals()[0].hypotheses[0].0.clone();
+        state.apply(0, &ProofTreeRule::Ident(u), &none).unwrap();
+
+        assert_proves(&state, "~~A -> A", &ctx);
+    }
+}
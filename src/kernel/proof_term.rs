@@ -4,7 +4,7 @@ use std::ops::Range;
 
 use super::{
     checker::identifier_context::IdentifierContext,
-    prop::{InstatiationError, Prop},
+    prop::{InstatiationError, Prop, PropDefinition},
 };
 use serde::{Deserialize, Serialize};
 use tsify_next::Tsify;
@@ -54,6 +54,26 @@ impl Type {
         }
     }
 
+    pub fn alpha_eq_modulo_definitions(
+        &self,
+        other: &Type,
+        definitions: &[PropDefinition],
+    ) -> bool {
+        match (self, other) {
+            (Type::Prop(lprop), Type::Prop(rprop)) => {
+                lprop.alpha_eq_modulo_definitions(rprop, definitions)
+            }
+            _ => self.alpha_eq(other),
+        }
+    }
+
+    pub fn unfold_head(&self, definitions: &[PropDefinition]) -> Type {
+        match self {
+            Type::Prop(prop) => Type::Prop(prop.unfold_head(definitions)),
+            Type::Datatype(_) => self.clone(),
+        }
+    }
+
     pub fn instantiate_parameters_with_context(
         &mut self,
         ctx: &IdentifierContext,
@@ -101,8 +121,11 @@ pub enum ProofTermKind {
     OrRight,
     Case,
     Abort,
+    DoubleNegElim,
     TypeAscription,
     Unit,
+    Refl,
+    Subst,
     Sorry,
 }
 
@@ -268,6 +291,15 @@ impl Abort {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
+pub struct DoubleNegElim(pub Box<ProofTerm>, pub Option<Range<usize>>);
+
+impl DoubleNegElim {
+    pub fn create(body: Box<ProofTerm>, span: Option<Range<usize>>) -> ProofTerm {
+        ProofTerm::DoubleNegElim(DoubleNegElim(body, span))
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
 pub struct TypeAscription {
     pub proof_term: Box<ProofTerm>,
@@ -276,6 +308,28 @@ pub struct TypeAscription {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
+pub struct Subst {
+    pub equality: Box<ProofTerm>,
+    pub body: Box<ProofTerm>,
+    pub span: Option<Range<usize>>,
+}
+
+impl Subst {
+    pub fn create(
+        equality: Box<ProofTerm>,
+        body: Box<ProofTerm>,
+        span: Option<Range<usize>>,
+    ) -> ProofTerm {
+        ProofTerm::Subst(Subst {
+            equality,
+            body,
+            span,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
 #[serde(tag = "kind", content = "value")]
 pub enum ProofTerm {
     Ident(Ident),
@@ -289,8 +343,11 @@ pub enum ProofTerm {
     OrRight(OrRight),
     Case(Case),
     Abort(Abort),
+    DoubleNegElim(DoubleNegElim),
     TypeAscription(TypeAscription),
     Unit(Option<Range<usize>>),
+    Refl(Option<Range<usize>>),
+    Subst(Subst),
     Sorry(Option<Range<usize>>),
 }
 
@@ -312,8 +369,11 @@ impl ProofTerm {
             ProofTerm::OrRight(OrRight(_, span)) => span,
             ProofTerm::Case(Case { span, .. }) => span,
             ProofTerm::Abort(Abort(_, span)) => span,
+            ProofTerm::DoubleNegElim(DoubleNegElim(_, span)) => span,
             ProofTerm::TypeAscription(TypeAscription { span, .. }) => span,
             ProofTerm::Unit(span) => span,
+            ProofTerm::Refl(span) => span,
+            ProofTerm::Subst(Subst { span, .. }) => span,
             ProofTerm::Sorry(span) => span,
         }
     }
@@ -322,8 +382,11 @@ impl ProofTerm {
         match self {
             ProofTerm::Unit(_) => 999,
             ProofTerm::Ident(_) => 999,
+            ProofTerm::Refl(_) => 999,
+            ProofTerm::Subst(_) => 3,
             ProofTerm::Sorry(_) => 999,
             ProofTerm::Abort(_) => 3,
+            ProofTerm::DoubleNegElim(_) => 3,
             ProofTerm::Pair(_) => 999,
             ProofTerm::ProjectFst(_) => 3,
             ProofTerm::ProjectSnd(_) => 3,
@@ -341,8 +404,11 @@ impl ProofTerm {
         match self {
             ProofTerm::Unit(_) => false,
             ProofTerm::Ident(_) => false,
+            ProofTerm::Refl(_) => false,
+            ProofTerm::Subst(_) => false,
             ProofTerm::Sorry(_) => false,
             ProofTerm::Abort(_) => false,
+            ProofTerm::DoubleNegElim(_) => false,
             ProofTerm::Pair(_) => false,
             ProofTerm::ProjectFst(_) => false,
             ProofTerm::ProjectSnd(_) => false,
@@ -360,8 +426,11 @@ impl ProofTerm {
         match self {
             ProofTerm::Unit(_) => false,
             ProofTerm::Ident(_) => false,
+            ProofTerm::Refl(_) => false,
+            ProofTerm::Subst(_) => true,
             ProofTerm::Sorry(_) => false,
             ProofTerm::Abort(_) => true,
+            ProofTerm::DoubleNegElim(_) => true,
             ProofTerm::Pair(_) => false,
             ProofTerm::ProjectFst(_) => true,
             ProofTerm::ProjectSnd(_) => true,
@@ -407,10 +476,15 @@ impl ProofTerm {
                 head.annotation_count() + fst_term.annotation_count() + snd_term.annotation_count()
             }
             ProofTerm::Abort(Abort(body, _)) => body.annotation_count(),
+            ProofTerm::DoubleNegElim(DoubleNegElim(body, _)) => body.annotation_count(),
             ProofTerm::TypeAscription(TypeAscription { proof_term, .. }) => {
                 1 + proof_term.annotation_count()
             }
             ProofTerm::Unit(_) => 0,
+            ProofTerm::Refl(_) => 0,
+            ProofTerm::Subst(Subst { equality, body, .. }) => {
+                equality.annotation_count() + body.annotation_count()
+            }
             ProofTerm::Sorry(_) => 0,
         }
     }
@@ -428,10 +502,15 @@ impl ProofTerm {
             ProofTerm::OrRight(or_right) => visitor.visit_or_right(or_right),
             ProofTerm::Case(case) => visitor.visit_case(case),
             ProofTerm::Abort(abort) => visitor.visit_abort(abort),
+            ProofTerm::DoubleNegElim(double_neg_elim) => {
+                visitor.visit_double_neg_elim(double_neg_elim)
+            }
             ProofTerm::TypeAscription(type_ascription) => {
                 visitor.visit_type_ascription(type_ascription)
             }
             ProofTerm::Unit(span) => visitor.visit_unit(span.clone()),
+            ProofTerm::Refl(span) => visitor.visit_refl(span.clone()),
+            ProofTerm::Subst(subst) => visitor.visit_subst(subst),
             ProofTerm::Sorry(span) => visitor.visit_sorry(span.clone()),
         }
     }
@@ -447,6 +526,24 @@ impl Display for ProofTerm {
             return write!(f, "()");
         }
 
+        if let ProofTerm::Refl(_) = self {
+            return write!(f, "refl");
+        }
+
+        if let ProofTerm::Subst(Subst { equality, body, .. }) = self {
+            let own_precedence = self.precedence();
+
+            let wrap = |proof_term: &ProofTerm| {
+                if proof_term.precedence() <= own_precedence {
+                    format!("({})", proof_term)
+                } else {
+                    format!("{}", proof_term)
+                }
+            };
+
+            return write!(f, "subst {} {}", wrap(equality), wrap(body));
+        }
+
         if let ProofTerm::Ident(Ident(ident, _)) = self {
             return write!(f, "{}", ident);
         }
@@ -558,6 +655,7 @@ impl Display for ProofTerm {
             ProofTerm::ProjectFst(ProjectFst(body, _)) => ("fst", body),
             ProofTerm::ProjectSnd(ProjectSnd(body, _)) => ("snd", body),
             ProofTerm::Abort(Abort(body, _)) => ("abort", body),
+            ProofTerm::DoubleNegElim(DoubleNegElim(body, _)) => ("dne", body),
             ProofTerm::OrLeft(OrLeft(body, _)) => ("inl", body),
             ProofTerm::OrRight(OrRight(body, _)) => ("inr", body),
             _ => unreachable!(),
@@ -589,7 +687,10 @@ pub trait ProofTermVisitor<R> {
     fn visit_or_right(&mut self, or_right: &OrRight) -> R;
     fn visit_case(&mut self, case: &Case) -> R;
     fn visit_abort(&mut self, abort: &Abort) -> R;
+    fn visit_double_neg_elim(&mut self, double_neg_elim: &DoubleNegElim) -> R;
     fn visit_type_ascription(&mut self, type_ascription: &TypeAscription) -> R;
     fn visit_unit(&mut self, span: Option<Range<usize>>) -> R;
+    fn visit_refl(&mut self, span: Option<Range<usize>>) -> R;
+    fn visit_subst(&mut self, subst: &Subst) -> R;
     fn visit_sorry(&mut self, span: Option<Range<usize>>) -> R;
 }
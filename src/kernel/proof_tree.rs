@@ -1,16 +1,24 @@
 use serde::{Deserialize, Serialize};
 
+use thiserror::Error;
 use tsify_next::Tsify;
 
 use crate::kernel::proof_term::{Application, Function, Type, TypeAscription};
 
 use super::{
-    checker::identifier::Identifier,
+    checker::{
+        check::{check, CheckError},
+        identifier::Identifier,
+        identifier_context::IdentifierContext,
+        Logic,
+    },
     proof::Proof,
     proof_term::{
-        Abort, Case, Ident, LetIn, OrLeft, OrRight, Pair, ProjectFst, ProjectSnd, ProofTerm,
+        Abort, Case, DoubleNegElim, Ident, LetIn, OrLeft, OrRight, Pair, ProjectFst, ProjectSnd,
+        ProofTerm, Subst,
     },
-    prop::Prop,
+    prop::{FunctionSignature, Prop, PropDefinition, PropParameter},
+    reduction::{normalize, DEFAULT_MAX_REDUCTION_STEPS},
 };
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Tsify, Debug)]
@@ -28,12 +36,30 @@ pub enum ProofTreeRule {
     OrIntroSnd,
     OrElim(Identifier, Identifier),
     FalsumElim,
+    DoubleNegElim,
     ForAllIntro(Identifier),
     ForAllElim,
     ExistsIntro,
     ExistsElim(Identifier, Identifier),
+    EqIntro,
+    EqElim,
+    FunctionApplication(String),
     Sorry,
     AlphaEquivalent,
+    DeltaEquivalent,
+}
+
+impl ProofTreeRule {
+    // Hypotheses that the rule introduces for the premisse with the given index.
+    pub fn discharged_identifiers(&self, premisse: usize) -> Vec<&Identifier> {
+        match (self, premisse) {
+            (ProofTreeRule::ImplIntro(ident), 0) => vec![ident],
+            (ProofTreeRule::OrElim(fst_ident, _), 1) => vec![fst_ident],
+            (ProofTreeRule::OrElim(_, snd_ident), 2) => vec![snd_ident],
+            (ProofTreeRule::ExistsElim(_, snd_ident), 1) => vec![snd_ident],
+            _ => vec![],
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Tsify, Debug)]
@@ -42,6 +68,7 @@ pub enum ProofTreeRule {
 pub enum ProofTreeConclusion {
     PropIsTrue(Prop),
     TypeJudgement(Identifier, String),
+    TermJudgement(PropParameter, String),
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Tsify, Debug)]
@@ -73,6 +100,14 @@ impl ProofTree {
                     panic!("Conclusions not alpha equivalent.");
                 }
             }
+            (
+                ProofTreeConclusion::TermJudgement(ref own_term, ref own_datatype),
+                ProofTreeConclusion::TermJudgement(ref term, ref datatype),
+            ) => {
+                if !PropParameter::eq(own_term, term) || !String::eq(own_datatype, datatype) {
+                    panic!("Conclusions not alpha equivalent.");
+                }
+            }
             _ => panic!("Conclusions not alpha equivalent."),
         }
 
@@ -83,9 +118,140 @@ impl ProofTree {
         }
     }
 
+    // Like create_alphq_eq_tree, but the conclusions may also differ in folded definitions.
+    pub fn create_equivalence_tree(
+        &self,
+        conclusion: ProofTreeConclusion,
+        definitions: &[PropDefinition],
+    ) -> ProofTree {
+        if let (ProofTreeConclusion::PropIsTrue(own_prop), ProofTreeConclusion::PropIsTrue(prop)) =
+            (&self.conclusion, &conclusion)
+        {
+            if !Prop::alpha_eq(own_prop, prop) {
+                if !own_prop.alpha_eq_modulo_definitions(prop, definitions) {
+                    panic!("Conclusions not equivalent.");
+                }
+
+                return ProofTree {
+                    premisses: vec![self.to_owned()],
+                    rule: ProofTreeRule::DeltaEquivalent,
+                    conclusion,
+                };
+            }
+        }
+
+        self.create_alphq_eq_tree(conclusion)
+    }
+
     pub fn as_proof(&self) -> Proof {
         ProofTreeExporter::export_as_proof(self)
     }
+
+    pub fn contains_rule(&self, rule: &ProofTreeRule) -> bool {
+        self.rule == *rule
+            || self
+                .premisses
+                .iter()
+                .any(|premisse| premisse.contains_rule(rule))
+    }
+
+    pub fn conclusion_prop(&self) -> &Prop {
+        match self.conclusion {
+            ProofTreeConclusion::PropIsTrue(ref prop) => prop,
+            _ => panic!("Expected proposition."),
+        }
+    }
+
+    pub fn conclusion_term(&self) -> PropParameter {
+        match self.conclusion {
+            ProofTreeConclusion::TypeJudgement(ref ident, _) => {
+                PropParameter::Instantiated(ident.clone())
+            }
+            ProofTreeConclusion::TermJudgement(ref term, _) => term.clone(),
+            ProofTreeConclusion::PropIsTrue(_) => panic!("Expected type judgement."),
+        }
+    }
+
+    // Checks every rule application locally, without going through a proof term.
+    pub fn validate(&self, ctx: &IdentifierContext) -> Result<(), ProofTreeValidationError> {
+        ProofTreeValidator::new().validate(self, ctx)
+    }
+
+    // Eliminates all detours and permutes eliminations out of or-, exists- and falsum-eliminations.
+    // The resulting normal proof has the subformula property and is checked again.
+    pub fn normalize(
+        &self,
+        ctx: &IdentifierContext,
+    ) -> Result<ProofTree, ProofTreeNormalizationError> {
+        let ProofTreeConclusion::PropIsTrue(ref prop) = self.conclusion else {
+            return Err(ProofTreeNormalizationError::ConclusionNotProp);
+        };
+
+        let normalization = normalize(&self.as_proof().proof_term, DEFAULT_MAX_REDUCTION_STEPS);
+
+        if !normalization.normalized {
+            return Err(ProofTreeNormalizationError::StepLimitReached(
+                DEFAULT_MAX_REDUCTION_STEPS,
+            ));
+        }
+
+        let result = check(&normalization.normal_form, prop, ctx)
+            .map_err(|err| ProofTreeNormalizationError::CheckError(Box::new(err)))?;
+
+        Ok(result.proof_tree)
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum ProofTreeValidationErrorKind {
+    #[error("Rule expects {expected} premisses, received {actual}")]
+    PremisseCountWrong { expected: usize, actual: usize },
+
+    #[error("Conclusion does not match the rule")]
+    ConclusionMismatch,
+
+    #[error("Premisse {0} does not match the rule")]
+    PremisseMismatch(usize),
+
+    #[error("Identifier {} is not in scope", .0.name())]
+    UnknownIdentifier(Identifier),
+
+    #[error("Parameter {0} is not bound")]
+    UnboundParameter(String),
+
+    #[error("Function {0} unknown")]
+    UnknownFunction(String),
+
+    #[error("Eigenvariable {} is not fresh", .0.name())]
+    EigenvariableNotFresh(Identifier),
+
+    #[error("Classical reasoning is not allowed in intuitionistic logic")]
+    ClassicalReasoningNotAllowed,
+}
+
+#[derive(Debug, Error, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[error("{kind}")]
+pub struct ProofTreeValidationError {
+    // indices of the premisses on the way from the root to the invalid node
+    pub path: Vec<usize>,
+    pub kind: ProofTreeValidationErrorKind,
+}
+
+#[derive(Debug, Error, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum ProofTreeNormalizationError {
+    #[error("Only proofs of propositions can be normalized")]
+    ConclusionNotProp,
+
+    #[error("No normal form found within {0} reduction steps")]
+    StepLimitReached(usize),
+
+    #[error("Normal form does not type check")]
+    CheckError(Box<CheckError>),
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -97,6 +263,7 @@ enum ReasoningMode {
 struct ProofTreeExporter {
     atoms: Vec<(String, usize)>,
     datatypes: Vec<String>,
+    functions: Vec<FunctionSignature>,
 }
 
 impl ProofTreeExporter {
@@ -104,6 +271,7 @@ impl ProofTreeExporter {
         Self {
             atoms: vec![],
             datatypes: vec![],
+            functions: vec![],
         }
     }
 
@@ -113,12 +281,28 @@ impl ProofTreeExporter {
 
         Proof {
             atoms: exporter.atoms,
+            atom_signatures: vec![],
             datatypes: exporter.datatypes,
+            functions: exporter.functions,
+            definitions: vec![],
             processing_state: super::proof::ProofProcessingState::TypesResolved,
             proof_term,
         }
     }
 
+    // Annotations refer to objects by name, like in the source of a proof.
+    fn annotation(prop: &Prop) -> Type {
+        let mut prop = prop.clone();
+
+        for param in prop.get_free_parameters_mut() {
+            if let PropParameter::Instantiated(identifier) = param {
+                *param = PropParameter::Uninstantiated(identifier.name().clone());
+            }
+        }
+
+        Type::Prop(prop)
+    }
+
     fn wrap_into_type_ascription(
         &mut self,
         mut proof_term: ProofTerm,
@@ -134,7 +318,7 @@ impl ProofTreeExporter {
             };
 
             proof_term = ProofTerm::TypeAscription(TypeAscription {
-                ascription: Type::Prop(prop.clone()),
+                ascription: Self::annotation(prop),
                 proof_term: proof_term.boxed(),
                 span: None,
             });
@@ -159,7 +343,7 @@ impl ProofTreeExporter {
         match rule {
             ProofTreeRule::TrueIntro => ProofTerm::Unit(None),
             ProofTreeRule::Ident(ident) => Ident::create(ident.name().clone()),
-            ProofTreeRule::AlphaEquivalent => {
+            ProofTreeRule::AlphaEquivalent | ProofTreeRule::DeltaEquivalent => {
                 self.do_export_as_proof_term(&premisses[0], reasoning_mode)
             }
             ProofTreeRule::Sorry => {
@@ -225,7 +409,7 @@ impl ProofTreeExporter {
                         panic!("Expected conclusion to be an implication.");
                     };
 
-                    annotation = Some(Type::Prop(*fst.clone()));
+                    annotation = Some(Self::annotation(fst));
 
                     self.atoms.append(&mut fst.get_atoms());
                 }
@@ -361,6 +545,27 @@ impl ProofTreeExporter {
                     &expected_reasoning_mode,
                 )
             }
+            ProofTreeRule::DoubleNegElim => {
+                let expected_reasoning_mode = Self::expected_conclusion_mode(rule);
+
+                let [ref body] = premisses[..] else {
+                    panic!("Not enough premisses.");
+                };
+
+                let body_reasoning_mode =
+                    Self::expected_premisse_mode(rule, &expected_reasoning_mode, 0);
+
+                let body_proof_term = self.do_export_as_proof_term(body, &body_reasoning_mode);
+
+                let proof_term = DoubleNegElim::create(body_proof_term.boxed(), None);
+
+                self.wrap_into_type_ascription(
+                    proof_term,
+                    conclusion,
+                    reasoning_mode,
+                    &expected_reasoning_mode,
+                )
+            }
             ProofTreeRule::ForAllIntro(param_ident) => {
                 let [ref body] = premisses[..] else {
                     panic!("Not enough premisses.");
@@ -458,6 +663,61 @@ impl ProofTreeExporter {
                     span: None,
                 })
             }
+            ProofTreeRule::EqIntro => {
+                let proof_term = ProofTerm::Refl(None);
+
+                self.wrap_into_type_ascription(
+                    proof_term,
+                    conclusion,
+                    reasoning_mode,
+                    &ReasoningMode::Check,
+                )
+            }
+            ProofTreeRule::EqElim => {
+                let [ref fst, ref snd] = premisses[..] else {
+                    panic!("Not enough premisses.");
+                };
+
+                let fst_reasoning_mode = Self::expected_premisse_mode(rule, reasoning_mode, 0);
+                let snd_reasoning_mode = Self::expected_premisse_mode(rule, reasoning_mode, 1);
+
+                let fst_proof_term = self.do_export_as_proof_term(fst, &fst_reasoning_mode);
+                let snd_proof_term = self.do_export_as_proof_term(snd, &snd_reasoning_mode);
+
+                Subst::create(fst_proof_term.boxed(), snd_proof_term.boxed(), None)
+            }
+            ProofTreeRule::FunctionApplication(name) => {
+                let param_types = premisses
+                    .iter()
+                    .map(|premisse| match premisse.conclusion {
+                        ProofTreeConclusion::TypeJudgement(_, ref datatype)
+                        | ProofTreeConclusion::TermJudgement(_, ref datatype) => datatype.clone(),
+                        ProofTreeConclusion::PropIsTrue(_) => panic!("Expected type judgement."),
+                    })
+                    .collect();
+
+                let ProofTreeConclusion::TermJudgement(_, return_type) = conclusion else {
+                    panic!("Expected term judgement.");
+                };
+
+                let signature = FunctionSignature {
+                    name: name.clone(),
+                    param_types,
+                    return_type: return_type.clone(),
+                };
+
+                if !self.functions.contains(&signature) {
+                    self.functions.push(signature);
+                }
+
+                premisses
+                    .iter()
+                    .fold(Ident::create(name.clone()), |function, premisse| {
+                        let applicant = self.do_export_as_proof_term(premisse, reasoning_mode);
+
+                        Application::create(function.boxed(), applicant.boxed(), None)
+                    })
+            }
         }
     }
 
@@ -470,10 +730,13 @@ impl ProofTreeExporter {
             ProofTreeRule::OrIntroFst => ReasoningMode::Check,
             ProofTreeRule::OrIntroSnd => ReasoningMode::Check,
             ProofTreeRule::FalsumElim => ReasoningMode::Check,
+            ProofTreeRule::DoubleNegElim => ReasoningMode::Check,
             ProofTreeRule::Sorry => ReasoningMode::Check,
             ProofTreeRule::AlphaEquivalent => ReasoningMode::Check,
+            ProofTreeRule::DeltaEquivalent => ReasoningMode::Check,
             ProofTreeRule::ForAllElim => ReasoningMode::Synthesize,
             ProofTreeRule::ExistsIntro => ReasoningMode::Check,
+            ProofTreeRule::EqIntro => ReasoningMode::Check,
             _ => panic!("Both modes are possible."),
         }
     }
@@ -499,6 +762,7 @@ impl ProofTreeExporter {
             (1, _, ProofTreeRule::OrElim(_, _)) => conclusion_mode.clone(),
             (2, _, ProofTreeRule::OrElim(_, _)) => conclusion_mode.clone(),
             (0, ReasoningMode::Check, ProofTreeRule::FalsumElim) => ReasoningMode::Check,
+            (0, ReasoningMode::Check, ProofTreeRule::DoubleNegElim) => ReasoningMode::Check,
             (0, _, ProofTreeRule::ForAllIntro(_)) => conclusion_mode.clone(),
             (0, ReasoningMode::Synthesize, ProofTreeRule::ForAllElim) => ReasoningMode::Synthesize,
             (1, ReasoningMode::Synthesize, ProofTreeRule::ForAllElim) => ReasoningMode::Check,
@@ -506,8 +770,850 @@ impl ProofTreeExporter {
             (1, ReasoningMode::Check, ProofTreeRule::ExistsIntro) => ReasoningMode::Check,
             (0, _, ProofTreeRule::ExistsElim(_, _)) => ReasoningMode::Synthesize,
             (1, _, ProofTreeRule::ExistsElim(_, _)) => conclusion_mode.clone(),
+            (0, _, ProofTreeRule::EqElim) => ReasoningMode::Synthesize,
+            (1, _, ProofTreeRule::EqElim) => conclusion_mode.clone(),
             (0, _, ProofTreeRule::AlphaEquivalent) => ReasoningMode::Synthesize,
+            (0, _, ProofTreeRule::DeltaEquivalent) => ReasoningMode::Synthesize,
             _ => panic!("Rule does not have that many premisses."),
         }
     }
 }
+
+struct ProofTreeValidator {
+    path: Vec<usize>,
+}
+
+impl ProofTreeValidator {
+    pub fn new() -> Self {
+        Self { path: vec![] }
+    }
+
+    pub fn validate(
+        &mut self,
+        proof_tree: &ProofTree,
+        ctx: &IdentifierContext,
+    ) -> Result<(), ProofTreeValidationError> {
+        let premisse_ctxs =
+            Self::validate_step(proof_tree, ctx).map_err(|kind| ProofTreeValidationError {
+                path: self.path.clone(),
+                kind,
+            })?;
+
+        for (i, (premisse, premisse_ctx)) in
+            proof_tree.premisses.iter().zip(premisse_ctxs).enumerate()
+        {
+            self.path.push(i);
+            self.validate(premisse, &premisse_ctx)?;
+            self.path.pop();
+        }
+
+        Ok(())
+    }
+
+    // Checks a single rule application and returns the contexts of its premisses.
+    fn validate_step(
+        proof_tree: &ProofTree,
+        ctx: &IdentifierContext,
+    ) -> Result<Vec<IdentifierContext>, ProofTreeValidationErrorKind> {
+        let ProofTree {
+            premisses,
+            rule,
+            conclusion,
+        } = proof_tree;
+
+        Self::validate_scope(conclusion, ctx)?;
+
+        let expected_premisse_count = match rule {
+            ProofTreeRule::TrueIntro
+            | ProofTreeRule::Ident(_)
+            | ProofTreeRule::EqIntro
+            | ProofTreeRule::Sorry => 0,
+            ProofTreeRule::AndElimFst
+            | ProofTreeRule::AndElimSnd
+            | ProofTreeRule::ImplIntro(_)
+            | ProofTreeRule::OrIntroFst
+            | ProofTreeRule::OrIntroSnd
+            | ProofTreeRule::FalsumElim
+            | ProofTreeRule::DoubleNegElim
+            | ProofTreeRule::ForAllIntro(_)
+            | ProofTreeRule::AlphaEquivalent
+            | ProofTreeRule::DeltaEquivalent => 1,
+            ProofTreeRule::AndIntro
+            | ProofTreeRule::ImplElim
+            | ProofTreeRule::ForAllElim
+            | ProofTreeRule::ExistsIntro
+            | ProofTreeRule::ExistsElim(_, _)
+            | ProofTreeRule::EqElim => 2,
+            ProofTreeRule::OrElim(_, _) => 3,
+            ProofTreeRule::FunctionApplication(_) => match conclusion {
+                ProofTreeConclusion::TermJudgement(PropParameter::Function(_, args), _) => {
+                    args.len()
+                }
+                _ => return Err(ProofTreeValidationErrorKind::ConclusionMismatch),
+            },
+        };
+
+        if premisses.len() != expected_premisse_count {
+            return Err(ProofTreeValidationErrorKind::PremisseCountWrong {
+                expected: expected_premisse_count,
+                actual: premisses.len(),
+            });
+        }
+
+        let premisse_prop = |i: usize| match premisses[i].conclusion {
+            ProofTreeConclusion::PropIsTrue(ref prop) => Ok(prop),
+            _ => Err(ProofTreeValidationErrorKind::PremisseMismatch(i)),
+        };
+
+        let expect = |condition: bool, err: ProofTreeValidationErrorKind| match condition {
+            true => Ok(()),
+            false => Err(err),
+        };
+
+        let same_ctx = vec![ctx.clone(); premisses.len()];
+
+        // judgements about terms only appear as premisses of the rules for terms
+        let prop = match (rule, conclusion) {
+            (ProofTreeRule::Sorry, _) => return Ok(vec![]),
+            (ProofTreeRule::Ident(ident), _) => {
+                return Self::validate_ident(ident, conclusion, ctx).map(|_| vec![])
+            }
+            (ProofTreeRule::AlphaEquivalent, _) => {
+                let equivalent = match (&premisses[0].conclusion, conclusion) {
+                    (
+                        ProofTreeConclusion::PropIsTrue(fst),
+                        ProofTreeConclusion::PropIsTrue(snd),
+                    ) => Prop::alpha_eq(fst, snd),
+                    (fst, snd) => fst == snd,
+                };
+                expect(
+                    equivalent,
+                    ProofTreeValidationErrorKind::PremisseMismatch(0),
+                )?;
+
+                return Ok(same_ctx);
+            }
+            (ProofTreeRule::FunctionApplication(name), _) => {
+                return Self::validate_function_application(name, premisses, conclusion, ctx)
+                    .map(|_| same_ctx)
+            }
+            (_, ProofTreeConclusion::PropIsTrue(prop)) => prop,
+            _ => return Err(ProofTreeValidationErrorKind::ConclusionMismatch),
+        };
+
+        match rule {
+            ProofTreeRule::TrueIntro => {
+                expect(
+                    *prop == Prop::True,
+                    ProofTreeValidationErrorKind::ConclusionMismatch,
+                )?;
+
+                Ok(vec![])
+            }
+            ProofTreeRule::AndIntro => {
+                let Prop::And(fst, snd) = prop else {
+                    return Err(ProofTreeValidationErrorKind::ConclusionMismatch);
+                };
+
+                expect(
+                    Prop::alpha_eq(premisse_prop(0)?, fst),
+                    ProofTreeValidationErrorKind::PremisseMismatch(0),
+                )?;
+                expect(
+                    Prop::alpha_eq(premisse_prop(1)?, snd),
+                    ProofTreeValidationErrorKind::PremisseMismatch(1),
+                )?;
+
+                Ok(same_ctx)
+            }
+            ProofTreeRule::AndElimFst | ProofTreeRule::AndElimSnd => {
+                let Prop::And(fst, snd) = premisse_prop(0)? else {
+                    return Err(ProofTreeValidationErrorKind::PremisseMismatch(0));
+                };
+
+                let component = match rule {
+                    ProofTreeRule::AndElimFst => fst,
+                    _ => snd,
+                };
+                expect(
+                    Prop::alpha_eq(component, prop),
+                    ProofTreeValidationErrorKind::ConclusionMismatch,
+                )?;
+
+                Ok(same_ctx)
+            }
+            ProofTreeRule::ImplIntro(param_ident) => {
+                let Prop::Impl(fst, snd) = prop else {
+                    return Err(ProofTreeValidationErrorKind::ConclusionMismatch);
+                };
+
+                expect(
+                    Prop::alpha_eq(premisse_prop(0)?, snd),
+                    ProofTreeValidationErrorKind::PremisseMismatch(0),
+                )?;
+
+                let mut body_ctx = ctx.clone();
+                body_ctx.insert(param_ident.clone(), Type::Prop(*fst.clone()));
+
+                Ok(vec![body_ctx])
+            }
+            ProofTreeRule::ImplElim => {
+                let Prop::Impl(fst, snd) = premisse_prop(0)? else {
+                    return Err(ProofTreeValidationErrorKind::PremisseMismatch(0));
+                };
+
+                expect(
+                    Prop::alpha_eq(premisse_prop(1)?, fst),
+                    ProofTreeValidationErrorKind::PremisseMismatch(1),
+                )?;
+                expect(
+                    Prop::alpha_eq(snd, prop),
+                    ProofTreeValidationErrorKind::ConclusionMismatch,
+                )?;
+
+                Ok(same_ctx)
+            }
+            ProofTreeRule::OrIntroFst | ProofTreeRule::OrIntroSnd => {
+                let Prop::Or(fst, snd) = prop else {
+                    return Err(ProofTreeValidationErrorKind::ConclusionMismatch);
+                };
+
+                let component = match rule {
+                    ProofTreeRule::OrIntroFst => fst,
+                    _ => snd,
+                };
+                expect(
+                    Prop::alpha_eq(premisse_prop(0)?, component),
+                    ProofTreeValidationErrorKind::PremisseMismatch(0),
+                )?;
+
+                Ok(same_ctx)
+            }
+            ProofTreeRule::OrElim(fst_ident, snd_ident) => {
+                let Prop::Or(fst, snd) = premisse_prop(0)? else {
+                    return Err(ProofTreeValidationErrorKind::PremisseMismatch(0));
+                };
+
+                for i in [1, 2] {
+                    expect(
+                        Prop::alpha_eq(premisse_prop(i)?, prop),
+                        ProofTreeValidationErrorKind::PremisseMismatch(i),
+                    )?;
+                }
+
+                let mut fst_ctx = ctx.clone();
+                fst_ctx.insert(fst_ident.clone(), Type::Prop(*fst.clone()));
+
+                let mut snd_ctx = ctx.clone();
+                snd_ctx.insert(snd_ident.clone(), Type::Prop(*snd.clone()));
+
+                Ok(vec![ctx.clone(), fst_ctx, snd_ctx])
+            }
+            ProofTreeRule::FalsumElim => {
+                expect(
+                    *premisse_prop(0)? == Prop::False,
+                    ProofTreeValidationErrorKind::PremisseMismatch(0),
+                )?;
+
+                Ok(same_ctx)
+            }
+            ProofTreeRule::DoubleNegElim => {
+                expect(
+                    *ctx.logic() == Logic::Classical,
+                    ProofTreeValidationErrorKind::ClassicalReasoningNotAllowed,
+                )?;
+
+                let double_negation = Prop::Impl(
+                    Prop::Impl(prop.boxed(), Prop::False.boxed()).boxed(),
+                    Prop::False.boxed(),
+                );
+                expect(
+                    Prop::alpha_eq(premisse_prop(0)?, &double_negation),
+                    ProofTreeValidationErrorKind::PremisseMismatch(0),
+                )?;
+
+                Ok(same_ctx)
+            }
+            ProofTreeRule::ForAllIntro(param_ident) => {
+                let Prop::ForAll {
+                    object_ident,
+                    object_type_ident,
+                    body,
+                } = prop
+                else {
+                    return Err(ProofTreeValidationErrorKind::ConclusionMismatch);
+                };
+
+                // as all conclusions are in scope, a fresh eigenvariable cannot occur in them
+                expect(
+                    ctx.get(param_ident).is_none(),
+                    ProofTreeValidationErrorKind::EigenvariableNotFresh(param_ident.clone()),
+                )?;
+
+                let mut body = body.clone();
+                body.instantiate_free_parameter(object_ident, param_ident);
+
+                expect(
+                    Prop::alpha_eq(premisse_prop(0)?, &body),
+                    ProofTreeValidationErrorKind::PremisseMismatch(0),
+                )?;
+
+                let mut body_ctx = ctx.clone();
+                body_ctx.insert(
+                    param_ident.clone(),
+                    Type::Datatype(object_type_ident.clone()),
+                );
+
+                Ok(vec![body_ctx])
+            }
+            ProofTreeRule::ForAllElim => {
+                let Prop::ForAll {
+                    object_ident,
+                    object_type_ident,
+                    body,
+                } = premisse_prop(0)?
+                else {
+                    return Err(ProofTreeValidationErrorKind::PremisseMismatch(0));
+                };
+
+                let term = Self::premisse_term(&premisses[1], object_type_ident)
+                    .ok_or(ProofTreeValidationErrorKind::PremisseMismatch(1))?;
+
+                let mut body = body.clone();
+                body.substitute_free_parameter(object_ident, &term);
+
+                expect(
+                    Prop::alpha_eq(&body, prop),
+                    ProofTreeValidationErrorKind::ConclusionMismatch,
+                )?;
+
+                Ok(same_ctx)
+            }
+            ProofTreeRule::ExistsIntro => {
+                let Prop::Exists {
+                    object_ident,
+                    object_type_ident,
+                    body,
+                } = prop
+                else {
+                    return Err(ProofTreeValidationErrorKind::ConclusionMismatch);
+                };
+
+                let term = Self::premisse_term(&premisses[0], object_type_ident)
+                    .ok_or(ProofTreeValidationErrorKind::PremisseMismatch(0))?;
+
+                let mut body = body.clone();
+                body.substitute_free_parameter(object_ident, &term);
+
+                expect(
+                    Prop::alpha_eq(premisse_prop(1)?, &body),
+                    ProofTreeValidationErrorKind::PremisseMismatch(1),
+                )?;
+
+                Ok(same_ctx)
+            }
+            ProofTreeRule::ExistsElim(fst_ident, snd_ident) => {
+                let Prop::Exists {
+                    object_ident,
+                    object_type_ident,
+                    body,
+                } = premisse_prop(0)?
+                else {
+                    return Err(ProofTreeValidationErrorKind::PremisseMismatch(0));
+                };
+
+                expect(
+                    ctx.get(fst_ident).is_none(),
+                    ProofTreeValidationErrorKind::EigenvariableNotFresh(fst_ident.clone()),
+                )?;
+                expect(
+                    Prop::alpha_eq(premisse_prop(1)?, prop),
+                    ProofTreeValidationErrorKind::PremisseMismatch(1),
+                )?;
+
+                let mut body = body.clone();
+                body.instantiate_free_parameter(object_ident, fst_ident);
+
+                let mut body_ctx = ctx.clone();
+                body_ctx.insert(fst_ident.clone(), Type::Datatype(object_type_ident.clone()));
+                body_ctx.insert(snd_ident.clone(), Type::Prop(*body));
+
+                Ok(vec![ctx.clone(), body_ctx])
+            }
+            ProofTreeRule::EqIntro => {
+                let Prop::Eq(lhs, rhs) = prop else {
+                    return Err(ProofTreeValidationErrorKind::ConclusionMismatch);
+                };
+                expect(lhs == rhs, ProofTreeValidationErrorKind::ConclusionMismatch)?;
+
+                Ok(vec![])
+            }
+            ProofTreeRule::EqElim => {
+                let Prop::Eq(lhs, rhs) = premisse_prop(0)? else {
+                    return Err(ProofTreeValidationErrorKind::PremisseMismatch(0));
+                };
+
+                // the body proves the conclusion with rhs replaced by lhs
+                let mut body = prop.clone();
+                body.replace_free_parameter(rhs, lhs);
+
+                expect(
+                    Prop::alpha_eq(premisse_prop(1)?, &body),
+                    ProofTreeValidationErrorKind::PremisseMismatch(1),
+                )?;
+
+                Ok(same_ctx)
+            }
+            ProofTreeRule::DeltaEquivalent => {
+                expect(
+                    premisse_prop(0)?.alpha_eq_modulo_definitions(prop, ctx.definitions()),
+                    ProofTreeValidationErrorKind::PremisseMismatch(0),
+                )?;
+
+                Ok(same_ctx)
+            }
+            ProofTreeRule::Sorry
+            | ProofTreeRule::Ident(_)
+            | ProofTreeRule::AlphaEquivalent
+            | ProofTreeRule::FunctionApplication(_) => unreachable!(),
+        }
+    }
+
+    fn validate_ident(
+        ident: &Identifier,
+        conclusion: &ProofTreeConclusion,
+        ctx: &IdentifierContext,
+    ) -> Result<(), ProofTreeValidationErrorKind> {
+        let Some(_type) = ctx.get(ident) else {
+            return Err(ProofTreeValidationErrorKind::UnknownIdentifier(
+                ident.clone(),
+            ));
+        };
+
+        let matches = match (conclusion, _type) {
+            (ProofTreeConclusion::PropIsTrue(prop), Type::Prop(hypothesis)) => {
+                Prop::alpha_eq(hypothesis, prop)
+            }
+            (
+                ProofTreeConclusion::TypeJudgement(judged_ident, datatype),
+                Type::Datatype(type_ident),
+            ) => judged_ident == ident && datatype == type_ident,
+            _ => false,
+        };
+
+        match matches {
+            true => Ok(()),
+            false => Err(ProofTreeValidationErrorKind::ConclusionMismatch),
+        }
+    }
+
+    fn validate_function_application(
+        name: &String,
+        premisses: &[ProofTree],
+        conclusion: &ProofTreeConclusion,
+        ctx: &IdentifierContext,
+    ) -> Result<(), ProofTreeValidationErrorKind> {
+        let ProofTreeConclusion::TermJudgement(
+            PropParameter::Function(function_name, args),
+            datatype,
+        ) = conclusion
+        else {
+            return Err(ProofTreeValidationErrorKind::ConclusionMismatch);
+        };
+
+        let signature = ctx
+            .get_function(name)
+            .ok_or(ProofTreeValidationErrorKind::UnknownFunction(name.clone()))?;
+
+        if function_name != name
+            || signature.return_type != *datatype
+            || signature.param_types.len() != args.len()
+        {
+            return Err(ProofTreeValidationErrorKind::ConclusionMismatch);
+        }
+
+        for (i, (premisse, (arg, param_type))) in premisses
+            .iter()
+            .zip(args.iter().zip(signature.param_types.iter()))
+            .enumerate()
+        {
+            if Self::premisse_term(premisse, param_type).as_ref() != Some(arg) {
+                return Err(ProofTreeValidationErrorKind::PremisseMismatch(i));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Term that a premisse judges to have the datatype.
+    fn premisse_term(premisse: &ProofTree, datatype: &String) -> Option<PropParameter> {
+        match premisse.conclusion {
+            ProofTreeConclusion::TypeJudgement(ref ident, ref judged_datatype)
+                if judged_datatype == datatype =>
+            {
+                Some(PropParameter::Instantiated(ident.clone()))
+            }
+            ProofTreeConclusion::TermJudgement(ref term, ref judged_datatype)
+                if judged_datatype == datatype =>
+            {
+                Some(term.clone())
+            }
+            _ => None,
+        }
+    }
+
+    // Every parameter of a conclusion has to be bound by a quantifier or the context.
+    fn validate_scope(
+        conclusion: &ProofTreeConclusion,
+        ctx: &IdentifierContext,
+    ) -> Result<(), ProofTreeValidationErrorKind> {
+        let params = match conclusion {
+            ProofTreeConclusion::PropIsTrue(prop) => prop.get_free_parameters(),
+            ProofTreeConclusion::TermJudgement(term, _) => {
+                term.get_variables().into_iter().cloned().collect()
+            }
+            ProofTreeConclusion::TypeJudgement(_, _) => vec![],
+        };
+
+        for param in params {
+            match param {
+                PropParameter::Instantiated(ident) => {
+                    if !matches!(ctx.get(&ident), Some(Type::Datatype(_))) {
+                        return Err(ProofTreeValidationErrorKind::UnknownIdentifier(ident));
+                    }
+                }
+                PropParameter::Uninstantiated(name) => {
+                    return Err(ProofTreeValidationErrorKind::UnboundParameter(name))
+                }
+                PropParameter::Function(_, _) => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        checker::{
+            check::check, identifier::Identifier, identifier_context::IdentifierContext, Logic,
+        },
+        parse::{fol::fol_parser, lexer::lexer, proof::proof_parser},
+        process::{stages::resolve_datatypes::ResolveDatatypes, ProofPipeline},
+        prop::Prop,
+    };
+
+    use super::{
+        ProofTree, ProofTreeConclusion, ProofTreeNormalizationError, ProofTreeRule,
+        ProofTreeValidationErrorKind,
+    };
+
+    fn parse_prop(prop: &str) -> Prop {
+        let len = prop.chars().count();
+
+        let tokens = lexer().parse(prop).unwrap();
+        fol_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    fn check_proof(proof: &str, prop: &str, logic: Logic) -> (ProofTree, IdentifierContext) {
+        let prop = parse_prop(prop);
+
+        let len = proof.chars().count();
+        let tokens = lexer().parse(proof).unwrap();
+        let proof_ast = proof_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap();
+        let proof = ProofPipeline::new()
+            .pipe(ResolveDatatypes::boxed())
+            .apply(proof_ast, &prop)
+            .unwrap();
+
+        let mut ctx = IdentifierContext::with_signatures(proof.functions, proof.atom_signatures);
+        ctx.add_definitions(proof.definitions);
+        ctx.set_logic(logic);
+
+        let proof_tree = check(&proof.proof_term, &prop, &ctx).unwrap().proof_tree;
+
+        (proof_tree, ctx)
+    }
+
+    fn assert_valid(proof: &str, prop: &str) {
+        let (proof_tree, ctx) = check_proof(proof, prop, Logic::Intuitionistic);
+
+        assert_eq!(proof_tree.validate(&ctx), Ok(()));
+    }
+
+    fn assert_normal_form(proof: &str, prop: &str, normal_form: &str) {
+        let (proof_tree, ctx) = check_proof(proof, prop, Logic::Intuitionistic);
+        let normal_proof_tree = proof_tree.normalize(&ctx).unwrap();
+
+        assert_eq!(normal_proof_tree.conclusion, proof_tree.conclusion);
+        assert_eq!(normal_proof_tree.validate(&ctx), Ok(()));
+        assert_eq!(
+            normal_proof_tree.as_proof().proof_term.to_string(),
+            normal_form
+        );
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_validate_propositional() {
+        assert_valid("atom A; atom B; fn u => (snd u, fst u)", "A && B -> B && A");
+        assert_valid(
+            "atom A; atom B; fn u => case u of inl a => inr a, inr b => inl b",
+            "A || B -> B || A",
+        );
+        assert_valid(
+            "atom A; atom B; fn u => fn v => abort (v u)",
+            "A -> ~A -> B",
+        );
+        assert_valid("atom A; atom B; fn u => sorry", "A -> B");
+        assert_valid("atom A; atom B; (fn u => u, fn u => u)", "A <-> A");
+    }
+
+    #[test]
+    fn test_validate_quantifiers() {
+        assert_valid(
+            "
+                datatype t;
+                atom P(1);
+
+                fn u => let (x, h) = u in (x, h)
+            ",
+            "(\\exists x:t. P(x)) -> \\exists y:t. P(y)",
+        );
+        assert_valid(
+            "
+                datatype t;
+                atom P(1);
+                function zero : t;
+
+                fn u => u zero
+            ",
+            "(\\forall x:t. P(x)) -> P(zero)",
+        );
+        assert_valid(
+            "
+                datatype t;
+
+                fn x => fn y => fn u => subst u refl
+            ",
+            "\\forall x:t. \\forall y:t. x = y -> y = x",
+        );
+    }
+
+    #[test]
+    fn test_validate_classical() {
+        let (proof_tree, mut ctx) = check_proof(
+            "atom A; atom B; fn u => dne u",
+            "~~A -> A",
+            Logic::Classical,
+        );
+
+        assert_eq!(proof_tree.validate(&ctx), Ok(()));
+
+        ctx.set_logic(Logic::Intuitionistic);
+        let err = proof_tree.validate(&ctx).unwrap_err();
+
+        assert_eq!(err.path, vec![0]);
+        assert_eq!(
+            err.kind,
+            ProofTreeValidationErrorKind::ClassicalReasoningNotAllowed
+        );
+    }
+
+    #[test]
+    fn test_validate_wrong_premisse() {
+        let (mut proof_tree, ctx) = check_proof(
+            "atom A; atom B; fn u => (snd u, fst u)",
+            "A && B -> B && A",
+            Logic::Intuitionistic,
+        );
+
+        // the second component claims to prove B instead of A
+        proof_tree.premisses[0].premisses[1].conclusion =
+            ProofTreeConclusion::PropIsTrue(parse_prop("B"));
+
+        let err = proof_tree.validate(&ctx).unwrap_err();
+        assert_eq!(err.path, vec![0]);
+        assert_eq!(err.kind, ProofTreeValidationErrorKind::PremisseMismatch(1));
+    }
+
+    #[test]
+    fn test_validate_undischarged_hypothesis() {
+        let (mut proof_tree, ctx) = check_proof(
+            "atom A; atom B; fn u => fn v => u",
+            "A -> A -> A",
+            Logic::Intuitionistic,
+        );
+
+        let leaf = &mut proof_tree.premisses[0].premisses[0];
+        leaf.rule = ProofTreeRule::Ident(Identifier::new("w".to_string(), 42));
+
+        let err = proof_tree.validate(&ctx).unwrap_err();
+        assert_eq!(err.path, vec![0, 0]);
+        assert_eq!(
+            err.kind,
+            ProofTreeValidationErrorKind::UnknownIdentifier(Identifier::new("w".to_string(), 42))
+        );
+    }
+
+    #[test]
+    fn test_validate_eigenvariable() {
+        let (mut proof_tree, ctx) = check_proof(
+            "
+                datatype t;
+                atom P(1);
+
+                fn x => fn y => fn u => u
+            ",
+            "\\forall x:t. \\forall y:t. P(x) -> P(x)",
+            Logic::Intuitionistic,
+        );
+
+        // reuse the eigenvariable of the outer quantifier for the inner one
+        let ProofTreeRule::ForAllIntro(ref outer) = proof_tree.rule else {
+            panic!("Expected universal introduction.");
+        };
+        let outer = outer.clone();
+
+        proof_tree.premisses[0].rule = ProofTreeRule::ForAllIntro(outer.clone());
+
+        let err = proof_tree.validate(&ctx).unwrap_err();
+        assert_eq!(err.path, vec![0]);
+        assert_eq!(
+            err.kind,
+            ProofTreeValidationErrorKind::EigenvariableNotFresh(outer)
+        );
+    }
+
+    #[test]
+    fn test_validate_premisse_count() {
+        let proof_tree = ProofTree {
+            premisses: vec![],
+            rule: ProofTreeRule::AndIntro,
+            conclusion: ProofTreeConclusion::PropIsTrue(parse_prop("A && B")),
+        };
+
+        assert_eq!(
+            proof_tree
+                .validate(&IdentifierContext::new())
+                .unwrap_err()
+                .kind,
+            ProofTreeValidationErrorKind::PremisseCountWrong {
+                expected: 2,
+                actual: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_normalize_detours() {
+        assert_normal_form(
+            "atom A; atom B; fn u => fn v => fst (u, v)",
+            "A -> B -> A",
+            "fn u => fn v => u",
+        );
+        assert_normal_form(
+            "atom A; fn u => (fn w: A => (w, w)) u",
+            "A -> A && A",
+            "fn u => (u, u)",
+        );
+        assert_normal_form(
+            "atom A; atom B; fn u => case (inr u: A || A && B) of inl a => a, inr b => fst b",
+            "A && B -> A",
+            "fn u => fst u",
+        );
+    }
+
+    #[test]
+    fn test_normalize_quantifier_detours() {
+        assert_normal_form(
+            "datatype t; atom P(1); fn y: t => (fn x: t => fn q: P(x) => q) y",
+            "\\forall y:t. P(y) -> P(y)",
+            "fn y => fn q => q",
+        );
+        assert_normal_form(
+            "
+                datatype t; atom P(1); function f: t -> t;
+                fn y: t => (fn x: t => fn q: P(x) => q) (f y)
+            ",
+            "\\forall y:t. P(f(y)) -> P(f(y))",
+            "fn y => fn q => q",
+        );
+        assert_normal_form(
+            "
+                datatype t; atom P(1);
+                fn y => fn p => let (z, h) = ((y, p): \\exists x:t. P(x)) in (z, h)
+            ",
+            "\\forall y:t. P(y) -> \\exists x:t. P(x)",
+            "fn y => fn p => (y, p)",
+        );
+    }
+
+    #[test]
+    fn test_normalize_or_elim_permutation() {
+        assert_normal_form(
+            "
+                atom A; atom B; atom C; atom D;
+                fn u => fn f => fn g => fst ((case u of inl a => f a, inr b => g b): C && D)
+            ",
+            "A || B -> (A -> C && D) -> (B -> C && D) -> C",
+            "fn u => fn f => fn g => case u of inl a => fst (f a), inr b => fst (g b)",
+        );
+    }
+
+    #[test]
+    fn test_normalize_exists_elim_permutation() {
+        assert_normal_form(
+            "
+                datatype t; atom A; atom B; atom P(1);
+                fn u => fst ((let (x, h) = u in snd h): A && B)
+            ",
+            "(\\exists x:t. P(x) && (A && B)) -> A",
+            "fn u => let (x, h) = u in fst (snd h)",
+        );
+    }
+
+    #[test]
+    fn test_normalize_falsum_elim_permutation() {
+        assert_normal_form(
+            "atom A; atom B; fn u => fst ((abort u): A && B)",
+            "False -> A",
+            "fn u => abort u",
+        );
+        assert_normal_form(
+            "
+                atom A; atom B; atom C;
+                fn u => fn v => ((case u of inl a => abort (v a), inr b => fn c => b): C -> B) (sorry: C)
+            ",
+            "A || B -> ~A -> B",
+            "fn u => fn v => case u of inl a => abort (v a), inr b => b",
+        );
+    }
+
+    #[test]
+    fn test_normalize_conclusion_not_prop() {
+        let proof_tree = ProofTree {
+            premisses: vec![],
+            rule: ProofTreeRule::Ident(Identifier::new("x".to_string(), 0)),
+            conclusion: ProofTreeConclusion::TypeJudgement(
+                Identifier::new("x".to_string(), 0),
+                "t".to_string(),
+            ),
+        };
+
+        assert_eq!(
+            proof_tree.normalize(&IdentifierContext::new()),
+            Err(ProofTreeNormalizationError::ConclusionNotProp)
+        );
+    }
+}
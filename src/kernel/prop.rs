@@ -13,6 +13,7 @@ use wasm_bindgen::prelude::*;
 #[serde(tag = "kind", content = "value")]
 pub enum PropKind {
     Atom,
+    Eq,
     And,
     Or,
     Impl,
@@ -20,6 +21,7 @@ pub enum PropKind {
     Exists,
     True,
     False,
+    Defined,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Tsify)]
@@ -36,6 +38,7 @@ pub enum QuantifierKind {
 pub enum PropParameter {
     Uninstantiated(String),
     Instantiated(Identifier),
+    Function(String, Vec<PropParameter>),
 }
 
 impl PropParameter {
@@ -43,6 +46,7 @@ impl PropParameter {
         match self {
             Self::Uninstantiated(ident) => ident,
             Self::Instantiated(ident) => ident.name(),
+            Self::Function(name, _) => name,
         }
     }
 
@@ -50,6 +54,7 @@ impl PropParameter {
         match self {
             Self::Uninstantiated(_) => None,
             Self::Instantiated(ident) => Some(ident.unique_id()),
+            Self::Function(_, _) => None,
         }
     }
 
@@ -57,6 +62,7 @@ impl PropParameter {
         match self {
             Self::Uninstantiated(_) => false,
             Self::Instantiated(_) => true,
+            Self::Function(_, _) => false,
         }
     }
 
@@ -64,13 +70,129 @@ impl PropParameter {
         match self {
             Self::Uninstantiated(_) => true,
             Self::Instantiated(_) => false,
+            Self::Function(_, _) => false,
         }
     }
+
+    // Variables occurring in this term, from left to right.
+    pub fn get_variables(&self) -> Vec<&PropParameter> {
+        match self {
+            Self::Uninstantiated(_) | Self::Instantiated(_) => vec![self],
+            Self::Function(_, params) => params.iter().flat_map(Self::get_variables).collect(),
+        }
+    }
+
+    pub fn get_variables_mut(&mut self) -> Vec<&mut PropParameter> {
+        match self {
+            Self::Uninstantiated(_) | Self::Instantiated(_) => vec![self],
+            Self::Function(_, params) => params
+                .iter_mut()
+                .flat_map(Self::get_variables_mut)
+                .collect(),
+        }
+    }
+
+    // Replaces every occurrence of substituent (which can be a term itself) with substitutor.
+    pub fn replace(&mut self, substituent: &PropParameter, substitutor: &PropParameter) {
+        if self == substituent {
+            *self = substitutor.clone();
+            return;
+        }
+
+        if let Self::Function(_, params) = self {
+            params
+                .iter_mut()
+                .for_each(|param| param.replace(substituent, substitutor));
+        }
+    }
+}
+
+impl Display for PropParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Uninstantiated(_) | Self::Instantiated(_) => write!(f, "{}", self.name()),
+            Self::Function(name, params) if params.is_empty() => write!(f, "{}", name),
+            Self::Function(name, params) => write!(f, "{}({})", name, params.iter().join(", ")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct FunctionSignature {
+    pub name: String,
+    pub param_types: Vec<String>,
+    pub return_type: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct AtomSignature {
+    pub name: String,
+    pub param_types: Vec<String>,
+}
+
+// Parametric abbreviation of a proposition, e.g. "def Iff(X, Y) := (X -> Y) & (Y -> X);".
+// Parameters are propositional variables, which occur as atoms without parameters in the body.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct PropDefinition {
+    pub name: String,
+    pub params: Vec<String>,
+    pub body: Prop,
+}
+
+impl PropDefinition {
+    // Definitions that are available without being declared.
+    pub fn builtins() -> Vec<PropDefinition> {
+        let var = |name: &str| Prop::Atom(name.to_string(), vec![]);
+
+        vec![PropDefinition {
+            name: "Iff".to_string(),
+            params: vec!["X".to_string(), "Y".to_string()],
+            body: Prop::And(
+                Prop::Impl(var("X").boxed(), var("Y").boxed()).boxed(),
+                Prop::Impl(var("Y").boxed(), var("X").boxed()).boxed(),
+            ),
+        }]
+    }
+
+    pub fn instantiate(&self, args: &[Prop]) -> Prop {
+        let mut body = self.body.clone();
+
+        // quantifiers of the body must not capture variables of the arguments
+        body.rename_bound_identifiers(&|name| args.iter().any(|arg| arg.contains_name(name)));
+        body.substitute_prop_variables(&self.params, args);
+
+        body
+    }
+}
+
+// Later definitions shadow earlier ones.
+pub fn find_definition<'a>(
+    definitions: &'a [PropDefinition],
+    name: &String,
+) -> Option<&'a PropDefinition> {
+    definitions
+        .iter()
+        .rev()
+        .find(|definition| definition.name == *name)
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum InstatiationError {
     UnknownIdentifier(String),
+    ArgumentDatatypeWrong {
+        ident: String,
+        argument: PropParameter,
+        expected: String,
+        actual: String,
+    },
+    DefinitionArityWrong {
+        ident: String,
+        expected: usize,
+        actual: usize,
+    },
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
@@ -78,10 +200,14 @@ pub enum InstatiationError {
 #[serde(tag = "kind", content = "value")]
 pub enum Prop {
     Atom(String, Vec<PropParameter>),
+    Eq(PropParameter, PropParameter),
     And(Box<Prop>, Box<Prop>),
     Or(Box<Prop>, Box<Prop>),
     Impl(Box<Prop>, Box<Prop>),
 
+    // Application of a definition, kept folded until it has to be unfolded
+    Defined(String, Vec<Prop>),
+
     ForAll {
         object_ident: String,
         object_type_ident: String,
@@ -105,6 +231,7 @@ impl Prop {
     pub fn precedence(&self) -> usize {
         match self {
             Prop::Atom(_, _) => 999,
+            Prop::Eq(_, _) => 999,
             Prop::True => 999,
             Prop::False => 999,
             Prop::And(_, _) => 4,
@@ -118,12 +245,20 @@ impl Prop {
             }
             Prop::ForAll { .. } => 1,
             Prop::Exists { .. } => 1,
+            Prop::Defined(_, _) if self.is_iff() => 0,
+            Prop::Defined(_, _) => 999,
         }
     }
 
+    // Iff is displayed as the biconditional connective
+    pub fn is_iff(&self) -> bool {
+        matches!(self, Prop::Defined(name, args) if name == "Iff" && args.len() == 2)
+    }
+
     pub fn left_associative(&self) -> bool {
         match self {
             Prop::Atom(_, _) => false,
+            Prop::Eq(_, _) => false,
             Prop::True => false,
             Prop::False => false,
             Prop::And(_, _) => true,
@@ -131,12 +266,14 @@ impl Prop {
             Prop::Impl(_, _) => false,
             Prop::ForAll { .. } => false,
             Prop::Exists { .. } => false,
+            Prop::Defined(_, _) => false,
         }
     }
 
     pub fn right_associative(&self) -> bool {
         match self {
             Prop::Atom(_, _) => false,
+            Prop::Eq(_, _) => false,
             Prop::True => false,
             Prop::False => false,
             Prop::And(_, _) => false,
@@ -144,6 +281,7 @@ impl Prop {
             Prop::Impl(_, _) => true,
             Prop::ForAll { .. } => false,
             Prop::Exists { .. } => false,
+            Prop::Defined(_, _) => false,
         }
     }
 
@@ -151,17 +289,44 @@ impl Prop {
         match self {
             Prop::True => vec![],
             Prop::False => vec![],
+            Prop::Eq(_, _) => vec![],
             Prop::Atom(atom, params) => vec![(atom.clone(), params.len())],
             Prop::ForAll { body, .. } | Prop::Exists { body, .. } => body.get_atoms(),
             Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
                 [fst.get_atoms(), snd.get_atoms()].concat()
             }
+            Prop::Defined(_, args) => args.iter().flat_map(Prop::get_atoms).collect(),
+        }
+    }
+
+    pub fn get_functions(&self) -> Vec<(String, usize)> {
+        fn _get_functions(param: &PropParameter) -> Vec<(String, usize)> {
+            match param {
+                PropParameter::Function(name, params) => [
+                    vec![(name.clone(), params.len())],
+                    params.iter().flat_map(_get_functions).collect(),
+                ]
+                .concat(),
+                _ => vec![],
+            }
+        }
+
+        match self {
+            Prop::True => vec![],
+            Prop::False => vec![],
+            Prop::Atom(_, params) => params.iter().flat_map(_get_functions).collect(),
+            Prop::Eq(lhs, rhs) => [_get_functions(lhs), _get_functions(rhs)].concat(),
+            Prop::ForAll { body, .. } | Prop::Exists { body, .. } => body.get_functions(),
+            Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+                [fst.get_functions(), snd.get_functions()].concat()
+            }
+            Prop::Defined(_, args) => args.iter().flat_map(Prop::get_functions).collect(),
         }
     }
 
     pub fn get_datatypes(&self) -> Vec<String> {
         match self {
-            Prop::True | Prop::False | Prop::Atom(_, _) => vec![],
+            Prop::True | Prop::False | Prop::Atom(_, _) | Prop::Eq(_, _) => vec![],
             Prop::ForAll {
                 object_type_ident,
                 body,
@@ -175,12 +340,14 @@ impl Prop {
             Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
                 [fst.get_datatypes(), snd.get_datatypes()].concat()
             }
+            Prop::Defined(_, args) => args.iter().flat_map(Prop::get_datatypes).collect(),
         }
     }
 
     pub fn has_quantifiers(&self) -> bool {
         match self {
             Prop::Atom(_, _) => false,
+            Prop::Eq(_, _) => false,
             Prop::True => false,
             Prop::False => false,
             Prop::And(fst, snd) => fst.has_quantifiers() || snd.has_quantifiers(),
@@ -188,6 +355,30 @@ impl Prop {
             Prop::Impl(fst, snd) => fst.has_quantifiers() || snd.has_quantifiers(),
             Prop::ForAll { .. } => true,
             Prop::Exists { .. } => true,
+            Prop::Defined(_, args) => args.iter().any(Prop::has_quantifiers),
+        }
+    }
+
+    pub fn has_equalities(&self) -> bool {
+        match self {
+            Prop::Atom(_, _) | Prop::True | Prop::False => false,
+            Prop::Eq(_, _) => true,
+            Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+                fst.has_equalities() || snd.has_equalities()
+            }
+            Prop::ForAll { body, .. } | Prop::Exists { body, .. } => body.has_equalities(),
+            Prop::Defined(_, args) => args.iter().any(Prop::has_equalities),
+        }
+    }
+
+    pub fn has_definitions(&self) -> bool {
+        match self {
+            Prop::Atom(_, _) | Prop::Eq(_, _) | Prop::True | Prop::False => false,
+            Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+                fst.has_definitions() || snd.has_definitions()
+            }
+            Prop::ForAll { body, .. } | Prop::Exists { body, .. } => body.has_definitions(),
+            Prop::Defined(_, _) => true,
         }
     }
 
@@ -218,6 +409,10 @@ impl Prop {
 
                     [fst_idents, snd_idents].concat()
                 }
+                Prop::Defined(_, args) => args
+                    .iter()
+                    .flat_map(|arg| _get_free_parameters(arg, &mut bound_idents.clone()))
+                    .collect(),
                 Prop::Exists {
                     object_ident, body, ..
                 } => {
@@ -233,7 +428,21 @@ impl Prop {
                     _get_free_parameters(body, bound_idents)
                 }
                 Prop::Atom(_, params) => {
-                    let mut free_params = params.clone();
+                    let mut free_params = params
+                        .iter()
+                        .flat_map(PropParameter::get_variables)
+                        .cloned()
+                        .collect_vec();
+                    free_params.retain(|param| !bound_idents.contains(param.name()));
+
+                    free_params
+                }
+                Prop::Eq(lhs, rhs) => {
+                    let mut free_params = [lhs, rhs]
+                        .into_iter()
+                        .flat_map(PropParameter::get_variables)
+                        .cloned()
+                        .collect_vec();
                     free_params.retain(|param| !bound_idents.contains(param.name()));
 
                     free_params
@@ -275,6 +484,10 @@ impl Prop {
                     fst_idents.append(&mut snd_idents);
                     fst_idents
                 }
+                Prop::Defined(_, args) => args
+                    .iter_mut()
+                    .flat_map(|arg| _get_free_parameters(arg, &mut bound_idents.clone()))
+                    .collect(),
                 Prop::Exists {
                     object_ident, body, ..
                 } => {
@@ -290,7 +503,19 @@ impl Prop {
                     _get_free_parameters(body, bound_idents)
                 }
                 Prop::Atom(_, ref mut params) => {
-                    let mut free_params = params.iter_mut().collect::<Vec<&'a mut PropParameter>>();
+                    let mut free_params = params
+                        .iter_mut()
+                        .flat_map(PropParameter::get_variables_mut)
+                        .collect::<Vec<&'a mut PropParameter>>();
+                    free_params.retain(|param| !bound_idents.contains(param.name()));
+
+                    free_params
+                }
+                Prop::Eq(ref mut lhs, ref mut rhs) => {
+                    let mut free_params = [lhs, rhs]
+                        .into_iter()
+                        .flat_map(PropParameter::get_variables_mut)
+                        .collect::<Vec<&'a mut PropParameter>>();
                     free_params.retain(|param| !bound_idents.contains(param.name()));
 
                     free_params
@@ -303,48 +528,144 @@ impl Prop {
         _get_free_parameters(self, &mut bound_idents)
     }
 
-    // This can only replace with single identifiers, but that should be ok at the current state of the type checker.
     // Substituent: The identifier that gets replaced. (Uninstantiated)
     // Substitutor: The identifier that will be replaced with.
     pub fn instantiate_free_parameter(&mut self, substituent: &String, substitutor: &Identifier) {
+        self.substitute_free_parameter(
+            substituent,
+            &PropParameter::Instantiated(substitutor.clone()),
+        );
+    }
+
+    // Capture-avoiding substitution of a term for a free (uninstantiated) parameter.
+    // Quantifiers that would capture a variable of the substitutor get renamed.
+    pub fn substitute_free_parameter(&mut self, substituent: &String, substitutor: &PropParameter) {
         match self {
             Prop::True => (),
             Prop::False => (),
-            Prop::And(ref mut fst, ref mut snd) => {
-                Prop::instantiate_free_parameter(fst, substituent, substitutor);
-                Prop::instantiate_free_parameter(snd, substituent, substitutor);
-            }
-            Prop::Or(ref mut fst, ref mut snd) => {
-                Prop::instantiate_free_parameter(fst, substituent, substitutor);
-                Prop::instantiate_free_parameter(snd, substituent, substitutor);
-            }
-            Prop::Impl(ref mut fst, ref mut snd) => {
-                Prop::instantiate_free_parameter(fst, substituent, substitutor);
-                Prop::instantiate_free_parameter(snd, substituent, substitutor);
+            Prop::And(ref mut fst, ref mut snd)
+            | Prop::Or(ref mut fst, ref mut snd)
+            | Prop::Impl(ref mut fst, ref mut snd) => {
+                Prop::substitute_free_parameter(fst, substituent, substitutor);
+                Prop::substitute_free_parameter(snd, substituent, substitutor);
             }
+            Prop::Defined(_, args) => args
+                .iter_mut()
+                .for_each(|arg| arg.substitute_free_parameter(substituent, substitutor)),
             Prop::Exists {
                 object_ident,
                 ref mut body,
                 ..
-            } => {
-                if object_ident != substituent {
-                    Prop::instantiate_free_parameter(body, substituent, substitutor);
-                }
             }
-            Prop::ForAll {
+            | Prop::ForAll {
                 object_ident,
                 ref mut body,
                 ..
             } => {
-                if object_ident != substituent {
-                    Prop::instantiate_free_parameter(body, substituent, substitutor);
+                if object_ident == substituent {
+                    return;
+                }
+
+                let substitutor_variables = substitutor.get_variables();
+                let captures = |name: &String| {
+                    substitutor_variables
+                        .iter()
+                        .any(|variable| variable.is_uninstantiated() && variable.name() == name)
+                };
+
+                if captures(object_ident) {
+                    let mut fresh_ident = object_ident.clone();
+                    while captures(&fresh_ident) || body.contains_name(&fresh_ident) {
+                        fresh_ident.push('\'');
+                    }
+
+                    body.replace_free_parameter(
+                        &PropParameter::Uninstantiated(object_ident.clone()),
+                        &PropParameter::Uninstantiated(fresh_ident.clone()),
+                    );
+                    *object_ident = fresh_ident;
                 }
+
+                Prop::substitute_free_parameter(body, substituent, substitutor);
             }
             Prop::Atom(_, params) => params.iter_mut().for_each(|param| {
-                if param.is_uninstantiated() && *param.name() == *substituent {
-                    *param = PropParameter::Instantiated(substitutor.clone())
-                }
+                param.replace(
+                    &PropParameter::Uninstantiated(substituent.clone()),
+                    substitutor,
+                )
             }),
+            Prop::Eq(lhs, rhs) => [lhs, rhs].into_iter().for_each(|param| {
+                param.replace(
+                    &PropParameter::Uninstantiated(substituent.clone()),
+                    substitutor,
+                )
+            }),
+        }
+    }
+
+    pub(crate) fn contains_name(&self, name: &String) -> bool {
+        match self {
+            Prop::True | Prop::False => false,
+            Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+                fst.contains_name(name) || snd.contains_name(name)
+            }
+            Prop::Defined(_, args) => args.iter().any(|arg| arg.contains_name(name)),
+            Prop::Exists {
+                object_ident, body, ..
+            }
+            | Prop::ForAll {
+                object_ident, body, ..
+            } => object_ident == name || body.contains_name(name),
+            Prop::Atom(_, params) => params
+                .iter()
+                .flat_map(PropParameter::get_variables)
+                .any(|param| param.name() == name),
+            Prop::Eq(lhs, rhs) => [lhs, rhs]
+                .into_iter()
+                .flat_map(PropParameter::get_variables)
+                .any(|param| param.name() == name),
+        }
+    }
+
+    // Replaces every free occurrence of substituent with substitutor.
+    // The substitutor should be instantiated, otherwise it might get captured by an inner quantifier.
+    pub fn replace_free_parameter(
+        &mut self,
+        substituent: &PropParameter,
+        substitutor: &PropParameter,
+    ) {
+        match self {
+            Prop::True => (),
+            Prop::False => (),
+            Prop::And(ref mut fst, ref mut snd)
+            | Prop::Or(ref mut fst, ref mut snd)
+            | Prop::Impl(ref mut fst, ref mut snd) => {
+                Prop::replace_free_parameter(fst, substituent, substitutor);
+                Prop::replace_free_parameter(snd, substituent, substitutor);
+            }
+            Prop::Defined(_, args) => args
+                .iter_mut()
+                .for_each(|arg| arg.replace_free_parameter(substituent, substitutor)),
+            Prop::Exists {
+                object_ident,
+                ref mut body,
+                ..
+            }
+            | Prop::ForAll {
+                object_ident,
+                ref mut body,
+                ..
+            } => {
+                if *substituent != PropParameter::Uninstantiated(object_ident.clone()) {
+                    Prop::replace_free_parameter(body, substituent, substitutor);
+                }
+            }
+            Prop::Atom(_, params) => params
+                .iter_mut()
+                .for_each(|param| param.replace(substituent, substitutor)),
+            Prop::Eq(lhs, rhs) => [lhs, rhs]
+                .into_iter()
+                .for_each(|param| param.replace(substituent, substitutor)),
         }
     }
 
@@ -374,6 +695,21 @@ impl Prop {
 
                     _instantiate(snd, index, substitutor, current_index, bound_identifiers);
                 }
+                Prop::Defined(_, args) => {
+                    for arg in args.iter_mut() {
+                        _instantiate(
+                            arg,
+                            index,
+                            substitutor,
+                            current_index,
+                            &mut bound_identifiers.clone(),
+                        );
+
+                        if *current_index > index {
+                            return;
+                        }
+                    }
+                }
                 Prop::ForAll {
                     object_ident,
                     ref mut body,
@@ -389,20 +725,48 @@ impl Prop {
                     _instantiate(body, index, substitutor, current_index, bound_identifiers);
                 }
                 Prop::Atom(_, params) => {
-                    let param_limit = index - *current_index + 1;
-                    if params.len() < param_limit {
-                        *current_index += params.len();
-                        return;
-                    }
-
-                    let Some(param) = params.get_mut(index - *current_index) else {
-                        panic!("Invalid index.");
-                    };
-
-                    *param = PropParameter::Instantiated(substitutor.clone());
-                    *current_index = index;
+                    _instantiate_params(
+                        params
+                            .iter_mut()
+                            .flat_map(PropParameter::get_variables_mut)
+                            .collect(),
+                        index,
+                        substitutor,
+                        current_index,
+                    );
                 }
+                Prop::Eq(lhs, rhs) => {
+                    _instantiate_params(
+                        [lhs, rhs]
+                            .into_iter()
+                            .flat_map(PropParameter::get_variables_mut)
+                            .collect(),
+                        index,
+                        substitutor,
+                        current_index,
+                    );
+                }
+            }
+        }
+
+        fn _instantiate_params(
+            mut params: Vec<&mut PropParameter>,
+            index: usize,
+            substitutor: &Identifier,
+            current_index: &mut usize,
+        ) {
+            let param_limit = index - *current_index + 1;
+            if params.len() < param_limit {
+                *current_index += params.len();
+                return;
             }
+
+            let Some(param) = params.get_mut(index - *current_index) else {
+                panic!("Invalid index.");
+            };
+
+            **param = PropParameter::Instantiated(substitutor.clone());
+            *current_index = index;
         }
 
         let mut current_index = 0;
@@ -495,6 +859,21 @@ impl Prop {
                     )
                     .boxed(),
                 ),
+                Prop::Defined(name, args) => Prop::Defined(
+                    name.clone(),
+                    args.iter()
+                        .map(|arg| {
+                            _bind_identifier(
+                                arg,
+                                identifier,
+                                identifier_indices,
+                                bind_name,
+                                &mut bound_identifiers.clone(),
+                                current_index,
+                            )
+                        })
+                        .collect(),
+                ),
                 Prop::Exists {
                     object_ident,
                     object_type_ident,
@@ -539,25 +918,78 @@ impl Prop {
                     let new_params = params
                         .iter()
                         .map(|param| {
-                            if param.is_instantiated()
-                                && param.name() == identifier.name()
-                                && param.unique_id().unwrap() == identifier.unique_id()
-                            {
-                                if identifier_indices.is_none() || identifier_indices.unwrap().contains(&current_index) {
-                                    *current_index += 1;
-                                    return PropParameter::Uninstantiated(bind_name.to_string());
-                                }
-
-                                *current_index += 1;
-                            }
-
-                            param.clone()
+                            _bind_param(
+                                param,
+                                identifier,
+                                identifier_indices,
+                                bind_name,
+                                current_index,
+                            )
                         })
                         .collect();
 
                     Prop::Atom(atom_ident.clone(), new_params)
                 }
+                Prop::Eq(lhs, rhs) => {
+                    let new_lhs = _bind_param(
+                        lhs,
+                        identifier,
+                        identifier_indices,
+                        bind_name,
+                        current_index,
+                    );
+                    let new_rhs = _bind_param(
+                        rhs,
+                        identifier,
+                        identifier_indices,
+                        bind_name,
+                        current_index,
+                    );
+
+                    Prop::Eq(new_lhs, new_rhs)
+                }
+            }
+        }
+
+        fn _bind_param(
+            param: &PropParameter,
+            identifier: &Identifier,
+            identifier_indices: Option<&Vec<usize>>,
+            bind_name: &str,
+            current_index: &mut usize,
+        ) -> PropParameter {
+            if let PropParameter::Function(name, params) = param {
+                let new_params = params
+                    .iter()
+                    .map(|param| {
+                        _bind_param(
+                            param,
+                            identifier,
+                            identifier_indices,
+                            bind_name,
+                            current_index,
+                        )
+                    })
+                    .collect();
+
+                return PropParameter::Function(name.clone(), new_params);
             }
+
+            if param.is_instantiated()
+                && param.name() == identifier.name()
+                && param.unique_id().unwrap() == identifier.unique_id()
+            {
+                if identifier_indices.is_none()
+                    || identifier_indices.unwrap().contains(current_index)
+                {
+                    *current_index += 1;
+                    return PropParameter::Uninstantiated(bind_name.to_string());
+                }
+
+                *current_index += 1;
+            }
+
+            param.clone()
         }
 
         let bound_body = _bind_identifier(
@@ -590,7 +1022,7 @@ impl Prop {
         fn _instantiate_with_ctx<'a>(
             prop: &'a mut Prop,
             ctx: &IdentifierContext,
-            mut bound_idents: Vec<&'a str>,
+            mut bound_idents: Vec<(&'a str, &'a str)>,
         ) -> Result<(), InstatiationError> {
             match prop {
                 Prop::True => {}
@@ -603,59 +1035,182 @@ impl Prop {
                 }
                 Prop::ForAll {
                     ref object_ident,
+                    ref object_type_ident,
                     body,
-                    ..
                 }
                 | Prop::Exists {
                     ref object_ident,
+                    ref object_type_ident,
                     body,
-                    ..
                 } => {
-                    bound_idents.push(object_ident);
+                    bound_idents.push((object_ident, object_type_ident));
 
                     _instantiate_with_ctx(body, ctx, bound_idents)?;
                 }
-                Prop::Atom(_, params) => {
-                    for param in params.iter_mut() {
-                        // sanity check
-                        if let PropParameter::Instantiated(identifier) = param {
-                            if ctx.get(identifier).is_none() {
-                                panic!("Instantiated parameter does not exist: {:#?}", identifier);
-                            }
-
-                            if let Some(Type::Prop(prop)) = ctx.get(&identifier) {
-                                panic!(
-                                    "Parameter is a proposition: {:#?}, {:#?}",
-                                    identifier, prop
-                                );
-                            }
-
-                            continue;
+                Prop::Defined(_, args) => {
+                    for arg in args.iter_mut() {
+                        _instantiate_with_ctx(arg, ctx, bound_idents.clone())?;
+                    }
+                }
+                Prop::Atom(name, params) => {
+                    let signature = ctx.get_atom(name);
+
+                    for (i, param) in params.iter_mut().enumerate() {
+                        let datatype = _instantiate_param_with_ctx(param, ctx, &bound_idents)?;
+
+                        let expected = signature.and_then(|signature| signature.param_types.get(i));
+
+                        if let (Some(expected), Some(actual)) = (expected, datatype) {
+                            _check_argument_datatype(name, param, expected, actual)?;
                         }
+                    }
+                }
+                Prop::Eq(lhs, rhs) => {
+                    let lhs_datatype = _instantiate_param_with_ctx(lhs, ctx, &bound_idents)?;
+                    let rhs_datatype = _instantiate_param_with_ctx(rhs, ctx, &bound_idents)?;
 
-                        let PropParameter::Uninstantiated(name) = param else {
-                            continue;
-                        };
+                    // both sides of an equality have to be of the same datatype
+                    if let (Some(expected), Some(actual)) = (lhs_datatype, rhs_datatype) {
+                        _check_argument_datatype("=", rhs, &expected, actual)?;
+                    }
+                }
+            };
+
+            Ok(())
+        }
 
-                        if bound_idents.contains(&name.as_str()) {
-                            continue;
+        fn _check_argument_datatype(
+            ident: &str,
+            argument: &PropParameter,
+            expected: &String,
+            actual: String,
+        ) -> Result<(), InstatiationError> {
+            if *expected != actual {
+                return Err(InstatiationError::ArgumentDatatypeWrong {
+                    ident: ident.to_string(),
+                    argument: argument.clone(),
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+
+            Ok(())
+        }
+
+        // returns the datatype of the parameter, if it is known
+        fn _instantiate_param_with_ctx(
+            param: &mut PropParameter,
+            ctx: &IdentifierContext,
+            bound_idents: &[(&str, &str)],
+        ) -> Result<Option<String>, InstatiationError> {
+            match param {
+                PropParameter::Instantiated(identifier) => {
+                    // sanity check
+                    match ctx.get(identifier) {
+                        None => panic!("Instantiated parameter does not exist: {:#?}", identifier),
+                        Some(Type::Prop(prop)) => {
+                            panic!("Parameter is a proposition: {:#?}, {:#?}", identifier, prop)
                         }
+                        Some(Type::Datatype(datatype)) => Ok(Some(datatype.clone())),
+                    }
+                }
+                PropParameter::Uninstantiated(name) => {
+                    if let Some((_, datatype)) = bound_idents
+                        .iter()
+                        .rev()
+                        .find(|(ident, _)| *ident == name.as_str())
+                    {
+                        return Ok(Some(datatype.to_string()));
+                    }
 
-                        let Some((identifier, _)) = ctx.get_by_name(&name) else {
-                            return Err(InstatiationError::UnknownIdentifier(name.clone()));
+                    if let Some((identifier, identifier_type)) = ctx.get_by_name(name) {
+                        let datatype = match identifier_type {
+                            Type::Datatype(datatype) => Some(datatype.clone()),
+                            Type::Prop(_) => None,
                         };
 
                         *param = PropParameter::Instantiated(identifier.clone());
+                        return Ok(datatype);
+                    }
+
+                    // constants are nullary function symbols
+                    match ctx.get_function(name) {
+                        Some(signature) if signature.param_types.is_empty() => {
+                            *param = PropParameter::Function(name.clone(), vec![]);
+                            Ok(Some(signature.return_type.clone()))
+                        }
+                        _ => Err(InstatiationError::UnknownIdentifier(name.clone())),
                     }
                 }
-            };
+                PropParameter::Function(name, params) => {
+                    let Some(signature) = ctx.get_function(name) else {
+                        return Err(InstatiationError::UnknownIdentifier(name.clone()));
+                    };
 
-            Ok(())
+                    for (i, param) in params.iter_mut().enumerate() {
+                        let datatype = _instantiate_param_with_ctx(param, ctx, bound_idents)?;
+
+                        if let (Some(expected), Some(actual)) =
+                            (signature.param_types.get(i), datatype)
+                        {
+                            _check_argument_datatype(name, param, expected, actual)?;
+                        }
+                    }
+
+                    Ok(Some(signature.return_type.clone()))
+                }
+            }
         }
 
+        self.resolve_definitions(ctx.definitions())?;
+
         _instantiate_with_ctx(self, ctx, vec![])
     }
 
+    // Turns atoms that name a definition into applications of it and checks their arity.
+    pub fn resolve_definitions(
+        &mut self,
+        definitions: &[PropDefinition],
+    ) -> Result<(), InstatiationError> {
+        match self {
+            Prop::True | Prop::False | Prop::Eq(_, _) => {}
+            Prop::Atom(name, params) => {
+                if find_definition(definitions, name).is_some() {
+                    let args = params.iter().map(Prop::from_parameter).collect();
+                    *self = Prop::Defined(name.clone(), args);
+
+                    return self.resolve_definitions(definitions);
+                }
+            }
+            Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+                fst.resolve_definitions(definitions)?;
+                snd.resolve_definitions(definitions)?;
+            }
+            Prop::ForAll { body, .. } | Prop::Exists { body, .. } => {
+                body.resolve_definitions(definitions)?;
+            }
+            Prop::Defined(name, args) => {
+                let Some(definition) = find_definition(definitions, name) else {
+                    return Err(InstatiationError::UnknownIdentifier(name.clone()));
+                };
+
+                if definition.params.len() != args.len() {
+                    return Err(InstatiationError::DefinitionArityWrong {
+                        ident: name.clone(),
+                        expected: definition.params.len(),
+                        actual: args.len(),
+                    });
+                }
+
+                for arg in args.iter_mut() {
+                    arg.resolve_definitions(definitions)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn alpha_eq(&self, other: &Prop) -> bool {
         let env = vec![];
 
@@ -721,35 +1276,202 @@ impl Prop {
                     );
                 }
 
-                for (l_param, r_param) in Iterator::zip(l_params.iter(), r_params.iter()) {
-                    if let (
-                        PropParameter::Uninstantiated(l_param_name),
-                        PropParameter::Uninstantiated(r_param_name),
-                    ) = (l_param, r_param)
-                    {
-                        // search for uninstantiated identifiers
-                        let pair = env
-                            .iter()
-                            .rev()
-                            .find(|(x, y)| *x == l_param_name || *y == r_param_name);
-
-                        if let Some((x, y)) = pair {
-                            if *x != l_param_name || *y != r_param_name {
-                                return false;
-                            }
-                        } else {
-                            panic!("Found uninstantiated parameter that is not bound by a quantor. left: {:#?}, right: {:#?}", left, right);
-                        }
-                    } else if l_param != r_param {
-                        return false;
+                Iterator::zip(l_params.iter(), r_params.iter())
+                    .all(|(l_param, r_param)| Self::_alpha_eq_param(l_param, r_param, &env))
+            }
+            (Prop::Eq(l_lhs, l_rhs), Prop::Eq(r_lhs, r_rhs)) => {
+                Self::_alpha_eq_param(l_lhs, r_lhs, &env)
+                    && Self::_alpha_eq_param(l_rhs, r_rhs, &env)
+            }
+            (Prop::Defined(l_name, l_args), Prop::Defined(r_name, r_args)) => {
+                l_name == r_name
+                    && l_args.len() == r_args.len()
+                    && Iterator::zip(l_args.iter(), r_args.iter())
+                        .all(|(l_arg, r_arg)| Self::_alpha_eq(l_arg, r_arg, env.clone()))
+            }
+            _ => false,
+        }
+    }
+
+    // Alpha equivalence modulo unfolding of definitions (delta reduction).
+    pub fn alpha_eq_modulo_definitions(
+        &self,
+        other: &Prop,
+        definitions: &[PropDefinition],
+    ) -> bool {
+        self.alpha_eq(other)
+            || Prop::alpha_eq(
+                &self.unfold_definitions(definitions),
+                &other.unfold_definitions(definitions),
+            )
+    }
+
+    // Unfolds definitions until the outermost connective is not a definition.
+    // Definitions must not be recursive, which holds as their bodies only refer to earlier definitions.
+    pub fn unfold_head(&self, definitions: &[PropDefinition]) -> Prop {
+        let mut prop = self.clone();
+
+        while let Some(unfolded) = prop.unfold_once(definitions) {
+            prop = unfolded;
+        }
+
+        prop
+    }
+
+    pub fn unfold_definitions(&self, definitions: &[PropDefinition]) -> Prop {
+        match self.unfold_head(definitions) {
+            Prop::And(fst, snd) => Prop::And(
+                fst.unfold_definitions(definitions).boxed(),
+                snd.unfold_definitions(definitions).boxed(),
+            ),
+            Prop::Or(fst, snd) => Prop::Or(
+                fst.unfold_definitions(definitions).boxed(),
+                snd.unfold_definitions(definitions).boxed(),
+            ),
+            Prop::Impl(fst, snd) => Prop::Impl(
+                fst.unfold_definitions(definitions).boxed(),
+                snd.unfold_definitions(definitions).boxed(),
+            ),
+            Prop::ForAll {
+                object_ident,
+                object_type_ident,
+                body,
+            } => Prop::ForAll {
+                object_ident,
+                object_type_ident,
+                body: body.unfold_definitions(definitions).boxed(),
+            },
+            Prop::Exists {
+                object_ident,
+                object_type_ident,
+                body,
+            } => Prop::Exists {
+                object_ident,
+                object_type_ident,
+                body: body.unfold_definitions(definitions).boxed(),
+            },
+            prop => prop,
+        }
+    }
+
+    // Atoms that name a definition are unfolded as well, as props parsed without context
+    // do not distinguish them from applications of definitions.
+    fn unfold_once(&self, definitions: &[PropDefinition]) -> Option<Prop> {
+        match self {
+            Prop::Defined(name, args) => find_definition(definitions, name)
+                .filter(|definition| definition.params.len() == args.len())
+                .map(|definition| definition.instantiate(args)),
+            Prop::Atom(name, params) => find_definition(definitions, name)
+                .filter(|definition| definition.params.len() == params.len())
+                .map(|definition| {
+                    definition.instantiate(&params.iter().map(Prop::from_parameter).collect_vec())
+                }),
+            _ => None,
+        }
+    }
+
+    // Arguments of definitions are parsed as terms if possible, e.g. "Iff(A, P(x))".
+    fn from_parameter(param: &PropParameter) -> Prop {
+        match param {
+            PropParameter::Function(name, params) => Prop::Atom(name.clone(), params.clone()),
+            _ => Prop::Atom(param.name().clone(), vec![]),
+        }
+    }
+
+    fn substitute_prop_variables(&mut self, variables: &[String], props: &[Prop]) {
+        match self {
+            Prop::Atom(name, params) if params.is_empty() => {
+                if let Some(idx) = variables.iter().position(|variable| variable == name) {
+                    *self = props[idx].clone();
+                }
+            }
+            Prop::True | Prop::False | Prop::Atom(_, _) | Prop::Eq(_, _) => (),
+            Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+                fst.substitute_prop_variables(variables, props);
+                snd.substitute_prop_variables(variables, props);
+            }
+            Prop::ForAll { body, .. } | Prop::Exists { body, .. } => {
+                body.substitute_prop_variables(variables, props)
+            }
+            Prop::Defined(_, args) => args
+                .iter_mut()
+                .for_each(|arg| arg.substitute_prop_variables(variables, props)),
+        }
+    }
+
+    fn rename_bound_identifiers(&mut self, captures: &dyn Fn(&String) -> bool) {
+        match self {
+            Prop::True | Prop::False | Prop::Atom(_, _) | Prop::Eq(_, _) => (),
+            Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+                fst.rename_bound_identifiers(captures);
+                snd.rename_bound_identifiers(captures);
+            }
+            Prop::ForAll {
+                object_ident, body, ..
+            }
+            | Prop::Exists {
+                object_ident, body, ..
+            } => {
+                if captures(object_ident) {
+                    let mut fresh_ident = object_ident.clone();
+                    while captures(&fresh_ident) || body.contains_name(&fresh_ident) {
+                        fresh_ident.push('\'');
                     }
+
+                    body.replace_free_parameter(
+                        &PropParameter::Uninstantiated(object_ident.clone()),
+                        &PropParameter::Uninstantiated(fresh_ident.clone()),
+                    );
+                    *object_ident = fresh_ident;
                 }
 
-                true
+                body.rename_bound_identifiers(captures);
             }
-            _ => false,
+            Prop::Defined(_, args) => args
+                .iter_mut()
+                .for_each(|arg| arg.rename_bound_identifiers(captures)),
         }
     }
+
+    fn _alpha_eq_param(
+        l_param: &PropParameter,
+        r_param: &PropParameter,
+        env: &[(&String, &String)],
+    ) -> bool {
+        if let (
+            PropParameter::Uninstantiated(l_param_name),
+            PropParameter::Uninstantiated(r_param_name),
+        ) = (l_param, r_param)
+        {
+            // search for uninstantiated identifiers
+            let pair = env
+                .iter()
+                .rev()
+                .find(|(x, y)| *x == l_param_name || *y == r_param_name);
+
+            if let Some((x, y)) = pair {
+                return *x == l_param_name && *y == r_param_name;
+            }
+
+            panic!(
+                "Found uninstantiated parameter that is not bound by a quantor. left: {:#?}, right: {:#?}",
+                l_param, r_param
+            );
+        }
+
+        if let (
+            PropParameter::Function(l_name, l_params),
+            PropParameter::Function(r_name, r_params),
+        ) = (l_param, r_param)
+        {
+            return l_name == r_name
+                && l_params.len() == r_params.len()
+                && Iterator::zip(l_params.iter(), r_params.iter())
+                    .all(|(l_param, r_param)| Self::_alpha_eq_param(l_param, r_param, env));
+        }
+
+        l_param == r_param
+    }
 }
 
 impl From<Prop> for Type {
@@ -765,11 +1487,23 @@ impl Display for Prop {
                 return write!(f, "{}", name);
             }
 
-            let param_list = params.iter().map(PropParameter::name).join(", ");
+            let param_list = params.iter().join(", ");
 
             return write!(f, "{}({})", name, param_list);
         }
 
+        if let Prop::Eq(lhs, rhs) = self {
+            return write!(f, "{} = {}", lhs, rhs);
+        }
+
+        if let (false, Prop::Defined(name, args)) = (self.is_iff(), self) {
+            if args.is_empty() {
+                return write!(f, "{}", name);
+            }
+
+            return write!(f, "{}({})", name, args.iter().join(", "));
+        }
+
         if let Prop::True = self {
             return write!(f, "⊤");
         }
@@ -806,6 +1540,19 @@ impl Display for Prop {
             format!("{}", prop)
         };
 
+        if let (true, Prop::Defined(_, args)) = (self.is_iff(), self) {
+            // quantifiers extend as far right as possible
+            let should_wrap_fst = args[0].precedence() <= 1;
+            let should_wrap_snd = args[1].precedence() == 0;
+
+            return write!(
+                f,
+                "{} ↔ {}",
+                wrap(&args[0], should_wrap_fst),
+                wrap(&args[1], should_wrap_snd)
+            );
+        }
+
         let (connective_symbol, fst, snd) = match self {
             Prop::And(ref fst, ref snd) => ("∧", fst, snd),
             Prop::Or(ref fst, ref snd) => ("∨", fst, snd),
@@ -849,6 +1596,8 @@ impl Debug for Prop {
                 }
             }
 
+            Prop::Eq(lhs, rhs) => write!(f, "{:?} = {:?}", lhs, rhs),
+
             Prop::And(left, right) => write!(f, "({:?}) ∧ ({:?})", left, right),
             Prop::Or(left, right) => write!(f, "({:?}) ∨ ({:?})", left, right),
             Prop::Impl(left, right) => write!(f, "({:?}) => ({:?})", left, right),
@@ -863,6 +1612,8 @@ impl Debug for Prop {
                 body,
             } => write!(f, "∃{}:{}. ({:?})", object_ident, object_type_ident, body),
 
+            Prop::Defined(name, args) => write!(f, "{}({:?})", name, args),
+
             Prop::True => write!(f, "T"),
             Prop::False => write!(f, "⊥"),
         }
@@ -885,7 +1636,10 @@ mod tests {
         checker::{identifier::Identifier, identifier_context::IdentifierContext},
         parse::{fol::fol_parser, lexer::lexer},
         proof_term::Type,
-        prop::{InstatiationError, PropParameter, QuantifierKind},
+        prop::{
+            AtomSignature, FunctionSignature, InstatiationError, PropDefinition, PropParameter,
+            QuantifierKind,
+        },
     };
 
     fn parse_prop(prop: &str) -> Prop {
@@ -1548,6 +2302,14 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_has_equalities() {
+        assert!(!parse_prop("A && B -> A").has_equalities());
+        assert!(!parse_prop("\\forall x:t. P(x)").has_equalities());
+        assert!(parse_prop("\\forall x:t. \\forall y:t. x = y -> y = x").has_equalities());
+        assert!(parse_prop("A || \\exists x:t. x = x").has_equalities());
+    }
+
     #[test]
     fn test_alpha_eq_atom_no_params() {
         assert!(parse_prop("A").alpha_eq(&parse_prop("A")))
@@ -2098,4 +2860,223 @@ mod tests {
 
         prop.instantiate_free_parameter_by_index(10, &subst);
     }
+
+    #[test]
+    fn test_substitute_free_parameter_avoids_capture() {
+        let mut prop = parse_prop("∀y:t. A(x, y)");
+
+        prop.substitute_free_parameter(
+            &"x".to_string(),
+            &PropParameter::Function(
+                "f".to_string(),
+                vec![PropParameter::Uninstantiated("y".to_string())],
+            ),
+        );
+
+        assert_eq!(
+            prop,
+            Prop::ForAll {
+                object_ident: "y'".to_string(),
+                object_type_ident: "t".to_string(),
+                body: Prop::Atom(
+                    "A".to_string(),
+                    vec![
+                        PropParameter::Function(
+                            "f".to_string(),
+                            vec![PropParameter::Uninstantiated("y".to_string())],
+                        ),
+                        PropParameter::Uninstantiated("y'".to_string()),
+                    ]
+                )
+                .boxed(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_display_function_terms() {
+        let prop = parse_prop("A(f(x, g(y)), zero)");
+
+        assert_eq!(prop.to_string(), "A(f(x, g(y)), zero)");
+    }
+
+    #[test]
+    fn test_alpha_eq_function_terms() {
+        let fst = parse_prop("∀x:t. ∀z:t. A(f(x, z))");
+        let snd = parse_prop("∀y:t. ∀x:t. A(f(y, x))");
+        let thd = parse_prop("∀y:t. ∀x:t. A(f(x, y))");
+
+        assert!(fst.alpha_eq(&snd));
+        assert!(!fst.alpha_eq(&thd));
+    }
+
+    #[test]
+    fn test_instantiate_with_context_resolves_constant() {
+        let mut prop = parse_prop("A(f(x, zero))");
+
+        let identifier = Identifier::new("x".to_string(), 42);
+        let mut ctx = IdentifierContext::with_functions(vec![
+            FunctionSignature {
+                name: "f".to_string(),
+                param_types: vec!["t".to_string(), "t".to_string()],
+                return_type: "t".to_string(),
+            },
+            FunctionSignature {
+                name: "zero".to_string(),
+                param_types: vec![],
+                return_type: "t".to_string(),
+            },
+        ]);
+        ctx.insert(identifier.clone(), Type::Datatype("t".to_string()));
+
+        assert_eq!(prop.instantiate_parameters_with_context(&ctx), Ok(()));
+        assert_eq!(
+            prop,
+            Prop::Atom(
+                "A".to_string(),
+                vec![PropParameter::Function(
+                    "f".to_string(),
+                    vec![
+                        PropParameter::Instantiated(identifier),
+                        PropParameter::Function("zero".to_string(), vec![]),
+                    ]
+                )]
+            )
+        );
+    }
+
+    #[test]
+    fn test_instantiate_with_context_unknown_function() {
+        let mut prop = parse_prop("A(f(x))");
+        let mut ctx = IdentifierContext::new();
+        ctx.insert(
+            Identifier::new("x".to_string(), 42),
+            Type::Datatype("t".to_string()),
+        );
+
+        assert_eq!(
+            prop.instantiate_parameters_with_context(&ctx),
+            Err(InstatiationError::UnknownIdentifier("f".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_instantiate_with_context_argument_datatype_wrong() {
+        let mut prop = parse_prop("∀x:nat. ∀p:person. Less(x, p)");
+        let ctx = IdentifierContext::with_signatures(
+            vec![],
+            vec![AtomSignature {
+                name: "Less".to_string(),
+                param_types: vec!["nat".to_string(), "nat".to_string()],
+            }],
+        );
+
+        assert_eq!(
+            prop.instantiate_parameters_with_context(&ctx),
+            Err(InstatiationError::ArgumentDatatypeWrong {
+                ident: "Less".to_string(),
+                argument: PropParameter::Uninstantiated("p".to_string()),
+                expected: "nat".to_string(),
+                actual: "person".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_instantiate_with_context_function_argument_datatype_wrong() {
+        let mut prop = parse_prop("A(succ(p))");
+
+        let identifier = Identifier::new("p".to_string(), 42);
+        let mut ctx = IdentifierContext::with_functions(vec![FunctionSignature {
+            name: "succ".to_string(),
+            param_types: vec!["nat".to_string()],
+            return_type: "nat".to_string(),
+        }]);
+        ctx.insert(identifier.clone(), Type::Datatype("person".to_string()));
+
+        assert_eq!(
+            prop.instantiate_parameters_with_context(&ctx),
+            Err(InstatiationError::ArgumentDatatypeWrong {
+                ident: "succ".to_string(),
+                argument: PropParameter::Instantiated(identifier),
+                expected: "nat".to_string(),
+                actual: "person".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_instantiate_with_context_eq_datatype_wrong() {
+        let mut prop = parse_prop("∀x:nat. ∀p:person. x = p");
+        let ctx = IdentifierContext::new();
+
+        assert_eq!(
+            prop.instantiate_parameters_with_context(&ctx),
+            Err(InstatiationError::ArgumentDatatypeWrong {
+                ident: "=".to_string(),
+                argument: PropParameter::Uninstantiated("p".to_string()),
+                expected: "nat".to_string(),
+                actual: "person".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_display_iff() {
+        let prop = parse_prop("(A <-> B) <-> C -> D");
+
+        assert_eq!(prop.to_string(), "(A ↔ B) ↔ C ⊃ D");
+    }
+
+    #[test]
+    fn test_unfold_iff() {
+        let prop = parse_prop("A <-> B && C");
+
+        assert_eq!(
+            prop.unfold_head(&PropDefinition::builtins()),
+            parse_prop("(A -> B && C) && (B && C -> A)")
+        );
+    }
+
+    #[test]
+    fn test_alpha_eq_modulo_definitions() {
+        let fst = parse_prop("∀x:t. A(x) <-> B");
+        let snd = parse_prop("∀y:t. (A(y) -> B) && (B -> A(y))");
+
+        assert!(!fst.alpha_eq(&snd));
+        assert!(fst.alpha_eq_modulo_definitions(&snd, &PropDefinition::builtins()));
+    }
+
+    #[test]
+    fn test_definition_instantiation_avoids_capture() {
+        let definition = PropDefinition {
+            name: "AllSat".to_string(),
+            params: vec!["X".to_string()],
+            body: parse_prop("∀x:t. X && P(x)"),
+        };
+
+        // bind the free "x" of the argument, so both sides can be compared
+        let prop = Prop::ForAll {
+            object_ident: "x".to_string(),
+            object_type_ident: "t".to_string(),
+            body: definition.instantiate(&[parse_prop("Q(x)")]).boxed(),
+        };
+
+        assert!(prop.alpha_eq(&parse_prop("∀x:t. ∀y:t. Q(x) && P(y)")));
+        assert!(!prop.alpha_eq(&parse_prop("∀x:t. ∀x:t. Q(x) && P(x)")));
+    }
+
+    #[test]
+    fn test_resolve_definitions_arity_wrong() {
+        let mut prop = parse_prop("Iff(A, B, C)");
+
+        assert_eq!(
+            prop.resolve_definitions(&PropDefinition::builtins()),
+            Err(InstatiationError::DefinitionArityWrong {
+                ident: "Iff".to_string(),
+                expected: 2,
+                actual: 3,
+            })
+        );
+    }
 }
@@ -0,0 +1,339 @@
+use crate::kernel::{
+    prop::{Prop, PropDefinition},
+    semantics::kripke::{KripkeFact, KripkeModel, KripkeModelWorld},
+};
+
+use super::ProverConfig;
+
+struct CountermodelTree {
+    atoms: Vec<Prop>,
+    children: Vec<CountermodelTree>,
+}
+
+fn from_tree(tree: CountermodelTree) -> KripkeModel {
+    let mut model = KripkeModel {
+        worlds: vec![],
+        order: vec![],
+    };
+
+    insert_tree(&mut model, tree);
+
+    model
+}
+
+fn insert_tree(model: &mut KripkeModel, tree: CountermodelTree) -> usize {
+    let id = model.worlds.len();
+
+    let mut facts = vec![];
+    // only nullary atoms occur, see generate_countermodel
+    for atom in tree.atoms {
+        let Prop::Atom(name, _) = atom else {
+            continue;
+        };
+
+        let fact = KripkeFact {
+            atom: name,
+            arguments: vec![],
+        };
+        if !facts.contains(&fact) {
+            facts.push(fact);
+        }
+    }
+
+    model.worlds.push(KripkeModelWorld {
+        domains: vec![],
+        facts,
+    });
+
+    for child in tree.children {
+        let child_id = insert_tree(model, child);
+        model.order.push((id, child_id));
+    }
+
+    id
+}
+
+// Generates a Kripke model whose root does not force the given proposition.
+// Returns None if the proposition is provable, not built from propositional atoms or if the
+// search exceeds the step or depth limit of the config.
+pub fn generate_countermodel(prop: &Prop, config: &ProverConfig) -> Option<KripkeModel> {
+    let prop = &prop.unfold_definitions(&PropDefinition::builtins());
+
+    if prop.has_quantifiers()
+        || prop.has_equalities()
+        || prop.has_definitions()
+        || prop.get_atoms().iter().any(|(_, arity)| *arity > 0)
+    {
+        return None;
+    }
+
+    let mut refuter = Refuter::new(config);
+    let tree = refuter.refute(vec![], prop.clone());
+
+    if refuter.gave_up {
+        return None;
+    }
+
+    tree.map(from_tree)
+}
+
+fn is_atomic(prop: &Prop) -> bool {
+    matches!(
+        prop,
+        Prop::Atom(_, _)
+            | Prop::Eq(_, _)
+            | Prop::ForAll { .. }
+            | Prop::Exists { .. }
+            | Prop::Defined(_, _)
+    )
+}
+
+fn without(ctx: &[Prop], idx: usize, additions: Vec<Prop>) -> Vec<Prop> {
+    let mut new_ctx = ctx.to_vec();
+    new_ctx.remove(idx);
+    new_ctx.extend(additions);
+
+    new_ctx
+}
+
+struct Refuter<'a> {
+    config: &'a ProverConfig,
+    steps: usize,
+    depth: usize,
+    gave_up: bool,
+}
+
+impl<'a> Refuter<'a> {
+    fn new(config: &'a ProverConfig) -> Self {
+        Self {
+            config,
+            steps: 0,
+            depth: 0,
+            gave_up: false,
+        }
+    }
+
+    // Counts a step and gives up if the limits of the config are exceeded.
+    fn refute(&mut self, ctx: Vec<Prop>, goal: Prop) -> Option<CountermodelTree> {
+        self.steps += 1;
+
+        if self.gave_up || self.steps > self.config.max_steps || self.depth >= self.config.max_depth
+        {
+            self.gave_up = true;
+            return None;
+        }
+
+        self.depth += 1;
+        let tree = self.refute_sequent(ctx, goal);
+        self.depth -= 1;
+
+        tree
+    }
+
+    // Refutation search that follows the rules of the G4ip prover.
+    // The root of the returned tree forces every prop in ctx but not the goal.
+    fn refute_sequent(&mut self, mut ctx: Vec<Prop>, goal: Prop) -> Option<CountermodelTree> {
+        // invertible right rules
+        match goal {
+            Prop::True => return None,
+            Prop::And(fst, snd) => {
+                return self
+                    .refute(ctx.clone(), *fst)
+                    .or_else(|| self.refute(ctx, *snd));
+            }
+            Prop::Impl(fst, snd) => {
+                ctx.push(*fst);
+                return self.refute(ctx, *snd);
+            }
+            _ => {}
+        }
+
+        // invertible left rules
+        for i in 0..ctx.len() {
+            match ctx[i].clone() {
+                Prop::True => return self.refute(without(&ctx, i, vec![]), goal),
+                Prop::False => return None,
+                Prop::And(fst, snd) => {
+                    return self.refute(without(&ctx, i, vec![*fst, *snd]), goal)
+                }
+                Prop::Or(fst, snd) => {
+                    return self
+                        .refute(without(&ctx, i, vec![*fst]), goal.clone())
+                        .or_else(|| self.refute(without(&ctx, i, vec![*snd]), goal));
+                }
+                Prop::Impl(fst, snd) => match *fst {
+                    Prop::True => return self.refute(without(&ctx, i, vec![*snd]), goal),
+                    Prop::False => return self.refute(without(&ctx, i, vec![]), goal),
+                    Prop::And(and_fst, and_snd) => {
+                        let new_prop = Prop::Impl(and_fst, Prop::Impl(and_snd, snd).boxed());
+
+                        return self.refute(without(&ctx, i, vec![new_prop]), goal);
+                    }
+                    Prop::Or(or_fst, or_snd) => {
+                        let new_props =
+                            vec![Prop::Impl(or_fst, snd.clone()), Prop::Impl(or_snd, snd)];
+
+                        return self.refute(without(&ctx, i, new_props), goal);
+                    }
+                    ref atom if is_atomic(atom) && ctx.contains(atom) => {
+                        return self.refute(without(&ctx, i, vec![*snd]), goal);
+                    }
+                    _ => {}
+                },
+                atom if is_atomic(&atom) && atom == goal => return None,
+                _ => {}
+            }
+        }
+
+        // refl rule
+        if let Prop::Eq(ref lhs, ref rhs) = goal {
+            if lhs == rhs {
+                return None;
+            }
+        }
+
+        // The sequent is irreducible now. Every non-invertible rule has to fail.
+        let mut children = vec![];
+
+        if let Prop::Or(ref fst, ref snd) = goal {
+            children.push(self.refute(ctx.clone(), *fst.clone())?);
+            children.push(self.refute(ctx.clone(), *snd.clone())?);
+        }
+
+        for i in 0..ctx.len() {
+            let Prop::Impl(ref impl_fst, ref impl_snd) = ctx[i] else {
+                continue;
+            };
+
+            let Prop::Impl(ref impl_impl_fst, ref impl_impl_snd) = **impl_fst else {
+                continue;
+            };
+
+            let fst_ctx = without(
+                &ctx,
+                i,
+                vec![
+                    Prop::Impl(impl_impl_snd.clone(), impl_snd.clone()),
+                    *impl_impl_fst.clone(),
+                ],
+            );
+
+            match self.refute(fst_ctx, *impl_impl_snd.clone()) {
+                Some(child) => children.push(child),
+
+                // if the first premisse is provable, the second one must be refutable
+                None => return self.refute(without(&ctx, i, vec![*impl_snd.clone()]), goal),
+            }
+        }
+
+        Some(CountermodelTree {
+            atoms: ctx.into_iter().filter(is_atomic).collect(),
+            children,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        parse::{fol::fol_parser, lexer::lexer},
+        prop::Prop,
+        semantics::kripke::KripkeModel,
+    };
+
+    use super::{generate_countermodel, ProverConfig};
+
+    fn parse_prop(prop: &str) -> Prop {
+        let len = prop.chars().count();
+
+        let tokens = lexer().parse(prop).unwrap();
+
+        fol_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    fn countermodel(prop: &str) -> Option<KripkeModel> {
+        generate_countermodel(&parse_prop(prop), &ProverConfig::default())
+    }
+
+    fn assert_countermodel(prop: &str) {
+        let prop = parse_prop(prop);
+        let model = generate_countermodel(&prop, &ProverConfig::default()).unwrap();
+
+        assert_eq!(model.validate(), Ok(()));
+        assert_eq!(model.forces(0, &prop), Ok(false));
+    }
+
+    #[test]
+    fn test_double_negation_elimination() {
+        assert_countermodel("~~A -> A");
+    }
+
+    #[test]
+    fn test_excluded_middle() {
+        assert_countermodel("A || ~A");
+    }
+
+    #[test]
+    fn test_peirce() {
+        assert_countermodel("((A -> B) -> A) -> A");
+    }
+
+    #[test]
+    fn test_de_morgan() {
+        assert_countermodel("~(A && B) -> ~A || ~B");
+    }
+
+    #[test]
+    fn test_implication_as_disjunction() {
+        assert_countermodel("(A -> B) -> ~A || B");
+    }
+
+    #[test]
+    fn test_linearity() {
+        assert_countermodel("(A -> B) || (B -> A)");
+    }
+
+    #[test]
+    fn test_simple_atom() {
+        let model = countermodel("A").unwrap();
+
+        assert_eq!(model.worlds.len(), 1);
+        assert!(model.worlds[0].facts.is_empty());
+    }
+
+    #[test]
+    fn test_no_countermodel_for_provable_prop() {
+        assert_eq!(countermodel("A && B -> B && A"), None);
+        assert_eq!(countermodel("~~(A || ~A)"), None);
+        assert_eq!(countermodel("((A -> B) -> C) -> (B -> C)"), None);
+    }
+
+    #[test]
+    fn test_no_countermodel_with_quantifiers() {
+        assert_eq!(countermodel("\\forall x:t. A(x)"), None);
+        assert_eq!(countermodel("A(x) || ~A(x)"), None);
+    }
+
+    #[test]
+    fn test_limits() {
+        let prop = parse_prop("((A -> B) -> A) -> A");
+        let config = ProverConfig {
+            max_steps: 2,
+            ..ProverConfig::default()
+        };
+
+        assert_eq!(generate_countermodel(&prop, &config), None);
+
+        let config = ProverConfig {
+            max_depth: 2,
+            ..ProverConfig::default()
+        };
+
+        assert_eq!(generate_countermodel(&prop, &config), None);
+    }
+}
@@ -1,3 +1,9 @@
+use chumsky::Parser;
+
+use crate::kernel::parse::{lexer::lexer, Token};
+
+const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
 pub struct IdentifierGenerator {
     idx: usize,
 }
@@ -7,23 +13,62 @@ impl IdentifierGenerator {
         Self { idx: 0 }
     }
 
+    // a, ..., z, aa, ab, ..., zz, aaa, ... without names that are keywords like fn or in
     pub fn generate(&mut self) -> String {
-        let alphabet = "abcdefghijklmnopqrstuvwxyz".chars().collect::<Vec<char>>();
-        let alphabet_length = alphabet.len();
-        let num_digits = f32::floor(self.idx as f32 / alphabet_length as f32) as usize + 1;
-
-        let mut identifier = String::new();
-        for i in 0..num_digits {
-            identifier.push(
-                alphabet[(f32::floor(
-                    (self.idx as f32) / usize::pow(alphabet_length, i.try_into().unwrap()) as f32,
-                ) as usize)
-                    % alphabet_length],
-            );
+        loop {
+            let identifier = Self::encode(self.idx);
+            self.idx += 1;
+
+            if identifier.len() == 1 || Self::is_identifier(&identifier) {
+                return identifier;
+            }
+        }
+    }
+
+    // bijective base-26 numeral of the index
+    fn encode(idx: usize) -> String {
+        let mut remainder = idx + 1;
+        let mut identifier = vec![];
+
+        while remainder > 0 {
+            remainder -= 1;
+            identifier.push(ALPHABET[remainder % ALPHABET.len()]);
+            remainder /= ALPHABET.len();
         }
 
-        self.idx += 1;
+        identifier.reverse();
+        String::from_utf8(identifier).unwrap()
+    }
+
+    fn is_identifier(identifier: &str) -> bool {
+        matches!(
+            lexer().parse(identifier).as_deref(),
+            Ok([(Token::IDENT(_), _)])
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::IdentifierGenerator;
+
+    #[test]
+    fn test_generate() {
+        let mut generator = IdentifierGenerator::new();
+        let identifiers = (0..2000).map(|_| generator.generate()).collect::<Vec<_>>();
+
+        assert_eq!(identifiers[0], "a");
+        assert_eq!(identifiers[25], "z");
+        assert_eq!(identifiers[26], "aa");
+        assert_eq!(identifiers[27], "ab");
+        assert!(identifiers.iter().all_unique());
+        assert!(identifiers.iter().all(|identifier| identifier.len() <= 3));
 
-        identifier.chars().rev().collect()
+        // keywords are skipped
+        assert!(!identifiers.contains(&"fn".to_string()));
+        assert!(!identifiers.contains(&"in".to_string()));
+        assert!(!identifiers.contains(&"of".to_string()));
     }
 }
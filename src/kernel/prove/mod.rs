@@ -1,19 +1,70 @@
 use identifier_generator::IdentifierGenerator;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
 
 use crate::kernel::proof_term::{ProjectFst, ProjectSnd};
 
 use super::{
     checker::{check::check, identifier_context::IdentifierContext},
     proof_term::{
-        Abort, Application, Case, Function, Ident, OrLeft, OrRight, Pair, ProofTerm, Type,
+        Abort, Application, Case, Function, Ident, LetIn, OrLeft, OrRight, Pair, ProofTerm, Type,
         TypeAscription,
     },
-    prop::Prop,
+    prop::{FunctionSignature, Prop, PropParameter},
 };
+use trace::{ProverRule, ProverTrace, TracedProverOutcome};
 
+pub mod countermodel;
 mod identifier_generator;
 mod tests;
+pub mod trace;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct ProverConfig {
+    // number of visited sequents
+    pub max_steps: usize,
+
+    pub max_depth: usize,
+    pub max_cloned_sequents: usize,
+
+    // number of ∀-left and ∃-right applications on every branch
+    pub max_instantiations: usize,
+}
+
+impl Default for ProverConfig {
+    fn default() -> Self {
+        Self {
+            max_steps: 20000,
+            max_depth: 200,
+            max_cloned_sequents: 20000,
+            max_instantiations: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum ProverOutcome {
+    Proved(ProofTerm),
+
+    // the search space was exhausted without finding a proof
+    Refuted,
+
+    // a limit of the config was reached
+    GaveUp,
+}
+
+impl ProverOutcome {
+    pub fn proof_term(self) -> Option<ProofTerm> {
+        match self {
+            Self::Proved(proof_term) => Some(proof_term),
+            Self::Refuted | Self::GaveUp => None,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct TypeJudgment {
@@ -31,6 +82,11 @@ impl TypeJudgment {
 struct Sequent<'a> {
     unordered_ctx: Vec<TypeJudgment>,
     ordered_ctx: Vec<TypeJudgment>,
+
+    // objects in scope together with their datatype
+    objects: Vec<(PropParameter, String)>,
+    instantiations: usize,
+
     goal: &'a Prop,
 }
 
@@ -39,6 +95,8 @@ impl<'a> Sequent<'a> {
         Self {
             unordered_ctx: vec![],
             ordered_ctx: vec![],
+            objects: vec![],
+            instantiations: 0,
             goal,
         }
     }
@@ -47,6 +105,8 @@ impl<'a> Sequent<'a> {
         Self {
             unordered_ctx: self.unordered_ctx.clone(),
             ordered_ctx: self.ordered_ctx.clone(),
+            objects: self.objects.clone(),
+            instantiations: self.instantiations,
             goal,
         }
     }
@@ -62,15 +122,55 @@ impl<'a> Sequent<'a> {
     pub fn find_in_unordered_context_by_prop(&self, prop: &Prop) -> Option<&TypeJudgment> {
         self.unordered_ctx.iter().find(|elem| elem.prop == *prop)
     }
+
+    pub fn contains_prop(&self, prop: &Prop) -> bool {
+        self.unordered_ctx
+            .iter()
+            .chain(self.ordered_ctx.iter())
+            .any(|elem| elem.prop == *prop)
+    }
 }
 
 pub fn prove(prop: &Prop) -> Option<ProofTerm> {
     prove_with_ctx(prop, &IdentifierContext::new())
 }
 
+pub fn prove_with_config(prop: &Prop, config: &ProverConfig) -> ProverOutcome {
+    let mut ctx = IdentifierContext::new();
+    ctx.set_prover_config(config.clone());
+
+    find_proof(prop, &ctx)
+}
+
 pub fn prove_with_ctx(prop: &Prop, ctx: &IdentifierContext) -> Option<ProofTerm> {
-    let assumptions = ctx
-        .get_all_visible()
+    find_proof(prop, ctx).proof_term()
+}
+
+// Searches a proof within the limits of the prover config of the context.
+pub fn find_proof(prop: &Prop, ctx: &IdentifierContext) -> ProverOutcome {
+    let mut prover = Prover::new(ctx.prover_config().clone());
+
+    run_prover(&mut prover, prop, ctx)
+}
+
+// Like find_proof, but additionally records every visited sequent.
+pub fn find_proof_with_trace(prop: &Prop, ctx: &IdentifierContext) -> TracedProverOutcome {
+    let mut prover = Prover::new(ctx.prover_config().clone());
+    prover.trace = Some(vec![]);
+
+    let outcome = run_prover(&mut prover, prop, ctx);
+
+    TracedProverOutcome {
+        outcome,
+        trace: prover.trace_root,
+    }
+}
+
+fn run_prover(prover: &mut Prover, prop: &Prop, ctx: &IdentifierContext) -> ProverOutcome {
+    let visible = ctx.get_all_visible();
+    let definitions = ctx.definitions();
+
+    let assumptions = visible
         .iter()
         .filter_map(|(ident, _type)| {
             let Type::Prop(prop) = _type else {
@@ -80,72 +180,232 @@ pub fn prove_with_ctx(prop: &Prop, ctx: &IdentifierContext) -> Option<ProofTerm>
             let name = ident.name();
 
             Some(TypeJudgment {
-                prop: prop.clone(),
+                prop: prop.unfold_definitions(definitions),
                 proof_term: Ident::create(name.clone()),
             })
         })
         .collect_vec();
 
-    let proof_term = Prover::prove_with_assumptions(prop, assumptions)?;
+    let objects = visible
+        .iter()
+        .filter_map(|(ident, _type)| {
+            let Type::Datatype(datatype) = _type else {
+                return None;
+            };
+
+            Some((PropParameter::Instantiated(ident.clone()), datatype.clone()))
+        })
+        .collect_vec();
 
-    // sanity check
-    let check_result = check(&proof_term, prop, ctx);
-    if check_result.is_err() {
-        panic!(
-            "Prover returned wrong proof. Prop: {:#?}, proof term: {}, context: {:#?}, error: {:#?}",
-            prop, proof_term, ctx, check_result,
-        );
+    // the search only knows the primitive connectives, the checker unfolds on demand
+    let unfolded_prop = prop.unfold_definitions(definitions);
+
+    // unknown definitions cannot be reasoned about
+    if unfolded_prop.has_definitions()
+        || assumptions
+            .iter()
+            .any(|assumption| assumption.prop.has_definitions())
+    {
+        return ProverOutcome::GaveUp;
+    }
+
+    // Without quantifiers the search never has to choose terms, so a failed search refutes the
+    // sequent. Equalities are excluded as there is no rule for equality elimination.
+    let is_decidable = [&unfolded_prop]
+        .into_iter()
+        .chain(assumptions.iter().map(|assumption| &assumption.prop))
+        .all(|prop| !prop.has_quantifiers() && !prop.has_equalities());
+
+    let proof_term = match prover.prove_with_assumptions(
+        &unfolded_prop,
+        assumptions,
+        objects,
+        ctx.functions().to_vec(),
+    ) {
+        Some(proof_term) => proof_term,
+        None if prover.gave_up || !is_decidable => return ProverOutcome::GaveUp,
+        None => return ProverOutcome::Refuted,
+    };
+
+    // sanity check, e.g. the context may lack signatures of the functions used by the proof
+    if check(&proof_term, prop, ctx).is_err() {
+        return ProverOutcome::GaveUp;
     }
 
-    Some(proof_term)
+    ProverOutcome::Proved(proof_term)
 }
 
 struct Prover {
     forbidden_idents: Vec<String>,
     identifier_generator: IdentifierGenerator,
+    functions: Vec<FunctionSignature>,
+
+    config: ProverConfig,
+    steps: usize,
+    depth: usize,
+    cloned_sequents: usize,
+    gave_up: bool,
+
+    // stack of the sequents currently visited, None if tracing is disabled
+    trace: Option<Vec<ProverTrace>>,
+    trace_root: Option<ProverTrace>,
 }
 
 impl Prover {
     pub fn prove_with_assumptions(
+        &mut self,
         prop: &Prop,
         assumptions: Vec<TypeJudgment>,
+        objects: Vec<(PropParameter, String)>,
+        functions: Vec<FunctionSignature>,
     ) -> Option<ProofTerm> {
         let mut sequent = Sequent::new(prop);
 
-        let mut forbidden_idents = vec![];
+        // generated identifiers must not capture anything mentioned in the sequent
+        let mut forbidden_idents = get_identifiers(prop);
         for assumption in assumptions {
             if let ProofTerm::Ident(Ident(ref name, _)) = assumption.proof_term {
                 forbidden_idents.push(name.clone());
             }
+            forbidden_idents.extend(get_identifiers(&assumption.prop));
 
             sequent.append_ordered(assumption);
         }
 
-        let mut prover = Prover::new();
-        prover.forbidden_idents = forbidden_idents;
+        for (object, _) in objects.iter() {
+            forbidden_idents.push(object.name().clone());
+        }
+        sequent.objects = objects;
+
+        for function in functions.iter() {
+            forbidden_idents.push(function.name.clone());
+        }
+
+        self.forbidden_idents = forbidden_idents;
+        self.functions = functions;
 
-        prover.prove_right(sequent)
+        self.prove_right(sequent, ProverRule::Start)
     }
 
-    fn new() -> Self {
+    fn new(config: ProverConfig) -> Self {
         Self {
             identifier_generator: IdentifierGenerator::new(),
             forbidden_idents: vec![],
+            functions: vec![],
+            config,
+            steps: 0,
+            depth: 0,
+            cloned_sequents: 0,
+            gave_up: false,
+            trace: None,
+            trace_root: None,
+        }
+    }
+
+    // Counts a step and checks the limits of the config.
+    fn enter(&mut self) -> Option<()> {
+        self.steps += 1;
+
+        if self.gave_up
+            || self.steps > self.config.max_steps
+            || self.depth >= self.config.max_depth
+            || self.cloned_sequents > self.config.max_cloned_sequents
+        {
+            self.gave_up = true;
+            return None;
         }
+
+        self.depth += 1;
+        Some(())
+    }
+
+    fn fork<'a>(&mut self, sequent: &Sequent<'a>) -> Sequent<'a> {
+        self.cloned_sequents += 1;
+        sequent.clone()
     }
 
-    fn prove_right(&mut self, sequent: Sequent) -> Option<ProofTerm> {
+    fn fork_with_goal<'a>(&mut self, sequent: &Sequent<'a>, goal: &'a Prop) -> Sequent<'a> {
+        self.cloned_sequents += 1;
+        sequent.with_new_goal(goal)
+    }
+
+    // Records a visited sequent, only if tracing is enabled.
+    fn begin_trace(&mut self, sequent: &Sequent, rule: ProverRule) {
+        let Some(stack) = self.trace.as_mut() else {
+            return;
+        };
+
+        stack.push(ProverTrace {
+            ordered_ctx: sequent
+                .ordered_ctx
+                .iter()
+                .map(|judgment| judgment.prop.clone())
+                .collect(),
+            unordered_ctx: sequent
+                .unordered_ctx
+                .iter()
+                .map(|judgment| judgment.prop.clone())
+                .collect(),
+            goal: sequent.goal.clone(),
+            rule,
+            closed_by: None,
+            success: false,
+            backtracked: false,
+            children: vec![],
+        });
+    }
+
+    fn end_trace(&mut self, success: bool) {
+        let Some(stack) = self.trace.as_mut() else {
+            return;
+        };
+
+        let mut node = stack.pop().unwrap();
+        node.success = success;
+
+        match stack.last_mut() {
+            Some(parent) => {
+                if parent.children.last().is_some_and(|child| !child.success) {
+                    parent.backtracked = true;
+                }
+
+                parent.children.push(node);
+            }
+            None => self.trace_root = Some(node),
+        }
+    }
+
+    fn close_trace(&mut self, rule: ProverRule) {
+        if let Some(node) = self.trace.as_mut().and_then(|stack| stack.last_mut()) {
+            node.closed_by = Some(rule);
+        }
+    }
+
+    fn prove_right(&mut self, sequent: Sequent, rule: ProverRule) -> Option<ProofTerm> {
+        self.enter()?;
+        self.begin_trace(&sequent, rule);
+        let result = self.apply_right_rules(sequent);
+        self.end_trace(result.is_some());
+        self.depth -= 1;
+
+        result
+    }
+
+    fn apply_right_rules(&mut self, sequent: Sequent) -> Option<ProofTerm> {
         match sequent.goal {
-            Prop::True => Some(ProofTerm::Unit(None)),
-            Prop::False => self.prove_left(sequent),
-            Prop::Atom(_, _) => self.prove_left(sequent),
-            Prop::Or(_, _) => self.prove_left(sequent),
+            Prop::True => {
+                self.close_trace(ProverRule::TrueRight);
+                Some(ProofTerm::Unit(None))
+            }
+            Prop::False => self.prove_left(sequent, ProverRule::FocusLeft),
+            Prop::Atom(_, _) => self.prove_left(sequent, ProverRule::FocusLeft),
+            Prop::Eq(_, _) => self.prove_left(sequent, ProverRule::FocusLeft),
+            Prop::Or(_, _) => self.prove_left(sequent, ProverRule::FocusLeft),
             Prop::And(_, _) => self.handle_and_right(sequent),
             Prop::Impl(_, _) => self.handle_impl_right(sequent),
-            Prop::ForAll { .. } | Prop::Exists { .. } => {
-                // treat quantifiers as atoms
-                self.prove_left(sequent)
-            }
+            Prop::ForAll { .. } => self.handle_forall_right(sequent),
+            Prop::Exists { .. } => self.prove_left(sequent, ProverRule::FocusLeft),
+            Prop::Defined(_, _) => unreachable!("Definitions are unfolded before the search."),
         }
     }
 
@@ -154,11 +414,11 @@ impl Prover {
             panic!("Expected conjunction.");
         };
 
-        let fst_sequent = sequent.with_new_goal(fst);
-        let fst_proof_term = self.prove_right(fst_sequent)?;
+        let fst_sequent = self.fork_with_goal(&sequent, fst);
+        let fst_proof_term = self.prove_right(fst_sequent, ProverRule::AndRight)?;
 
-        let snd_sequent = sequent.with_new_goal(snd);
-        let snd_proof_term = self.prove_right(snd_sequent)?;
+        let snd_sequent = self.fork_with_goal(&sequent, snd);
+        let snd_proof_term = self.prove_right(snd_sequent, ProverRule::AndRight)?;
 
         Some(Pair::create(
             fst_proof_term.boxed(),
@@ -178,7 +438,7 @@ impl Prover {
         sequent.append_ordered(param_judgment);
         sequent.goal = snd;
 
-        let body_proof_term = self.prove_right(sequent)?;
+        let body_proof_term = self.prove_right(sequent, ProverRule::ImplRight)?;
 
         Some(Function::create(
             param_ident,
@@ -188,7 +448,48 @@ impl Prover {
         ))
     }
 
-    fn prove_left(&mut self, mut sequent: Sequent) -> Option<ProofTerm> {
+    fn handle_forall_right(&mut self, sequent: Sequent) -> Option<ProofTerm> {
+        let Prop::ForAll {
+            object_ident,
+            object_type_ident,
+            body,
+        } = sequent.goal
+        else {
+            panic!("Expected universal quantification.");
+        };
+
+        let eigenvariable = self.generate_identifier();
+        let eigenvariable_param = PropParameter::Uninstantiated(eigenvariable.clone());
+
+        let mut body = *body.clone();
+        body.substitute_free_parameter(object_ident, &eigenvariable_param);
+
+        let mut body_sequent = self.fork_with_goal(&sequent, &body);
+        body_sequent
+            .objects
+            .push((eigenvariable_param, object_type_ident.clone()));
+
+        let body_proof_term = self.prove_right(body_sequent, ProverRule::ForAllRight)?;
+
+        Some(Function::create(
+            eigenvariable,
+            None,
+            body_proof_term.boxed(),
+            None,
+        ))
+    }
+
+    fn prove_left(&mut self, sequent: Sequent, rule: ProverRule) -> Option<ProofTerm> {
+        self.enter()?;
+        self.begin_trace(&sequent, rule);
+        let result = self.apply_left_rules(sequent);
+        self.end_trace(result.is_some());
+        self.depth -= 1;
+
+        result
+    }
+
+    fn apply_left_rules(&mut self, mut sequent: Sequent) -> Option<ProofTerm> {
         if sequent.ordered_ctx.is_empty() {
             return self.search(sequent);
         }
@@ -196,19 +497,62 @@ impl Prover {
         let type_judgment = sequent.ordered_ctx.pop().unwrap();
 
         match &type_judgment.prop {
-            Prop::True => self.prove_left(sequent),
-            Prop::False => Some(Abort::create(type_judgment.proof_term.boxed(), None)),
+            Prop::True => self.prove_left(sequent, ProverRule::TrueLeft),
+            Prop::False => {
+                self.close_trace(ProverRule::FalseLeft);
+                Some(Abort::create(type_judgment.proof_term.boxed(), None))
+            }
             Prop::Atom(_, _) => self.handle_atom_left(type_judgment, sequent),
+            Prop::Eq(_, _) => self.handle_atom_left(type_judgment, sequent),
             Prop::And(_, _) => self.handle_and_left(type_judgment, sequent),
             Prop::Or(_, _) => self.handle_or_left(type_judgment, sequent),
             Prop::Impl(_, _) => self.handle_impl_left(type_judgment, sequent),
-            Prop::ForAll { .. } | Prop::Exists { .. } => {
-                // handle quantifiers as atoms
+            Prop::Exists { .. } => self.handle_exists_left(type_judgment, sequent),
+            Prop::ForAll { .. } => {
+                // universal quantifications stay in context, they are instantiated during search
                 self.handle_atom_left(type_judgment, sequent)
             }
+            Prop::Defined(_, _) => unreachable!("Definitions are unfolded before the search."),
         }
     }
 
+    fn handle_exists_left(
+        &mut self,
+        type_judgment: TypeJudgment,
+        mut sequent: Sequent,
+    ) -> Option<ProofTerm> {
+        let TypeJudgment { prop, proof_term } = type_judgment;
+        let Prop::Exists {
+            object_ident,
+            object_type_ident,
+            mut body,
+        } = prop
+        else {
+            panic!("Expected existential quantification");
+        };
+
+        let eigenvariable = self.generate_identifier();
+        let eigenvariable_param = PropParameter::Uninstantiated(eigenvariable.clone());
+        let body_ident = self.generate_identifier();
+
+        body.substitute_free_parameter(&object_ident, &eigenvariable_param);
+
+        sequent
+            .objects
+            .push((eigenvariable_param, object_type_ident));
+        sequent.append_ordered(TypeJudgment::new(*body, Ident::create(body_ident.clone())));
+
+        let body_proof_term = self.prove_left(sequent, ProverRule::ExistsLeft)?;
+
+        Some(ProofTerm::LetIn(LetIn {
+            fst_ident: eigenvariable,
+            snd_ident: body_ident,
+            head: proof_term.boxed(),
+            body: body_proof_term.boxed(),
+            span: None,
+        }))
+    }
+
     fn handle_and_left(
         &mut self,
         type_judgment: TypeJudgment,
@@ -225,7 +569,7 @@ impl Prover {
         let snd_judgment = TypeJudgment::new(*snd, ProjectSnd::create(proof_term.boxed(), None));
         sequent.append_ordered(snd_judgment);
 
-        self.prove_left(sequent)
+        self.prove_left(sequent, ProverRule::AndLeft)
     }
 
     fn handle_or_left(
@@ -238,17 +582,17 @@ impl Prover {
             panic!("Expected disjunction");
         };
 
-        let mut fst_sequent = sequent.clone();
+        let mut fst_sequent = self.fork(&sequent);
         let fst_ident = self.generate_identifier();
         let fst_judgment = TypeJudgment::new(*fst, Ident::create(fst_ident.clone()));
         fst_sequent.append_ordered(fst_judgment);
-        let fst_term = self.prove_left(fst_sequent)?;
+        let fst_term = self.prove_left(fst_sequent, ProverRule::OrLeft)?;
 
         let mut snd_sequent = sequent;
         let snd_ident = self.generate_identifier();
         let snd_judgment = TypeJudgment::new(*snd, Ident::create(snd_ident.clone()));
         snd_sequent.append_ordered(snd_judgment);
-        let snd_term = self.prove_left(snd_sequent)?;
+        let snd_term = self.prove_left(snd_sequent, ProverRule::OrLeft)?;
 
         Some(Case::create(
             proof_term.boxed(),
@@ -268,6 +612,7 @@ impl Prover {
         let TypeJudgment { ref prop, .. } = type_judgment;
 
         if !(matches!(prop, Prop::Atom(_, _))
+            | matches!(prop, Prop::Eq(_, _))
             | matches!(prop, Prop::ForAll { .. })
             | matches!(prop, Prop::Exists { .. }))
         {
@@ -275,7 +620,7 @@ impl Prover {
         }
 
         sequent.append_unordered(type_judgment);
-        self.prove_left(sequent)
+        self.prove_left(sequent, ProverRule::StoreLeft)
     }
 
     fn handle_impl_left(
@@ -294,7 +639,7 @@ impl Prover {
                     Application::create(proof_term.boxed(), ProofTerm::Unit(None).boxed(), None);
                 let application_judgment = TypeJudgment::new(*snd, application_proof_term);
                 sequent.append_ordered(application_judgment);
-                self.prove_left(sequent)
+                self.prove_left(sequent, ProverRule::TrueImplLeft)
             }
 
             Prop::And(and_fst, and_snd) => {
@@ -332,7 +677,7 @@ impl Prover {
                 let new_judgment = TypeJudgment::new(new_prop, new_proof_term);
                 sequent.append_ordered(new_judgment);
 
-                self.prove_left(sequent)
+                self.prove_left(sequent, ProverRule::AndImplLeft)
             }
 
             Prop::Or(or_fst, or_snd) => {
@@ -386,23 +731,24 @@ impl Prover {
                 let or_snd_judgment = TypeJudgment::new(or_snd_prop, or_snd_proof_term);
                 sequent.append_ordered(or_snd_judgment);
 
-                self.prove_left(sequent)
+                self.prove_left(sequent, ProverRule::OrImplLeft)
             }
 
-            Prop::False => self.prove_left(sequent),
-            Prop::Atom(_, _) => {
+            Prop::False => self.prove_left(sequent, ProverRule::FalseImplLeft),
+            Prop::Atom(_, _) | Prop::Eq(_, _) => {
                 sequent.append_unordered(TypeJudgment::new(Prop::Impl(fst, snd), proof_term));
-                self.prove_left(sequent)
+                self.prove_left(sequent, ProverRule::StoreLeft)
             }
             Prop::Impl(_, _) => {
                 sequent.append_unordered(TypeJudgment::new(Prop::Impl(fst, snd), proof_term));
-                self.prove_left(sequent)
+                self.prove_left(sequent, ProverRule::StoreLeft)
             }
             Prop::ForAll { .. } | Prop::Exists { .. } => {
                 // handle quantifiers as atoms
                 sequent.append_unordered(TypeJudgment::new(Prop::Impl(fst, snd), proof_term));
-                self.prove_left(sequent)
+                self.prove_left(sequent, ProverRule::StoreLeft)
             }
+            Prop::Defined(_, _) => unreachable!("Definitions are unfolded before the search."),
         }
     }
 
@@ -413,15 +759,25 @@ impl Prover {
         );
 
         // id rule
-        if let Prop::Atom(_, _) = sequent.goal {
+        if let Prop::Atom(_, _) | Prop::Eq(_, _) = sequent.goal {
             if let Some(elem) = sequent.find_in_unordered_context_by_prop(sequent.goal) {
+                self.close_trace(ProverRule::Id);
                 return Some(elem.proof_term.clone());
             }
         }
 
+        // refl rule
+        if let Prop::Eq(lhs, rhs) = sequent.goal {
+            if lhs == rhs {
+                self.close_trace(ProverRule::Refl);
+                return Some(ProofTerm::Refl(None));
+            }
+        }
+
         // id rule universal quantification
         if let Prop::ForAll { .. } = sequent.goal {
             if let Some(elem) = sequent.find_in_unordered_context_by_prop(sequent.goal) {
+                self.close_trace(ProverRule::Id);
                 return Some(elem.proof_term.clone());
             }
         }
@@ -429,6 +785,7 @@ impl Prover {
         // id rule existential quantification
         if let Prop::Exists { .. } = sequent.goal {
             if let Some(elem) = sequent.find_in_unordered_context_by_prop(sequent.goal) {
+                self.close_trace(ProverRule::Id);
                 return Some(elem.proof_term.clone());
             }
         }
@@ -449,6 +806,7 @@ impl Prover {
             };
 
             let is_atom = matches!(**impl_fst, Prop::Atom(_, _))
+                || matches!(**impl_fst, Prop::Eq(_, _))
                 || matches!(**impl_fst, Prop::ForAll { .. })
                 || matches!(**impl_fst, Prop::Exists { .. });
 
@@ -466,34 +824,36 @@ impl Prover {
             }
         }
 
-        if atom_impl_changes.len() > 0 {
+        if !atom_impl_changes.is_empty() {
             atom_impl_changes.reverse();
-            let mut impl_sequent = sequent.clone();
+            let mut impl_sequent = self.fork(&sequent);
             for (i, new_judgment) in atom_impl_changes {
                 impl_sequent.unordered_ctx.remove(i);
                 impl_sequent.append_ordered(new_judgment);
             }
 
-            return self.prove_left(impl_sequent);
+            return self.prove_left(impl_sequent, ProverRule::AtomImplLeft);
         }
 
         // or left rule
         if let Prop::Or(fst, _) = sequent.goal {
-            if let Some(proof_term) = self.prove_right(sequent.with_new_goal(fst)) {
+            let fst_sequent = self.fork_with_goal(&sequent, fst);
+            if let Some(proof_term) = self.prove_right(fst_sequent, ProverRule::OrRightFst) {
                 return Some(OrLeft::create(proof_term.boxed(), None));
             }
         }
 
         // or right rule
         if let Prop::Or(_, snd) = sequent.goal {
-            if let Some(proof_term) = self.prove_right(sequent.with_new_goal(snd)) {
+            let snd_sequent = self.fork_with_goal(&sequent, snd);
+            if let Some(proof_term) = self.prove_right(snd_sequent, ProverRule::OrRightSnd) {
                 return Some(OrRight::create(proof_term.boxed(), None));
             }
         }
 
         // try Impl Rules for every element in unordered ctx.
         for i in 0..sequent.unordered_ctx.len() {
-            let mut searching_sequent = sequent.clone();
+            let mut searching_sequent = self.fork(&sequent);
             let TypeJudgment { prop, proof_term } = searching_sequent.unordered_ctx.remove(i);
             let Prop::Impl(impl_fst, impl_snd) = prop else {
                 continue;
@@ -502,7 +862,7 @@ impl Prover {
             // Impl Impl left rule
             if let Prop::Impl(impl_impl_fst, impl_impl_snd) = *impl_fst {
                 let fst_goal = Prop::Impl(impl_impl_fst, impl_impl_snd.clone());
-                let mut fst_sequent = searching_sequent.with_new_goal(&fst_goal);
+                let mut fst_sequent = self.fork_with_goal(&searching_sequent, &fst_goal);
                 let first_param_ident = self.generate_identifier();
 
                 let new_prop = Prop::Impl(impl_impl_snd.boxed(), impl_snd.boxed());
@@ -532,23 +892,249 @@ impl Prover {
                     }),
                 ));
 
-                if let Some(fst_proof_term) = self.prove_right(fst_sequent) {
-                    let mut snd_sequent = searching_sequent.clone();
+                if let Some(fst_proof_term) =
+                    self.prove_right(fst_sequent, ProverRule::ImplImplLeft)
+                {
+                    let mut snd_sequent = self.fork(&searching_sequent);
+                    snd_sequent.append_ordered(TypeJudgment::new(
+                        *impl_snd,
+                        Application::create(proof_term.boxed(), fst_proof_term.boxed(), None),
+                    ));
+
+                    if let Some(final_proof_term) =
+                        self.prove_left(snd_sequent, ProverRule::ImplImplLeft)
+                    {
+                        return Some(final_proof_term);
+                    }
+                }
+            } else if let Prop::ForAll { .. } | Prop::Exists { .. } = *impl_fst {
+                // Impl quantifier left rule
+                let fst_sequent = self.fork_with_goal(&searching_sequent, &impl_fst);
+                if let Some(fst_proof_term) =
+                    self.prove_right(fst_sequent, ProverRule::ImplQuantifierLeft)
+                {
+                    let mut snd_sequent = self.fork(&searching_sequent);
                     snd_sequent.append_ordered(TypeJudgment::new(
                         *impl_snd,
                         Application::create(proof_term.boxed(), fst_proof_term.boxed(), None),
                     ));
 
-                    if let Some(final_proof_term) = self.prove_left(snd_sequent) {
+                    if let Some(final_proof_term) =
+                        self.prove_left(snd_sequent, ProverRule::ImplQuantifierLeft)
+                    {
                         return Some(final_proof_term);
                     }
                 }
             }
         }
 
+        if sequent.instantiations >= self.config.max_instantiations {
+            return None;
+        }
+
+        // exists right rule
+        if let Prop::Exists {
+            object_ident,
+            object_type_ident,
+            body,
+        } = sequent.goal
+        {
+            for witness in
+                self.get_instantiation_candidates(&sequent, object_ident, object_type_ident, body)
+            {
+                let mut instance = *body.clone();
+                instance.substitute_free_parameter(object_ident, &witness);
+
+                let mut instance_sequent = self.fork_with_goal(&sequent, &instance);
+                instance_sequent.instantiations += 1;
+
+                if let Some(proof_term) =
+                    self.prove_right(instance_sequent, ProverRule::ExistsRight)
+                {
+                    return Some(Pair::create(
+                        term_to_proof_term(&witness).boxed(),
+                        proof_term.boxed(),
+                        None,
+                    ));
+                }
+            }
+        }
+
+        // forall left rule
+        for TypeJudgment { prop, proof_term } in sequent.unordered_ctx.iter() {
+            let Prop::ForAll {
+                object_ident,
+                object_type_ident,
+                body,
+            } = prop
+            else {
+                continue;
+            };
+
+            for term in
+                self.get_instantiation_candidates(&sequent, object_ident, object_type_ident, body)
+            {
+                let mut instance = *body.clone();
+                instance.substitute_free_parameter(object_ident, &term);
+
+                if sequent.contains_prop(&instance) {
+                    continue;
+                }
+
+                let mut instance_sequent = self.fork(&sequent);
+                instance_sequent.instantiations += 1;
+                instance_sequent.append_ordered(TypeJudgment::new(
+                    instance,
+                    Application::create(
+                        proof_term.boxed(),
+                        term_to_proof_term(&term).boxed(),
+                        None,
+                    ),
+                ));
+
+                if let Some(final_proof_term) =
+                    self.prove_left(instance_sequent, ProverRule::ForAllLeft)
+                {
+                    return Some(final_proof_term);
+                }
+            }
+        }
+
         None
     }
 
+    // Terms of the given datatype to instantiate a quantifier with.
+    // Terms obtained by unifying atoms of the body with atoms of the sequent come first.
+    fn get_instantiation_candidates(
+        &self,
+        sequent: &Sequent,
+        object_ident: &String,
+        object_type_ident: &String,
+        body: &Prop,
+    ) -> Vec<PropParameter> {
+        let mut metavariables = vec![object_ident.clone()];
+        let mut patterns = vec![];
+        collect_atoms(body, &mut metavariables, &mut patterns, true);
+
+        let mut targets = vec![];
+        for prop in sequent
+            .unordered_ctx
+            .iter()
+            .chain(sequent.ordered_ctx.iter())
+            .map(|elem| &elem.prop)
+            .chain(std::iter::once(sequent.goal))
+        {
+            collect_atoms(prop, &mut vec![], &mut targets, false);
+        }
+
+        let mut candidates = vec![];
+        for pattern in patterns.iter() {
+            for target in targets.iter() {
+                let mut substitution = vec![];
+                if !unify_atoms(pattern, target, &metavariables, &mut substitution) {
+                    continue;
+                }
+
+                let Some((_, term)) = substitution.iter().find(|(name, _)| name == object_ident)
+                else {
+                    continue;
+                };
+
+                let term = apply_substitution(term, &substitution);
+                if !candidates.contains(&term)
+                    && self.get_term_datatype(sequent, &term).as_ref() == Some(object_type_ident)
+                {
+                    candidates.push(term);
+                }
+            }
+        }
+
+        for (object, datatype) in sequent.objects.iter() {
+            if datatype == object_type_ident && !candidates.contains(object) {
+                candidates.push(object.clone());
+            }
+        }
+
+        // declared constants and functions applied to objects and constants, e.g. "f(a)"
+        let constants = self
+            .functions
+            .iter()
+            .filter(|function| function.param_types.is_empty())
+            .map(|function| {
+                (
+                    PropParameter::Function(function.name.clone(), vec![]),
+                    function.return_type.clone(),
+                )
+            })
+            .collect_vec();
+        let arguments = sequent
+            .objects
+            .iter()
+            .cloned()
+            .chain(constants.iter().cloned())
+            .collect_vec();
+
+        for function in self
+            .functions
+            .iter()
+            .filter(|function| function.return_type == *object_type_ident)
+        {
+            let terms = match function.param_types.is_empty() {
+                true => vec![PropParameter::Function(function.name.clone(), vec![])],
+                false => function
+                    .param_types
+                    .iter()
+                    .map(|param_type| {
+                        arguments
+                            .iter()
+                            .filter(|(_, datatype)| datatype == param_type)
+                            .map(|(argument, _)| argument.clone())
+                            .collect_vec()
+                    })
+                    .multi_cartesian_product()
+                    .map(|params| PropParameter::Function(function.name.clone(), params))
+                    .collect_vec(),
+            };
+
+            for term in terms {
+                if !candidates.contains(&term) {
+                    candidates.push(term);
+                }
+            }
+        }
+
+        candidates
+    }
+
+    fn get_term_datatype(&self, sequent: &Sequent, term: &PropParameter) -> Option<String> {
+        if let Some((_, datatype)) = sequent.objects.iter().find(|(object, _)| object == term) {
+            return Some(datatype.clone());
+        }
+
+        let (name, params) = match term {
+            PropParameter::Uninstantiated(name) => (name, &vec![]),
+            PropParameter::Function(name, params) => (name, params),
+            PropParameter::Instantiated(_) => return None,
+        };
+
+        let signature = self
+            .functions
+            .iter()
+            .find(|signature| signature.name == *name)?;
+
+        if signature.param_types.len() != params.len() {
+            return None;
+        }
+
+        for (param, param_type) in params.iter().zip(signature.param_types.iter()) {
+            if self.get_term_datatype(sequent, param).as_ref() != Some(param_type) {
+                return None;
+            }
+        }
+
+        Some(signature.return_type.clone())
+    }
+
     fn generate_identifier(&mut self) -> String {
         let mut ident = self.identifier_generator.generate();
 
@@ -559,3 +1145,171 @@ impl Prover {
         ident
     }
 }
+
+fn get_identifiers(prop: &Prop) -> Vec<String> {
+    fn _get_term_identifiers(param: &PropParameter) -> Vec<String> {
+        match param {
+            PropParameter::Uninstantiated(_) | PropParameter::Instantiated(_) => {
+                vec![param.name().clone()]
+            }
+            PropParameter::Function(name, params) => [
+                vec![name.clone()],
+                params.iter().flat_map(_get_term_identifiers).collect(),
+            ]
+            .concat(),
+        }
+    }
+
+    match prop {
+        Prop::True | Prop::False => vec![],
+        Prop::Atom(_, params) => params.iter().flat_map(_get_term_identifiers).collect(),
+        Prop::Eq(lhs, rhs) => [_get_term_identifiers(lhs), _get_term_identifiers(rhs)].concat(),
+        Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+            [get_identifiers(fst), get_identifiers(snd)].concat()
+        }
+        Prop::ForAll {
+            object_ident, body, ..
+        }
+        | Prop::Exists {
+            object_ident, body, ..
+        } => [vec![object_ident.clone()], get_identifiers(body)].concat(),
+        Prop::Defined(_, args) => args.iter().flat_map(get_identifiers).collect(),
+    }
+}
+
+// Collects atoms and equalities. Bound identifiers are added to bound_idents.
+fn collect_atoms(
+    prop: &Prop,
+    bound_idents: &mut Vec<String>,
+    atoms: &mut Vec<Prop>,
+    under_quantifiers: bool,
+) {
+    match prop {
+        Prop::True | Prop::False => {}
+        Prop::Atom(_, _) | Prop::Eq(_, _) => atoms.push(prop.clone()),
+        Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+            collect_atoms(fst, bound_idents, atoms, under_quantifiers);
+            collect_atoms(snd, bound_idents, atoms, under_quantifiers);
+        }
+        Prop::ForAll {
+            object_ident, body, ..
+        }
+        | Prop::Exists {
+            object_ident, body, ..
+        } => {
+            if under_quantifiers {
+                bound_idents.push(object_ident.clone());
+                collect_atoms(body, bound_idents, atoms, under_quantifiers);
+            }
+        }
+        Prop::Defined(_, args) => args
+            .iter()
+            .for_each(|arg| collect_atoms(arg, bound_idents, atoms, under_quantifiers)),
+    }
+}
+
+fn unify_atoms(
+    lhs: &Prop,
+    rhs: &Prop,
+    metavariables: &[String],
+    substitution: &mut Vec<(String, PropParameter)>,
+) -> bool {
+    match (lhs, rhs) {
+        (Prop::Atom(lhs_name, lhs_params), Prop::Atom(rhs_name, rhs_params)) => {
+            lhs_name == rhs_name
+                && lhs_params.len() == rhs_params.len()
+                && lhs_params
+                    .iter()
+                    .zip(rhs_params.iter())
+                    .all(|(lhs, rhs)| unify(lhs, rhs, metavariables, substitution))
+        }
+        (Prop::Eq(lhs_fst, lhs_snd), Prop::Eq(rhs_fst, rhs_snd)) => {
+            unify(lhs_fst, rhs_fst, metavariables, substitution)
+                && unify(lhs_snd, rhs_snd, metavariables, substitution)
+        }
+        _ => false,
+    }
+}
+
+// Syntactic unification, extending the given substitution.
+fn unify(
+    lhs: &PropParameter,
+    rhs: &PropParameter,
+    metavariables: &[String],
+    substitution: &mut Vec<(String, PropParameter)>,
+) -> bool {
+    let lhs = apply_substitution(lhs, substitution);
+    let rhs = apply_substitution(rhs, substitution);
+
+    if lhs == rhs {
+        return true;
+    }
+
+    match (&lhs, &rhs) {
+        (PropParameter::Uninstantiated(name), term)
+        | (term, PropParameter::Uninstantiated(name))
+            if metavariables.contains(name) =>
+        {
+            // occurs check
+            if term
+                .get_variables()
+                .iter()
+                .any(|variable| variable.is_uninstantiated() && variable.name() == name)
+            {
+                return false;
+            }
+
+            substitution.push((name.clone(), term.clone()));
+            true
+        }
+        (
+            PropParameter::Function(lhs_name, lhs_params),
+            PropParameter::Function(rhs_name, rhs_params),
+        ) => {
+            lhs_name == rhs_name
+                && lhs_params.len() == rhs_params.len()
+                && lhs_params
+                    .iter()
+                    .zip(rhs_params.iter())
+                    .all(|(lhs, rhs)| unify(lhs, rhs, metavariables, substitution))
+        }
+        _ => false,
+    }
+}
+
+fn apply_substitution(
+    term: &PropParameter,
+    substitution: &[(String, PropParameter)],
+) -> PropParameter {
+    match term {
+        PropParameter::Uninstantiated(name) => {
+            match substitution.iter().find(|(variable, _)| variable == name) {
+                Some((_, substitutor)) => apply_substitution(substitutor, substitution),
+                None => term.clone(),
+            }
+        }
+        PropParameter::Instantiated(_) => term.clone(),
+        PropParameter::Function(name, params) => PropParameter::Function(
+            name.clone(),
+            params
+                .iter()
+                .map(|param| apply_substitution(param, substitution))
+                .collect(),
+        ),
+    }
+}
+
+fn term_to_proof_term(term: &PropParameter) -> ProofTerm {
+    match term {
+        PropParameter::Uninstantiated(_) | PropParameter::Instantiated(_) => {
+            Ident::create(term.name().clone())
+        }
+        PropParameter::Function(name, params) => {
+            params
+                .iter()
+                .fold(Ident::create(name.clone()), |function, param| {
+                    Application::create(function.boxed(), term_to_proof_term(param).boxed(), None)
+                })
+        }
+    }
+}
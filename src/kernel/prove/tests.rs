@@ -4,7 +4,13 @@ mod tests {
 
     use crate::kernel::{
         checker::{check::check, identifier_context::IdentifierContext},
-        parse::{fol::fol_parser, lexer::lexer}, prove::prove,
+        parse::{fol::fol_parser, lexer::lexer},
+        prop::{FunctionSignature, Prop},
+        prove::{
+            find_proof, find_proof_with_trace, prove, prove_with_config, prove_with_ctx,
+            trace::{ProverRule, ProverTrace},
+            ProverConfig, ProverOutcome,
+        },
     };
 
     pub fn assert_proof(prop: &str) {
@@ -30,6 +36,19 @@ mod tests {
         .is_ok());
     }
 
+    fn parse_prop(prop: &str) -> Prop {
+        let prop_tokens = lexer().then_ignore(end()).parse(prop).unwrap();
+
+        let prop_len = prop.chars().count();
+
+        fol_parser()
+            .parse(Stream::from_iter(
+                prop_len..prop_len + 1,
+                prop_tokens.into_iter(),
+            ))
+            .unwrap()
+    }
+
     pub fn assert_no_proof(prop: &str) {
         let prop_tokens = lexer().then_ignore(end()).parse(prop).unwrap();
 
@@ -172,6 +191,16 @@ mod tests {
         assert_proof("A || B -> B || A -> B || A -> A || B");
     }
 
+    #[test]
+    fn test_iff_symmetric() {
+        assert_proof("(A <-> B) -> (B <-> A)");
+    }
+
+    #[test]
+    fn test_iff_no_proof() {
+        assert_no_proof("(A -> B) -> (A <-> B)");
+    }
+
     #[test]
     fn test_peirces_law() {
         assert_no_proof("((A -> B) -> A) -> A");
@@ -249,4 +278,262 @@ mod tests {
     fn test_three_way_composition() {
         assert_proof("(A -> B) -> (B -> C) -> (C -> D) -> (A -> D)");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_forall_renaming() {
+        assert_proof("(\\forall x:t. P(x)) -> \\forall y:t. P(y)");
+        assert_proof("(\\exists x:t. P(x)) -> \\exists y:t. P(y)");
+    }
+
+    #[test]
+    fn test_forall_distribution() {
+        assert_proof("(\\forall x:t. P(x) -> Q(x)) -> (\\forall x:t. P(x)) -> \\forall x:t. Q(x)");
+        assert_proof("(\\forall x:t. P(x) && Q(x)) -> (\\forall x:t. P(x)) && \\forall x:t. Q(x)");
+        assert_proof("(\\exists x:t. P(x) && Q(x)) -> \\exists x:t. P(x)");
+        assert_proof("(\\forall x:t. \\forall y:t. R(x, y)) -> \\forall z:t. R(z, z)");
+    }
+
+    #[test]
+    fn test_quantifier_swap() {
+        assert_proof(
+            "(\\exists y:t. \\forall x:t. R(x, y)) -> \\forall x:t. \\exists y:t. R(x, y)",
+        );
+        assert_no_proof(
+            "(\\forall x:t. \\exists y:t. R(x, y)) -> \\exists y:t. \\forall x:t. R(x, y)",
+        );
+    }
+
+    #[test]
+    fn test_quantifier_negation() {
+        assert_proof("~(\\exists x:t. P(x)) -> \\forall x:t. ~P(x)");
+        assert_proof("(\\forall x:t. ~P(x)) -> ~\\exists x:t. P(x)");
+        assert_proof("(\\exists x:t. ~P(x)) -> ~\\forall x:t. P(x)");
+        assert_no_proof("~(\\forall x:t. P(x)) -> \\exists x:t. ~P(x)");
+    }
+
+    #[test]
+    fn test_quantifier_empty_domain() {
+        assert_no_proof("(\\forall x:t. P(x)) -> \\exists x:t. P(x)");
+        assert_proof("\\forall y:t. (\\forall x:t. P(x)) -> \\exists x:t. P(x)");
+    }
+
+    #[test]
+    fn test_quantified_equality() {
+        assert_proof("\\forall x:t. x = x");
+        assert_proof("(\\forall x:t. \\forall y:t. x = y) -> \\forall x:t. \\forall y:t. y = x");
+    }
+
+    #[test]
+    fn test_forall_instantiation_with_function() {
+        let prop = "(\\forall x:nat. P(x)) -> \\forall y:nat. P(succ(y))";
+        let prop_tokens = lexer().then_ignore(end()).parse(prop).unwrap();
+        let prop_len = prop.chars().count();
+        let prop = fol_parser()
+            .parse(Stream::from_iter(
+                prop_len..prop_len + 1,
+                prop_tokens.into_iter(),
+            ))
+            .unwrap();
+
+        let ctx = IdentifierContext::with_functions(vec![FunctionSignature {
+            name: "succ".to_string(),
+            param_types: vec!["nat".to_string()],
+            return_type: "nat".to_string(),
+        }]);
+
+        assert!(prove_with_ctx(&prop, &ctx).is_some());
+        assert!(prove(&prop).is_none());
+    }
+
+    #[test]
+    fn test_exists_witness_with_function() {
+        let prop = parse_prop("\\forall x:t. P(succ(x)) -> \\exists y:t. P(y)");
+        let ctx = IdentifierContext::with_functions(vec![FunctionSignature {
+            name: "succ".to_string(),
+            param_types: vec!["t".to_string()],
+            return_type: "t".to_string(),
+        }]);
+
+        assert!(prove_with_ctx(&prop, &ctx).is_some());
+    }
+
+    #[test]
+    fn test_exists_witness_function_term() {
+        let ctx = IdentifierContext::with_functions(vec![
+            FunctionSignature {
+                name: "f".to_string(),
+                param_types: vec!["t".to_string()],
+                return_type: "t".to_string(),
+            },
+            FunctionSignature {
+                name: "zero".to_string(),
+                param_types: vec![],
+                return_type: "nat".to_string(),
+            },
+        ]);
+
+        assert!(
+            prove_with_ctx(&parse_prop("\\forall a:t. \\exists y:t. y = f(a)"), &ctx).is_some()
+        );
+        assert!(prove_with_ctx(&parse_prop("\\exists x:nat. \\top"), &ctx).is_some());
+        assert!(prove(&parse_prop("\\exists x:nat. \\top")).is_none());
+    }
+
+    #[test]
+    fn test_function_signatures_unknown() {
+        let prop = parse_prop("\\forall y:t. P(f(y)) -> P(f(y))");
+
+        // the proof does not type check without the signature of f
+        assert_eq!(
+            prove_with_config(&prop, &ProverConfig::default()),
+            ProverOutcome::GaveUp
+        );
+
+        let ctx = IdentifierContext::with_functions(vec![FunctionSignature {
+            name: "f".to_string(),
+            param_types: vec!["t".to_string()],
+            return_type: "t".to_string(),
+        }]);
+
+        assert!(prove_with_ctx(&prop, &ctx).is_some());
+    }
+
+    #[test]
+    fn test_outcome() {
+        let config = ProverConfig::default();
+
+        assert!(matches!(
+            prove_with_config(&parse_prop("A -> A"), &config),
+            ProverOutcome::Proved(_)
+        ));
+        assert_eq!(
+            prove_with_config(&parse_prop("A -> B"), &config),
+            ProverOutcome::Refuted
+        );
+        assert_eq!(
+            prove_with_config(
+                &parse_prop("(\\forall x:t. P(x)) -> \\exists x:t. P(x)"),
+                &config
+            ),
+            ProverOutcome::GaveUp
+        );
+        assert_eq!(
+            prove_with_config(
+                &parse_prop("\\forall z:t. (\\forall x:t. \\exists y:t. R(x, y)) -> \\exists y:t. \\forall x:t. R(x, y)"),
+                &config
+            ),
+            ProverOutcome::GaveUp
+        );
+    }
+
+    #[test]
+    fn test_outcome_with_witnesses() {
+        let ctx = IdentifierContext::with_functions(vec![
+            FunctionSignature {
+                name: "f".to_string(),
+                param_types: vec!["t".to_string()],
+                return_type: "t".to_string(),
+            },
+            FunctionSignature {
+                name: "zero".to_string(),
+                param_types: vec![],
+                return_type: "nat".to_string(),
+            },
+        ]);
+
+        // a failed search for a witness does not refute the proposition
+        assert_ne!(
+            find_proof(&parse_prop("\\forall a:t. \\exists y:t. y = f(a)"), &ctx),
+            ProverOutcome::Refuted
+        );
+        assert_ne!(
+            find_proof(&parse_prop("\\exists x:nat. \\top"), &ctx),
+            ProverOutcome::Refuted
+        );
+    }
+
+    #[test]
+    fn test_outcome_with_limits() {
+        let prop = parse_prop("(A -> B) -> (B -> C) -> (C -> D) -> (A -> D)");
+
+        let config = ProverConfig {
+            max_steps: 5,
+            ..ProverConfig::default()
+        };
+        assert_eq!(prove_with_config(&prop, &config), ProverOutcome::GaveUp);
+
+        let config = ProverConfig {
+            max_depth: 3,
+            ..ProverConfig::default()
+        };
+        assert_eq!(prove_with_config(&prop, &config), ProverOutcome::GaveUp);
+
+        let config = ProverConfig {
+            max_cloned_sequents: 0,
+            ..ProverConfig::default()
+        };
+        assert_eq!(prove_with_config(&prop, &config), ProverOutcome::GaveUp);
+    }
+
+    fn trace_nodes(trace: &ProverTrace) -> Vec<&ProverTrace> {
+        let mut nodes = vec![trace];
+        for child in trace.children.iter() {
+            nodes.extend(trace_nodes(child));
+        }
+
+        nodes
+    }
+
+    #[test]
+    fn test_trace() {
+        let prop = parse_prop("A && B -> B && A");
+        let traced = find_proof_with_trace(&prop, &IdentifierContext::new());
+
+        assert!(matches!(traced.outcome, ProverOutcome::Proved(_)));
+
+        let trace = traced.trace.unwrap();
+        assert_eq!(trace.rule, ProverRule::Start);
+        assert_eq!(trace.goal, prop);
+        assert!(trace.success);
+        assert!(!trace.backtracked);
+
+        let nodes = trace_nodes(&trace);
+        assert!(nodes.iter().all(|node| node.success));
+        assert_eq!(
+            nodes
+                .iter()
+                .filter(|node| node.closed_by == Some(ProverRule::Id))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_trace_backtracking() {
+        let traced = find_proof_with_trace(&parse_prop("A -> B || A"), &IdentifierContext::new());
+
+        assert!(matches!(traced.outcome, ProverOutcome::Proved(_)));
+
+        let trace = traced.trace.unwrap();
+        let backtracked = trace_nodes(&trace)
+            .into_iter()
+            .find(|node| node.backtracked)
+            .unwrap();
+
+        assert_eq!(backtracked.children.len(), 2);
+        assert_eq!(backtracked.children[0].rule, ProverRule::OrRightFst);
+        assert!(!backtracked.children[0].success);
+        assert_eq!(backtracked.children[1].rule, ProverRule::OrRightSnd);
+        assert!(backtracked.children[1].success);
+    }
+
+    #[test]
+    fn test_trace_failure() {
+        let traced = find_proof_with_trace(&parse_prop("A -> B"), &IdentifierContext::new());
+
+        assert_eq!(traced.outcome, ProverOutcome::Refuted);
+
+        let trace = traced.trace.unwrap();
+        assert!(trace_nodes(&trace).iter().all(|node| !node.success));
+    }
+}
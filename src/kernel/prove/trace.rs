@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+
+use crate::kernel::prop::Prop;
+
+use super::ProverOutcome;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum ProverRule {
+    Start,
+
+    // goal is not decomposed any further, continue with the context
+    FocusLeft,
+
+    // right rules
+    AndRight,
+    ImplRight,
+    ForAllRight,
+    OrRightFst,
+    OrRightSnd,
+    ExistsRight,
+
+    // left rules
+    TrueLeft,
+    AndLeft,
+    OrLeft,
+    ExistsLeft,
+    ForAllLeft,
+    StoreLeft,
+    TrueImplLeft,
+    AndImplLeft,
+    OrImplLeft,
+    FalseImplLeft,
+    AtomImplLeft,
+    ImplImplLeft,
+    ImplQuantifierLeft,
+
+    // axioms
+    TrueRight,
+    FalseLeft,
+    Id,
+    Refl,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct ProverTrace {
+    pub ordered_ctx: Vec<Prop>,
+    pub unordered_ctx: Vec<Prop>,
+    pub goal: Prop,
+
+    // rule that yielded this sequent from its parent
+    pub rule: ProverRule,
+
+    // axiom that closed this sequent
+    pub closed_by: Option<ProverRule>,
+
+    pub success: bool,
+
+    // true if a failed attempt was followed by another one
+    pub backtracked: bool,
+
+    pub children: Vec<ProverTrace>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct TracedProverOutcome {
+    pub outcome: ProverOutcome,
+    pub trace: Option<ProverTrace>,
+}
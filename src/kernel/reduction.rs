@@ -0,0 +1,939 @@
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+
+use super::{
+    proof_term::{
+        Abort, Application, Case, DoubleNegElim, Function, Ident, LetIn, OrLeft, OrRight, Pair,
+        ProjectFst, ProjectSnd, ProofTerm, Subst, Type, TypeAscription,
+    },
+    prop::PropParameter,
+};
+
+pub const DEFAULT_MAX_REDUCTION_STEPS: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum ReductionRule {
+    // (fn x => t) s
+    Beta,
+    // fst (t, s), snd (t, s)
+    ProjectFst,
+    ProjectSnd,
+    // case inl t of ..., case inr t of ...
+    CaseLeft,
+    CaseRight,
+    // let (x, u) = (t, s) in ...
+    LetIn,
+    // E[case t of ...], E[let (x, u) = t in ...], E[abort t] for eliminations E
+    CaseCommute,
+    LetInCommute,
+    AbortCommute,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct ReductionStep {
+    pub rule: ReductionRule,
+
+    // the contracted subterm, before contraction
+    pub redex: ProofTerm,
+
+    // the whole proof term, after contraction
+    pub proof_term: ProofTerm,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct Normalization {
+    pub steps: Vec<ReductionStep>,
+    pub normal_form: ProofTerm,
+
+    // false if the step limit was reached first
+    pub normalized: bool,
+}
+
+// Reduces the leftmost outermost redex, or returns None if the proof term is in normal form.
+pub fn reduce_step(proof_term: &ProofTerm) -> Option<ReductionStep> {
+    let (rule, redex, proof_term) = reduce_leftmost_outermost(proof_term)?;
+
+    Some(ReductionStep {
+        rule,
+        redex,
+        proof_term,
+    })
+}
+
+// Normal order reduction, which finds the normal form whenever there is one.
+pub fn normalize(proof_term: &ProofTerm, max_steps: usize) -> Normalization {
+    let mut steps: Vec<ReductionStep> = vec![];
+    let mut current = proof_term.clone();
+
+    while steps.len() < max_steps {
+        let Some(step) = reduce_step(&current) else {
+            return Normalization {
+                steps,
+                normal_form: current,
+                normalized: true,
+            };
+        };
+
+        current = step.proof_term.clone();
+        steps.push(step);
+    }
+
+    let normalized = reduce_step(&current).is_none();
+
+    Normalization {
+        steps,
+        normal_form: current,
+        normalized,
+    }
+}
+
+// Ascriptions do not block reductions, e.g. "(fn x => x: A -> A) a".
+fn strip_ascriptions(proof_term: &ProofTerm) -> &ProofTerm {
+    match proof_term {
+        ProofTerm::TypeAscription(TypeAscription { proof_term, .. }) => {
+            strip_ascriptions(proof_term)
+        }
+        _ => proof_term,
+    }
+}
+
+fn contract(proof_term: &ProofTerm) -> Option<(ReductionRule, ProofTerm)> {
+    eliminate_detour(proof_term).or_else(|| commute(proof_term))
+}
+
+// An introduction immediately followed by an elimination.
+fn eliminate_detour(proof_term: &ProofTerm) -> Option<(ReductionRule, ProofTerm)> {
+    match proof_term {
+        ProofTerm::Application(Application {
+            function,
+            applicant,
+            ..
+        }) => match strip_ascriptions(function) {
+            ProofTerm::Function(Function {
+                param_ident, body, ..
+            }) => Some((
+                ReductionRule::Beta,
+                substitute(body, param_ident, applicant),
+            )),
+            _ => None,
+        },
+        ProofTerm::ProjectFst(ProjectFst(body, _)) => match strip_ascriptions(body) {
+            ProofTerm::Pair(Pair(fst, _, _)) => Some((ReductionRule::ProjectFst, *fst.clone())),
+            _ => None,
+        },
+        ProofTerm::ProjectSnd(ProjectSnd(body, _)) => match strip_ascriptions(body) {
+            ProofTerm::Pair(Pair(_, snd, _)) => Some((ReductionRule::ProjectSnd, *snd.clone())),
+            _ => None,
+        },
+        ProofTerm::Case(Case {
+            head,
+            fst_ident,
+            fst_term,
+            snd_ident,
+            snd_term,
+            ..
+        }) => match strip_ascriptions(head) {
+            ProofTerm::OrLeft(OrLeft(body, _)) => Some((
+                ReductionRule::CaseLeft,
+                substitute(fst_term, fst_ident, body),
+            )),
+            ProofTerm::OrRight(OrRight(body, _)) => Some((
+                ReductionRule::CaseRight,
+                substitute(snd_term, snd_ident, body),
+            )),
+            _ => None,
+        },
+        ProofTerm::LetIn(LetIn {
+            fst_ident,
+            snd_ident,
+            head,
+            body,
+            ..
+        }) => match strip_ascriptions(head) {
+            ProofTerm::Pair(Pair(witness, proof, _)) => {
+                // the second identifier shadows the first one
+                if fst_ident == snd_ident {
+                    return Some((ReductionRule::LetIn, substitute(body, snd_ident, proof)));
+                }
+
+                // substituting the witness must not introduce occurrences of the second identifier
+                let (snd_ident, body) = if free_variables(witness).contains(snd_ident) {
+                    let fresh_ident = fresh_identifier(snd_ident, &[witness, proof, body]);
+                    let body = substitute(body, snd_ident, &Ident::create(fresh_ident.clone()));
+
+                    (fresh_ident, body)
+                } else {
+                    (snd_ident.clone(), *body.clone())
+                };
+
+                let body = substitute(&body, fst_ident, witness);
+
+                Some((ReductionRule::LetIn, substitute(&body, &snd_ident, proof)))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+type Elimination<'a> = (&'a ProofTerm, Box<dyn Fn(ProofTerm) -> ProofTerm + 'a>);
+
+// The major premisse of an elimination, and the elimination of another major premisse.
+fn split_elimination(proof_term: &ProofTerm) -> Option<Elimination<'_>> {
+    match proof_term {
+        ProofTerm::ProjectFst(ProjectFst(body, span)) => Some((
+            body,
+            Box::new(move |body| ProjectFst::create(body.boxed(), span.clone())),
+        )),
+        ProofTerm::ProjectSnd(ProjectSnd(body, span)) => Some((
+            body,
+            Box::new(move |body| ProjectSnd::create(body.boxed(), span.clone())),
+        )),
+        ProofTerm::Abort(Abort(body, span)) => Some((
+            body,
+            Box::new(move |body| Abort::create(body.boxed(), span.clone())),
+        )),
+        ProofTerm::Application(application) => Some((
+            &application.function,
+            Box::new(move |function| {
+                ProofTerm::Application(Application {
+                    function: function.boxed(),
+                    ..application.clone()
+                })
+            }),
+        )),
+        ProofTerm::Case(case) => Some((
+            &case.head,
+            Box::new(move |head| {
+                ProofTerm::Case(Case {
+                    head: head.boxed(),
+                    ..case.clone()
+                })
+            }),
+        )),
+        ProofTerm::LetIn(let_in) => Some((
+            &let_in.head,
+            Box::new(move |head| {
+                ProofTerm::LetIn(LetIn {
+                    head: head.boxed(),
+                    ..let_in.clone()
+                })
+            }),
+        )),
+        ProofTerm::Subst(subst) => Some((
+            &subst.equality,
+            Box::new(move |equality| {
+                ProofTerm::Subst(Subst {
+                    equality: equality.boxed(),
+                    ..subst.clone()
+                })
+            }),
+        )),
+        _ => None,
+    }
+}
+
+// Permutes an elimination into the branches of a case, let or abort it is applied to.
+fn commute(proof_term: &ProofTerm) -> Option<(ReductionRule, ProofTerm)> {
+    let (major, eliminate) = split_elimination(proof_term)?;
+
+    // the elimination must not get captured by the binders it is moved below
+    let context = eliminate(ProofTerm::Unit(None));
+    let context_variables = free_variables(&context);
+
+    let rename = |ident: &String, body: &ProofTerm, avoid: &[&ProofTerm]| {
+        if !context_variables.contains(ident) {
+            return (ident.clone(), body.clone());
+        }
+
+        let fresh_ident = fresh_identifier(ident, &[&[body, &context], avoid].concat());
+        let body = substitute(body, ident, &Ident::create(fresh_ident.clone()));
+
+        (fresh_ident, body)
+    };
+
+    match strip_ascriptions(major) {
+        ProofTerm::Case(case) => {
+            let (fst_ident, fst_term) = rename(&case.fst_ident, &case.fst_term, &[]);
+            let (snd_ident, snd_term) = rename(&case.snd_ident, &case.snd_term, &[]);
+
+            Some((
+                ReductionRule::CaseCommute,
+                ProofTerm::Case(Case {
+                    fst_ident,
+                    fst_term: eliminate(fst_term).boxed(),
+                    snd_ident,
+                    snd_term: eliminate(snd_term).boxed(),
+                    ..case.clone()
+                }),
+            ))
+        }
+        ProofTerm::LetIn(let_in) => {
+            let (snd_ident, body) = rename(&let_in.snd_ident, &let_in.body, &[]);
+            let (fst_ident, body) = rename(
+                &let_in.fst_ident,
+                &body,
+                &[&Ident::create(snd_ident.clone())],
+            );
+
+            Some((
+                ReductionRule::LetInCommute,
+                ProofTerm::LetIn(LetIn {
+                    fst_ident,
+                    snd_ident,
+                    body: eliminate(body).boxed(),
+                    ..let_in.clone()
+                }),
+            ))
+        }
+        abort @ ProofTerm::Abort(_) => Some((ReductionRule::AbortCommute, abort.clone())),
+        _ => None,
+    }
+}
+
+type Reduction = (ReductionRule, ProofTerm, ProofTerm);
+
+// Reduces within a child and rebuilds the proof term around the reduced child.
+fn reduce_child(
+    child: &ProofTerm,
+    rebuild: impl FnOnce(Box<ProofTerm>) -> ProofTerm,
+) -> Option<Reduction> {
+    let (rule, redex, reduced) = reduce_leftmost_outermost(child)?;
+
+    Some((rule, redex, rebuild(reduced.boxed())))
+}
+
+fn reduce_leftmost_outermost(proof_term: &ProofTerm) -> Option<Reduction> {
+    if let Some((rule, contractum)) = contract(proof_term) {
+        return Some((rule, proof_term.clone(), contractum));
+    }
+
+    match proof_term {
+        ProofTerm::Ident(_) | ProofTerm::Unit(_) | ProofTerm::Refl(_) | ProofTerm::Sorry(_) => None,
+        ProofTerm::Pair(Pair(fst, snd, span)) => {
+            reduce_child(fst, |fst| Pair::create(fst, snd.clone(), span.clone()))
+                .or_else(|| reduce_child(snd, |snd| Pair::create(fst.clone(), snd, span.clone())))
+        }
+        ProofTerm::ProjectFst(ProjectFst(body, span)) => {
+            reduce_child(body, |body| ProjectFst::create(body, span.clone()))
+        }
+        ProofTerm::ProjectSnd(ProjectSnd(body, span)) => {
+            reduce_child(body, |body| ProjectSnd::create(body, span.clone()))
+        }
+        ProofTerm::Function(function) => reduce_child(&function.body, |body| {
+            ProofTerm::Function(Function {
+                body,
+                ..function.clone()
+            })
+        }),
+        ProofTerm::Application(application) => reduce_child(&application.function, |function| {
+            ProofTerm::Application(Application {
+                function,
+                ..application.clone()
+            })
+        })
+        .or_else(|| {
+            reduce_child(&application.applicant, |applicant| {
+                ProofTerm::Application(Application {
+                    applicant,
+                    ..application.clone()
+                })
+            })
+        }),
+        ProofTerm::LetIn(let_in) => reduce_child(&let_in.head, |head| {
+            ProofTerm::LetIn(LetIn {
+                head,
+                ..let_in.clone()
+            })
+        })
+        .or_else(|| {
+            reduce_child(&let_in.body, |body| {
+                ProofTerm::LetIn(LetIn {
+                    body,
+                    ..let_in.clone()
+                })
+            })
+        }),
+        ProofTerm::OrLeft(OrLeft(body, span)) => {
+            reduce_child(body, |body| OrLeft::create(body, span.clone()))
+        }
+        ProofTerm::OrRight(OrRight(body, span)) => {
+            reduce_child(body, |body| OrRight::create(body, span.clone()))
+        }
+        ProofTerm::Case(case) => reduce_child(&case.head, |head| {
+            ProofTerm::Case(Case {
+                head,
+                ..case.clone()
+            })
+        })
+        .or_else(|| {
+            reduce_child(&case.fst_term, |fst_term| {
+                ProofTerm::Case(Case {
+                    fst_term,
+                    ..case.clone()
+                })
+            })
+        })
+        .or_else(|| {
+            reduce_child(&case.snd_term, |snd_term| {
+                ProofTerm::Case(Case {
+                    snd_term,
+                    ..case.clone()
+                })
+            })
+        }),
+        ProofTerm::Abort(Abort(body, span)) => {
+            reduce_child(body, |body| Abort::create(body, span.clone()))
+        }
+        ProofTerm::DoubleNegElim(DoubleNegElim(body, span)) => {
+            reduce_child(body, |body| DoubleNegElim::create(body, span.clone()))
+        }
+        ProofTerm::TypeAscription(type_ascription) => {
+            reduce_child(&type_ascription.proof_term, |proof_term| {
+                ProofTerm::TypeAscription(TypeAscription {
+                    proof_term,
+                    ..type_ascription.clone()
+                })
+            })
+        }
+        ProofTerm::Subst(subst) => reduce_child(&subst.equality, |equality| {
+            ProofTerm::Subst(Subst {
+                equality,
+                ..subst.clone()
+            })
+        })
+        .or_else(|| {
+            reduce_child(&subst.body, |body| {
+                ProofTerm::Subst(Subst {
+                    body,
+                    ..subst.clone()
+                })
+            })
+        }),
+    }
+}
+
+// Identifiers occurring free in the proof term, including the object variables of its annotations.
+pub fn free_variables(proof_term: &ProofTerm) -> Vec<String> {
+    fn type_variables(_type: &Option<Type>) -> Vec<String> {
+        match _type {
+            Some(Type::Prop(prop)) => prop
+                .get_free_parameters()
+                .iter()
+                .map(|param| param.name().clone())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    let without = |variables: Vec<String>, idents: &[&String]| {
+        variables
+            .into_iter()
+            .filter(|variable| !idents.contains(&variable))
+            .collect::<Vec<_>>()
+    };
+
+    match proof_term {
+        ProofTerm::Ident(Ident(ident, _)) => vec![ident.clone()],
+        ProofTerm::Unit(_) | ProofTerm::Refl(_) | ProofTerm::Sorry(_) => vec![],
+        ProofTerm::Pair(Pair(fst, snd, _)) => [free_variables(fst), free_variables(snd)].concat(),
+        ProofTerm::ProjectFst(ProjectFst(body, _))
+        | ProofTerm::ProjectSnd(ProjectSnd(body, _))
+        | ProofTerm::OrLeft(OrLeft(body, _))
+        | ProofTerm::OrRight(OrRight(body, _))
+        | ProofTerm::Abort(Abort(body, _))
+        | ProofTerm::DoubleNegElim(DoubleNegElim(body, _)) => free_variables(body),
+        ProofTerm::Function(Function {
+            param_ident,
+            param_type,
+            body,
+            ..
+        }) => [
+            type_variables(param_type),
+            without(free_variables(body), &[param_ident]),
+        ]
+        .concat(),
+        ProofTerm::Application(Application {
+            function,
+            applicant,
+            ..
+        }) => [free_variables(function), free_variables(applicant)].concat(),
+        ProofTerm::LetIn(LetIn {
+            fst_ident,
+            snd_ident,
+            head,
+            body,
+            ..
+        }) => [
+            free_variables(head),
+            without(free_variables(body), &[fst_ident, snd_ident]),
+        ]
+        .concat(),
+        ProofTerm::Case(Case {
+            head,
+            fst_ident,
+            fst_term,
+            snd_ident,
+            snd_term,
+            ..
+        }) => [
+            free_variables(head),
+            without(free_variables(fst_term), &[fst_ident]),
+            without(free_variables(snd_term), &[snd_ident]),
+        ]
+        .concat(),
+        ProofTerm::TypeAscription(TypeAscription {
+            proof_term,
+            ascription,
+            ..
+        }) => [
+            free_variables(proof_term),
+            type_variables(&Some(ascription.clone())),
+        ]
+        .concat(),
+        ProofTerm::Subst(Subst { equality, body, .. }) => {
+            [free_variables(equality), free_variables(body)].concat()
+        }
+    }
+}
+
+// Whether the name occurs anywhere in the proof term, bound or free.
+fn contains_name(proof_term: &ProofTerm, name: &String) -> bool {
+    let type_contains_name = |_type: &Option<Type>| match _type {
+        Some(Type::Prop(prop)) => prop.contains_name(name),
+        _ => false,
+    };
+
+    match proof_term {
+        ProofTerm::Ident(Ident(ident, _)) => ident == name,
+        ProofTerm::Unit(_) | ProofTerm::Refl(_) | ProofTerm::Sorry(_) => false,
+        ProofTerm::Pair(Pair(fst, snd, _)) => contains_name(fst, name) || contains_name(snd, name),
+        ProofTerm::ProjectFst(ProjectFst(body, _))
+        | ProofTerm::ProjectSnd(ProjectSnd(body, _))
+        | ProofTerm::OrLeft(OrLeft(body, _))
+        | ProofTerm::OrRight(OrRight(body, _))
+        | ProofTerm::Abort(Abort(body, _))
+        | ProofTerm::DoubleNegElim(DoubleNegElim(body, _)) => contains_name(body, name),
+        ProofTerm::Function(Function {
+            param_ident,
+            param_type,
+            body,
+            ..
+        }) => param_ident == name || type_contains_name(param_type) || contains_name(body, name),
+        ProofTerm::Application(Application {
+            function,
+            applicant,
+            ..
+        }) => contains_name(function, name) || contains_name(applicant, name),
+        ProofTerm::LetIn(LetIn {
+            fst_ident,
+            snd_ident,
+            head,
+            body,
+            ..
+        }) => {
+            fst_ident == name
+                || snd_ident == name
+                || contains_name(head, name)
+                || contains_name(body, name)
+        }
+        ProofTerm::Case(Case {
+            head,
+            fst_ident,
+            fst_term,
+            snd_ident,
+            snd_term,
+            ..
+        }) => {
+            fst_ident == name
+                || snd_ident == name
+                || contains_name(head, name)
+                || contains_name(fst_term, name)
+                || contains_name(snd_term, name)
+        }
+        ProofTerm::TypeAscription(TypeAscription {
+            proof_term,
+            ascription,
+            ..
+        }) => contains_name(proof_term, name) || type_contains_name(&Some(ascription.clone())),
+        ProofTerm::Subst(Subst { equality, body, .. }) => {
+            contains_name(equality, name) || contains_name(body, name)
+        }
+    }
+}
+
+fn fresh_identifier(ident: &str, proof_terms: &[&ProofTerm]) -> String {
+    let mut fresh_ident = ident.to_string();
+    while proof_terms
+        .iter()
+        .any(|proof_term| contains_name(proof_term, &fresh_ident))
+    {
+        fresh_ident.push('\'');
+    }
+
+    fresh_ident
+}
+
+// The object a term-shaped proof term denotes, e.g. "f (g x)" denotes f(g(x)).
+fn object_term(proof_term: &ProofTerm) -> Option<PropParameter> {
+    let mut head = strip_ascriptions(proof_term);
+    let mut args = vec![];
+
+    while let ProofTerm::Application(Application {
+        function,
+        applicant,
+        ..
+    }) = head
+    {
+        args.insert(0, object_term(applicant)?);
+        head = strip_ascriptions(function);
+    }
+
+    let ProofTerm::Ident(Ident(name, _)) = head else {
+        return None;
+    };
+
+    if args.is_empty() {
+        Some(PropParameter::Uninstantiated(name.clone()))
+    } else {
+        Some(PropParameter::Function(name.clone(), args))
+    }
+}
+
+// Object variables in annotations are replaced by the object the substitutor denotes.
+fn substitute_type(_type: &Type, ident: &String, substitutor: &ProofTerm) -> Type {
+    match (_type, object_term(substitutor)) {
+        (Type::Prop(prop), Some(term)) => {
+            let mut prop = prop.clone();
+            prop.substitute_free_parameter(ident, &term);
+
+            Type::Prop(prop)
+        }
+        _ => _type.clone(),
+    }
+}
+
+// Capture-avoiding substitution of the substitutor for the free occurrences of the identifier.
+pub fn substitute(proof_term: &ProofTerm, ident: &String, substitutor: &ProofTerm) -> ProofTerm {
+    let substitutor_variables = free_variables(substitutor);
+
+    // Substitutes below a binder, which gets renamed if it would capture the substitutor.
+    let substitute_bound = |bound_ident: &String, body: &ProofTerm| -> (String, ProofTerm) {
+        if bound_ident == ident {
+            return (bound_ident.clone(), body.clone());
+        }
+
+        if substitutor_variables.contains(bound_ident) && free_variables(body).contains(ident) {
+            let fresh_ident = fresh_identifier(bound_ident, &[body, substitutor]);
+            let body = substitute(body, bound_ident, &Ident::create(fresh_ident.clone()));
+
+            return (fresh_ident, substitute(&body, ident, substitutor));
+        }
+
+        (bound_ident.clone(), substitute(body, ident, substitutor))
+    };
+
+    match proof_term {
+        ProofTerm::Ident(Ident(name, _)) if name == ident => substitutor.clone(),
+        ProofTerm::Ident(_) | ProofTerm::Unit(_) | ProofTerm::Refl(_) | ProofTerm::Sorry(_) => {
+            proof_term.clone()
+        }
+        ProofTerm::Pair(Pair(fst, snd, span)) => Pair::create(
+            substitute(fst, ident, substitutor).boxed(),
+            substitute(snd, ident, substitutor).boxed(),
+            span.clone(),
+        ),
+        ProofTerm::ProjectFst(ProjectFst(body, span)) => {
+            ProjectFst::create(substitute(body, ident, substitutor).boxed(), span.clone())
+        }
+        ProofTerm::ProjectSnd(ProjectSnd(body, span)) => {
+            ProjectSnd::create(substitute(body, ident, substitutor).boxed(), span.clone())
+        }
+        ProofTerm::OrLeft(OrLeft(body, span)) => {
+            OrLeft::create(substitute(body, ident, substitutor).boxed(), span.clone())
+        }
+        ProofTerm::OrRight(OrRight(body, span)) => {
+            OrRight::create(substitute(body, ident, substitutor).boxed(), span.clone())
+        }
+        ProofTerm::Abort(Abort(body, span)) => {
+            Abort::create(substitute(body, ident, substitutor).boxed(), span.clone())
+        }
+        ProofTerm::DoubleNegElim(DoubleNegElim(body, span)) => {
+            DoubleNegElim::create(substitute(body, ident, substitutor).boxed(), span.clone())
+        }
+        ProofTerm::Function(Function {
+            param_ident,
+            param_type,
+            body,
+            span,
+        }) => {
+            let (param_ident, body) = substitute_bound(param_ident, body);
+
+            Function::create(
+                param_ident,
+                param_type
+                    .as_ref()
+                    .map(|param_type| substitute_type(param_type, ident, substitutor)),
+                body.boxed(),
+                span.clone(),
+            )
+        }
+        ProofTerm::Application(Application {
+            function,
+            applicant,
+            span,
+        }) => Application::create(
+            substitute(function, ident, substitutor).boxed(),
+            substitute(applicant, ident, substitutor).boxed(),
+            span.clone(),
+        ),
+        ProofTerm::LetIn(LetIn {
+            fst_ident,
+            snd_ident,
+            head,
+            body,
+            span,
+        }) => {
+            let head = substitute(head, ident, substitutor).boxed();
+
+            // both identifiers are bound in the body
+            if fst_ident == ident || snd_ident == ident {
+                return ProofTerm::LetIn(LetIn {
+                    fst_ident: fst_ident.clone(),
+                    snd_ident: snd_ident.clone(),
+                    head,
+                    body: body.clone(),
+                    span: span.clone(),
+                });
+            }
+
+            let mut fst_ident = fst_ident.clone();
+            let mut snd_ident = snd_ident.clone();
+            let mut body = *body.clone();
+
+            if free_variables(&body).contains(ident) {
+                for bound_ident in [&mut fst_ident, &mut snd_ident] {
+                    if substitutor_variables.contains(bound_ident) {
+                        let fresh_ident = fresh_identifier(bound_ident, &[proof_term, substitutor]);
+                        body = substitute(&body, bound_ident, &Ident::create(fresh_ident.clone()));
+                        *bound_ident = fresh_ident;
+                    }
+                }
+            }
+
+            ProofTerm::LetIn(LetIn {
+                fst_ident,
+                snd_ident,
+                head,
+                body: substitute(&body, ident, substitutor).boxed(),
+                span: span.clone(),
+            })
+        }
+        ProofTerm::Case(Case {
+            head,
+            fst_ident,
+            fst_term,
+            snd_ident,
+            snd_term,
+            span,
+        }) => {
+            let (fst_ident, fst_term) = substitute_bound(fst_ident, fst_term);
+            let (snd_ident, snd_term) = substitute_bound(snd_ident, snd_term);
+
+            Case::create(
+                substitute(head, ident, substitutor).boxed(),
+                fst_ident,
+                fst_term.boxed(),
+                snd_ident,
+                snd_term.boxed(),
+                span.clone(),
+            )
+        }
+        ProofTerm::TypeAscription(TypeAscription {
+            proof_term,
+            ascription,
+            span,
+        }) => ProofTerm::TypeAscription(TypeAscription {
+            proof_term: substitute(proof_term, ident, substitutor).boxed(),
+            ascription: substitute_type(ascription, ident, substitutor),
+            span: span.clone(),
+        }),
+        ProofTerm::Subst(Subst {
+            equality,
+            body,
+            span,
+        }) => Subst::create(
+            substitute(equality, ident, substitutor).boxed(),
+            substitute(body, ident, substitutor).boxed(),
+            span.clone(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{Parser, Stream};
+
+    use crate::kernel::{
+        parse::{lexer::lexer, proof_term::proof_term_parser},
+        proof_term::ProofTerm,
+    };
+
+    use super::{normalize, reduce_step, ReductionRule, DEFAULT_MAX_REDUCTION_STEPS};
+
+    fn parse(proof_term: &str) -> ProofTerm {
+        let len = proof_term.chars().count();
+
+        let tokens = lexer().parse(proof_term).unwrap();
+        proof_term_parser()
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    fn normal_form(proof_term: &str) -> String {
+        let normalization = normalize(&parse(proof_term), DEFAULT_MAX_REDUCTION_STEPS);
+        assert!(normalization.normalized);
+
+        normalization.normal_form.to_string()
+    }
+
+    fn rules(proof_term: &str) -> Vec<ReductionRule> {
+        normalize(&parse(proof_term), DEFAULT_MAX_REDUCTION_STEPS)
+            .steps
+            .into_iter()
+            .map(|step| step.rule)
+            .collect()
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_beta_reduction() {
+        assert_eq!(normal_form("(fn x => (x, x)) a"), "(a, a)");
+        assert_eq!(normal_form("(fn x => fn y => y x) a b"), "b a");
+        assert_eq!(
+            rules("(fn x => fn y => y x) a b"),
+            vec![ReductionRule::Beta; 2]
+        );
+    }
+
+    #[test]
+    fn test_projection_reduction() {
+        assert_eq!(normal_form("fst (a, b)"), "a");
+        assert_eq!(normal_form("snd ((fn x => x) (a, b))"), "b");
+        assert_eq!(
+            rules("snd ((fn x => x) (a, b))"),
+            vec![ReductionRule::Beta, ReductionRule::ProjectSnd]
+        );
+    }
+
+    #[test]
+    fn test_case_reduction() {
+        assert_eq!(
+            normal_form("case inl a of inl x => (x, b), inr y => y"),
+            "(a, b)"
+        );
+        assert_eq!(
+            normal_form("case inr (fst (a, b)) of inl x => x, inr y => (y, y)"),
+            "(a, a)"
+        );
+        assert_eq!(
+            rules("case inr (fst (a, b)) of inl x => x, inr y => (y, y)"),
+            vec![
+                ReductionRule::CaseRight,
+                ReductionRule::ProjectFst,
+                ReductionRule::ProjectFst
+            ]
+        );
+    }
+
+    #[test]
+    fn test_let_in_reduction() {
+        assert_eq!(normal_form("let (x, u) = (t, p) in (x, u)"), "(t, p)");
+        assert_eq!(
+            normal_form("let (x, u) = (t, p) in fn v: P(x) => (u, v)"),
+            "fn v: P(t) => (p, v)"
+        );
+        assert_eq!(normal_form("let (x, u) = (u, p) in (x, u)"), "(u, p)");
+    }
+
+    #[test]
+    fn test_commuting_conversions() {
+        assert_eq!(
+            normal_form("fst (case u of inl x => x, inr y => y)"),
+            "case u of inl x => fst x, inr y => fst y"
+        );
+        assert_eq!(
+            normal_form("(case u of inl x => x, inr y => y) x"),
+            "case u of inl x' => x' x, inr y => y x"
+        );
+        assert_eq!(
+            normal_form("(let (x, h) = u in h) x"),
+            "let (x', h) = u in h x"
+        );
+        assert_eq!(normal_form("snd (fst (abort u))"), "abort u");
+        assert_eq!(
+            rules("fst ((case u of inl x => abort x, inr y => fn w => (w, y)) z)"),
+            vec![
+                ReductionRule::CaseCommute,
+                ReductionRule::CaseCommute,
+                ReductionRule::AbortCommute,
+                ReductionRule::AbortCommute,
+                ReductionRule::Beta,
+                ReductionRule::ProjectFst
+            ]
+        );
+    }
+
+    #[test]
+    fn test_capture_avoiding_substitution() {
+        assert_eq!(
+            normal_form("(fn x => fn y => (x, y)) y"),
+            "fn y' => (y, y')"
+        );
+        assert_eq!(
+            normal_form("(fn x: t => fn y: P(x) => (x, y)) y"),
+            "fn y': P(y) => (y, y')"
+        );
+        assert_eq!(
+            normal_form("(fn x => case u of inl x => x, inr y => x) y"),
+            "case u of inl x => x, inr y' => y"
+        );
+        assert_eq!(normal_form("(fn x => fn x => x) y"), "fn x => x");
+        assert_eq!(
+            normal_form("fn y: t => (fn x: t => fn q: P(x) => q) (f y)"),
+            "fn y: t => fn q: P(f(y)) => q"
+        );
+        assert_eq!(
+            normal_form("let (x, u) = (f (g y), p) in fn v: P(x) => u"),
+            "fn v: P(f(g(y))) => p"
+        );
+    }
+
+    #[test]
+    fn test_normal_order() {
+        let omega = "(fn x => x x) (fn x => x x)";
+
+        assert_eq!(normal_form(&format!("(fn x => c) ({})", omega)), "c");
+        assert_eq!(normal_form("((fn x => x): A -> A) (fn y => y) a"), "a");
+
+        let normalization = normalize(&parse(omega), 10);
+        assert!(!normalization.normalized);
+        assert_eq!(normalization.steps.len(), 10);
+        assert_eq!(normalization.normal_form.to_string(), omega);
+    }
+
+    #[test]
+    fn test_reduce_step() {
+        assert_eq!(reduce_step(&parse("fn x => (x, fst x)")), None);
+
+        let step = reduce_step(&parse("(fst (a, b), (fn x => x) c)")).unwrap();
+        assert_eq!(step.rule, ReductionRule::ProjectFst);
+        assert_eq!(step.redex.to_string(), "fst (a, b)");
+        assert_eq!(step.proof_term.to_string(), "(a, (fn x => x) c)");
+    }
+}
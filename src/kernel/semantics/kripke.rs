@@ -0,0 +1,476 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tsify_next::Tsify;
+
+use crate::kernel::prop::{Prop, PropDefinition, PropParameter};
+
+#[derive(Debug, Error, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum KripkeModelError {
+    #[error("World {0} is unknown")]
+    WorldUnknown(usize),
+
+    #[error("Worlds {fst} and {snd} are distinct but mutually accessible")]
+    OrderNotAntisymmetric { fst: usize, snd: usize },
+
+    #[error("Element \"{element}\" of {datatype} in world {from} is missing in world {to}")]
+    DomainNotMonotone {
+        datatype: String,
+        element: String,
+        from: usize,
+        to: usize,
+    },
+
+    #[error("Atom \"{atom}\" holds in world {from} but not in world {to}")]
+    ValuationNotMonotone {
+        atom: String,
+        from: usize,
+        to: usize,
+    },
+
+    #[error("Element \"{element}\" does not exist in world {world}")]
+    ElementUnknown { element: String, world: usize },
+
+    #[error("Term \"{0}\" cannot be evaluated")]
+    TermUnknown(String),
+
+    #[error("Function \"{0}\" has no interpretation")]
+    FunctionNotSupported(String),
+
+    #[error("Definition \"{0}\" is unknown")]
+    DefinitionUnknown(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct KripkeDomain {
+    pub datatype: String,
+    pub elements: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct KripkeFact {
+    pub atom: String,
+    pub arguments: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct KripkeModelWorld {
+    pub domains: Vec<KripkeDomain>,
+    pub facts: Vec<KripkeFact>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct KripkeModel {
+    pub worlds: Vec<KripkeModelWorld>,
+
+    // the accessibility order is the reflexive-transitive closure of these pairs
+    pub order: Vec<(usize, usize)>,
+}
+
+impl KripkeModel {
+    pub fn validate(&self) -> Result<(), KripkeModelError> {
+        for (from, to) in self.order.iter() {
+            self.world(*from)?;
+            self.world(*to)?;
+        }
+
+        for (id, world) in self.worlds.iter().enumerate() {
+            for fact in world.facts.iter() {
+                if let Some(element) = fact
+                    .arguments
+                    .iter()
+                    .find(|element| !self.has_element(id, element))
+                {
+                    return Err(KripkeModelError::ElementUnknown {
+                        element: element.clone(),
+                        world: id,
+                    });
+                }
+            }
+
+            for successor in self.successors(id) {
+                if successor == id {
+                    continue;
+                }
+
+                if self.successors(successor).contains(&id) {
+                    return Err(KripkeModelError::OrderNotAntisymmetric {
+                        fst: id,
+                        snd: successor,
+                    });
+                }
+
+                for domain in world.domains.iter() {
+                    if let Some(element) = domain
+                        .elements
+                        .iter()
+                        .find(|element| !self.domain(successor, &domain.datatype).contains(element))
+                    {
+                        return Err(KripkeModelError::DomainNotMonotone {
+                            datatype: domain.datatype.clone(),
+                            element: element.clone(),
+                            from: id,
+                            to: successor,
+                        });
+                    }
+                }
+
+                if let Some(fact) = world
+                    .facts
+                    .iter()
+                    .find(|fact| !self.worlds[successor].facts.contains(fact))
+                {
+                    return Err(KripkeModelError::ValuationNotMonotone {
+                        atom: fact.atom.clone(),
+                        from: id,
+                        to: successor,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // All worlds accessible from the given world, including itself.
+    pub fn successors(&self, world: usize) -> Vec<usize> {
+        let mut successors = vec![world];
+        let mut i = 0;
+
+        while i < successors.len() {
+            let current = successors[i];
+
+            for (from, to) in self.order.iter() {
+                if *from == current && !successors.contains(to) {
+                    successors.push(*to);
+                }
+            }
+
+            i += 1;
+        }
+
+        successors
+    }
+
+    pub fn forces(&self, world: usize, prop: &Prop) -> Result<bool, KripkeModelError> {
+        self.world(world)?;
+
+        let unfolded_prop = prop.unfold_definitions(&PropDefinition::builtins());
+
+        self.forces_with_env(world, &unfolded_prop, &[])
+    }
+
+    fn forces_with_env(
+        &self,
+        world: usize,
+        prop: &Prop,
+        env: &[(String, String)],
+    ) -> Result<bool, KripkeModelError> {
+        match prop {
+            Prop::True => Ok(true),
+            Prop::False => Ok(false),
+            Prop::Atom(atom, params) => {
+                let fact = KripkeFact {
+                    atom: atom.clone(),
+                    arguments: params
+                        .iter()
+                        .map(|param| self.evaluate_term(world, param, env))
+                        .collect::<Result<Vec<_>, _>>()?,
+                };
+
+                Ok(self.worlds[world].facts.contains(&fact))
+            }
+            Prop::Eq(lhs, rhs) => {
+                Ok(self.evaluate_term(world, lhs, env)? == self.evaluate_term(world, rhs, env)?)
+            }
+            Prop::And(fst, snd) => {
+                Ok(self.forces_with_env(world, fst, env)?
+                    && self.forces_with_env(world, snd, env)?)
+            }
+            Prop::Or(fst, snd) => {
+                Ok(self.forces_with_env(world, fst, env)?
+                    || self.forces_with_env(world, snd, env)?)
+            }
+            Prop::Impl(fst, snd) => {
+                for successor in self.successors(world) {
+                    if self.forces_with_env(successor, fst, env)?
+                        && !self.forces_with_env(successor, snd, env)?
+                    {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
+            Prop::ForAll {
+                object_ident,
+                object_type_ident,
+                body,
+            } => {
+                for successor in self.successors(world) {
+                    for element in self.domain(successor, object_type_ident) {
+                        let env = [env, &[(object_ident.clone(), element.clone())]].concat();
+
+                        if !self.forces_with_env(successor, body, &env)? {
+                            return Ok(false);
+                        }
+                    }
+                }
+
+                Ok(true)
+            }
+            Prop::Exists {
+                object_ident,
+                object_type_ident,
+                body,
+            } => {
+                for element in self.domain(world, object_type_ident) {
+                    let env = [env, &[(object_ident.clone(), element.clone())]].concat();
+
+                    if self.forces_with_env(world, body, &env)? {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            }
+            Prop::Defined(name, _) => Err(KripkeModelError::DefinitionUnknown(name.clone())),
+        }
+    }
+
+    // Bound variables evaluate to their element, other identifiers denote the element of the same name.
+    fn evaluate_term(
+        &self,
+        world: usize,
+        term: &PropParameter,
+        env: &[(String, String)],
+    ) -> Result<String, KripkeModelError> {
+        match term {
+            PropParameter::Function(name, params) if !params.is_empty() => {
+                Err(KripkeModelError::FunctionNotSupported(name.clone()))
+            }
+            _ => {
+                let name = term.name();
+
+                if let Some((_, element)) = env.iter().rev().find(|(ident, _)| ident == name) {
+                    return Ok(element.clone());
+                }
+
+                if self.has_element(world, name) {
+                    return Ok(name.clone());
+                }
+
+                Err(KripkeModelError::TermUnknown(name.clone()))
+            }
+        }
+    }
+
+    fn world(&self, world: usize) -> Result<&KripkeModelWorld, KripkeModelError> {
+        self.worlds
+            .get(world)
+            .ok_or(KripkeModelError::WorldUnknown(world))
+    }
+
+    fn domain(&self, world: usize, datatype: &String) -> &[String] {
+        self.worlds[world]
+            .domains
+            .iter()
+            .find(|domain| domain.datatype == *datatype)
+            .map(|domain| domain.elements.as_slice())
+            .unwrap_or(&[])
+    }
+
+    fn has_element(&self, world: usize, element: &String) -> bool {
+        self.worlds[world]
+            .domains
+            .iter()
+            .any(|domain| domain.elements.contains(element))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        parse::{fol::fol_parser, lexer::lexer},
+        prop::Prop,
+    };
+
+    use super::{KripkeDomain, KripkeFact, KripkeModel, KripkeModelError, KripkeModelWorld};
+
+    fn parse_prop(prop: &str) -> Prop {
+        let len = prop.chars().count();
+
+        let tokens = lexer().parse(prop).unwrap();
+
+        fol_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    fn world(domains: Vec<(&str, Vec<&str>)>, facts: Vec<(&str, Vec<&str>)>) -> KripkeModelWorld {
+        KripkeModelWorld {
+            domains: domains
+                .into_iter()
+                .map(|(datatype, elements)| KripkeDomain {
+                    datatype: datatype.to_string(),
+                    elements: elements.into_iter().map(str::to_string).collect(),
+                })
+                .collect(),
+            facts: facts
+                .into_iter()
+                .map(|(atom, arguments)| KripkeFact {
+                    atom: atom.to_string(),
+                    arguments: arguments.into_iter().map(str::to_string).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    fn forces(model: &KripkeModel, world: usize, prop: &str) -> bool {
+        model.forces(world, &parse_prop(prop)).unwrap()
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_propositional_forcing() {
+        let model = KripkeModel {
+            worlds: vec![world(vec![], vec![]), world(vec![], vec![("A", vec![])])],
+            order: vec![(0, 1)],
+        };
+
+        assert_eq!(model.validate(), Ok(()));
+
+        assert!(!forces(&model, 0, "A"));
+        assert!(!forces(&model, 0, "~A"));
+        assert!(forces(&model, 0, "~~A"));
+        assert!(!forces(&model, 0, "~~A -> A"));
+        assert!(!forces(&model, 0, "A || ~A"));
+        assert!(forces(&model, 1, "A || ~A"));
+        assert!(forces(&model, 0, "A -> A && A"));
+    }
+
+    #[test]
+    fn test_quantifier_forcing() {
+        let model = KripkeModel {
+            worlds: vec![
+                world(vec![("t", vec!["a"])], vec![("P", vec!["a"])]),
+                world(vec![("t", vec!["a", "b"])], vec![("P", vec!["a"])]),
+            ],
+            order: vec![(0, 1)],
+        };
+
+        assert_eq!(model.validate(), Ok(()));
+
+        assert!(forces(&model, 0, "P(a)"));
+        assert!(forces(&model, 0, "\\exists x:t. P(x)"));
+        assert!(!forces(&model, 0, "\\forall x:t. P(x)"));
+        assert!(!forces(&model, 0, "\\exists x:t. ~P(x)"));
+        assert!(forces(&model, 0, "\\forall x:t. x = x"));
+        assert!(!forces(&model, 1, "\\forall x:t. \\forall y:t. x = y"));
+    }
+
+    #[test]
+    fn test_unknown_term() {
+        let model = KripkeModel {
+            worlds: vec![world(vec![("t", vec!["a"])], vec![])],
+            order: vec![],
+        };
+
+        assert_eq!(
+            model.forces(0, &parse_prop("P(b)")),
+            Err(KripkeModelError::TermUnknown("b".to_string()))
+        );
+        assert_eq!(
+            model.forces(0, &parse_prop("P(f(a))")),
+            Err(KripkeModelError::FunctionNotSupported("f".to_string()))
+        );
+        assert_eq!(
+            model.forces(1, &parse_prop("P(a)")),
+            Err(KripkeModelError::WorldUnknown(1))
+        );
+    }
+
+    #[test]
+    fn test_valuation_not_monotone() {
+        let model = KripkeModel {
+            worlds: vec![world(vec![], vec![("A", vec![])]), world(vec![], vec![])],
+            order: vec![(0, 1)],
+        };
+
+        assert_eq!(
+            model.validate(),
+            Err(KripkeModelError::ValuationNotMonotone {
+                atom: "A".to_string(),
+                from: 0,
+                to: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_domain_not_monotone() {
+        let model = KripkeModel {
+            worlds: vec![
+                world(vec![("t", vec!["a"])], vec![]),
+                world(vec![], vec![]),
+                world(vec![], vec![]),
+            ],
+            order: vec![(1, 2), (0, 1)],
+        };
+
+        assert_eq!(
+            model.validate(),
+            Err(KripkeModelError::DomainNotMonotone {
+                datatype: "t".to_string(),
+                element: "a".to_string(),
+                from: 0,
+                to: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_order_not_antisymmetric() {
+        let model = KripkeModel {
+            worlds: vec![world(vec![], vec![]), world(vec![], vec![])],
+            order: vec![(0, 1), (1, 0)],
+        };
+
+        assert_eq!(
+            model.validate(),
+            Err(KripkeModelError::OrderNotAntisymmetric { fst: 0, snd: 1 })
+        );
+    }
+
+    #[test]
+    fn test_invalid_references() {
+        let model = KripkeModel {
+            worlds: vec![world(vec![], vec![])],
+            order: vec![(0, 2)],
+        };
+
+        assert_eq!(model.validate(), Err(KripkeModelError::WorldUnknown(2)));
+
+        let model = KripkeModel {
+            worlds: vec![world(vec![], vec![("P", vec!["a"])])],
+            order: vec![],
+        };
+
+        assert_eq!(
+            model.validate(),
+            Err(KripkeModelError::ElementUnknown {
+                element: "a".to_string(),
+                world: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod kripke;
+pub mod truth_table;
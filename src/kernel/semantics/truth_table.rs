@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tsify_next::Tsify;
+
+use crate::kernel::prop::{Prop, PropDefinition};
+
+// 2^16 rows are still reasonable to display
+const MAX_ATOMS: usize = 16;
+
+#[derive(Debug, Error, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum TruthTableError {
+    #[error("Truth tables cannot be generated for quantified propositions")]
+    PropContainsQuantifiers,
+
+    #[error("Proposition has {actual} atoms, at most {max} are supported")]
+    TooManyAtoms { max: usize, actual: usize },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct TruthTableRow {
+    // one value per atom of the table, in the same order
+    pub values: Vec<bool>,
+    pub result: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct TruthTable {
+    pub atoms: Vec<Prop>,
+    pub rows: Vec<TruthTableRow>,
+}
+
+impl TruthTable {
+    pub fn generate(prop: &Prop) -> Result<Self, TruthTableError> {
+        let prop = &prop.unfold_definitions(&PropDefinition::builtins());
+
+        if prop.has_quantifiers() {
+            return Err(TruthTableError::PropContainsQuantifiers);
+        }
+
+        let atoms = get_atomic_props(prop);
+
+        if atoms.len() > MAX_ATOMS {
+            return Err(TruthTableError::TooManyAtoms {
+                max: MAX_ATOMS,
+                actual: atoms.len(),
+            });
+        }
+
+        // the first atom alternates slowest, starting with true
+        let rows = (0..1usize << atoms.len())
+            .map(|row| {
+                let values = (0..atoms.len())
+                    .map(|i| row & (1 << (atoms.len() - i - 1)) == 0)
+                    .collect::<Vec<_>>();
+                let result = evaluate(prop, &atoms, &values);
+
+                TruthTableRow { values, result }
+            })
+            .collect();
+
+        Ok(Self { atoms, rows })
+    }
+
+    pub fn is_tautology(&self) -> bool {
+        self.rows.iter().all(|row| row.result)
+    }
+
+    pub fn is_satisfiable(&self) -> bool {
+        self.rows.iter().any(|row| row.result)
+    }
+
+    pub fn falsifying_assignments(&self) -> Vec<TruthTableRow> {
+        self.rows
+            .iter()
+            .filter(|row| !row.result)
+            .cloned()
+            .collect()
+    }
+}
+
+// Evaluates a quantifier-free prop, where values[i] is the value of atoms[i].
+pub fn evaluate(prop: &Prop, atoms: &[Prop], values: &[bool]) -> bool {
+    match prop {
+        Prop::True => true,
+        Prop::False => false,
+        Prop::And(fst, snd) => evaluate(fst, atoms, values) && evaluate(snd, atoms, values),
+        Prop::Or(fst, snd) => evaluate(fst, atoms, values) || evaluate(snd, atoms, values),
+        Prop::Impl(fst, snd) => !evaluate(fst, atoms, values) || evaluate(snd, atoms, values),
+        Prop::Eq(lhs, rhs) if lhs == rhs => true,
+        _ => atoms
+            .iter()
+            .position(|atom| atom == prop)
+            .map(|i| values[i])
+            .unwrap_or(false),
+    }
+}
+
+// Atoms (with their arguments) and non-trivial equalities, in order of their first occurrence.
+fn get_atomic_props(prop: &Prop) -> Vec<Prop> {
+    fn _get_atomic_props(prop: &Prop, atoms: &mut Vec<Prop>) {
+        match prop {
+            Prop::True | Prop::False => {}
+            Prop::Eq(lhs, rhs) if lhs == rhs => {}
+            // unknown definitions are opaque
+            Prop::Atom(_, _)
+            | Prop::Eq(_, _)
+            | Prop::ForAll { .. }
+            | Prop::Exists { .. }
+            | Prop::Defined(_, _) => {
+                if !atoms.contains(prop) {
+                    atoms.push(prop.clone());
+                }
+            }
+            Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+                _get_atomic_props(fst, atoms);
+                _get_atomic_props(snd, atoms);
+            }
+        }
+    }
+
+    let mut atoms = vec![];
+    _get_atomic_props(prop, &mut atoms);
+
+    atoms
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        parse::{fol::fol_parser, lexer::lexer},
+        prop::Prop,
+    };
+
+    use super::{TruthTable, TruthTableError, TruthTableRow};
+
+    fn parse_prop(prop: &str) -> Prop {
+        let len = prop.chars().count();
+
+        let tokens = lexer().parse(prop).unwrap();
+
+        fol_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    fn truth_table(prop: &str) -> TruthTable {
+        TruthTable::generate(&parse_prop(prop)).unwrap()
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_truth_table() {
+        let table = truth_table("A -> B");
+
+        assert_eq!(table.atoms, vec![parse_prop("A"), parse_prop("B")]);
+        assert_eq!(
+            table
+                .rows
+                .iter()
+                .map(|row| (row.values.clone(), row.result))
+                .collect::<Vec<_>>(),
+            vec![
+                (vec![true, true], true),
+                (vec![true, false], false),
+                (vec![false, true], true),
+                (vec![false, false], true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repeated_atoms() {
+        let table = truth_table("P(a) && Q -> P(a) || P(b)");
+
+        assert_eq!(
+            table.atoms,
+            vec![parse_prop("P(a)"), parse_prop("Q"), parse_prop("P(b)")]
+        );
+        assert_eq!(table.rows.len(), 8);
+        assert!(table.is_tautology());
+    }
+
+    #[test]
+    fn test_classical_tautologies() {
+        assert!(truth_table("A || ~A").is_tautology());
+        assert!(truth_table("~~A -> A").is_tautology());
+        assert!(truth_table("((A -> B) -> A) -> A").is_tautology());
+        assert!(truth_table("a = a").is_tautology());
+        assert!(!truth_table("a = b").is_tautology());
+    }
+
+    #[test]
+    fn test_satisfiability() {
+        assert!(truth_table("A && ~B").is_satisfiable());
+        assert!(!truth_table("A && ~A").is_satisfiable());
+        assert!(!truth_table("\\bot").is_satisfiable());
+        assert!(truth_table("\\top").is_tautology());
+    }
+
+    #[test]
+    fn test_falsifying_assignments() {
+        assert_eq!(
+            truth_table("A && B").falsifying_assignments(),
+            vec![
+                TruthTableRow {
+                    values: vec![true, false],
+                    result: false
+                },
+                TruthTableRow {
+                    values: vec![false, true],
+                    result: false
+                },
+                TruthTableRow {
+                    values: vec![false, false],
+                    result: false
+                },
+            ]
+        );
+        assert_eq!(truth_table("A -> A").falsifying_assignments(), vec![]);
+    }
+
+    #[test]
+    fn test_quantifiers() {
+        assert_eq!(
+            TruthTable::generate(&parse_prop("\\forall x:t. A(x)")),
+            Err(TruthTableError::PropContainsQuantifiers)
+        );
+    }
+}
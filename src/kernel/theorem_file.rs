@@ -0,0 +1,363 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tsify_next::Tsify;
+
+use super::{
+    checker::{
+        check::{check, CheckError},
+        identifier::Identifier,
+        identifier_context::IdentifierContext,
+        Logic, TypeCheckerResult,
+    },
+    process::{stages::resolve_datatypes::ResolveDatatypes, ProofPipeline, ProofPipelineError},
+    proof::{Proof, ProofProcessingState},
+    proof_term::{ProofTerm, Type},
+    prop::{AtomSignature, FunctionSignature, Prop, PropDefinition},
+    prove::ProverConfig,
+};
+
+#[derive(Clone, PartialEq, Eq, Tsify, Serialize, Deserialize, Debug)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct Theorem {
+    pub name: String,
+    pub prop: Prop,
+    pub proof_term: ProofTerm,
+    pub span: Option<Range<usize>>,
+}
+
+// Declarations are shared by all theorems of the file, regardless of their position.
+#[derive(Clone, PartialEq, Eq, Tsify, Serialize, Deserialize, Debug)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct TheoremFile {
+    pub datatypes: Vec<String>,
+    pub atoms: Vec<(String, usize)>,
+    pub atom_signatures: Vec<AtomSignature>,
+    pub functions: Vec<FunctionSignature>,
+    pub definitions: Vec<PropDefinition>,
+    pub theorems: Vec<Theorem>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum TheoremError {
+    #[error("Theorem {0} is already defined")]
+    DuplicateName(String),
+
+    #[error("The proof could not be processed")]
+    ProofPipelineError(#[from] ProofPipelineError),
+}
+
+#[derive(Clone, PartialEq, Eq, Tsify, Serialize, Deserialize, Debug)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum TheoremCheckResult {
+    DuplicateName(String),
+    ProofPipelineError(ProofPipelineError),
+    TypeCheckerError(CheckError),
+    TypeCheckSucceeded(TypeCheckerResult),
+}
+
+impl TheoremCheckResult {
+    pub fn is_closed(&self) -> bool {
+        matches!(self, TheoremCheckResult::TypeCheckSucceeded(result) if result.is_closed())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Tsify, Serialize, Deserialize, Debug)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct CheckedTheorem {
+    pub name: String,
+    pub prop: Prop,
+    pub span: Option<Range<usize>>,
+    pub result: TheoremCheckResult,
+}
+
+// A theorem with the context it is checked in and the result of the check, None if the proof
+// could not be processed.
+pub struct PreparedTheorem {
+    pub proof: Result<Proof, TheoremError>,
+    pub ctx: IdentifierContext,
+    pub result: Option<Result<TypeCheckerResult, CheckError>>,
+}
+
+impl TheoremFile {
+    pub fn proof_of(&self, theorem: &Theorem) -> Proof {
+        Proof {
+            processing_state: ProofProcessingState::Parsed,
+            datatypes: self.datatypes.clone(),
+            atoms: self.atoms.clone(),
+            atom_signatures: self.atom_signatures.clone(),
+            functions: self.functions.clone(),
+            definitions: self.definitions.clone(),
+            proof_term: theorem.proof_term.clone(),
+        }
+    }
+
+    // Resolves and checks every theorem in the context of the earlier theorems. Only theorems
+    // whose proof is correct and complete are available as lemmas. A theorem that reuses the
+    // name of an earlier one is rejected, the earlier one stays available.
+    pub fn prepare(&self, logic: Logic, prover_config: ProverConfig) -> Vec<PreparedTheorem> {
+        let mut lemmas: Vec<(Identifier, Type)> = vec![];
+        let mut prepared = vec![];
+
+        for (idx, theorem) in self.theorems.iter().enumerate() {
+            let mut ctx = IdentifierContext::with_signatures(
+                self.functions.clone(),
+                self.atom_signatures.clone(),
+            );
+            ctx.set_logic(logic.clone());
+            ctx.set_prover_config(prover_config.clone());
+
+            for (ident, _type) in lemmas.iter() {
+                ctx.insert(ident.clone(), _type.clone());
+            }
+
+            if self.theorems[..idx]
+                .iter()
+                .any(|earlier| earlier.name == theorem.name)
+            {
+                prepared.push(PreparedTheorem {
+                    proof: Err(TheoremError::DuplicateName(theorem.name.clone())),
+                    ctx,
+                    result: None,
+                });
+                continue;
+            }
+
+            let proof = ProofPipeline::new()
+                .pipe(ResolveDatatypes::boxed())
+                .apply(self.proof_of(theorem), &theorem.prop)
+                .map_err(TheoremError::from);
+
+            if let Ok(ref proof) = proof {
+                ctx.add_definitions(proof.definitions.clone());
+            }
+
+            let result = match proof {
+                Ok(ref proof) => Some(check(&proof.proof_term, &theorem.prop, &ctx)),
+                Err(_) => None,
+            };
+
+            // lemmas are referenced like assumptions, so their constants have to be resolved
+            let mut lemma_prop = theorem.prop.clone();
+            if matches!(result, Some(Ok(ref result)) if result.is_closed())
+                && lemma_prop.instantiate_parameters_with_context(&ctx).is_ok()
+            {
+                lemmas.push((
+                    Identifier::new(theorem.name.clone(), idx),
+                    Type::Prop(lemma_prop),
+                ));
+            }
+
+            prepared.push(PreparedTheorem { proof, ctx, result });
+        }
+
+        prepared
+    }
+
+    // Checks every theorem on its own.
+    pub fn check(&self, logic: Logic, prover_config: ProverConfig) -> Vec<CheckedTheorem> {
+        self.theorems
+            .iter()
+            .zip(self.prepare(logic, prover_config))
+            .map(|(theorem, prepared)| {
+                let result = match (prepared.proof, prepared.result) {
+                    (_, Some(Ok(result))) => TheoremCheckResult::TypeCheckSucceeded(result),
+                    (_, Some(Err(err))) => TheoremCheckResult::TypeCheckerError(err),
+                    (Err(TheoremError::DuplicateName(name)), None) => {
+                        TheoremCheckResult::DuplicateName(name)
+                    }
+                    (Err(TheoremError::ProofPipelineError(err)), None) => {
+                        TheoremCheckResult::ProofPipelineError(err)
+                    }
+                    (Ok(_), None) => unreachable!("Processed proofs are checked."),
+                };
+
+                CheckedTheorem {
+                    name: theorem.name.clone(),
+                    prop: theorem.prop.clone(),
+                    span: theorem.span.clone(),
+                    result,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        checker::{check::CheckError, synthesize::SynthesizeError, Logic},
+        parse::{lexer::lexer, proof::theorem_file_parser},
+        prove::ProverConfig,
+    };
+
+    use super::{CheckedTheorem, TheoremCheckResult, TheoremFile};
+
+    fn parse_theorem_file(src: &str) -> TheoremFile {
+        let len = src.chars().count();
+
+        let tokens = lexer().parse(src).unwrap();
+
+        theorem_file_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    fn check_theorem_file(src: &str) -> Vec<CheckedTheorem> {
+        parse_theorem_file(src).check(Logic::Intuitionistic, ProverConfig::default())
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_theorems() {
+        let checked = check_theorem_file(
+            "
+            atom A;
+            atom B;
+
+            theorem swap : A & B -> B & A := fn p => (snd p, fst p);
+            lemma id : A -> A := fn u => u;
+            ",
+        );
+
+        assert_eq!(checked.len(), 2);
+        assert_eq!(checked[0].name, "swap");
+        assert_eq!(checked[1].name, "id");
+        assert!(checked.iter().all(|theorem| theorem.result.is_closed()));
+    }
+
+    #[test]
+    fn test_lemma_reuse() {
+        let checked = check_theorem_file(
+            "
+            atom A;
+            atom B;
+
+            lemma swap : A & B -> B & A := fn p => (snd p, fst p);
+            theorem swap_inl : A & B -> (B & A) || A := fn p => inl (swap p);
+            ",
+        );
+
+        assert!(checked[1].result.is_closed());
+    }
+
+    #[test]
+    fn test_lemma_reuse_with_quantifiers() {
+        let checked = check_theorem_file(
+            "
+            datatype t;
+            atom P(1);
+            function c : t;
+
+            lemma all : \\forall x:t. P(x) -> P(x) := fn x => fn u => u;
+            theorem inst : P(c) -> P(c) := all c;
+            ",
+        );
+
+        assert!(checked[0].result.is_closed());
+        assert!(checked[1].result.is_closed());
+    }
+
+    #[test]
+    fn test_later_theorems_are_unknown() {
+        let checked = check_theorem_file(
+            "
+            atom A;
+
+            theorem first : A -> A := second;
+            theorem second : A -> A := fn u => u;
+            ",
+        );
+
+        assert!(matches!(
+            checked[0].result,
+            TheoremCheckResult::TypeCheckerError(CheckError::SynthesizeError(
+                SynthesizeError::UnknownIdentifier(_, _)
+            ))
+        ));
+        assert!(checked[1].result.is_closed());
+    }
+
+    #[test]
+    fn test_theorems_are_checked_individually() {
+        let checked = check_theorem_file(
+            "
+            atom A;
+            atom B;
+
+            theorem wrong : A -> B := fn u => u;
+            theorem open : A -> B -> A := fn u => sorry;
+            theorem uses_wrong : A -> B := fn u => wrong u;
+            theorem uses_open : A -> B -> A := open;
+            ",
+        );
+
+        assert!(matches!(
+            checked[0].result,
+            TheoremCheckResult::TypeCheckerError(_)
+        ));
+        assert!(matches!(
+            checked[1].result,
+            TheoremCheckResult::TypeCheckSucceeded(_)
+        ));
+        assert!(!checked[1].result.is_closed());
+        assert!(matches!(
+            checked[2].result,
+            TheoremCheckResult::TypeCheckerError(CheckError::SynthesizeError(
+                SynthesizeError::UnknownIdentifier(_, _)
+            ))
+        ));
+        assert!(matches!(
+            checked[3].result,
+            TheoremCheckResult::TypeCheckerError(CheckError::SynthesizeError(
+                SynthesizeError::UnknownIdentifier(_, _)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_names() {
+        let checked = check_theorem_file(
+            "
+            atom A;
+            atom B;
+
+            theorem id : A -> A := fn u => u;
+            theorem id : B -> B := fn u => u;
+            theorem uses_id : A -> A := id;
+            ",
+        );
+
+        assert!(checked[0].result.is_closed());
+        assert_eq!(
+            checked[1].result,
+            TheoremCheckResult::DuplicateName("id".to_string())
+        );
+        assert!(checked[2].result.is_closed());
+    }
+
+    #[test]
+    fn test_definitions() {
+        let checked = check_theorem_file(
+            "
+            atom A;
+            atom B;
+            def Xor(X, Y) := (X || Y) && ~(X && Y);
+
+            theorem xor_comm : Xor(A, B) -> Xor(B, A) :=
+                fn h => ((case fst h of inl a => inr a, inr b => inl b), fn p => snd h (snd p, fst p));
+            theorem iff_refl : A <-> A := (fn u => u, fn u => u);
+            ",
+        );
+
+        assert!(checked.iter().all(|theorem| theorem.result.is_closed()));
+    }
+}
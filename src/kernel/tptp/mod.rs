@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tsify_next::Tsify;
+
+use super::prop::{AtomSignature, FunctionSignature, Prop};
+
+pub mod parse;
+pub mod print;
+
+// Datatype of the individuals of untyped formulas, which TPTP calls $i
+pub const INDIVIDUAL_TYPE: &str = "i";
+
+#[derive(Debug, Error, PartialEq, Eq, Clone, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum TptpError {
+    #[error("Failed to parse TPTP problem: {0}")]
+    ParserError(String),
+
+    #[error("Included file '{0}' cannot be resolved")]
+    IncludeUnsupported(String),
+
+    #[error("Variable {0} is not bound")]
+    VariableUnbound(String),
+
+    #[error("Symbol {0} is used with different arities")]
+    ArityConflict(String),
+
+    #[error("Name '{0}' is not a valid identifier")]
+    NameInvalid(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum TptpRole {
+    Axiom,
+    Hypothesis,
+    Definition,
+    Assumption,
+    Lemma,
+    Theorem,
+    Corollary,
+    Conjecture,
+    NegatedConjecture,
+}
+
+impl TptpRole {
+    const ALL: [TptpRole; 9] = [
+        TptpRole::Axiom,
+        TptpRole::Hypothesis,
+        TptpRole::Definition,
+        TptpRole::Assumption,
+        TptpRole::Lemma,
+        TptpRole::Theorem,
+        TptpRole::Corollary,
+        TptpRole::Conjecture,
+        TptpRole::NegatedConjecture,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TptpRole::Axiom => "axiom",
+            TptpRole::Hypothesis => "hypothesis",
+            TptpRole::Definition => "definition",
+            TptpRole::Assumption => "assumption",
+            TptpRole::Lemma => "lemma",
+            TptpRole::Theorem => "theorem",
+            TptpRole::Corollary => "corollary",
+            TptpRole::Conjecture => "conjecture",
+            TptpRole::NegatedConjecture => "negated_conjecture",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<TptpRole> {
+        Self::ALL.into_iter().find(|role| role.name() == name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct TptpFormula {
+    pub name: String,
+    pub role: TptpRole,
+    pub prop: Prop,
+}
+
+// Propositional atoms are represented by signatures without parameters.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct TptpProblem {
+    pub datatypes: Vec<String>,
+    pub atoms: Vec<AtomSignature>,
+    pub functions: Vec<FunctionSignature>,
+    pub formulas: Vec<TptpFormula>,
+}
+
+impl TptpProblem {
+    // The conjectures under the assumption of all other formulas.
+    // A problem without conjecture asks for a refutation of its formulas.
+    pub fn to_prop(&self) -> Prop {
+        let (conjectures, premisses): (Vec<_>, Vec<_>) = self
+            .formulas
+            .iter()
+            .partition(|formula| formula.role == TptpRole::Conjecture);
+
+        let conclusion = conjectures
+            .into_iter()
+            .map(|formula| formula.prop.clone())
+            .reduce(|fst, snd| Prop::And(fst.boxed(), snd.boxed()))
+            .unwrap_or(Prop::False);
+
+        premisses
+            .into_iter()
+            .rev()
+            .fold(conclusion, |conclusion, premisse| {
+                Prop::Impl(premisse.prop.boxed(), conclusion.boxed())
+            })
+    }
+}
@@ -0,0 +1,748 @@
+use chumsky::prelude::*;
+use itertools::Itertools;
+
+use crate::kernel::{
+    parse::{lexer::lexer, Token},
+    prop::{AtomSignature, FunctionSignature, Prop, PropParameter},
+};
+
+use super::{TptpError, TptpFormula, TptpProblem, TptpRole, INDIVIDUAL_TYPE};
+
+// Type of a symbol in a tff type declaration, e.g. "(t * t) > $o".
+struct TypeDeclaration {
+    param_types: Vec<String>,
+    return_type: String,
+}
+
+enum Statement {
+    Type(String, TypeDeclaration),
+    Formula(TptpFormula),
+    Include(String),
+}
+
+#[derive(Clone)]
+enum Connective {
+    Iff,
+    Xor,
+    Impl,
+    ReverseImpl,
+    Nor,
+    Nand,
+}
+
+enum FormulaTail {
+    Binary(Connective, Prop),
+    Or(Vec<Prop>),
+    And(Vec<Prop>),
+}
+
+fn not(prop: Prop) -> Prop {
+    Prop::Impl(prop.boxed(), Prop::False.boxed())
+}
+
+// Whitespace and comments
+fn blank() -> impl Parser<char, (), Error = Simple<char>> + Clone {
+    let line_comment = just('%')
+        .then(take_until(text::newline().or(end())))
+        .ignored();
+    let block_comment = just("/*").then(take_until(just("*/"))).ignored();
+
+    filter(|c: &char| c.is_whitespace())
+        .ignored()
+        .or(line_comment)
+        .or(block_comment)
+        .repeated()
+        .ignored()
+}
+
+fn symbol(symbol: &'static str) -> impl Parser<char, (), Error = Simple<char>> + Clone {
+    just(symbol).padded_by(blank()).ignored()
+}
+
+fn word(
+    first: impl Fn(&char) -> bool + Clone,
+) -> impl Parser<char, String, Error = Simple<char>> + Clone {
+    filter(first)
+        .chain(filter(|c: &char| c.is_ascii_alphanumeric() || *c == '_').repeated())
+        .collect::<String>()
+        .padded_by(blank())
+}
+
+fn lower_word() -> impl Parser<char, String, Error = Simple<char>> + Clone {
+    word(|c: &char| c.is_ascii_lowercase())
+}
+
+fn upper_word() -> impl Parser<char, String, Error = Simple<char>> + Clone {
+    word(|c: &char| c.is_ascii_uppercase())
+}
+
+fn dollar_word() -> impl Parser<char, String, Error = Simple<char>> + Clone {
+    just('$')
+        .chain(filter(|c: &char| c.is_ascii_alphanumeric() || *c == '_').repeated())
+        .collect::<String>()
+        .padded_by(blank())
+}
+
+// Names of symbols and formulas, either lower words or single quoted, e.g. 'Nat'
+fn atomic_word() -> impl Parser<char, String, Error = Simple<char>> + Clone {
+    let single_quoted = just('\'')
+        .ignore_then(
+            filter(|c: &char| *c != '\'' && *c != '\\')
+                .or(just('\\').ignore_then(any()))
+                .repeated(),
+        )
+        .then_ignore(just('\''))
+        .collect::<String>()
+        .padded_by(blank());
+
+    lower_word().or(single_quoted)
+}
+
+fn type_parser() -> impl Parser<char, String, Error = Simple<char>> + Clone {
+    let defined_type = dollar_word().try_map(|name, span| match name.as_str() {
+        "$i" => Ok(INDIVIDUAL_TYPE.to_string()),
+        "$o" | "$tType" => Ok(name),
+        _ => Err(Simple::custom(
+            span,
+            format!("Type {} is not supported", name),
+        )),
+    });
+
+    atomic_word().or(defined_type)
+}
+
+// Datatypes of quantified variables
+fn object_type_parser() -> impl Parser<char, String, Error = Simple<char>> + Clone {
+    type_parser().try_map(|name, span| match name.as_str() {
+        "$o" | "$tType" => Err(Simple::custom(
+            span,
+            "Only individuals can be quantified".to_string(),
+        )),
+        _ => Ok(name),
+    })
+}
+
+fn type_declaration_parser() -> impl Parser<char, TypeDeclaration, Error = Simple<char>> {
+    let param_types = type_parser()
+        .separated_by(symbol("*"))
+        .at_least(1)
+        .delimited_by(symbol("("), symbol(")"))
+        .or(type_parser().map(|param_type| vec![param_type]));
+
+    param_types
+        .then_ignore(symbol(">"))
+        .then(type_parser())
+        .map(|(param_types, return_type)| TypeDeclaration {
+            param_types,
+            return_type,
+        })
+        .or(type_parser().map(|return_type| TypeDeclaration {
+            param_types: vec![],
+            return_type,
+        }))
+}
+
+// Constants are parsed as functions without parameters, to tell them apart from variables.
+fn term_parser() -> impl Parser<char, PropParameter, Error = Simple<char>> + Clone {
+    recursive(|term| {
+        let args = term
+            .separated_by(symbol(","))
+            .at_least(1)
+            .delimited_by(symbol("("), symbol(")"));
+
+        upper_word()
+            .map(PropParameter::Uninstantiated)
+            .or(atomic_word()
+                .then(args.or_not())
+                .map(|(name, args)| PropParameter::Function(name, args.unwrap_or_default())))
+    })
+}
+
+pub fn tptp_formula_parser() -> impl Parser<char, Prop, Error = Simple<char>> {
+    recursive(|formula: Recursive<char, Prop, Simple<char>>| {
+        let unitary = recursive(|unitary: Recursive<char, Prop, Simple<char>>| {
+            let variables = upper_word()
+                .then(symbol(":").ignore_then(object_type_parser()).or_not())
+                .separated_by(symbol(","))
+                .at_least(1)
+                .delimited_by(symbol("["), symbol("]"));
+
+            let quantified = symbol("!")
+                .to(true)
+                .or(symbol("?").to(false))
+                .then(variables)
+                .then_ignore(symbol(":"))
+                .then(unitary.clone())
+                .map(|((universal, variables), body)| {
+                    variables.into_iter().rev().fold(
+                        body,
+                        |body, (object_ident, object_type_ident)| {
+                            let object_type_ident =
+                                object_type_ident.unwrap_or(INDIVIDUAL_TYPE.to_string());
+
+                            if universal {
+                                Prop::ForAll {
+                                    object_ident,
+                                    object_type_ident,
+                                    body: body.boxed(),
+                                }
+                            } else {
+                                Prop::Exists {
+                                    object_ident,
+                                    object_type_ident,
+                                    body: body.boxed(),
+                                }
+                            }
+                        },
+                    )
+                });
+
+            let negation = symbol("~").ignore_then(unitary).map(not);
+
+            let defined = dollar_word().try_map(|name, span| match name.as_str() {
+                "$true" => Ok(Prop::True),
+                "$false" => Ok(Prop::False),
+                _ => Err(Simple::custom(
+                    span,
+                    format!("Proposition {} is not supported", name),
+                )),
+            });
+
+            // "=" must not be confused with the implication "=>"
+            let equality = symbol("!=").to(false).or(just('=')
+                .then_ignore(just('>').not().rewind())
+                .padded_by(blank())
+                .to(true));
+
+            let atomic = term_parser()
+                .then(equality.then(term_parser()).or_not())
+                .try_map(|(lhs, equality), span| match (lhs, equality) {
+                    (lhs, Some((true, rhs))) => Ok(Prop::Eq(lhs, rhs)),
+                    (lhs, Some((false, rhs))) => Ok(not(Prop::Eq(lhs, rhs))),
+                    (PropParameter::Function(name, params), None) => Ok(Prop::Atom(name, params)),
+                    (variable, None) => Err(Simple::custom(
+                        span,
+                        format!("Variable {} is not a formula", variable),
+                    )),
+                });
+
+            choice((
+                quantified,
+                negation,
+                formula.delimited_by(symbol("("), symbol(")")),
+                defined,
+                atomic,
+            ))
+        });
+
+        let connective = choice((
+            symbol("<=>").to(Connective::Iff),
+            symbol("<~>").to(Connective::Xor),
+            symbol("=>").to(Connective::Impl),
+            symbol("<=").to(Connective::ReverseImpl),
+            symbol("~|").to(Connective::Nor),
+            symbol("~&").to(Connective::Nand),
+        ));
+
+        // binary connectives do not associate, except for conjunction and disjunction
+        let tail = choice((
+            connective
+                .then(unitary.clone())
+                .map(|(connective, rhs)| FormulaTail::Binary(connective, rhs)),
+            symbol("|")
+                .ignore_then(unitary.clone())
+                .repeated()
+                .at_least(1)
+                .map(FormulaTail::Or),
+            symbol("&")
+                .ignore_then(unitary.clone())
+                .repeated()
+                .at_least(1)
+                .map(FormulaTail::And),
+        ));
+
+        unitary.then(tail.or_not()).map(|(lhs, tail)| match tail {
+            None => lhs,
+            Some(FormulaTail::Binary(connective, rhs)) => {
+                let iff = |lhs: Prop, rhs: Prop| Prop::Defined("Iff".to_string(), vec![lhs, rhs]);
+
+                match connective {
+                    Connective::Iff => iff(lhs, rhs),
+                    Connective::Xor => not(iff(lhs, rhs)),
+                    Connective::Impl => Prop::Impl(lhs.boxed(), rhs.boxed()),
+                    Connective::ReverseImpl => Prop::Impl(rhs.boxed(), lhs.boxed()),
+                    Connective::Nor => not(Prop::Or(lhs.boxed(), rhs.boxed())),
+                    Connective::Nand => not(Prop::And(lhs.boxed(), rhs.boxed())),
+                }
+            }
+            Some(FormulaTail::Or(props)) => props
+                .into_iter()
+                .fold(lhs, |lhs, rhs| Prop::Or(lhs.boxed(), rhs.boxed())),
+            Some(FormulaTail::And(props)) => props
+                .into_iter()
+                .fold(lhs, |lhs, rhs| Prop::And(lhs.boxed(), rhs.boxed())),
+        })
+    })
+}
+
+fn statement_parser() -> impl Parser<char, Statement, Error = Simple<char>> {
+    let name = atomic_word().or(text::int(10).padded_by(blank()));
+
+    let role = lower_word().try_map(|role, span| {
+        TptpRole::from_name(&role)
+            .ok_or_else(|| Simple::custom(span, format!("Role {} is not supported", role)))
+    });
+
+    // tff(nat_type, type, nat: $tType).
+    let type_statement = just("tff")
+        .ignore_then(symbol("("))
+        .ignore_then(name.clone())
+        .then_ignore(symbol(","))
+        .then_ignore(symbol("type"))
+        .then_ignore(symbol(","))
+        .ignore_then(atomic_word())
+        .then_ignore(symbol(":"))
+        .then(type_declaration_parser())
+        .then_ignore(symbol(")"))
+        .map(|(name, declaration)| Statement::Type(name, declaration));
+
+    // fof(commutativity, axiom, ! [X, Y] : X + Y = Y + X).
+    let formula_statement = just("fof")
+        .or(just("tff"))
+        .ignore_then(symbol("("))
+        .ignore_then(name)
+        .then_ignore(symbol(","))
+        .then(role)
+        .then_ignore(symbol(","))
+        .then(tptp_formula_parser())
+        .then_ignore(symbol(")"))
+        .map(|((name, role), prop)| Statement::Formula(TptpFormula { name, role, prop }));
+
+    let include_statement = just("include")
+        .ignore_then(atomic_word().delimited_by(symbol("("), symbol(")")))
+        .map(Statement::Include);
+
+    choice((type_statement, formula_statement, include_statement)).then_ignore(symbol("."))
+}
+
+pub fn parse_tptp_problem(problem: &str) -> Result<TptpProblem, TptpError> {
+    let statements = blank()
+        .ignore_then(statement_parser().repeated())
+        .then_ignore(end())
+        .parse(problem)
+        .map_err(|errors| {
+            TptpError::ParserError(errors.into_iter().map(|err| err.to_string()).join("\n"))
+        })?;
+
+    let mut problem = TptpProblem {
+        datatypes: vec![],
+        atoms: vec![],
+        functions: vec![],
+        formulas: vec![],
+    };
+
+    for statement in statements {
+        match statement {
+            Statement::Include(file) => return Err(TptpError::IncludeUnsupported(file)),
+            Statement::Type(name, declaration) if declaration.return_type == "$tType" => {
+                problem.datatypes.push(name)
+            }
+            Statement::Type(name, declaration) if declaration.return_type == "$o" => {
+                problem.atoms.push(AtomSignature {
+                    name,
+                    param_types: declaration.param_types,
+                })
+            }
+            Statement::Type(name, declaration) => problem.functions.push(FunctionSignature {
+                name,
+                param_types: declaration.param_types,
+                return_type: declaration.return_type,
+            }),
+            Statement::Formula(mut formula) => {
+                if let Some(variable) = formula.prop.get_free_parameters().first() {
+                    return Err(TptpError::VariableUnbound(variable.name().clone()));
+                }
+
+                declare_symbols(&mut problem, &formula.prop)?;
+                rename_variables(&mut formula.prop);
+                replace_constants(&mut formula.prop);
+
+                problem.formulas.push(formula);
+            }
+        }
+    }
+
+    let names = problem
+        .datatypes
+        .iter()
+        .chain(problem.atoms.iter().map(|atom| &atom.name))
+        .chain(problem.functions.iter().map(|function| &function.name));
+    for name in names {
+        if !is_identifier(name) {
+            return Err(TptpError::NameInvalid(name.clone()));
+        }
+    }
+
+    let uses_individuals = problem
+        .formulas
+        .iter()
+        .flat_map(|formula| formula.prop.get_datatypes())
+        .chain(
+            problem
+                .atoms
+                .iter()
+                .flat_map(|atom| atom.param_types.clone()),
+        )
+        .chain(problem.functions.iter().flat_map(|function| {
+            [
+                function.param_types.clone(),
+                vec![function.return_type.clone()],
+            ]
+            .concat()
+        }))
+        .any(|datatype| datatype == INDIVIDUAL_TYPE);
+    if uses_individuals
+        && !problem
+            .datatypes
+            .iter()
+            .any(|datatype| datatype == INDIVIDUAL_TYPE)
+    {
+        problem.datatypes.insert(0, INDIVIDUAL_TYPE.to_string());
+    }
+
+    Ok(problem)
+}
+
+// Undeclared symbols range over individuals.
+fn declare_symbols(problem: &mut TptpProblem, prop: &Prop) -> Result<(), TptpError> {
+    for (name, arity) in prop.get_atoms().into_iter().unique() {
+        match problem.atoms.iter().find(|atom| atom.name == name) {
+            Some(atom) if atom.param_types.len() != arity => {
+                return Err(TptpError::ArityConflict(name))
+            }
+            Some(_) => {}
+            None => problem.atoms.push(AtomSignature {
+                name,
+                param_types: vec![INDIVIDUAL_TYPE.to_string(); arity],
+            }),
+        }
+    }
+
+    for (name, arity) in prop.get_functions().into_iter().unique() {
+        match problem
+            .functions
+            .iter()
+            .find(|function| function.name == name)
+        {
+            Some(function) if function.param_types.len() != arity => {
+                return Err(TptpError::ArityConflict(name))
+            }
+            Some(_) => {}
+            None => problem.functions.push(FunctionSignature {
+                name,
+                param_types: vec![INDIVIDUAL_TYPE.to_string(); arity],
+                return_type: INDIVIDUAL_TYPE.to_string(),
+            }),
+        }
+    }
+
+    Ok(())
+}
+
+// Constants are referred to by identifiers, just like variables.
+fn replace_constants(prop: &mut Prop) {
+    fn replace_in_term(term: &mut PropParameter) {
+        match term {
+            PropParameter::Function(name, params) if params.is_empty() => {
+                *term = PropParameter::Uninstantiated(name.clone())
+            }
+            PropParameter::Function(_, params) => params.iter_mut().for_each(replace_in_term),
+            _ => {}
+        }
+    }
+
+    match prop {
+        Prop::Atom(_, params) => params.iter_mut().for_each(replace_in_term),
+        Prop::Eq(lhs, rhs) => {
+            replace_in_term(lhs);
+            replace_in_term(rhs);
+        }
+        Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+            replace_constants(fst);
+            replace_constants(snd);
+        }
+        Prop::ForAll { body, .. } | Prop::Exists { body, .. } => replace_constants(body),
+        Prop::Defined(_, args) => args.iter_mut().for_each(replace_constants),
+        Prop::True | Prop::False => {}
+    }
+}
+
+// Variables like T are no identifiers of Alice, these are renamed to fresh identifiers like t.
+fn rename_variables(prop: &mut Prop) {
+    fn collect_names(prop: &Prop, names: &mut Vec<String>) {
+        match prop {
+            Prop::ForAll {
+                object_ident, body, ..
+            }
+            | Prop::Exists {
+                object_ident, body, ..
+            } => {
+                names.push(object_ident.clone());
+                collect_names(body, names);
+            }
+            Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+                collect_names(fst, names);
+                collect_names(snd, names);
+            }
+            Prop::Defined(_, args) => args.iter().for_each(|arg| collect_names(arg, names)),
+            Prop::Atom(_, _) | Prop::Eq(_, _) | Prop::True | Prop::False => {}
+        }
+    }
+
+    fn rename(prop: &mut Prop, names: &mut Vec<String>) {
+        match prop {
+            Prop::ForAll {
+                object_ident, body, ..
+            }
+            | Prop::Exists {
+                object_ident, body, ..
+            } => {
+                if !is_identifier(object_ident) {
+                    let fresh_name = std::iter::once(object_ident.to_lowercase())
+                        .chain((1..).map(|idx| format!("v{}", idx)))
+                        .find(|candidate| is_identifier(candidate) && !names.contains(candidate))
+                        .unwrap();
+
+                    body.substitute_free_parameter(
+                        object_ident,
+                        &PropParameter::Uninstantiated(fresh_name.clone()),
+                    );
+                    *object_ident = fresh_name.clone();
+                    names.push(fresh_name);
+                }
+
+                rename(body, names);
+            }
+            Prop::And(fst, snd) | Prop::Or(fst, snd) | Prop::Impl(fst, snd) => {
+                rename(fst, names);
+                rename(snd, names);
+            }
+            Prop::Defined(_, args) => args.iter_mut().for_each(|arg| rename(arg, names)),
+            Prop::Atom(_, _) | Prop::Eq(_, _) | Prop::True | Prop::False => {}
+        }
+    }
+
+    let mut names = prop
+        .get_functions()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    collect_names(prop, &mut names);
+
+    rename(prop, &mut names);
+}
+
+// Names have to be lexed as a single identifier by Alice, which rules out keywords.
+fn is_identifier(name: &str) -> bool {
+    matches!(
+        lexer().then_ignore(end()).parse(name).as_deref(),
+        Ok([(Token::IDENT(ident), _)]) if ident == name
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        parse::{fol::fol_parser, lexer::lexer},
+        prop::{AtomSignature, FunctionSignature, Prop},
+        tptp::{TptpError, TptpRole},
+    };
+
+    use super::{parse_tptp_problem, tptp_formula_parser};
+
+    fn parse_prop(prop: &str) -> Prop {
+        let len = prop.chars().count();
+
+        let tokens = lexer().parse(prop).unwrap();
+        fol_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    fn parse_tptp_formula(formula: &str) -> Prop {
+        tptp_formula_parser()
+            .then_ignore(end())
+            .parse(formula)
+            .unwrap()
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_parse_propositional() {
+        let problem = parse_tptp_problem(include_str!("samples/contraposition.p")).unwrap();
+
+        assert!(problem.datatypes.is_empty());
+        assert!(problem.functions.is_empty());
+        assert_eq!(
+            problem.atoms,
+            vec![
+                AtomSignature {
+                    name: "p".to_string(),
+                    param_types: vec![]
+                },
+                AtomSignature {
+                    name: "q".to_string(),
+                    param_types: vec![]
+                },
+            ]
+        );
+        assert_eq!(problem.to_prop(), parse_prop("(p -> q) -> ~q -> ~p"));
+    }
+
+    #[test]
+    fn test_parse_untyped() {
+        let problem = parse_tptp_problem(include_str!("samples/socrates.p")).unwrap();
+
+        assert_eq!(problem.datatypes, vec!["i".to_string()]);
+        assert_eq!(
+            problem.functions,
+            vec![FunctionSignature {
+                name: "socrates".to_string(),
+                param_types: vec![],
+                return_type: "i".to_string()
+            }]
+        );
+        assert_eq!(
+            problem
+                .formulas
+                .iter()
+                .map(|formula| formula.role)
+                .collect::<Vec<_>>(),
+            vec![TptpRole::Axiom, TptpRole::Axiom, TptpRole::Conjecture]
+        );
+        assert_eq!(
+            problem.to_prop(),
+            parse_prop("(\\forall X:i. man(X) -> mortal(X)) -> man(socrates) -> mortal(socrates)")
+        );
+
+        let problem = parse_tptp_problem(include_str!("samples/drinker.p")).unwrap();
+
+        assert_eq!(
+            problem.to_prop(),
+            parse_prop("\\exists Y:i. \\forall X:i. drinks(Y) -> drinks(X)")
+        );
+    }
+
+    #[test]
+    fn test_parse_typed() {
+        let problem = parse_tptp_problem(include_str!("samples/naturals.p")).unwrap();
+
+        assert_eq!(problem.datatypes, vec!["nat".to_string()]);
+        assert_eq!(
+            problem.atoms,
+            vec![AtomSignature {
+                name: "less".to_string(),
+                param_types: vec!["nat".to_string(), "nat".to_string()]
+            }]
+        );
+        assert_eq!(
+            problem.functions,
+            vec![
+                FunctionSignature {
+                    name: "zero".to_string(),
+                    param_types: vec![],
+                    return_type: "nat".to_string()
+                },
+                FunctionSignature {
+                    name: "succ".to_string(),
+                    param_types: vec!["nat".to_string()],
+                    return_type: "nat".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            problem
+                .formulas
+                .iter()
+                .map(|formula| formula.prop.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                parse_prop("\\forall N:nat. less(N, succ(N))"),
+                parse_prop("\\forall M:nat. \\forall N:nat. succ(M) = succ(N) -> M = N"),
+                parse_prop("\\forall N:nat. ~(zero = succ(N))"),
+                parse_prop("less(zero, succ(zero))"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_connectives() {
+        assert_eq!(parse_tptp_formula("p <= q"), parse_prop("q -> p"));
+        assert_eq!(parse_tptp_formula("p <=> q"), parse_prop("p <-> q"));
+        assert_eq!(parse_tptp_formula("p <~> q"), parse_prop("~(p <-> q)"));
+        assert_eq!(parse_tptp_formula("p ~| q"), parse_prop("~(p || q)"));
+        assert_eq!(parse_tptp_formula("p ~& q"), parse_prop("~(p && q)"));
+        assert_eq!(
+            parse_tptp_formula("p & q & ~ ~ r"),
+            parse_prop("p && q && ~~r")
+        );
+        assert_eq!(
+            parse_tptp_formula("$true | ($false => p)"),
+            parse_prop("\\top || (\\bot -> p)")
+        );
+        assert_eq!(
+            parse_tptp_formula("! [X] : p(X) => ? [Y] : p(Y)"),
+            parse_prop("(\\forall X:i. p(X)) -> \\exists Y:i. p(Y)")
+        );
+    }
+
+    #[test]
+    fn test_rename_variables() {
+        let problem = parse_tptp_problem("fof(true, conjecture, ! [T] : p(T)).").unwrap();
+        assert_eq!(problem.to_prop(), parse_prop("\\forall t:i. p(t)"));
+
+        // renamed variables must not capture constants or other variables
+        let problem = parse_tptp_problem(
+            "fof(capture, conjecture, ! [T, True] : (p(T, t) => ? [X] : q(X, v1, True))).",
+        )
+        .unwrap();
+        assert_eq!(
+            problem.to_prop(),
+            parse_prop("\\forall v2:i. \\forall v3:i. p(v2, t) -> \\exists X:i. q(X, v1, v3)")
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_tptp_problem("include('Axioms/SET001-0.ax')."),
+            Err(TptpError::IncludeUnsupported(
+                "Axioms/SET001-0.ax".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_tptp_problem("fof(free, axiom, p(X))."),
+            Err(TptpError::VariableUnbound("X".to_string()))
+        );
+        assert_eq!(
+            parse_tptp_problem("fof(arity, axiom, p(c) | p)."),
+            Err(TptpError::ArityConflict("p".to_string()))
+        );
+        assert_eq!(
+            parse_tptp_problem("fof(keyword, axiom, 'fn')."),
+            Err(TptpError::NameInvalid("fn".to_string()))
+        );
+        assert!(matches!(
+            parse_tptp_problem("fof(role, plain, p)."),
+            Err(TptpError::ParserError(_))
+        ));
+        assert!(matches!(
+            parse_tptp_problem("tff(int, axiom, ! [X: $int] : p(X))."),
+            Err(TptpError::ParserError(_))
+        ));
+    }
+}
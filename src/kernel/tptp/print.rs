@@ -0,0 +1,430 @@
+use std::fmt::{self, Display};
+
+use itertools::Itertools;
+
+use crate::kernel::prop::{AtomSignature, FunctionSignature, Prop, PropParameter};
+
+use super::{TptpFormula, TptpProblem, TptpRole, INDIVIDUAL_TYPE};
+
+impl TptpProblem {
+    // Symbols without signature range over individuals.
+    pub fn from_prop(
+        prop: &Prop,
+        atom_signatures: &[AtomSignature],
+        functions: &[FunctionSignature],
+    ) -> Self {
+        let atoms = prop
+            .get_atoms()
+            .into_iter()
+            .unique()
+            .map(|(name, arity)| {
+                atom_signatures
+                    .iter()
+                    .find(|atom| atom.name == name && atom.param_types.len() == arity)
+                    .cloned()
+                    .unwrap_or(AtomSignature {
+                        name,
+                        param_types: vec![INDIVIDUAL_TYPE.to_string(); arity],
+                    })
+            })
+            .collect_vec();
+
+        // constants are the free parameters of the proposition
+        let constants = prop
+            .get_free_parameters()
+            .into_iter()
+            .map(|param| (param.name().clone(), 0));
+        let functions = prop
+            .get_functions()
+            .into_iter()
+            .chain(constants)
+            .unique()
+            .map(|(name, arity)| {
+                functions
+                    .iter()
+                    .find(|function| function.name == name && function.param_types.len() == arity)
+                    .cloned()
+                    .unwrap_or(FunctionSignature {
+                        name,
+                        param_types: vec![INDIVIDUAL_TYPE.to_string(); arity],
+                        return_type: INDIVIDUAL_TYPE.to_string(),
+                    })
+            })
+            .collect_vec();
+
+        let datatypes = prop
+            .get_datatypes()
+            .into_iter()
+            .chain(atoms.iter().flat_map(|atom| atom.param_types.clone()))
+            .chain(functions.iter().flat_map(|function| {
+                [
+                    function.param_types.clone(),
+                    vec![function.return_type.clone()],
+                ]
+                .concat()
+            }))
+            .unique()
+            .collect_vec();
+
+        Self {
+            datatypes,
+            atoms,
+            functions,
+            formulas: vec![TptpFormula {
+                name: "goal".to_string(),
+                role: TptpRole::Conjecture,
+                prop: prop.clone(),
+            }],
+        }
+    }
+}
+
+impl Display for TptpProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // individuals are built into TPTP
+        let datatype_declarations = self
+            .datatypes
+            .iter()
+            .filter(|datatype| *datatype != INDIVIDUAL_TYPE)
+            .map(|datatype| type_declaration(datatype, "$tType".to_string()));
+        let atom_declarations = self.atoms.iter().map(|atom| {
+            type_declaration(
+                &atom.name,
+                signature_type(&atom.param_types, "$o".to_string()),
+            )
+        });
+        let function_declarations = self.functions.iter().map(|function| {
+            type_declaration(
+                &function.name,
+                signature_type(&function.param_types, tptp_type(&function.return_type)),
+            )
+        });
+        let declarations = datatype_declarations
+            .chain(atom_declarations)
+            .chain(function_declarations)
+            .collect_vec();
+
+        let formulas = self.formulas.iter().map(|formula| {
+            format!(
+                "tff({}, {}, {}).",
+                tptp_name(&formula.name),
+                formula.role.name(),
+                tptp_formula(&formula.prop, &mut vec![])
+            )
+        });
+
+        if declarations.is_empty() {
+            write!(f, "{}", formulas.format("\n"))
+        } else {
+            write!(
+                f,
+                "{}\n\n{}",
+                declarations.iter().format("\n"),
+                formulas.format("\n")
+            )
+        }
+    }
+}
+
+fn type_declaration(name: &str, _type: String) -> String {
+    format!(
+        "tff({}, type, {}: {}).",
+        tptp_name(&format!("{}_type", name)),
+        tptp_name(name),
+        _type
+    )
+}
+
+fn signature_type(param_types: &[String], return_type: String) -> String {
+    match param_types {
+        [] => return_type,
+        [param_type] => format!("{} > {}", tptp_type(param_type), return_type),
+        _ => format!(
+            "({}) > {}",
+            param_types
+                .iter()
+                .map(|param_type| tptp_type(param_type))
+                .join(" * "),
+            return_type
+        ),
+    }
+}
+
+// Names which are not lower words have to be quoted.
+fn tptp_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_lower_word = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if is_lower_word {
+        name.to_string()
+    } else {
+        format!("'{}'", name.replace('\\', "\\\\").replace('\'', "\\'"))
+    }
+}
+
+fn tptp_type(datatype: &str) -> String {
+    if datatype == INDIVIDUAL_TYPE {
+        "$i".to_string()
+    } else {
+        tptp_name(datatype)
+    }
+}
+
+// Variables have to start with an upper case letter and must not be captured by other variables.
+fn tptp_variable(ident: &str, variables: &[(String, String)]) -> String {
+    let mut chars = ident.chars();
+    let mut variable = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+        None => ident.to_string(),
+    };
+
+    while variables
+        .iter()
+        .any(|(other_ident, other_variable)| other_ident != ident && *other_variable == variable)
+    {
+        variable.push('_');
+    }
+
+    variable
+}
+
+fn tptp_term(term: &PropParameter, variables: &[(String, String)]) -> String {
+    match term {
+        PropParameter::Function(name, params) if !params.is_empty() => format!(
+            "{}({})",
+            tptp_name(name),
+            params
+                .iter()
+                .map(|param| tptp_term(param, variables))
+                .join(", ")
+        ),
+        _ => match variables
+            .iter()
+            .rev()
+            .find(|(ident, _)| ident == term.name())
+        {
+            Some((_, variable)) => variable.clone(),
+            None => tptp_name(term.name()),
+        },
+    }
+}
+
+fn is_unitary(prop: &Prop) -> bool {
+    match prop {
+        Prop::And(_, _) | Prop::Or(_, _) => false,
+        Prop::Impl(_, snd) => **snd == Prop::False,
+        Prop::Defined(_, _) => !prop.is_iff(),
+        _ => true,
+    }
+}
+
+fn tptp_unitary_formula(prop: &Prop, variables: &mut Vec<(String, String)>) -> String {
+    if is_unitary(prop) {
+        tptp_formula(prop, variables)
+    } else {
+        format!("({})", tptp_formula(prop, variables))
+    }
+}
+
+fn tptp_formula(prop: &Prop, variables: &mut Vec<(String, String)>) -> String {
+    match prop {
+        Prop::True => "$true".to_string(),
+        Prop::False => "$false".to_string(),
+        Prop::Atom(name, params) if params.is_empty() => tptp_name(name),
+        Prop::Atom(name, params) => format!(
+            "{}({})",
+            tptp_name(name),
+            params
+                .iter()
+                .map(|param| tptp_term(param, variables))
+                .join(", ")
+        ),
+        Prop::Eq(lhs, rhs) => format!(
+            "{} = {}",
+            tptp_term(lhs, variables),
+            tptp_term(rhs, variables)
+        ),
+        Prop::Impl(fst, snd) if **snd == Prop::False => match fst.as_ref() {
+            Prop::Eq(lhs, rhs) => format!(
+                "{} != {}",
+                tptp_term(lhs, variables),
+                tptp_term(rhs, variables)
+            ),
+            _ => format!("~ {}", tptp_unitary_formula(fst, variables)),
+        },
+        Prop::Impl(fst, snd) => format!(
+            "{} => {}",
+            tptp_unitary_formula(fst, variables),
+            tptp_unitary_formula(snd, variables)
+        ),
+        // conjunction and disjunction associate to the left
+        Prop::And(fst, snd) => {
+            let fst = match fst.as_ref() {
+                Prop::And(_, _) => tptp_formula(fst, variables),
+                _ => tptp_unitary_formula(fst, variables),
+            };
+
+            format!("{} & {}", fst, tptp_unitary_formula(snd, variables))
+        }
+        Prop::Or(fst, snd) => {
+            let fst = match fst.as_ref() {
+                Prop::Or(_, _) => tptp_formula(fst, variables),
+                _ => tptp_unitary_formula(fst, variables),
+            };
+
+            format!("{} | {}", fst, tptp_unitary_formula(snd, variables))
+        }
+        Prop::Defined(_, args) if prop.is_iff() => format!(
+            "{} <=> {}",
+            tptp_unitary_formula(&args[0], variables),
+            tptp_unitary_formula(&args[1], variables)
+        ),
+        Prop::Defined(_, _) => panic!("Only unfolded propositions can be exported."),
+        Prop::ForAll { .. } | Prop::Exists { .. } => {
+            let (quantifier, universal) = match prop {
+                Prop::ForAll { .. } => ("!", true),
+                _ => ("?", false),
+            };
+
+            // consecutive quantifiers of the same kind share a variable list
+            let scope = variables.len();
+            let mut bindings = vec![];
+            let mut body = prop;
+            while let (
+                Prop::ForAll {
+                    object_ident,
+                    object_type_ident,
+                    body: inner,
+                },
+                true,
+            )
+            | (
+                Prop::Exists {
+                    object_ident,
+                    object_type_ident,
+                    body: inner,
+                },
+                false,
+            ) = (body, universal)
+            {
+                let variable = tptp_variable(object_ident, variables);
+                bindings.push(format!("{}: {}", variable, tptp_type(object_type_ident)));
+                variables.push((object_ident.clone(), variable));
+                body = inner;
+            }
+
+            let body = tptp_unitary_formula(body, variables);
+            variables.truncate(scope);
+
+            format!("{} [{}] : {}", quantifier, bindings.join(", "), body)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{primitive::end, Parser, Stream};
+
+    use crate::kernel::{
+        parse::{fol::fol_parser, lexer::lexer},
+        prop::{AtomSignature, FunctionSignature, Prop},
+        tptp::{parse::parse_tptp_problem, TptpProblem},
+    };
+
+    fn parse_prop(prop: &str) -> Prop {
+        let len = prop.chars().count();
+
+        let tokens = lexer().parse(prop).unwrap();
+        fol_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_print_propositional() {
+        let problem = TptpProblem::from_prop(
+            &parse_prop("(A -> B) && (B <-> C) -> ~C -> ~A || \\bot"),
+            &[],
+            &[],
+        );
+
+        assert_eq!(
+            problem.to_string(),
+            "tff('A_type', type, 'A': $o).\n\
+             tff('B_type', type, 'B': $o).\n\
+             tff('C_type', type, 'C': $o).\n\
+             \n\
+             tff(goal, conjecture, (('A' => 'B') & ('B' <=> 'C')) => (~ 'C' => (~ 'A' | $false)))."
+        );
+    }
+
+    #[test]
+    fn test_print_typed() {
+        let problem = TptpProblem::from_prop(
+            &parse_prop("\\forall n:nat. \\exists m:nat. Less(n, m) && ~(m = zero) && n = s(n)"),
+            &[AtomSignature {
+                name: "Less".to_string(),
+                param_types: vec!["nat".to_string(), "nat".to_string()],
+            }],
+            &[
+                FunctionSignature {
+                    name: "zero".to_string(),
+                    param_types: vec![],
+                    return_type: "nat".to_string(),
+                },
+                FunctionSignature {
+                    name: "s".to_string(),
+                    param_types: vec!["nat".to_string()],
+                    return_type: "nat".to_string(),
+                },
+            ],
+        );
+
+        assert_eq!(
+            problem.to_string(),
+            "tff(nat_type, type, nat: $tType).\n\
+             tff('Less_type', type, 'Less': (nat * nat) > $o).\n\
+             tff(s_type, type, s: nat > nat).\n\
+             tff(zero_type, type, zero: nat).\n\
+             \n\
+             tff(goal, conjecture, ! [N: nat] : ? [M: nat] : ('Less'(N, M) & M != zero & N = s(N)))."
+        );
+    }
+
+    #[test]
+    fn test_print_variables() {
+        let problem = TptpProblem::from_prop(
+            &parse_prop("\\forall x:i. \\forall X:i. P(x, X, c) -> \\forall x:i. P(x, X, x)"),
+            &[],
+            &[],
+        );
+
+        assert_eq!(
+            problem.to_string(),
+            "tff('P_type', type, 'P': ($i * $i * $i) > $o).\n\
+             tff(c_type, type, c: $i).\n\
+             \n\
+             tff(goal, conjecture, ! [X: $i, X_: $i] : ('P'(X, X_, c) => ! [X: $i] : 'P'(X, X_, X)))."
+        );
+    }
+
+    #[test]
+    fn test_print_round_trip() {
+        let samples = [
+            include_str!("samples/contraposition.p"),
+            include_str!("samples/drinker.p"),
+            include_str!("samples/socrates.p"),
+            include_str!("samples/naturals.p"),
+        ];
+
+        for sample in samples {
+            let problem = parse_tptp_problem(sample).unwrap();
+
+            assert_eq!(parse_tptp_problem(&problem.to_string()), Ok(problem));
+        }
+    }
+}
@@ -0,0 +1,8 @@
+%------------------------------------------------------------------------------
+% Problem  : Contraposition of an implication
+% Logic    : Propositional, intuitionistically valid
+%------------------------------------------------------------------------------
+fof(contraposition, conjecture,
+    ( ( p => q )
+   => ( ~ q => ~ p ) ) ).
+%------------------------------------------------------------------------------
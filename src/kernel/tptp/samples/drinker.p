@@ -0,0 +1,10 @@
+%------------------------------------------------------------------------------
+% Problem  : Drinker paradox (Pelletier 18)
+% Logic    : First-order, classically valid
+%------------------------------------------------------------------------------
+fof(pel18, conjecture,
+    ? [Y] :
+    ! [X] :
+      ( drinks(Y)
+     => drinks(X) ) ).
+%------------------------------------------------------------------------------
@@ -0,0 +1,24 @@
+%------------------------------------------------------------------------------
+% Problem  : Zero is less than one
+% Logic    : First-order, typed
+%------------------------------------------------------------------------------
+tff(nat_type, type, nat: $tType).
+tff(zero_type, type, zero: nat).
+tff(succ_type, type, succ: nat > nat).
+tff(less_type, type, less: ( nat * nat ) > $o).
+
+tff(less_succ, axiom,
+    ! [N: nat] : less(N, succ(N)) ).
+
+tff(succ_injective, axiom,
+    ! [M: nat, N: nat] :
+      ( succ(M) = succ(N)
+     => M = N ) ).
+
+tff(zero_not_succ, axiom,
+    ! [N: nat] : zero != succ(N) ).
+
+/* instance of less_succ */
+tff(zero_less_one, conjecture,
+    less(zero, succ(zero)) ).
+%------------------------------------------------------------------------------
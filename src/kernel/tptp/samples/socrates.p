@@ -0,0 +1,15 @@
+%------------------------------------------------------------------------------
+% Problem  : Socrates is mortal
+% Logic    : First-order, untyped
+%------------------------------------------------------------------------------
+fof(all_men_mortal, axiom,
+    ! [X] :
+      ( man(X)
+     => mortal(X) ) ).
+
+fof(socrates_man, axiom,
+    man(socrates) ).
+
+fof(socrates_mortal, conjecture,
+    mortal(socrates) ).
+%------------------------------------------------------------------------------
@@ -8,20 +8,51 @@ use kernel::{
         check::{check, CheckError},
         identifier::Identifier,
         identifier_context::IdentifierContext,
-        TypeCheckerResult,
+        Logic, TypeCheckerResult,
+    },
+    export::{
+        agda_exporter::AgdaExporter,
+        coq_exporter::CoqExporter,
+        fitch::FitchProof,
+        haskell_exporter::HaskellExporter,
+        latex_exporter::{LatexExporter, LatexProofTreeStyle},
+        lean_exporter::LeanExporter,
+        ocaml_exporter::OcamlExporter,
+        typescript_exporter::TypescriptExporter,
+        ProofExporter,
+    },
+    parse::{
+        fol::fol_parser,
+        lexer::lexer,
+        proof::{proof_parser, theorem_file_parser},
+    },
+    process::{
+        stages::resolve_datatypes::{resolve_declarations, ResolveDatatypes},
+        ProofPipeline, ProofPipelineError,
     },
-    export::{ocaml_exporter::OcamlExporter, ProofExporter},
-    parse::{fol::fol_parser, lexer::lexer, proof::proof_parser},
-    process::{stages::resolve_datatypes::ResolveDatatypes, ProofPipeline, ProofPipelineError},
     proof::Proof,
-    proof_tree::{ProofTree, ProofTreeConclusion},
-    prop::{Prop, PropParameter, QuantifierKind},
-    prove::prove,
+    proof_term::ProofTerm,
+    proof_tree::{ProofTree, ProofTreeConclusion, ProofTreeNormalizationError},
+    prop::{AtomSignature, FunctionSignature, Prop, PropDefinition, PropParameter, QuantifierKind},
+    prove::{
+        countermodel::generate_countermodel,
+        find_proof, find_proof_with_trace,
+        trace::TracedProverOutcome,
+        ProverConfig, ProverOutcome,
+    },
+    reduction::{normalize, Normalization, DEFAULT_MAX_REDUCTION_STEPS},
+    semantics::{
+        kripke::{KripkeModel, KripkeModelError},
+        truth_table::{TruthTable, TruthTableError, TruthTableRow},
+    },
+    theorem_file::CheckedTheorem,
+    tptp::{parse::parse_tptp_problem, TptpError, TptpProblem},
 };
 
 use wasm_bindgen::prelude::*;
 
 pub mod kernel;
+pub mod lsp;
 pub mod util;
 
 use serde::{Deserialize, Serialize};
@@ -41,8 +72,18 @@ pub enum BackendError {
     #[error("Failed to process proof term")]
     ProofTermProcessingError(#[from] ProofPipelineError),
 
+    // boxed, as type errors contain whole proof terms
     #[error("Failed to type check")]
-    CheckError(#[from] CheckError),
+    CheckError(Box<CheckError>),
+
+    #[error("Failed to normalize proof tree")]
+    ProofTreeNormalizationError(#[from] ProofTreeNormalizationError),
+}
+
+impl From<CheckError> for BackendError {
+    fn from(err: CheckError) -> Self {
+        BackendError::CheckError(Box::new(err))
+    }
 }
 
 #[wasm_bindgen]
@@ -78,6 +119,7 @@ pub fn print_prop(prop: &Prop) -> String {
 pub enum VerificationResultSolvableStatus {
     Solvable,
     Unsolvable,
+    ClassicallySolvable,
     Unknown,
 }
 
@@ -88,50 +130,94 @@ pub enum VerificationResult {
     LexerError {
         error_message: String,
         solvable: VerificationResultSolvableStatus,
+        countermodel: Option<KripkeModel>,
     },
 
     ParserError {
         error_message: String,
         solvable: VerificationResultSolvableStatus,
+        countermodel: Option<KripkeModel>,
     },
 
     ProofPipelineError {
         error: ProofPipelineError,
         solvable: VerificationResultSolvableStatus,
+        countermodel: Option<KripkeModel>,
     },
 
     TypeCheckerError {
         error: CheckError,
         solvable: VerificationResultSolvableStatus,
+        countermodel: Option<KripkeModel>,
     },
 
     TypeCheckSucceeded {
         result: TypeCheckerResult,
         solvable: VerificationResultSolvableStatus,
+        countermodel: Option<KripkeModel>,
     },
 }
 
 #[wasm_bindgen]
-pub fn verify(prop: &Prop, proof_term: &str) -> VerificationResult {
-    let get_prop_solvable_status = |prop: &Prop| {
-        let solvable = prove(&prop).is_some();
+pub fn verify(
+    prop: &Prop,
+    proof_term: &str,
+    logic: Logic,
+    prover_config: Option<ProverConfig>,
+) -> VerificationResult {
+    let prover_config = prover_config.unwrap_or_default();
+
+    // the prover needs the declarations of the proof, which are unknown until it is processed
+    let mut declarations_ctx = IdentifierContext::new();
+    declarations_ctx.set_prover_config(prover_config.clone());
+
+    let get_prop_solvable_status = |prop: &Prop, ctx: &IdentifierContext| {
+        let unfolded_prop = prop.unfold_definitions(ctx.definitions());
+        let is_propositional = !unfolded_prop.has_quantifiers()
+            && !unfolded_prop.has_equalities()
+            && !unfolded_prop.has_definitions()
+            && !unfolded_prop.has_free_parameters();
+
+        // Glivenko: a propositional A is classically provable iff ~~A is intuitionistically
+        // provable. Otherwise only an intuitionistic proof of A itself is searched for.
+        let goal = match logic {
+            Logic::Classical if is_propositional => Prop::Impl(
+                Prop::Impl(prop.boxed(), Prop::False.boxed()).boxed(),
+                Prop::False.boxed(),
+            ),
+            _ => prop.clone(),
+        };
 
-        if solvable {
-            VerificationResultSolvableStatus::Solvable
-        } else {
-            if !prop.has_quantifiers() && !prop.has_free_parameters() {
-                return VerificationResultSolvableStatus::Unsolvable;
-            }
+        match find_proof(&goal, ctx) {
+            ProverOutcome::Proved(_) => VerificationResultSolvableStatus::Solvable,
+            // the search is only complete for propositional logic, it lacks e.g. equality elimination
+            ProverOutcome::Refuted if is_propositional => {
+                let classically_valid = logic == Logic::Intuitionistic
+                    && TruthTable::generate(prop).is_ok_and(|table| table.is_tautology());
+
+                if classically_valid {
+                    return VerificationResultSolvableStatus::ClassicallySolvable;
+                }
 
-            let negative_solvable = prove(&Prop::Impl(prop.boxed(), Prop::False.boxed())).is_some();
-            if negative_solvable {
-                return VerificationResultSolvableStatus::Unsolvable;
+                VerificationResultSolvableStatus::Unsolvable
             }
+            _ => {
+                let negative_goal = Prop::Impl(prop.boxed(), Prop::False.boxed());
+                if let ProverOutcome::Proved(_) = find_proof(&negative_goal, ctx) {
+                    return VerificationResultSolvableStatus::Unsolvable;
+                }
 
-            VerificationResultSolvableStatus::Unknown
+                VerificationResultSolvableStatus::Unknown
+            }
         }
     };
 
+    // Kripke models only refute intuitionistic provability
+    let get_prop_countermodel = |prop: &Prop| match logic {
+        Logic::Intuitionistic => generate_countermodel(prop, &prover_config),
+        Logic::Classical => None,
+    };
+
     let proof_term_len = proof_term.chars().count();
 
     // Step 1: Parse ProofTerm tokens
@@ -140,7 +226,8 @@ pub fn verify(prop: &Prop, proof_term: &str) -> VerificationResult {
     if let Err(err) = token_result {
         return VerificationResult::LexerError {
             error_message: format_errors(err, proof_term),
-            solvable: get_prop_solvable_status(prop),
+            solvable: get_prop_solvable_status(prop, &declarations_ctx),
+            countermodel: get_prop_countermodel(prop),
         };
     }
 
@@ -155,7 +242,8 @@ pub fn verify(prop: &Prop, proof_term: &str) -> VerificationResult {
     if let Err(err) = proof_result {
         return VerificationResult::ParserError {
             error_message: format_errors(err, proof_term),
-            solvable: get_prop_solvable_status(prop),
+            solvable: get_prop_solvable_status(prop, &declarations_ctx),
+            countermodel: get_prop_countermodel(prop),
         };
     }
 
@@ -169,25 +257,34 @@ pub fn verify(prop: &Prop, proof_term: &str) -> VerificationResult {
     if let Err(err) = processed_proof_result {
         return VerificationResult::ProofPipelineError {
             error: err,
-            solvable: get_prop_solvable_status(prop),
+            solvable: get_prop_solvable_status(prop, &declarations_ctx),
+            countermodel: get_prop_countermodel(prop),
         };
     }
 
     let processed_proof = processed_proof_result.unwrap();
 
     // Step 4: Type Checking
-    let type_checking_result = check(
-        &processed_proof.proof_term,
-        &prop,
-        &IdentifierContext::new(),
+    let mut ctx = IdentifierContext::with_signatures(
+        processed_proof.functions,
+        processed_proof.atom_signatures,
     );
+    ctx.add_definitions(processed_proof.definitions);
+    ctx.set_logic(logic.clone());
+    ctx.set_prover_config(prover_config.clone());
+
+    let type_checking_result = check(&processed_proof.proof_term, &prop, &ctx);
+
+    // the prover and countermodels only know the built-in definitions
+    let prop = &prop.unfold_definitions(ctx.definitions());
 
     // Step 5: Prepare response
 
     if type_checking_result.is_err() {
         return VerificationResult::TypeCheckerError {
             error: type_checking_result.unwrap_err(),
-            solvable: get_prop_solvable_status(&prop),
+            solvable: get_prop_solvable_status(prop, &ctx),
+            countermodel: get_prop_countermodel(prop),
         };
     }
 
@@ -201,18 +298,162 @@ pub fn verify(prop: &Prop, proof_term: &str) -> VerificationResult {
         .iter()
         .all(|goal| goal.solution.is_some());
 
-    let status = if all_goals_have_solution {
-        VerificationResultSolvableStatus::Solvable
+    let (status, countermodel) = if all_goals_have_solution {
+        (VerificationResultSolvableStatus::Solvable, None)
     } else {
-        get_prop_solvable_status(prop)
+        (get_prop_solvable_status(prop, &ctx), get_prop_countermodel(prop))
     };
 
     VerificationResult::TypeCheckSucceeded {
         result: checker_result,
         solvable: status,
+        countermodel,
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", content = "value")]
+pub enum TheoremFileVerificationResult {
+    LexerError { error_message: String },
+    ParserError { error_message: String },
+    Checked { theorems: Vec<CheckedTheorem> },
+}
+
+#[wasm_bindgen]
+pub fn verify_theorem_file(
+    src: &str,
+    logic: Logic,
+    prover_config: Option<ProverConfig>,
+) -> TheoremFileVerificationResult {
+    let len = src.chars().count();
+
+    // Step 1: Parse tokens
+    let tokens = match lexer().then_ignore(end()).parse(src) {
+        Ok(tokens) => tokens,
+        Err(err) => {
+            return TheoremFileVerificationResult::LexerError {
+                error_message: format_errors(err, src),
+            }
+        }
+    };
+
+    // Step 2: Parse theorems
+    let theorem_file = match theorem_file_parser()
+        .then_ignore(end())
+        .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+    {
+        Ok(theorem_file) => theorem_file,
+        Err(err) => {
+            return TheoremFileVerificationResult::ParserError {
+                error_message: format_errors(err, src),
+            }
+        }
+    };
+
+    // Step 3: Check every theorem
+    TheoremFileVerificationResult::Checked {
+        theorems: theorem_file.check(logic, prover_config.unwrap_or_default()),
     }
 }
 
+// Builds the context of a theorem file without theorems, e.g. "datatype t; function f: t -> t;".
+fn parse_declarations(declarations: &str) -> Result<IdentifierContext, BackendError> {
+    let len = declarations.chars().count();
+
+    // Step 1: Parse tokens
+    let tokens = lexer()
+        .then_ignore(end())
+        .parse(declarations)
+        .map_err(|err| BackendError::LexerError(format_errors(err, declarations)))?;
+
+    // Step 2: Parse declarations, a theorem file without theorems
+    let theorem_file = theorem_file_parser()
+        .then_ignore(end())
+        .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+        .map_err(|err| BackendError::ParserError(format_errors(err, declarations)))?;
+
+    // Step 3: Check declarations
+    let definitions = resolve_declarations(
+        &theorem_file.atoms,
+        &theorem_file.datatypes,
+        &theorem_file.functions,
+        &theorem_file.definitions,
+    )
+    .map_err(ProofPipelineError::from)?;
+
+    let mut ctx =
+        IdentifierContext::with_signatures(theorem_file.functions, theorem_file.atom_signatures);
+    ctx.add_definitions(definitions);
+
+    Ok(ctx)
+}
+
+#[wasm_bindgen]
+pub fn prove_with_declarations(
+    prop: &Prop,
+    declarations: &str,
+    prover_config: Option<ProverConfig>,
+) -> Result<ProverOutcome, BackendError> {
+    let mut ctx = parse_declarations(declarations)?;
+    ctx.set_prover_config(prover_config.unwrap_or_default());
+
+    Ok(find_proof(prop, &ctx))
+}
+
+#[wasm_bindgen]
+pub fn trace_proof_search(
+    prop: &Prop,
+    declarations: &str,
+    prover_config: Option<ProverConfig>,
+) -> Result<TracedProverOutcome, BackendError> {
+    let mut ctx = parse_declarations(declarations)?;
+    ctx.set_prover_config(prover_config.unwrap_or_default());
+
+    Ok(find_proof_with_trace(prop, &ctx))
+}
+
+#[wasm_bindgen]
+pub fn find_countermodel(prop: &Prop, prover_config: Option<ProverConfig>) -> Option<KripkeModel> {
+    generate_countermodel(prop, &prover_config.unwrap_or_default())
+}
+
+#[wasm_bindgen]
+pub fn validate_kripke_model(model: &KripkeModel) -> Result<(), KripkeModelError> {
+    model.validate()
+}
+
+#[wasm_bindgen]
+pub fn kripke_model_forces(
+    model: &KripkeModel,
+    world: usize,
+    prop: &Prop,
+) -> Result<bool, KripkeModelError> {
+    model.validate()?;
+
+    model.forces(world, prop)
+}
+
+#[wasm_bindgen]
+pub fn generate_truth_table(prop: &Prop) -> Result<TruthTable, TruthTableError> {
+    TruthTable::generate(prop)
+}
+
+#[wasm_bindgen]
+pub fn is_tautology(prop: &Prop) -> Result<bool, TruthTableError> {
+    Ok(TruthTable::generate(prop)?.is_tautology())
+}
+
+#[wasm_bindgen]
+pub fn is_satisfiable(prop: &Prop) -> Result<bool, TruthTableError> {
+    Ok(TruthTable::generate(prop)?.is_satisfiable())
+}
+
+#[wasm_bindgen]
+pub fn get_falsifying_assignments(prop: &Prop) -> Result<Vec<TruthTableRow>, TruthTableError> {
+    Ok(TruthTable::generate(prop)?.falsifying_assignments())
+}
+
 #[wasm_bindgen]
 pub fn parse_prop(prop: &str) -> Result<Prop, BackendError> {
     let len = prop.chars().count();
@@ -254,6 +495,25 @@ pub fn parse_proof_term(proof_term: &str, prop: &Prop) -> Result<Proof, BackendE
         .apply(proof, prop)?)
 }
 
+#[wasm_bindgen]
+pub fn print_proof_term(proof_term: &ProofTerm) -> String {
+    format!("{}", proof_term)
+}
+
+#[wasm_bindgen]
+pub fn normalize_proof_term(
+    proof_term: &str,
+    prop: &Prop,
+    max_steps: Option<usize>,
+) -> Result<Normalization, BackendError> {
+    let proof = parse_proof_term(proof_term, prop)?;
+
+    Ok(normalize(
+        &proof.proof_term,
+        max_steps.unwrap_or(DEFAULT_MAX_REDUCTION_STEPS),
+    ))
+}
+
 #[wasm_bindgen]
 pub fn get_free_parameters(prop: &Prop) -> Vec<PropParameter> {
     prop.get_free_parameters()
@@ -303,35 +563,66 @@ pub fn bind_identifier(
 }
 
 #[wasm_bindgen]
-pub fn generate_proof_term_from_proof_tree(proof_tree: &ProofTree, prop: &Prop) -> String {
+pub fn generate_proof_term_from_proof_tree(
+    proof_tree: &ProofTree,
+    prop: &Prop,
+    proof: &Proof,
+) -> String {
     let Proof {
         atoms,
         datatypes,
+        functions,
         proof_term,
         ..
     } = proof_tree.as_proof();
 
-    let atom_decls = [atoms, prop.get_atoms()]
-        .concat()
+    // the proof provides the declarations the proof tree cannot tell, e.g. sorts of atoms
+    let declared_names = proof
+        .atom_signatures
         .iter()
-        .unique() // print Atom with multiple arities, let type checker throw error
-        .map(|(atom_name, arity)| {
-            if *arity == 0 {
-                format!("atom {};", atom_name)
-            } else {
-                format!("atom {}({});", atom_name, arity)
-            }
-        })
-        .join("\n");
+        .map(|signature| &signature.name)
+        .chain(proof.definitions.iter().map(|definition| &definition.name))
+        .collect_vec();
 
-    let datatype_decls = [datatypes, prop.get_datatypes()]
+    let atoms = [proof.atoms.clone(), atoms, prop.get_atoms()]
         .concat()
-        .iter()
-        .unique()
-        .map(|datatype| format!("datatype {};", datatype))
-        .join("\n");
+        .into_iter()
+        .filter(|(atom_name, _)| !declared_names.contains(&atom_name))
+        .collect();
 
-    format!("{}\n{}\n\n{}", atom_decls, datatype_decls, proof_term)
+    let functions = [proof.functions.clone(), functions]
+        .concat()
+        .into_iter()
+        .unique_by(|function| function.name.clone())
+        .collect();
+
+    let decls = [
+        print_atom_decls(atoms),
+        print_atom_signature_decls(proof.atom_signatures.clone()),
+        print_datatype_decls([proof.datatypes.clone(), datatypes, prop.get_datatypes()].concat()),
+        print_function_decls(functions),
+        print_definition_decls(proof.definitions.clone()),
+    ]
+    .into_iter()
+    .filter(|decls| !decls.is_empty())
+    .join("\n");
+
+    format!("{}\n\n{}", decls, proof_term)
+}
+
+#[wasm_bindgen]
+pub fn normalize_proof_tree(
+    proof_tree: &ProofTree,
+    proof: &Proof,
+    logic: Logic,
+) -> Result<ProofTree, BackendError> {
+    // the proof provides the declarations the proof tree refers to
+    let mut ctx =
+        IdentifierContext::with_signatures(proof.functions.clone(), proof.atom_signatures.clone());
+    ctx.add_definitions(proof.definitions.clone());
+    ctx.set_logic(logic);
+
+    Ok(proof_tree.normalize(&ctx)?)
 }
 
 #[wasm_bindgen]
@@ -356,6 +647,21 @@ pub fn print_atom_decls(atoms: Vec<(String, usize)>) -> String {
         .join("\n")
 }
 
+pub fn print_function_decls(functions: Vec<FunctionSignature>) -> String {
+    functions
+        .iter()
+        .map(|function| {
+            let signature = function
+                .param_types
+                .iter()
+                .chain(std::iter::once(&function.return_type))
+                .join(" -> ");
+
+            format!("function {}: {};", function.name, signature)
+        })
+        .join("\n")
+}
+
 pub fn print_datatype_decls(datatypes: Vec<String>) -> String {
     datatypes
         .iter()
@@ -364,6 +670,59 @@ pub fn print_datatype_decls(datatypes: Vec<String>) -> String {
         .join("\n")
 }
 
+pub fn print_atom_signature_decls(atoms: Vec<AtomSignature>) -> String {
+    atoms
+        .iter()
+        .map(|atom| {
+            if atom.param_types.is_empty() {
+                format!("atom {};", atom.name)
+            } else {
+                format!("atom {}({});", atom.name, atom.param_types.join(", "))
+            }
+        })
+        .join("\n")
+}
+
+pub fn print_definition_decls(definitions: Vec<PropDefinition>) -> String {
+    definitions
+        .iter()
+        .map(|definition| {
+            if definition.params.is_empty() {
+                format!("def {} := {};", definition.name, definition.body)
+            } else {
+                format!(
+                    "def {}({}) := {};",
+                    definition.name,
+                    definition.params.join(", "),
+                    definition.body
+                )
+            }
+        })
+        .join("\n")
+}
+
+#[wasm_bindgen]
+pub fn import_tptp_problem(problem: &str) -> Result<TptpProblem, TptpError> {
+    parse_tptp_problem(problem)
+}
+
+#[wasm_bindgen]
+pub fn tptp_problem_to_prop(problem: &TptpProblem) -> Prop {
+    problem.to_prop()
+}
+
+#[wasm_bindgen]
+pub fn print_tptp_problem_decls(problem: &TptpProblem) -> String {
+    [
+        print_datatype_decls(problem.datatypes.clone()),
+        print_atom_signature_decls(problem.atoms.clone()),
+        print_function_decls(problem.functions.clone()),
+    ]
+    .into_iter()
+    .filter(|decls| !decls.is_empty())
+    .join("\n")
+}
+
 #[wasm_bindgen]
 pub fn proof_tree_conclusion_alpha_eq(fst: ProofTreeConclusion, snd: ProofTreeConclusion) -> bool {
     match (fst, snd) {
@@ -375,6 +734,10 @@ pub fn proof_tree_conclusion_alpha_eq(fst: ProofTreeConclusion, snd: ProofTreeCo
             ProofTreeConclusion::TypeJudgement(fst_ident, fst_datatype),
             ProofTreeConclusion::TypeJudgement(snd_ident, snd_datatype),
         ) => (fst_ident == snd_ident) && (fst_datatype == snd_datatype),
+        (
+            ProofTreeConclusion::TermJudgement(fst_term, fst_datatype),
+            ProofTreeConclusion::TermJudgement(snd_term, snd_datatype),
+        ) => (fst_term == snd_term) && (fst_datatype == snd_datatype),
         _ => false,
     }
 }
@@ -393,3 +756,87 @@ pub fn export_as_ocaml(prop: &Prop, proof_term: &str) -> String {
         "Invalid proof term".to_string()
     }
 }
+
+#[wasm_bindgen]
+pub fn export_as_lean(prop: &Prop, proof_term: &str) -> String {
+    if let Ok(proof) = parse_proof_term(proof_term, prop) {
+        LeanExporter::new(prop, &proof).export(&proof.proof_term)
+    } else {
+        "Invalid proof term".to_string()
+    }
+}
+
+#[wasm_bindgen]
+pub fn export_as_coq(prop: &Prop, proof_term: &str) -> String {
+    if let Ok(proof) = parse_proof_term(proof_term, prop) {
+        CoqExporter::new(prop, &proof).export(&proof.proof_term)
+    } else {
+        "Invalid proof term".to_string()
+    }
+}
+
+#[wasm_bindgen]
+pub fn export_as_agda(prop: &Prop, proof_term: &str) -> String {
+    if let Ok(proof) = parse_proof_term(proof_term, prop) {
+        AgdaExporter::new(prop, &proof).export(&proof.proof_term)
+    } else {
+        "Invalid proof term".to_string()
+    }
+}
+
+#[wasm_bindgen]
+pub fn export_as_haskell(prop: &Prop, proof_term: &str) -> String {
+    if prop.has_quantifiers() {
+        return "Haskell does not support dependent types.".to_string();
+    }
+
+    if let Ok(proof) = parse_proof_term(proof_term, prop) {
+        HaskellExporter::new(prop, &proof.definitions).export(&proof.proof_term)
+    } else {
+        "Invalid proof term".to_string()
+    }
+}
+
+#[wasm_bindgen]
+pub fn export_as_typescript(prop: &Prop, proof_term: &str) -> String {
+    if prop.has_quantifiers() {
+        return "TypeScript does not support dependent types.".to_string();
+    }
+
+    if let Ok(proof) = parse_proof_term(proof_term, prop) {
+        TypescriptExporter::new(prop, &proof.definitions).export(&proof.proof_term)
+    } else {
+        "Invalid proof term".to_string()
+    }
+}
+
+#[wasm_bindgen]
+pub fn export_as_tptp(prop: &Prop, proof_term: &str) -> String {
+    if let Ok(proof) = parse_proof_term(proof_term, prop) {
+        let definitions = [PropDefinition::builtins(), proof.definitions.clone()].concat();
+
+        // iff is a connective of TPTP, other definitions have to be unfolded
+        let mut prop = prop.clone();
+        let _ = prop.resolve_definitions(&definitions);
+        let prop = prop.unfold_definitions(&proof.definitions);
+
+        TptpProblem::from_prop(&prop, &proof.atom_signatures, &proof.functions).to_string()
+    } else {
+        "Invalid proof term".to_string()
+    }
+}
+
+#[wasm_bindgen]
+pub fn export_as_latex(proof_tree: &ProofTree, style: LatexProofTreeStyle) -> String {
+    LatexExporter::new(style).export(proof_tree)
+}
+
+#[wasm_bindgen]
+pub fn generate_fitch_proof(proof_tree: &ProofTree) -> FitchProof {
+    FitchProof::from_proof_tree(proof_tree)
+}
+
+#[wasm_bindgen]
+pub fn export_as_fitch(proof_tree: &ProofTree) -> String {
+    FitchProof::from_proof_tree(proof_tree).to_string()
+}
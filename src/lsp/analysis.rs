@@ -0,0 +1,401 @@
+use chumsky::{prelude::end, Parser, Stream};
+
+use crate::kernel::{
+    checker::{
+        check::CheckError,
+        identifier_context::IdentifierContext,
+        synthesize::SynthesizeError,
+        Logic,
+    },
+    parse::{lexer::lexer, proof::theorem_file_parser},
+    process::{ProofPipelineError, StageError},
+    proof::Proof,
+    proof_term::Type,
+    proof_tree::ProofTreeConclusion,
+    prove::ProverConfig,
+    theorem_file::{PreparedTheorem, Theorem, TheoremError},
+};
+
+use super::{
+    protocol::{
+        CompletionItem, CompletionItemKind, Diagnostic, DiagnosticSeverity, Hover, MarkupContent,
+        Position,
+    },
+    scope::{scope_at, Scope},
+    text::LineIndex,
+};
+
+struct AnalyzedTheorem {
+    theorem: Theorem,
+    proof: Option<Proof>,
+    ctx: IdentifierContext,
+}
+
+impl AnalyzedTheorem {
+    fn scope_at(&self, offset: usize) -> Option<Scope<'_>> {
+        let mut prop = self.theorem.prop.clone();
+        let expected_type = prop
+            .instantiate_parameters_with_context(&self.ctx)
+            .ok()
+            .map(|_| Type::Prop(prop));
+
+        scope_at(
+            &self.proof.as_ref()?.proof_term,
+            expected_type,
+            self.ctx.clone(),
+            offset,
+        )
+    }
+}
+
+// Result of checking a theorem file, as it is presented to the editor.
+pub struct Analysis {
+    line_index: LineIndex,
+    theorems: Vec<AnalyzedTheorem>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Analysis {
+    pub fn new(src: &str, logic: Logic, prover_config: ProverConfig) -> Self {
+        let line_index = LineIndex::new(src);
+        let len = src.chars().count();
+
+        let error = |span, message| Diagnostic {
+            range: line_index.range(&span),
+            severity: DiagnosticSeverity::Error,
+            source: "alice".to_string(),
+            message,
+        };
+
+        // Step 1: Parse tokens
+        let tokens = match lexer().then_ignore(end()).parse(src) {
+            Ok(tokens) => tokens,
+            Err(errors) => {
+                let diagnostics = errors
+                    .into_iter()
+                    .map(|err| error(err.span(), err.to_string()))
+                    .collect();
+
+                return Self {
+                    line_index,
+                    theorems: vec![],
+                    diagnostics,
+                };
+            }
+        };
+
+        // Step 2: Parse theorems
+        let theorem_file = match theorem_file_parser()
+            .then_ignore(end())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+        {
+            Ok(theorem_file) => theorem_file,
+            Err(errors) => {
+                let diagnostics = errors
+                    .into_iter()
+                    .map(|err| error(err.span(), err.to_string()))
+                    .collect();
+
+                return Self {
+                    line_index,
+                    theorems: vec![],
+                    diagnostics,
+                };
+            }
+        };
+
+        // Step 3: Check every theorem
+        let mut theorems = vec![];
+        let mut diagnostics = vec![];
+
+        let prepared = theorem_file.prepare(logic, prover_config);
+        for (theorem, prepared) in theorem_file.theorems.into_iter().zip(prepared) {
+            let theorem_span = theorem.span.clone().unwrap_or(0..0);
+            let PreparedTheorem { proof, ctx, result } = prepared;
+
+            let (proof, result) = match (proof, result) {
+                (Ok(proof), Some(result)) => (proof, result),
+                (Ok(_), None) => unreachable!("Processed proofs are checked."),
+                (Err(err), _) => {
+                    diagnostics.push(error(theorem_span, theorem_error_message(&err)));
+                    theorems.push(AnalyzedTheorem {
+                        theorem,
+                        proof: None,
+                        ctx,
+                    });
+
+                    continue;
+                }
+            };
+
+            match result {
+                Ok(result) => {
+                    for goal in result.goals {
+                        let mut message =
+                            format!("Open goal: {}", print_conclusion(&goal.conclusion));
+                        if let Some(solution) = goal.solution {
+                            message += &format!("\nThe prover found a proof: {}", solution);
+                        }
+
+                        diagnostics.push(Diagnostic {
+                            range: line_index.range(&goal.span.unwrap_or(theorem_span.clone())),
+                            severity: DiagnosticSeverity::Warning,
+                            source: "alice".to_string(),
+                            message,
+                        });
+                    }
+                }
+                Err(err) => diagnostics.push(error(
+                    err.span().unwrap_or(theorem_span),
+                    check_error_message(&err),
+                )),
+            }
+
+            theorems.push(AnalyzedTheorem {
+                theorem,
+                proof: Some(proof),
+                ctx,
+            });
+        }
+
+        Self {
+            line_index,
+            theorems,
+            diagnostics,
+        }
+    }
+
+    pub fn diagnostics(&self) -> &Vec<Diagnostic> {
+        &self.diagnostics
+    }
+
+    pub fn hover(&self, position: &Position) -> Option<Hover> {
+        let offset = self.line_index.offset(position);
+        let scope = self.theorem_at(offset)?.scope_at(offset)?;
+
+        Some(Hover {
+            contents: MarkupContent {
+                kind: "plaintext".to_string(),
+                value: scope.get_type()?.to_string(),
+            },
+            range: self.line_index.range(scope.proof_term.span().as_ref()?),
+        })
+    }
+
+    pub fn completions(&self, position: &Position) -> Vec<CompletionItem> {
+        let offset = self.line_index.offset(position);
+        let Some(theorem) = self.theorem_at(offset) else {
+            return vec![];
+        };
+
+        // outside of the proof term, only the identifiers of the theorem itself are in scope
+        let ctx = theorem
+            .scope_at(offset)
+            .map(|scope| scope.ctx)
+            .unwrap_or(theorem.ctx.clone());
+
+        let identifiers = ctx
+            .get_all_visible()
+            .into_iter()
+            .map(|(identifier, _type)| CompletionItem {
+                label: identifier.name().clone(),
+                kind: CompletionItemKind::Variable,
+                detail: _type.to_string(),
+            });
+
+        let functions = ctx.functions().iter().map(|function| CompletionItem {
+            label: function.name.clone(),
+            kind: CompletionItemKind::Function,
+            detail: function
+                .param_types
+                .iter()
+                .chain(std::iter::once(&function.return_type))
+                .cloned()
+                .collect::<Vec<String>>()
+                .join(" -> "),
+        });
+
+        identifiers.chain(functions).collect()
+    }
+
+    fn theorem_at(&self, offset: usize) -> Option<&AnalyzedTheorem> {
+        self.theorems.iter().find(|theorem| {
+            theorem
+                .theorem
+                .span
+                .as_ref()
+                .is_some_and(|span| span.start <= offset && offset <= span.end)
+        })
+    }
+}
+
+fn print_conclusion(conclusion: &ProofTreeConclusion) -> String {
+    match conclusion {
+        ProofTreeConclusion::PropIsTrue(prop) => prop.to_string(),
+        ProofTreeConclusion::TypeJudgement(ident, datatype) => {
+            format!("{} : {}", ident.name(), datatype)
+        }
+        ProofTreeConclusion::TermJudgement(term, datatype) => format!("{} : {}", term, datatype),
+    }
+}
+
+// The message of the failing stage is more helpful than the generic pipeline message.
+fn theorem_error_message(error: &TheoremError) -> String {
+    match error {
+        TheoremError::ProofPipelineError(err) => pipeline_error_message(err),
+        _ => error.to_string(),
+    }
+}
+
+fn pipeline_error_message(error: &ProofPipelineError) -> String {
+    match error {
+        ProofPipelineError::StageError(StageError::ResolveDatatypesStageError(err)) => {
+            err.to_string()
+        }
+        _ => error.to_string(),
+    }
+}
+
+fn check_error_message(error: &CheckError) -> String {
+    match error {
+        CheckError::SynthesizeError(synth_err) => synthesize_error_message(synth_err),
+        CheckError::PropHasFreeParameters(prop) => format!("{}: {}", error, prop),
+        CheckError::IncompatibleProofTerm { expected_type, .. } => {
+            format!("{}: expected {}", error, expected_type)
+        }
+        CheckError::UnexpectedPropKind { received, .. } => {
+            format!("{}: received {}", error, received)
+        }
+        CheckError::UnexpectedType {
+            expected, received, ..
+        } => format!("{}: expected {}, received {}", error, expected, received),
+        CheckError::UnexpectedTypeAscription {
+            expected,
+            ascription,
+            ..
+        } => format!(
+            "{}: expected {}, ascription {}",
+            error, expected, ascription
+        ),
+        _ => error.to_string(),
+    }
+}
+
+fn synthesize_error_message(error: &SynthesizeError) -> String {
+    match error {
+        SynthesizeError::CheckError(check_err) => check_error_message(check_err),
+        SynthesizeError::UnknownIdentifier(ident, _) => format!("{} {}", error, ident),
+        SynthesizeError::UnexpectedPropKind { received, .. } => {
+            format!("{}: received {}", error, received)
+        }
+        SynthesizeError::CaseArmsDifferent {
+            fst_type, snd_type, ..
+        } => format!("{}: {} and {}", error, fst_type, snd_type),
+        _ => error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        kernel::{checker::Logic, prove::ProverConfig},
+        lsp::protocol::{CompletionItem, DiagnosticSeverity, Position},
+    };
+
+    use super::Analysis;
+
+    fn analyze(src: &str) -> Analysis {
+        Analysis::new(src, Logic::Intuitionistic, ProverConfig::default())
+    }
+
+    fn position(line: u32, character: u32) -> Position {
+        Position { line, character }
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_no_diagnostics() {
+        let analysis = analyze("atom A;\ntheorem id : A -> A := fn u => u;\n");
+
+        assert!(analysis.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_parser_error() {
+        let analysis = analyze("atom A;\ntheorem id : A -> A := fn u => u\n");
+
+        assert_eq!(analysis.diagnostics().len(), 1);
+        assert_eq!(
+            analysis.diagnostics()[0].severity,
+            DiagnosticSeverity::Error
+        );
+        assert_eq!(analysis.diagnostics()[0].range.start.line, 2);
+    }
+
+    #[test]
+    fn test_check_error_span() {
+        let analysis = analyze("atom A;\natom B;\ntheorem wrong : A -> B := fn u => u;\n");
+
+        let diagnostic = &analysis.diagnostics()[0];
+        assert_eq!(diagnostic.severity, DiagnosticSeverity::Error);
+        assert_eq!(diagnostic.range.start, position(2, 34));
+        assert_eq!(diagnostic.range.end, position(2, 35));
+        assert!(diagnostic.message.contains("expected B"));
+    }
+
+    #[test]
+    fn test_open_goals() {
+        let analysis = analyze("atom A;\ntheorem open : A -> A && A := fn u => (u, sorry);\n");
+
+        let diagnostic = &analysis.diagnostics()[0];
+        assert_eq!(diagnostic.severity, DiagnosticSeverity::Warning);
+        assert_eq!(diagnostic.range.start, position(1, 42));
+        assert!(diagnostic.message.starts_with("Open goal: A"));
+    }
+
+    #[test]
+    fn test_hover() {
+        let analysis = analyze(
+            "atom A;\natom B;\ntheorem swap : A && B -> B && A := fn p => (snd p, fst p);\n",
+        );
+
+        let ident = analysis.hover(&position(2, 48)).unwrap();
+        assert_eq!(ident.contents.value, "A ∧ B");
+
+        let projection = analysis.hover(&position(2, 46)).unwrap();
+        assert_eq!(projection.contents.value, "B");
+        assert_eq!(projection.range.start, position(2, 44));
+    }
+
+    #[test]
+    fn test_hover_checked_subterm() {
+        let analysis = analyze("atom A;\ntheorem id : A -> A := fn u => u;\n");
+
+        let function = analysis.hover(&position(1, 23)).unwrap();
+        assert_eq!(function.contents.value, "A ⊃ A");
+    }
+
+    #[test]
+    fn test_completions() {
+        let analysis = analyze(
+            "datatype t;\natom P(1);\nfunction zero : t;\n\nlemma same : P(zero) -> P(zero) := fn u => u;\ntheorem all : \\forall x:t. P(x) -> P(x) := fn x => fn h => h;\n",
+        );
+
+        let labels = |items: Vec<CompletionItem>| {
+            items
+                .into_iter()
+                .map(|item| item.label)
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(
+            labels(analysis.completions(&position(5, 59))),
+            vec!["h", "x", "same", "zero"]
+        );
+        assert_eq!(
+            labels(analysis.completions(&position(4, 44))),
+            vec!["u", "zero"]
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod analysis;
+pub mod protocol;
+pub mod scope;
+pub mod server;
+pub mod text;
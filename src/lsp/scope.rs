@@ -0,0 +1,342 @@
+use std::ops::Range;
+
+use crate::{
+    kernel::{
+        checker::{
+            identifier::IdentifierFactory, identifier_context::IdentifierContext,
+            synthesize::synthesize,
+        },
+        proof_term::{
+            Abort, Application, Case, DoubleNegElim, Function, LetIn, OrLeft, OrRight, Pair,
+            ProjectFst, ProjectSnd, ProofTerm, Subst, Type, TypeAscription,
+        },
+        prop::Prop,
+    },
+    util::counter::Counter,
+};
+
+pub struct Scope<'a> {
+    // innermost subterm at the offset
+    pub proof_term: &'a ProofTerm,
+    pub ctx: IdentifierContext,
+
+    // type the subterm is checked against, if it is in checking position
+    pub expected_type: Option<Type>,
+}
+
+impl<'a> Scope<'a> {
+    // Re-synthesizes the subterm, falls back to the type it is checked against.
+    pub fn get_type(&self) -> Option<Type> {
+        let mut identifier_factory = IdentifierFactory::new(Counter::new());
+
+        match synthesize(self.proof_term, &self.ctx, &mut identifier_factory) {
+            Ok((_type, _)) => Some(_type),
+            Err(_) => self.expected_type.clone(),
+        }
+    }
+}
+
+// Finds the innermost subterm at the offset together with the context the checker uses for it.
+// Binders get the same types as in the checker, as far as they can be reconstructed
+// without checking the whole term.
+pub fn scope_at(
+    proof_term: &ProofTerm,
+    expected_type: Option<Type>,
+    ctx: IdentifierContext,
+    offset: usize,
+) -> Option<Scope<'_>> {
+    let mut identifier_factory = IdentifierFactory::new(Counter::new());
+
+    find_scope(
+        proof_term,
+        expected_type,
+        ctx,
+        &mut identifier_factory,
+        offset,
+    )
+}
+
+fn contains(span: &Option<Range<usize>>, offset: usize) -> bool {
+    // the end is included, so the cursor right behind an identifier still hits it
+    span.as_ref()
+        .is_some_and(|span| span.start <= offset && offset <= span.end)
+}
+
+fn synthesize_unfolded(
+    proof_term: &ProofTerm,
+    ctx: &IdentifierContext,
+    identifier_factory: &mut IdentifierFactory,
+) -> Option<Type> {
+    synthesize(proof_term, ctx, identifier_factory)
+        .ok()
+        .map(|(_type, _)| _type.unfold_head(ctx.definitions()))
+}
+
+fn find_scope<'a>(
+    proof_term: &'a ProofTerm,
+    expected_type: Option<Type>,
+    ctx: IdentifierContext,
+    identifier_factory: &mut IdentifierFactory,
+    offset: usize,
+) -> Option<Scope<'a>> {
+    if !contains(proof_term.span(), offset) {
+        return None;
+    }
+
+    let expected_prop = match expected_type {
+        Some(Type::Prop(ref prop)) => Some(prop.unfold_head(ctx.definitions())),
+        _ => None,
+    };
+
+    let inner = match proof_term {
+        ProofTerm::Ident(_) | ProofTerm::Unit(_) | ProofTerm::Refl(_) | ProofTerm::Sorry(_) => None,
+
+        ProofTerm::Pair(Pair(fst, snd, _)) => {
+            let (fst_type, snd_type) = match expected_prop {
+                Some(Prop::And(fst, snd)) => (Some(Type::Prop(*fst)), Some(Type::Prop(*snd))),
+                Some(Prop::Exists {
+                    object_type_ident, ..
+                }) => (Some(Type::Datatype(object_type_ident)), None),
+                _ => (None, None),
+            };
+
+            find_scope(fst, fst_type, ctx.clone(), identifier_factory, offset)
+                .or_else(|| find_scope(snd, snd_type, ctx.clone(), identifier_factory, offset))
+        }
+
+        ProofTerm::ProjectFst(ProjectFst(body, _)) | ProofTerm::ProjectSnd(ProjectSnd(body, _)) => {
+            find_scope(body, None, ctx.clone(), identifier_factory, offset)
+        }
+
+        ProofTerm::Function(Function {
+            param_ident,
+            param_type,
+            body,
+            ..
+        }) => {
+            let param_identifier = identifier_factory.create(param_ident.clone());
+
+            let (expected_param_type, expected_body_type) = match expected_prop {
+                Some(Prop::Impl(fst, snd)) => (Some(Type::Prop(*fst)), Some(Type::Prop(*snd))),
+                Some(Prop::ForAll {
+                    object_ident,
+                    object_type_ident,
+                    mut body,
+                }) => {
+                    body.instantiate_free_parameter(&object_ident, &param_identifier);
+
+                    (
+                        Some(Type::Datatype(object_type_ident)),
+                        Some(Type::Prop(*body)),
+                    )
+                }
+                _ => (None, None),
+            };
+
+            // without an expected type, the function is synthesized from its annotation
+            let param_type = expected_param_type.or_else(|| {
+                let mut param_type = param_type.clone()?;
+                param_type.instantiate_parameters_with_context(&ctx).ok()?;
+
+                Some(param_type)
+            });
+
+            let mut body_ctx = ctx.clone();
+            if let Some(param_type) = param_type {
+                body_ctx.insert(param_identifier, param_type);
+            }
+
+            find_scope(
+                body,
+                expected_body_type,
+                body_ctx,
+                identifier_factory,
+                offset,
+            )
+        }
+
+        ProofTerm::Application(Application {
+            function,
+            applicant,
+            ..
+        }) => match find_scope(function, None, ctx.clone(), identifier_factory, offset) {
+            Some(scope) => Some(scope),
+            None => {
+                let applicant_type = match synthesize_unfolded(function, &ctx, identifier_factory) {
+                    Some(Type::Prop(Prop::Impl(fst, _))) => Some(Type::Prop(*fst)),
+                    Some(Type::Prop(Prop::ForAll {
+                        object_type_ident, ..
+                    })) => Some(Type::Datatype(object_type_ident)),
+                    _ => None,
+                };
+
+                find_scope(
+                    applicant,
+                    applicant_type,
+                    ctx.clone(),
+                    identifier_factory,
+                    offset,
+                )
+            }
+        },
+
+        ProofTerm::LetIn(LetIn {
+            fst_ident,
+            snd_ident,
+            head,
+            body,
+            ..
+        }) => match find_scope(head, None, ctx.clone(), identifier_factory, offset) {
+            Some(scope) => Some(scope),
+            None => {
+                let mut body_ctx = ctx.clone();
+
+                if let Some(Type::Prop(Prop::Exists {
+                    object_ident,
+                    object_type_ident,
+                    body: mut exists_body,
+                })) = synthesize_unfolded(head, &ctx, identifier_factory)
+                {
+                    let fst_identifier = identifier_factory.create(fst_ident.clone());
+                    let snd_identifier = identifier_factory.create(snd_ident.clone());
+
+                    exists_body.instantiate_free_parameter(&object_ident, &fst_identifier);
+
+                    body_ctx.insert(fst_identifier, Type::Datatype(object_type_ident));
+                    body_ctx.insert(snd_identifier, Type::Prop(*exists_body));
+                }
+
+                find_scope(
+                    body,
+                    expected_type.clone(),
+                    body_ctx,
+                    identifier_factory,
+                    offset,
+                )
+            }
+        },
+
+        ProofTerm::OrLeft(OrLeft(body, _)) => match expected_prop {
+            Some(Prop::Or(fst, _)) => find_scope(
+                body,
+                Some(Type::Prop(*fst)),
+                ctx.clone(),
+                identifier_factory,
+                offset,
+            ),
+            _ => find_scope(body, None, ctx.clone(), identifier_factory, offset),
+        },
+
+        ProofTerm::OrRight(OrRight(body, _)) => match expected_prop {
+            Some(Prop::Or(_, snd)) => find_scope(
+                body,
+                Some(Type::Prop(*snd)),
+                ctx.clone(),
+                identifier_factory,
+                offset,
+            ),
+            _ => find_scope(body, None, ctx.clone(), identifier_factory, offset),
+        },
+
+        ProofTerm::Case(Case {
+            head,
+            fst_ident,
+            fst_term,
+            snd_ident,
+            snd_term,
+            ..
+        }) => match find_scope(head, None, ctx.clone(), identifier_factory, offset) {
+            Some(scope) => Some(scope),
+            None => {
+                let mut fst_ctx = ctx.clone();
+                let mut snd_ctx = ctx.clone();
+
+                if let Some(Type::Prop(Prop::Or(fst, snd))) =
+                    synthesize_unfolded(head, &ctx, identifier_factory)
+                {
+                    fst_ctx.insert(
+                        identifier_factory.create(fst_ident.clone()),
+                        Type::Prop(*fst),
+                    );
+                    snd_ctx.insert(
+                        identifier_factory.create(snd_ident.clone()),
+                        Type::Prop(*snd),
+                    );
+                }
+
+                match find_scope(
+                    fst_term,
+                    expected_type.clone(),
+                    fst_ctx,
+                    identifier_factory,
+                    offset,
+                ) {
+                    Some(scope) => Some(scope),
+                    None => find_scope(
+                        snd_term,
+                        expected_type.clone(),
+                        snd_ctx,
+                        identifier_factory,
+                        offset,
+                    ),
+                }
+            }
+        },
+
+        ProofTerm::Abort(Abort(body, _)) => find_scope(
+            body,
+            Some(Type::Prop(Prop::False)),
+            ctx.clone(),
+            identifier_factory,
+            offset,
+        ),
+
+        ProofTerm::DoubleNegElim(DoubleNegElim(body, _)) => {
+            let double_negation = expected_prop.map(|prop| {
+                Type::Prop(Prop::Impl(
+                    Prop::Impl(prop.boxed(), Prop::False.boxed()).boxed(),
+                    Prop::False.boxed(),
+                ))
+            });
+
+            find_scope(
+                body,
+                double_negation,
+                ctx.clone(),
+                identifier_factory,
+                offset,
+            )
+        }
+
+        ProofTerm::TypeAscription(TypeAscription {
+            proof_term,
+            ascription,
+            ..
+        }) => {
+            let mut ascription = ascription.clone();
+            let ascription = ascription
+                .instantiate_parameters_with_context(&ctx)
+                .ok()
+                .map(|_| ascription);
+
+            find_scope(
+                proof_term,
+                ascription,
+                ctx.clone(),
+                identifier_factory,
+                offset,
+            )
+        }
+
+        ProofTerm::Subst(Subst { equality, body, .. }) => {
+            find_scope(equality, None, ctx.clone(), identifier_factory, offset)
+                .or_else(|| find_scope(body, None, ctx.clone(), identifier_factory, offset))
+        }
+    };
+
+    inner.or(Some(Scope {
+        proof_term,
+        ctx,
+        expected_type,
+    }))
+}
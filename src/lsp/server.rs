@@ -0,0 +1,331 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+};
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use crate::kernel::{checker::Logic, prove::ProverConfig};
+
+use super::{
+    analysis::Analysis,
+    protocol::{
+        read_message, write_message, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+        DidOpenTextDocumentParams, TextDocumentPositionParams, INVALID_PARAMS, METHOD_NOT_FOUND,
+        PARSE_ERROR,
+    },
+};
+
+pub struct Server {
+    documents: HashMap<String, Analysis>,
+    logic: Logic,
+    prover_config: ProverConfig,
+    shutdown_requested: bool,
+    exited: bool,
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Self {
+            documents: HashMap::new(),
+            logic: Logic::Intuitionistic,
+            prover_config: ProverConfig::default(),
+            shutdown_requested: false,
+            exited: false,
+        }
+    }
+
+    pub fn shutdown_requested(&self) -> bool {
+        self.shutdown_requested
+    }
+
+    pub fn exited(&self) -> bool {
+        self.exited
+    }
+
+    // Handles a single message and returns the messages to send back.
+    pub fn handle(&mut self, message: Value) -> Vec<Value> {
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // responses of the client are not needed
+            if message.get("id").is_some() {
+                return vec![];
+            }
+
+            return vec![error_response(Value::Null, PARSE_ERROR, "Invalid message")];
+        };
+
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        match message.get("id").cloned() {
+            Some(id) => vec![self.handle_request(id, method, params)],
+            None => self.handle_notification(method, params),
+        }
+    }
+
+    fn handle_request(&mut self, id: Value, method: &str, params: Value) -> Value {
+        let result = match method {
+            "initialize" => {
+                let logic = params
+                    .pointer("/initializationOptions/logic")
+                    .and_then(Value::as_str);
+
+                if logic == Some("classical") {
+                    self.logic = Logic::Classical;
+                }
+
+                json!({
+                    "capabilities": {
+                        "textDocumentSync": 1,
+                        "hoverProvider": true,
+                        "completionProvider": {}
+                    },
+                    "serverInfo": {
+                        "name": "alice",
+                        "version": env!("CARGO_PKG_VERSION")
+                    }
+                })
+            }
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Value::Null
+            }
+            "textDocument/hover" => {
+                let params: TextDocumentPositionParams = match parse_params(params) {
+                    Ok(params) => params,
+                    Err(err) => return error_response(id, INVALID_PARAMS, &err),
+                };
+
+                self.documents
+                    .get(&params.text_document.uri)
+                    .and_then(|analysis| analysis.hover(&params.position))
+                    .map_or(Value::Null, |hover| json!(hover))
+            }
+            "textDocument/completion" => {
+                let params: TextDocumentPositionParams = match parse_params(params) {
+                    Ok(params) => params,
+                    Err(err) => return error_response(id, INVALID_PARAMS, &err),
+                };
+
+                let items = self
+                    .documents
+                    .get(&params.text_document.uri)
+                    .map(|analysis| analysis.completions(&params.position))
+                    .unwrap_or_default();
+
+                json!(items)
+            }
+            _ => {
+                return error_response(
+                    id,
+                    METHOD_NOT_FOUND,
+                    &format!("Unknown method '{}'", method),
+                )
+            }
+        };
+
+        json!({ "jsonrpc": "2.0", "id": id, "result": result })
+    }
+
+    fn handle_notification(&mut self, method: &str, params: Value) -> Vec<Value> {
+        match method {
+            "exit" => {
+                self.exited = true;
+                vec![]
+            }
+            "textDocument/didOpen" => match parse_params::<DidOpenTextDocumentParams>(params) {
+                Ok(params) => self.update(params.text_document.uri, &params.text_document.text),
+                Err(_) => vec![],
+            },
+            "textDocument/didChange" => match parse_params::<DidChangeTextDocumentParams>(params) {
+                Ok(params) => match params.content_changes.last() {
+                    Some(change) => self.update(params.text_document.uri, &change.text),
+                    None => vec![],
+                },
+                Err(_) => vec![],
+            },
+            "textDocument/didClose" => match parse_params::<DidCloseTextDocumentParams>(params) {
+                Ok(params) => {
+                    self.documents.remove(&params.text_document.uri);
+                    vec![publish_diagnostics(&params.text_document.uri, json!([]))]
+                }
+                Err(_) => vec![],
+            },
+
+            // e.g. "initialized"
+            _ => vec![],
+        }
+    }
+
+    fn update(&mut self, uri: String, text: &str) -> Vec<Value> {
+        let analysis = Analysis::new(text, self.logic.clone(), self.prover_config.clone());
+        let notification = publish_diagnostics(&uri, json!(analysis.diagnostics()));
+
+        self.documents.insert(uri, analysis);
+
+        vec![notification]
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, String> {
+    serde_json::from_value(params).map_err(|err| err.to_string())
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message }
+    })
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics }
+    })
+}
+
+// Serves requests until the client sends "exit" or closes the connection.
+pub fn run(mut reader: impl BufRead, mut writer: impl Write) -> io::Result<Server> {
+    let mut server = Server::new();
+
+    while let Some(message) = read_message(&mut reader)? {
+        for response in server.handle(message) {
+            write_message(&mut writer, &response)?;
+        }
+
+        if server.exited() {
+            break;
+        }
+    }
+
+    Ok(server)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use serde_json::{json, Value};
+
+    use crate::lsp::protocol::{read_message, write_message};
+
+    use super::run;
+
+    fn frame(messages: Vec<Value>) -> Vec<u8> {
+        let mut input = vec![];
+        for message in messages.iter() {
+            write_message(&mut input, message).unwrap();
+        }
+
+        input
+    }
+
+    fn unframe(output: Vec<u8>) -> Vec<Value> {
+        let mut reader = Cursor::new(output);
+        let mut messages = vec![];
+        while let Some(message) = read_message(&mut reader).unwrap() {
+            messages.push(message);
+        }
+
+        messages
+    }
+
+    // END Helper
+
+    #[test]
+    fn test_session() {
+        let uri = "file:///swap.alice";
+        let input = frame(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+            json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": {
+                    "textDocument": {
+                        "uri": uri,
+                        "languageId": "alice",
+                        "version": 1,
+                        "text": "atom A;\ntheorem id : A -> A := fn u => sorry;\n"
+                    }
+                }
+            }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "textDocument/hover",
+                "params": {
+                    "textDocument": { "uri": uri },
+                    "position": { "line": 1, "character": 31 }
+                }
+            }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "textDocument/definition", "params": {} }),
+            json!({ "jsonrpc": "2.0", "id": 4, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ]);
+
+        let mut output = vec![];
+        let server = run(Cursor::new(input), &mut output).unwrap();
+        assert!(server.shutdown_requested());
+
+        let messages = unframe(output);
+        assert_eq!(messages.len(), 5);
+
+        assert_eq!(messages[0]["id"], 1);
+        assert_eq!(messages[0]["result"]["capabilities"]["hoverProvider"], true);
+
+        assert_eq!(messages[1]["method"], "textDocument/publishDiagnostics");
+        assert_eq!(messages[1]["params"]["uri"], uri);
+        assert_eq!(messages[1]["params"]["diagnostics"][0]["severity"], 2);
+        assert_eq!(
+            messages[1]["params"]["diagnostics"][0]["range"]["start"],
+            json!({ "line": 1, "character": 31 })
+        );
+
+        assert_eq!(messages[2]["id"], 2);
+        assert_eq!(messages[2]["result"]["contents"]["value"], "A");
+
+        assert_eq!(messages[3]["id"], 3);
+        assert_eq!(messages[3]["error"]["code"], -32601);
+
+        assert_eq!(messages[4]["id"], 4);
+        assert_eq!(messages[4]["result"], Value::Null);
+    }
+
+    #[test]
+    fn test_close_clears_diagnostics() {
+        let uri = "file:///wrong.alice";
+        let input = frame(vec![
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": {
+                    "textDocument": { "uri": uri, "text": "theorem wrong : A := ();" }
+                }
+            }),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didClose",
+                "params": { "textDocument": { "uri": uri } }
+            }),
+        ]);
+
+        let mut output = vec![];
+        let server = run(Cursor::new(input), &mut output).unwrap();
+        assert!(!server.shutdown_requested());
+
+        let messages = unframe(output);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0]["params"]["diagnostics"][0]["severity"], 1);
+        assert_eq!(messages[1]["params"]["diagnostics"], json!([]));
+    }
+}
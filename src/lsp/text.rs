@@ -0,0 +1,107 @@
+use std::ops::Range;
+
+use super::protocol::{Position, TextRange};
+
+// Translates between the char offsets used by spans and the positions of the protocol,
+// which count UTF-16 code units per line.
+pub struct LineIndex {
+    lines: Vec<Vec<char>>,
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(src: &str) -> Self {
+        let mut lines = vec![];
+        let mut line_starts = vec![];
+        let mut offset = 0;
+
+        for line in src.split('\n') {
+            let chars: Vec<char> = line.chars().collect();
+
+            line_starts.push(offset);
+            offset += chars.len() + 1;
+            lines.push(chars);
+        }
+
+        Self { lines, line_starts }
+    }
+
+    pub fn position(&self, offset: usize) -> Position {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+
+        let column = (offset - self.line_starts[line]).min(self.lines[line].len());
+        let character: usize = self.lines[line][..column]
+            .iter()
+            .map(|c| c.len_utf16())
+            .sum();
+
+        Position {
+            line: line as u32,
+            character: character as u32,
+        }
+    }
+
+    pub fn offset(&self, position: &Position) -> usize {
+        let line = (position.line as usize).min(self.lines.len() - 1);
+
+        let mut character = 0;
+        let mut column = 0;
+        for c in self.lines[line].iter() {
+            if character >= position.character as usize {
+                break;
+            }
+
+            character += c.len_utf16();
+            column += 1;
+        }
+
+        self.line_starts[line] + column
+    }
+
+    pub fn range(&self, span: &Range<usize>) -> TextRange {
+        TextRange {
+            start: self.position(span.start),
+            end: self.position(span.end),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lsp::protocol::Position;
+
+    use super::LineIndex;
+
+    #[test]
+    fn test_position_roundtrip() {
+        let index = LineIndex::new("atom A;\ntheorem id : A ⊃ A := fn u => u;\n");
+
+        let position = index.position(25);
+        assert_eq!(
+            position,
+            Position {
+                line: 1,
+                character: 17
+            }
+        );
+        assert_eq!(index.offset(&position), 25);
+    }
+
+    #[test]
+    fn test_position_utf16() {
+        // 𝔸 is outside the basic multilingual plane and takes two UTF-16 code units
+        let index = LineIndex::new("𝔸 x");
+
+        assert_eq!(index.position(2).character, 3);
+        assert_eq!(
+            index.offset(&Position {
+                line: 0,
+                character: 3
+            }),
+            2
+        );
+    }
+}
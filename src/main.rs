@@ -1,123 +1,515 @@
-use alice::kernel::{
-    checker::{check::check, identifier_context::IdentifierContext},
-    export::{ocaml_exporter::OcamlExporter, ProofExporter},
-    parse::{fol::fol_parser, lexer::lexer, proof::proof_parser},
-    process::{stages::resolve_datatypes::ResolveDatatypes, ProofPipeline},
-    prove::prove,
+use std::{fs, process::ExitCode};
+
+use alice::{
+    kernel::{
+        checker::{Logic, TypeCheckerResult},
+        export::{
+            agda_exporter::AgdaExporter, coq_exporter::CoqExporter,
+            haskell_exporter::HaskellExporter, lean_exporter::LeanExporter,
+            ocaml_exporter::OcamlExporter, typescript_exporter::TypescriptExporter, ProofExporter,
+        },
+        process::{ProofPipelineError, StageError},
+        proof_term::Type,
+        proof_tree::ProofTreeConclusion,
+        prop::Prop,
+        prove::ProverOutcome,
+        theorem_file::TheoremCheckResult,
+    },
+    parse_proof_term, parse_prop, prove_with_declarations, verify, verify_theorem_file,
+    BackendError, TheoremFileVerificationResult, VerificationResult,
+    VerificationResultSolvableStatus,
 };
-use ariadne::{Color, Label, Report, ReportKind, Source};
-use chumsky::{Parser, Stream};
+use serde::Serialize;
+
+const USAGE: &str = "Usage:
+    alice check <file> --prop <prop> [--logic intuitionistic|classical] [--json]
+    alice check <file> [--logic intuitionistic|classical] [--json]
+    alice prove <prop> [--decls <file>] [--json]
+    alice export <file> --prop <prop> [--format ocaml|lean|coq|agda|haskell|typescript] [--json]
+
+Without --prop, the file has to contain named theorems, e.g.
+    theorem swap : A & B -> B & A := fn p => (snd p, fst p);
+
+The file given by --decls contains declarations only, e.g.
+    datatype t; function f: t -> t;
+
+Exit codes:
+    0  success
+    1  the proof is incomplete or wrong, or the proposition is not provable
+    2  invalid usage";
+
+enum Command {
+    Check {
+        file: String,
 
-fn main() {
-    let src = std::fs::read_to_string("test.proof").unwrap();
+        // None if the file contains theorems
+        prop: Option<String>,
+        logic: Logic,
+    },
+    Prove {
+        prop: String,
 
-    // Step 1: Parse tokens
-    let tokens = lexer().parse(src.clone());
+        // file with the declarations the proposition refers to
+        decls: Option<String>,
+    },
+    Export {
+        file: String,
+        prop: String,
+        format: ExportFormat,
+    },
+    Help,
+}
+
+enum ExportFormat {
+    Ocaml,
+    Lean,
+    Coq,
+    Agda,
+    Haskell,
+    Typescript,
+}
+
+struct Args {
+    command: Command,
+    json: bool,
+}
 
-    println!("{:#?}", tokens);
+fn parse_args(args: Vec<String>) -> Result<Args, String> {
+    let mut positionals = vec![];
+    let mut prop = None;
+    let mut decls = None;
+    let mut logic = Logic::Intuitionistic;
+    let mut format = ExportFormat::Ocaml;
+    let mut json = false;
+    let mut help = false;
 
-    if let Err(err) = tokens.clone() {
-        err.into_iter().for_each(|e| {
-            Report::build(ReportKind::Error, (), e.span().start)
-                .with_message(e.to_string())
-                .with_label(Label::new(e.span()).with_color(Color::Red))
-                .finish()
-                .print(Source::from(src.clone()))
-                .unwrap();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => help = true,
+            "--prop" => prop = Some(args.next().ok_or("Missing value for --prop")?),
+            "--decls" => decls = Some(args.next().ok_or("Missing value for --decls")?),
+            "--logic" => {
+                logic = match args.next().as_deref() {
+                    Some("intuitionistic") => Logic::Intuitionistic,
+                    Some("classical") => Logic::Classical,
+                    Some(other) => return Err(format!("Unknown logic '{}'", other)),
+                    None => return Err("Missing value for --logic".to_string()),
+                }
+            }
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("ocaml") => ExportFormat::Ocaml,
+                    Some("lean") => ExportFormat::Lean,
+                    Some("coq") => ExportFormat::Coq,
+                    Some("agda") => ExportFormat::Agda,
+                    Some("haskell") => ExportFormat::Haskell,
+                    Some("typescript") => ExportFormat::Typescript,
+                    Some(other) => return Err(format!("Unknown export format '{}'", other)),
+                    None => return Err("Missing value for --format".to_string()),
+                }
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+            _ => positionals.push(arg),
+        }
+    }
+
+    if help {
+        return Ok(Args {
+            command: Command::Help,
+            json,
         });
+    }
+
+    let mut positionals = positionals.into_iter();
+    let command = match positionals.next().as_deref() {
+        Some("check") => Command::Check {
+            file: positionals.next().ok_or("Missing proof file")?,
+            prop,
+            logic,
+        },
+        Some("prove") => Command::Prove {
+            prop: positionals.next().ok_or("Missing proposition")?,
+            decls,
+        },
+        Some("export") => Command::Export {
+            file: positionals.next().ok_or("Missing proof file")?,
+            prop: prop.ok_or("Missing --prop")?,
+            format,
+        },
+        Some("help") | None => Command::Help,
+        Some(other) => return Err(format!("Unknown command '{}'", other)),
+    };
+
+    if let Some(extra) = positionals.next() {
+        return Err(format!("Unexpected argument '{}'", extra));
+    }
+
+    Ok(Args { command, json })
+}
+
+fn print_json<T: Serialize>(value: &T) {
+    println!("{}", serde_json::to_string_pretty(value).unwrap());
+}
+
+// The message of the failing stage is more helpful than the generic pipeline message.
+fn pipeline_error_message(error: &ProofPipelineError) -> String {
+    match error {
+        ProofPipelineError::StageError(StageError::ResolveDatatypesStageError(err)) => {
+            err.to_string()
+        }
+        _ => error.to_string(),
+    }
+}
+
+// Lexer and parser errors are already rendered by ariadne.
+fn report_backend_error(error: BackendError, json: bool) -> ExitCode {
+    if json {
+        print_json(&error);
+        return ExitCode::FAILURE;
+    }
+
+    match error {
+        BackendError::LexerError(message) | BackendError::ParserError(message) => {
+            eprint!("{}", message)
+        }
+        BackendError::ProofTermProcessingError(err) => {
+            eprintln!("Error: {}", pipeline_error_message(&err))
+        }
+        BackendError::CheckError(err) => eprintln!("Error: {}", err),
+        BackendError::ProofTreeNormalizationError(err) => eprintln!("Error: {}", err),
+    }
+
+    ExitCode::FAILURE
+}
+
+fn with_prop(prop: &str, json: bool, command: impl FnOnce(&Prop) -> ExitCode) -> ExitCode {
+    match parse_prop(prop) {
+        Ok(prop) => command(&prop),
+        Err(err) => report_backend_error(err, json),
+    }
+}
+
+fn read_file(file: &str) -> Result<String, ExitCode> {
+    fs::read_to_string(file).map_err(|err| {
+        eprintln!("Error: Cannot read '{}': {}", file, err);
+        ExitCode::from(2)
+    })
+}
+
+fn print_solvable_status(solvable: &VerificationResultSolvableStatus) {
+    let status = match solvable {
+        VerificationResultSolvableStatus::Solvable => "The proposition is provable.",
+        VerificationResultSolvableStatus::Unsolvable => "The proposition is not provable.",
+        VerificationResultSolvableStatus::ClassicallySolvable => {
+            "The proposition is only provable in classical logic."
+        }
+        VerificationResultSolvableStatus::Unknown => {
+            "The provability of the proposition is unknown."
+        }
+    };
+
+    eprintln!("{}", status);
+}
+
+fn print_open_goals(result: &TypeCheckerResult) {
+    for goal in result.goals.iter() {
+        match &goal.conclusion {
+            ProofTreeConclusion::PropIsTrue(prop) => eprintln!("Open goal: {}", prop),
+            ProofTreeConclusion::TypeJudgement(ident, datatype) => {
+                eprintln!("Open goal: {} : {}", ident.name(), datatype)
+            }
+            ProofTreeConclusion::TermJudgement(term, datatype) => {
+                eprintln!("Open goal: {} : {}", term, datatype)
+            }
+        }
+    }
+}
 
-        return;
+fn check_command(file: &str, prop: &Prop, logic: Logic, json: bool) -> ExitCode {
+    let src = match read_file(file) {
+        Ok(src) => src,
+        Err(code) => return code,
+    };
+
+    let result = verify(prop, &src, logic, None);
+
+    // goals left open by sorry count as failure, even if the prover can solve them
+    let success = match &result {
+        VerificationResult::TypeCheckSucceeded { result, .. } => result.is_closed(),
+        _ => false,
+    };
+
+    if json {
+        print_json(&result);
+    } else {
+        match &result {
+            VerificationResult::LexerError { error_message, .. }
+            | VerificationResult::ParserError { error_message, .. } => eprint!("{}", error_message),
+            VerificationResult::ProofPipelineError { error, .. } => {
+                eprintln!("Error: {}", pipeline_error_message(error))
+            }
+            VerificationResult::TypeCheckerError {
+                error, solvable, ..
+            } => {
+                eprintln!("Error: {}", error);
+                print_solvable_status(solvable);
+            }
+            VerificationResult::TypeCheckSucceeded { .. } if success => {
+                println!("Proof of {} is correct.", prop)
+            }
+            VerificationResult::TypeCheckSucceeded {
+                result, solvable, ..
+            } => {
+                eprintln!("Proof of {} is incomplete.", prop);
+                print_open_goals(result);
+                print_solvable_status(solvable);
+            }
+        }
     }
 
-    // Step 2: Parse Proof
-    let len = src.chars().count();
-    let proof = proof_parser().parse(Stream::from_iter(len..len + 1, tokens.unwrap().into_iter()));
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn check_theorems_command(file: &str, logic: Logic, json: bool) -> ExitCode {
+    let src = match read_file(file) {
+        Ok(src) => src,
+        Err(code) => return code,
+    };
 
-    if let Err(err) = proof.clone() {
-        println!("{:#?}", err);
+    let result = verify_theorem_file(&src, logic, None);
+
+    let success = match &result {
+        TheoremFileVerificationResult::Checked { theorems } => {
+            theorems.iter().all(|theorem| theorem.result.is_closed())
+        }
+        _ => false,
+    };
+
+    if json {
+        print_json(&result);
+    } else {
+        match &result {
+            TheoremFileVerificationResult::LexerError { error_message }
+            | TheoremFileVerificationResult::ParserError { error_message } => {
+                eprint!("{}", error_message)
+            }
+            TheoremFileVerificationResult::Checked { theorems } => {
+                for theorem in theorems.iter() {
+                    match &theorem.result {
+                        TheoremCheckResult::DuplicateName(name) => {
+                            eprintln!(
+                                "{}: Error: Theorem {} is already defined",
+                                theorem.name, name
+                            )
+                        }
+                        TheoremCheckResult::ProofPipelineError(error) => {
+                            eprintln!("{}: Error: {}", theorem.name, pipeline_error_message(error))
+                        }
+                        TheoremCheckResult::TypeCheckerError(error) => {
+                            eprintln!("{}: Error: {}", theorem.name, error)
+                        }
+                        TheoremCheckResult::TypeCheckSucceeded(result) if result.is_closed() => {
+                            println!("{}: Proof of {} is correct.", theorem.name, theorem.prop)
+                        }
+                        TheoremCheckResult::TypeCheckSucceeded(result) => {
+                            eprintln!("{}: Proof of {} is incomplete.", theorem.name, theorem.prop);
+                            print_open_goals(result);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn prove_command(prop: &Prop, decls: Option<&str>, json: bool) -> ExitCode {
+    let declarations = match decls.map(read_file).transpose() {
+        Ok(declarations) => declarations.unwrap_or_default(),
+        Err(code) => return code,
+    };
+
+    let outcome = match prove_with_declarations(prop, &declarations, None) {
+        Ok(outcome) => outcome,
+        Err(err) => return report_backend_error(err, json),
+    };
+
+    if json {
+        print_json(&outcome);
+    } else {
+        match &outcome {
+            ProverOutcome::Proved(proof_term) => println!("{}", proof_term),
+            ProverOutcome::Refuted => eprintln!("The proposition is not provable."),
+            ProverOutcome::GaveUp => eprintln!("No proof was found within the prover limits."),
+        }
+    }
+
+    match outcome {
+        ProverOutcome::Proved(_) => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+#[derive(Serialize)]
+struct ExportOutput {
+    format: String,
+    output: String,
+}
 
-        err.into_iter().for_each(|e| {
-            Report::build(ReportKind::Error, (), e.span().start)
-                .with_message(e.to_string())
-                .with_label(Label::new(e.span()).with_color(Color::Red))
-                .finish()
-                .print(Source::from(src.clone()))
-                .unwrap();
+fn export_command(file: &str, prop: &Prop, format: ExportFormat, json: bool) -> ExitCode {
+    let src = match read_file(file) {
+        Ok(src) => src,
+        Err(code) => return code,
+    };
+
+    let proof = match parse_proof_term(&src, prop) {
+        Ok(proof) => proof,
+        Err(err) => return report_backend_error(err, json),
+    };
+
+    let (format, exporter): (&str, Box<dyn ProofExporter>) = match format {
+        ExportFormat::Ocaml => ("ocaml", Box::new(OcamlExporter::new())),
+        ExportFormat::Lean => ("lean", Box::new(LeanExporter::new(prop, &proof))),
+        ExportFormat::Coq => ("coq", Box::new(CoqExporter::new(prop, &proof))),
+        ExportFormat::Agda => ("agda", Box::new(AgdaExporter::new(prop, &proof))),
+        ExportFormat::Haskell => (
+            "haskell",
+            Box::new(HaskellExporter::new(prop, &proof.definitions)),
+        ),
+        ExportFormat::Typescript => (
+            "typescript",
+            Box::new(TypescriptExporter::new(prop, &proof.definitions)),
+        ),
+    };
+
+    if !exporter.can_export_for_type(Type::Prop(prop.clone()))
+        || !exporter.can_export(&proof.proof_term)
+    {
+        eprintln!("Error: The proof cannot be exported to {}.", format);
+        return ExitCode::FAILURE;
+    }
+
+    let output = exporter.export(&proof.proof_term);
+
+    if json {
+        print_json(&ExportOutput {
+            format: format.to_string(),
+            output,
         });
+    } else {
+        println!("{}", output);
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    let Args { command, json } = match parse_args(std::env::args().skip(1).collect()) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("Error: {}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
 
-        return;
+    match command {
+        Command::Check {
+            file,
+            prop: Some(prop),
+            logic,
+        } => with_prop(&prop, json, |prop| check_command(&file, prop, logic, json)),
+        Command::Check {
+            file,
+            prop: None,
+            logic,
+        } => check_theorems_command(&file, logic, json),
+        Command::Prove { prop, decls } => with_prop(&prop, json, |prop| {
+            prove_command(prop, decls.as_deref(), json)
+        }),
+        Command::Export { file, prop, format } => with_prop(&prop, json, |prop| {
+            export_command(&file, prop, format, json)
+        }),
+        Command::Help => {
+            println!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
     }
+}
 
-    let fol = "A -> A";
-    let fol_tokens = lexer().parse(fol).unwrap();
-    let fol_len = fol.chars().count();
+#[cfg(test)]
+mod tests {
+    use alice::kernel::checker::Logic;
 
-    let prop = fol_parser()
-        .parse(Stream::from_iter(
-            fol_len..fol_len + 1,
-            fol_tokens.into_iter(),
-        ))
-        .unwrap();
+    use super::{parse_args, Command};
 
-    let processed_proof = ProofPipeline::new()
-        .pipe(ResolveDatatypes::boxed())
-        .apply(proof.unwrap(), &prop)
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = parse_args(args(&[
+            "check",
+            "proof.txt",
+            "--prop",
+            "A -> A",
+            "--logic",
+            "classical",
+            "--json",
+        ]))
         .unwrap();
 
-    // Step 3: Preprocess ProofTerm
-
-    println!("{:#?}", processed_proof);
-
-    // println!("{:#?}", prop.get_free_parameters());
-
-    let _type = check(
-        &processed_proof.proof_term,
-        &prop,
-        &IdentifierContext::new(),
-    );
-
-    println!("{:#?}", _type);
-
-    println!("{}", prove(&prop).unwrap());
-
-    let ocaml_exporter = OcamlExporter::new();
-
-    println!("{}", ocaml_exporter.export(&processed_proof.proof_term));
-
-    // let proof_tree = ProofTree {
-    //     premisses: vec![
-    //         ProofTree {
-    //             premisses: vec![ProofTree {
-    //                 premisses: vec![],
-    //                 rule: ProofTreeRule::Ident("w".to_string()),
-    //                 conclusion: ProofTreeConclusion::PropIsTrue(parse_prop("A").unwrap()),
-    //             }],
-    //             rule: ProofTreeRule::ImplIntro("w".to_string()),
-    //             conclusion: ProofTreeConclusion::PropIsTrue(
-    //                 parse_prop("(A -> A) -> A -> A").unwrap(),
-    //             ),
-    //         },
-    //         ProofTree {
-    //             premisses: vec![ProofTree {
-    //                 premisses: vec![],
-    //                 rule: ProofTreeRule::Ident("v".to_string()),
-    //                 conclusion: ProofTreeConclusion::PropIsTrue(parse_prop("A").unwrap()),
-    //             }],
-    //             rule: ProofTreeRule::ImplIntro("v".to_string()),
-    //             conclusion: ProofTreeConclusion::PropIsTrue(parse_prop("A -> A").unwrap()),
-    //         },
-    //     ],
-    //     rule: ProofTreeRule::ImplElim,
-    //     conclusion: ProofTreeConclusion::PropIsTrue(parse_prop("A -> A").unwrap()),
-    // };
-
-    // println!("{}", proof_tree.as_proof_term());
-    // println!(
-    //     "{:#?}",
-    //     check(
-    //         &proof_tree.as_proof_term(),
-    //         &parse_prop("A -> A").unwrap(),
-    //         &IdentifierContext::new()
-    //     )
-    // );
+        assert!(parsed.json);
+        assert!(matches!(
+            parsed.command,
+            Command::Check { file, prop: Some(prop), logic: Logic::Classical } if file == "proof.txt" && prop == "A -> A"
+        ));
+
+        let parsed = parse_args(args(&["prove", "A && B -> A"])).unwrap();
+        assert!(!parsed.json);
+        assert!(matches!(
+            parsed.command,
+            Command::Prove { prop, decls: None } if prop == "A && B -> A"
+        ));
+
+        let parsed = parse_args(args(&["prove", "P(f(a))", "--decls", "decls.txt"])).unwrap();
+        assert!(matches!(
+            parsed.command,
+            Command::Prove { decls: Some(decls), .. } if decls == "decls.txt"
+        ));
+
+        assert!(matches!(
+            parse_args(args(&[])).unwrap().command,
+            Command::Help
+        ));
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse_args(args(&["check"])).is_err());
+        assert!(parse_args(args(&["prove"])).is_err());
+        assert!(parse_args(args(&["prove", "A", "B"])).is_err());
+        assert!(parse_args(args(&[
+            "export",
+            "proof.txt",
+            "--prop",
+            "A",
+            "--format",
+            "pdf"
+        ]))
+        .is_err());
+        assert!(parse_args(args(&["frob"])).is_err());
+        assert!(parse_args(args(&["prove", "A", "--verbose"])).is_err());
+    }
 }
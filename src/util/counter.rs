@@ -13,6 +13,10 @@ impl Counter {
         Self { count: 0 }
     }
 
+    pub fn starting_at(count: usize) -> Self {
+        Self { count }
+    }
+
     pub fn next_value(&mut self) -> usize {
         let result = self.count;
         self.count += 1;